@@ -22,6 +22,7 @@ use crate::{
     rustc_data_structures::stable_hasher::StableHasher,
     sync::{Lock, LockGuard, MappedLockGuard},
 };
+use fxhash::{FxHashMap, FxHashSet};
 use log::debug;
 use sourcemap::SourceMapBuilder;
 use std::{
@@ -1004,13 +1005,38 @@ impl SourceMap {
         mappings: &mut Vec<(BytePos, LineCol)>,
         orig: Option<&sourcemap::SourceMap>,
     ) -> sourcemap::SourceMap {
+        self.build_source_map_with(mappings, &[], |_| orig)
+    }
+
+    /// Creates a `.map` file for code generated from multiple source files.
+    ///
+    /// `names` is the original names of identifiers, keyed by the start of
+    /// their span, and is used to populate `names` of the source map.
+    ///
+    /// `orig` is called for each source file and the returned source map (if
+    /// any) is chained, so the result points to the original sources of the
+    /// source file.
+    pub fn build_source_map_with<'a, F>(
+        &self,
+        mappings: &mut Vec<(BytePos, LineCol)>,
+        names: &[(BytePos, String)],
+        mut orig: F,
+    ) -> sourcemap::SourceMap
+    where
+        F: FnMut(&SourceFile) -> Option<&'a sourcemap::SourceMap>,
+    {
         let mut builder = SourceMapBuilder::new(None);
+        let names: FxHashMap<_, _> = names.iter().map(|(pos, name)| (*pos, &**name)).collect();
 
         // // This method is optimized based on the fact that mapping is sorted.
         // mappings.sort_by_key(|v| v.0);
 
         let mut cur_file: Option<Arc<SourceFile>> = None;
-        let mut src_id = 0;
+        let mut cur_orig: Option<&sourcemap::SourceMap> = None;
+        // Lazily added, as a source file with an input source map is not a source
+        // of the result.
+        let mut src_id = None;
+        let mut with_contents = FxHashSet::default();
 
         let mut ch_start = 0;
         let mut line_ch_start = 0;
@@ -1029,8 +1055,8 @@ impl SourceMap {
                 Some(ref f) if f.start_pos <= pos && pos < f.end_pos => f,
                 _ => {
                     f = self.lookup_source_file(pos);
-                    src_id = builder.add_source(&f.name.to_string());
-                    builder.set_source_contents(src_id, Some(&f.src));
+                    cur_orig = orig(&*f);
+                    src_id = None;
                     cur_file = Some(f.clone());
                     ch_start = 0;
                     line_ch_start = 0;
@@ -1043,7 +1069,6 @@ impl SourceMap {
                 None => continue,
             };
 
-            let mut line = a + 1; // Line numbers start at 1
             let linebpos = f.lines[a as usize];
             debug_assert!(
                 pos >= linebpos,
@@ -1055,16 +1080,47 @@ impl SourceMap {
             let chpos = { self.calc_extra_bytes(&f, &mut ch_start, pos) };
             let linechpos = { self.calc_extra_bytes(&f, &mut line_ch_start, linebpos) };
 
-            let mut col = max(chpos, linechpos) - min(chpos, linechpos);
+            let col = max(chpos, linechpos) - min(chpos, linechpos);
+
+            if let Some(orig) = cur_orig {
+                let token = match orig.lookup_token(a, col) {
+                    Some(token) => token,
+                    None => continue,
+                };
+                let src = match token.get_source() {
+                    Some(src) => src,
+                    None => continue,
+                };
 
-            if let Some(orig) = &orig {
-                if let Some(token) = orig.lookup_token(line, col) {
-                    line = token.get_src_line() + 1;
-                    col = token.get_src_col();
+                let id = builder.add_source(src);
+                if with_contents.insert(id) {
+                    builder.set_source_contents(id, orig.get_source_contents(token.get_src_id()));
                 }
+
+                builder.add_raw(
+                    lc.line,
+                    lc.col,
+                    token.get_src_line(),
+                    token.get_src_col(),
+                    Some(id),
+                    token.get_name().or_else(|| names.get(&pos).copied()),
+                );
+                continue;
             }
 
-            builder.add_raw(lc.line, lc.col, line - 1, col, Some(src_id), None);
+            let id = match src_id {
+                Some(id) => id,
+                None => {
+                    let id = builder.add_source(&f.name.to_string());
+                    if with_contents.insert(id) {
+                        builder.set_source_contents(id, Some(&f.src));
+                    }
+                    src_id = Some(id);
+                    id
+                }
+            };
+
+            builder.add_raw(lc.line, lc.col, a, col, Some(id), names.get(&pos).copied());
         }
 
         builder.into_sourcemap()
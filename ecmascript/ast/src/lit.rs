@@ -39,6 +39,12 @@ pub struct BigInt {
     pub span: Span,
     #[cfg_attr(feature = "fold", fold(ignore))]
     pub value: BigIntValue,
+
+    /// Source text of the literal, without the trailing `n`.
+    ///
+    /// This is `None` if the node is created by a transform.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw: Option<JsWord>,
 }
 
 #[ast_node("StringLiteral")]
@@ -85,13 +91,19 @@ pub struct Regex {
 }
 
 #[ast_node("NumericLiteral")]
-#[derive(Copy)]
 pub struct Number {
     pub span: Span,
     /// **Note**: This should not be `NaN`. Use [crate::Ident] to represent NaN.
     ///
     /// If you store `NaN` in this field, a hash map will behave strangely.
     pub value: f64,
+
+    /// Source text of the literal, e.g. `0x1F` or `1_000`.
+    ///
+    /// This is `None` if the node is created by a transform. Transforms which
+    /// modify `value` should reset this to `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw: Option<JsWord>,
 }
 
 impl Eq for Number {}
@@ -114,6 +126,7 @@ impl Hash for Number {
 
         self.span.hash(state);
         integer_decode(self.value).hash(state);
+        self.raw.hash(state);
     }
 }
 
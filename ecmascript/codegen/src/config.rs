@@ -1,4 +1,10 @@
+use swc_ecma_parser::JscTarget;
+
 #[derive(Debug, Default, Clone, Copy)]
 pub struct Config {
     pub minify: bool,
+
+    /// If set, raw source text of numeric literals is printed if the target
+    /// supports its syntax. Otherwise literals are printed using their values.
+    pub target: Option<JscTarget>,
 }
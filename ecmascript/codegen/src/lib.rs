@@ -12,6 +12,7 @@ use swc_atoms::JsWord;
 use swc_common::{comments::Comments, BytePos, SourceMap, Span, Spanned, SyntaxContext, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_codegen_macros::emitter;
+use swc_ecma_parser::JscTarget;
 
 #[macro_use]
 pub mod macros;
@@ -413,6 +414,11 @@ impl<'a> Emitter<'a> {
     fn emit_num_lit(&mut self, num: &Number) -> Result {
         self.emit_leading_comments_of_pos(num.span().lo())?;
 
+        if let Some(raw) = self.raw_num_lit(num.raw.as_ref()) {
+            self.wr.write_lit(num.span, &raw)?;
+            return Ok(());
+        }
+
         // Handle infinity
         if num.value.is_infinite() {
             if num.value.is_sign_negative() {
@@ -432,10 +438,39 @@ impl<'a> Emitter<'a> {
     fn emit_big_lit(&mut self, v: &BigInt) -> Result {
         self.emit_leading_comments_of_pos(v.span.lo())?;
 
-        self.wr.write_lit(v.span, &v.value.to_string())?;
+        match self.raw_num_lit(v.raw.as_ref()) {
+            Some(raw) => self.wr.write_lit(v.span, &raw)?,
+            None => self.wr.write_lit(v.span, &v.value.to_string())?,
+        }
         self.wr.write_lit(v.span, "n")?;
     }
 
+    /// Returns the source text of a numeric literal if it's valid for the
+    /// target.
+    fn raw_num_lit<'r>(&self, raw: Option<&'r JsWord>) -> Option<Cow<'r, str>> {
+        if self.cfg.minify {
+            return None;
+        }
+        let target = self.cfg.target?;
+        let raw: &str = &**raw?;
+
+        let bytes = raw.as_bytes();
+        if bytes.len() > 1 && bytes[0] == b'0' {
+            match bytes[1] {
+                // Legacy octal literals like `010` are invalid in strict mode.
+                b'0'..=b'9' => return None,
+                b'b' | b'B' | b'o' | b'O' if target < JscTarget::Es2015 => return None,
+                _ => {}
+            }
+        }
+
+        if target < JscTarget::Es2021 && raw.contains('_') {
+            return Some(Cow::Owned(raw.replace('_', "")));
+        }
+
+        Some(Cow::Borrowed(raw))
+    }
+
     // fn emit_object_binding_pat(&mut self, node: &ObjectPat) -> Result {
     //     self.wr.write_punct("{")?;
     //     self.emit_list(
@@ -608,7 +643,17 @@ impl<'a> Emitter<'a> {
         match *expr {
             ExprOrSuper::Expr(ref expr) => {
                 match **expr {
-                    Expr::Lit(Lit::Num(Number { span, value })) => {
+                    Expr::Lit(Lit::Num(Number {
+                        span,
+                        value,
+                        ref raw,
+                    })) => {
+                        if let Some(raw) = self.raw_num_lit(raw.as_ref()) {
+                            // `1.toString()` is invalid, but `1e3.toString()` and
+                            // `0x1.toString()` are valid.
+                            return raw.bytes().all(|c| c.is_ascii_digit() || c == b'_');
+                        }
+
                         if value.fract() == 0.0 {
                             return true;
                         }
@@ -763,6 +808,18 @@ impl<'a> Emitter<'a> {
             keyword!("extends");
             space!();
             emit!(node.super_class);
+            emit!(node.super_type_params);
+            space!();
+        }
+
+        if !node.implements.is_empty() {
+            keyword!("implements");
+            space!();
+            self.emit_list(
+                node.span,
+                Some(&node.implements),
+                ListFormat::HeritageClauseTypes,
+            )?;
             space!();
         }
 
@@ -841,6 +898,12 @@ impl<'a> Emitter<'a> {
             keyword!("static");
             space!();
         }
+
+        if n.is_abstract {
+            keyword!("abstract");
+            space!();
+        }
+
         match n.kind {
             MethodKind::Method => {
                 if n.function.is_async {
@@ -867,6 +930,14 @@ impl<'a> Emitter<'a> {
             }
         }
 
+        if n.is_optional {
+            punct!("?");
+        }
+
+        if let Some(type_params) = &n.function.type_params {
+            emit!(type_params);
+        }
+
         punct!("(");
         self.emit_list(
             n.function.span,
@@ -904,16 +975,21 @@ impl<'a> Emitter<'a> {
             self.emit_accesibility(n.accessibility)?;
         }
 
-        if n.readonly {
-            keyword!("readonly");
-            space!()
-        }
-
         if n.is_static {
             keyword!("static");
             space!();
         }
 
+        if n.is_abstract {
+            keyword!("abstract");
+            space!();
+        }
+
+        if n.readonly {
+            keyword!("readonly");
+            space!()
+        }
+
         if n.computed {
             punct!("[");
             emit!(n.key);
@@ -922,6 +998,14 @@ impl<'a> Emitter<'a> {
             emit!(n.key);
         }
 
+        if n.is_optional {
+            punct!("?");
+        }
+
+        if n.definite {
+            punct!("!");
+        }
+
         if let Some(ty) = &n.type_ann {
             punct!(":");
             space!();
@@ -1354,6 +1438,10 @@ impl<'a> Emitter<'a> {
             // TODO: span
             self.wr.write_symbol(ident.span, &ident.sym)?;
 
+            if ident.optional {
+                punct!("?");
+            }
+
             if let Some(ty) = &ident.type_ann {
                 punct!(":");
                 formatting_space!();
@@ -2368,6 +2456,9 @@ fn escape(s: &str) -> Cow<str> {
             .replace("\07", "\\x007")
             .replace("\08", "\\x008")
             .replace("\09", "\\x009")
-            .replace("\0", "\\0"),
+            .replace("\0", "\\0")
+            // Line terminators are not allowed in string literals before es2019.
+            .replace('\u{2028}', "\\u2028")
+            .replace('\u{2029}', "\\u2029"),
     )
 }
@@ -1,4 +1,4 @@
-use self::swc_ecma_parser::{EsConfig, Parser, Session, SourceFileInput, Syntax};
+use self::swc_ecma_parser::{EsConfig, JscTarget, Parser, Session, SourceFileInput, Syntax};
 use super::*;
 use crate::config::Config;
 use std::{
@@ -76,13 +76,27 @@ fn parse_then_emit(from: &str, cfg: Config, syntax: Syntax) -> String {
 }
 
 pub(crate) fn assert_min(from: &str, to: &str) {
-    let out = parse_then_emit(from, Config { minify: true }, Syntax::default());
+    let out = parse_then_emit(
+        from,
+        Config {
+            minify: true,
+            ..Default::default()
+        },
+        Syntax::default(),
+    );
 
     assert_eq!(DebugUsingDisplay(out.trim()), DebugUsingDisplay(to),);
 }
 
 pub(crate) fn assert_pretty(from: &str, to: &str) {
-    let out = parse_then_emit(from, Config { minify: false }, Syntax::default());
+    let out = parse_then_emit(
+        from,
+        Config {
+            minify: false,
+            ..Default::default()
+        },
+        Syntax::default(),
+    );
 
     assert_eq!(DebugUsingDisplay(&out.trim()), DebugUsingDisplay(to),);
 }
@@ -157,6 +171,11 @@ a;",
     );
 }
 
+#[test]
+fn line_terminators_in_str() {
+    test_from_to(r#"'\u2028\u2029';"#, r#"'\u2028\u2029';"#);
+}
+
 #[test]
 fn no_octal_escape() {
     test_from_to(
@@ -171,6 +190,14 @@ fn no_octal_escape() {
     );
 }
 
+#[test]
+fn logical_assign() {
+    test_from_to(
+        "a &&= b; a.b ||= c; a[b] ??= d;",
+        "a &&= b;\na.b ||= c;\na[b] ??= d;",
+    );
+}
+
 #[test]
 fn empty_named_export() {
     test_from_to("export { }", "export { };");
@@ -181,7 +208,10 @@ fn empty_named_export_min() {
     test_from_to_custom_config(
         "export { }",
         "export{};",
-        Config { minify: true },
+        Config {
+            minify: true,
+            ..Default::default()
+        },
         Default::default(),
     );
 }
@@ -196,7 +226,10 @@ fn empty_named_export_from_min() {
     test_from_to_custom_config(
         "export { } from 'foo';",
         "export{}from'foo';",
-        Config { minify: true },
+        Config {
+            minify: true,
+            ..Default::default()
+        },
         Default::default(),
     );
 }
@@ -211,7 +244,10 @@ fn named_export_from_min() {
     test_from_to_custom_config(
         "export { bar } from 'foo';",
         "export{bar}from'foo';",
-        Config { minify: true },
+        Config {
+            minify: true,
+            ..Default::default()
+        },
         Default::default(),
     );
 }
@@ -234,7 +270,10 @@ fn export_namespace_from_min() {
     test_from_to_custom_config(
         "export * as Foo from 'foo';",
         "export*as Foo from'foo';",
-        Config { minify: true },
+        Config {
+            minify: true,
+            ..Default::default()
+        },
         Syntax::Es(EsConfig {
             export_namespace_from: true,
             ..EsConfig::default()
@@ -260,7 +299,10 @@ fn named_and_namespace_export_from_min() {
     test_from_to_custom_config(
         "export * as Foo, { bar } from 'foo';",
         "export*as Foo,{bar}from'foo';",
-        Config { minify: true },
+        Config {
+            minify: true,
+            ..Default::default()
+        },
         Syntax::Es(EsConfig {
             export_namespace_from: true,
             ..EsConfig::default()
@@ -306,6 +348,69 @@ fn issue_639() {
     test_from_to(r"`\x1b[33m Yellow \x1b[0m`;", r"`\x1b[33m Yellow \x1b[0m`;");
 }
 
+#[test]
+fn raw_num_lit() {
+    fn test(target: Option<JscTarget>, from: &str, to: &str) {
+        test_from_to_custom_config(
+            from,
+            to,
+            Config {
+                target,
+                ..Default::default()
+            },
+            Syntax::Es(EsConfig {
+                num_sep: true,
+                ..Default::default()
+            }),
+        );
+    }
+
+    test(None, "0x1F, 1e3, 1_000;", "31, 1000, 1000;");
+    test(
+        Some(JscTarget::Es5),
+        "0x1F, 1e3, 1_000;",
+        "0x1F, 1e3, 1000;",
+    );
+    test(Some(JscTarget::Es2021), "1_000, 0x1_0n;", "1_000, 0x1_0n;");
+    test(Some(JscTarget::Es2020), "1_000, 0x1_0n;", "1000, 0x10n;");
+    test(Some(JscTarget::Es5), "0b11, 0o7;", "3, 7;");
+    test(Some(JscTarget::Es2015), "0b11, 0o7;", "0b11, 0o7;");
+}
+
+#[test]
+fn raw_num_lit_member() {
+    let cfg = Config {
+        target: Some(JscTarget::Es5),
+        ..Default::default()
+    };
+
+    test_from_to_custom_config("1..toString();", "1..toString();", cfg, Default::default());
+    test_from_to_custom_config(
+        "1.0.toString();",
+        "1.0.toString();",
+        cfg,
+        Default::default(),
+    );
+    test_from_to_custom_config(
+        "0x1.toString();",
+        "0x1.toString();",
+        cfg,
+        Default::default(),
+    );
+    test_from_to_custom_config(
+        "1_000..toString();",
+        "1_000..toString();",
+        Config {
+            target: Some(JscTarget::Es2021),
+            ..Default::default()
+        },
+        Syntax::Es(EsConfig {
+            num_sep: true,
+            ..Default::default()
+        }),
+    );
+}
+
 #[derive(Debug, Clone)]
 struct Buf(Arc<RwLock<Vec<u8>>>);
 impl Write for Buf {
@@ -4,6 +4,7 @@ use std::{
     sync::Arc,
 };
 use swc_common::{BytePos, LineCol, SourceMap, Span};
+use swc_ecma_parser::lexer::util::CharExt;
 
 ///
 /// -----
@@ -12,14 +13,17 @@ use swc_common::{BytePos, LineCol, SourceMap, Span};
 ///
 /// https://github.com/Microsoft/TypeScript/blob/45eaf42006/src/compiler/utilities.ts#L2548
 pub struct JsWriter<'a, W: Write> {
-    /// We may use this in future...
-    _cm: Arc<SourceMap>,
+    /// Used to get original names of identifiers.
+    cm: Arc<SourceMap>,
     indent: usize,
     line_start: bool,
     line_count: usize,
     line_pos: usize,
     new_line: &'a str,
     srcmap: Option<&'a mut Vec<(BytePos, LineCol)>>,
+    /// Original names of renamed identifiers, keyed by the start of their
+    /// span.
+    names: Option<&'a mut Vec<(BytePos, String)>>,
     wr: W,
     written_bytes: usize,
 }
@@ -32,18 +36,28 @@ impl<'a, W: Write> JsWriter<'a, W> {
         srcmap: Option<&'a mut Vec<(BytePos, LineCol)>>,
     ) -> Self {
         JsWriter {
-            _cm: cm,
+            cm,
             indent: Default::default(),
             line_start: true,
             line_count: 0,
             line_pos: Default::default(),
             new_line,
             srcmap,
+            names: None,
             wr,
             written_bytes: 0,
         }
     }
 
+    /// Records original names of identifiers renamed by hygiene or the
+    /// mangler, so the source map can have `names`.
+    ///
+    /// This has no effect if source map is disabled.
+    pub fn with_names(mut self, names: &'a mut Vec<(BytePos, String)>) -> Self {
+        self.names = Some(names);
+        self
+    }
+
     fn write_indent_string(&mut self) -> io::Result<usize> {
         const INDENT: &[u8] = b"    ";
 
@@ -88,6 +102,27 @@ impl<'a, W: Write> JsWriter<'a, W> {
         Ok(cnt)
     }
 
+    fn record_name(&mut self, span: Span, sym: &str) {
+        if self.srcmap.is_none() || span.is_dummy() {
+            return;
+        }
+
+        if let Some(ref mut names) = self.names {
+            let orig = match self.cm.span_to_snippet(span) {
+                Ok(orig) => orig,
+                Err(..) => return,
+            };
+
+            // Synthesized identifiers may have a span of other node.
+            let is_ident = orig.chars().next().map_or(false, |c| c.is_ident_start())
+                && orig.chars().all(|c| c.is_ident_part());
+
+            if is_ident && orig != sym {
+                names.push((span.lo(), orig));
+            }
+        }
+    }
+
     fn srcmap(&mut self, byte_pos: BytePos) {
         if let Some(ref mut srcmap) = self.srcmap {
             srcmap.push((
@@ -182,6 +217,7 @@ impl<'a, W: Write> WriteJs for JsWriter<'a, W> {
     }
 
     fn write_symbol(&mut self, span: Span, s: &str) -> Result {
+        self.record_name(span, s);
         self.write(Some(span), s)?;
         Ok(())
     }
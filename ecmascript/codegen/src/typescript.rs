@@ -166,7 +166,12 @@ impl<'a> Emitter<'a> {
     fn emit_ts_export_assignment(&mut self, n: &TsExportAssignment) -> Result {
         self.emit_leading_comments_of_pos(n.span().lo())?;
 
-        unimplemented!("emit_ts_export_assignment")
+        keyword!("export");
+        formatting_space!();
+        punct!("=");
+        formatting_space!();
+        emit!(n.expr);
+        semi!();
     }
 
     #[emitter]
@@ -182,7 +187,10 @@ impl<'a> Emitter<'a> {
     fn emit_ts_external_module_ref(&mut self, n: &TsExternalModuleRef) -> Result {
         self.emit_leading_comments_of_pos(n.span().lo())?;
 
-        unimplemented!("emit_ts_external_module_ref")
+        keyword!("require");
+        punct!("(");
+        emit!(n.expr);
+        punct!(")");
     }
 
     #[emitter]
@@ -226,7 +234,19 @@ impl<'a> Emitter<'a> {
     fn emit_ts_import_equals_decl(&mut self, n: &TsImportEqualsDecl) -> Result {
         self.emit_leading_comments_of_pos(n.span().lo())?;
 
-        unimplemented!("emit_ts_import_equals_decl")
+        if n.is_export {
+            keyword!("export");
+            space!();
+        }
+
+        keyword!("import");
+        space!();
+        emit!(n.id);
+        formatting_space!();
+        punct!("=");
+        formatting_space!();
+        emit!(n.module_ref);
+        semi!();
     }
 
     #[emitter]
@@ -476,9 +496,13 @@ impl<'a> Emitter<'a> {
             space!();
         }
 
-        keyword!("module");
-        space!();
-        emit!(n.id);
+        if n.global {
+            keyword!("global");
+        } else {
+            keyword!("module");
+            space!();
+            emit!(n.id);
+        }
         formatting_space!();
 
         if let Some(body) = &n.body {
@@ -496,9 +520,10 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     fn emit_ts_module_ref(&mut self, n: &TsModuleRef) -> Result {
-        self.emit_leading_comments_of_pos(n.span().lo())?;
-
-        unimplemented!("emit_ts_module_ref")
+        match n {
+            TsModuleRef::TsEntityName(n) => emit!(n),
+            TsModuleRef::TsExternalModuleRef(n) => emit!(n),
+        }
     }
 
     #[emitter]
@@ -44,8 +44,20 @@ pub struct Error {
     pub error: SyntaxError,
 }
 
+impl Error {
+    /// Creates a diagnostic which can be emitted using `handler`.
+    pub fn into_diagnostic(self, handler: &Handler) -> DiagnosticBuilder {
+        DiagnosticBuilder::from(ErrorToDiag {
+            handler,
+            span: self.span,
+            error: self.error,
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum SyntaxError {
+    Eof,
     TopLevelAwait,
 
     LegacyDecimal,
@@ -232,6 +244,7 @@ impl<'a> From<ErrorToDiag<'a>> for DiagnosticBuilder<'a> {
     #[cold]
     fn from(e: ErrorToDiag<'a>) -> Self {
         let msg: Cow<'static, _> = match e.error {
+            Eof => "Unexpected eof".into(),
             TopLevelAwait => "top level await requires target to es2017 or higher and \
                               topLevelAwait:true for ecmascript"
                 .into(),
@@ -14,9 +14,10 @@ use crate::{
     token::*,
     Context, JscTarget, Session, Syntax,
 };
-use either::Either::{Left, Right};
+use either::Either::{self, Left, Right};
+use num_bigint::BigInt as BigIntValue;
 use smallvec::{smallvec, SmallVec};
-use std::{char, iter::FusedIterator, mem::take};
+use std::{cell::RefCell, char, iter::FusedIterator, mem::take, rc::Rc};
 use swc_atoms::{js_word, JsWord};
 use swc_common::{
     comments::{Comment, Comments},
@@ -104,6 +105,9 @@ pub struct Lexer<'a, I: Input> {
     pub(crate) syntax: Syntax,
     pub(crate) target: JscTarget,
 
+    /// Errors stored while recovering from errors.
+    errors: Rc<RefCell<Vec<Error>>>,
+
     buf: String,
 }
 
@@ -130,6 +134,7 @@ impl<'a, I: Input> Lexer<'a, I> {
             ctx: Default::default(),
             syntax,
             target,
+            errors: Default::default(),
             buf: String::with_capacity(16),
         }
     }
@@ -178,10 +183,7 @@ impl<'a, I: Input> Lexer<'a, I> {
                 if '0' <= next && next <= '9' {
                     return self
                         .read_number(true)
-                        .map(|v| match v {
-                            Left(v) => Num(v),
-                            Right(v) => BigInt(v),
-                        })
+                        .map(|v| self.num_token(start, v))
                         .map(Some);
                 }
 
@@ -219,6 +221,13 @@ impl<'a, I: Input> Lexer<'a, I> {
                 Some('?') => {
                     self.input.bump();
                     self.input.bump();
+
+                    // '??='
+                    if self.input.cur() == Some('=') {
+                        self.input.bump();
+                        return Ok(Some(AssignOp(NullishAssign)));
+                    }
+
                     return Ok(Some(tok!("??")));
                 }
                 _ => {
@@ -253,29 +262,20 @@ impl<'a, I: Input> Lexer<'a, I> {
                     _ => {
                         return self
                             .read_number(false)
-                            .map(|v| match v {
-                                Left(v) => Num(v),
-                                Right(v) => BigInt(v),
-                            })
+                            .map(|v| self.num_token(start, v))
                             .map(Some)
                     }
                 };
 
                 return self
                     .read_radix_number(radix)
-                    .map(|v| match v {
-                        Left(v) => Num(v),
-                        Right(v) => BigInt(v),
-                    })
+                    .map(|v| self.num_token(start, v))
                     .map(Some);
             }
             '1'..='9' => {
                 return self
                     .read_number(false)
-                    .map(|v| match v {
-                        Left(v) => Num(v),
-                        Right(v) => BigInt(v),
-                    })
+                    .map(|v| self.num_token(start, v))
                     .map(Some)
             }
 
@@ -325,6 +325,17 @@ impl<'a, I: Input> Lexer<'a, I> {
                 // '||', '&&'
                 if self.input.cur() == Some(c) {
                     self.input.bump();
+
+                    // '||=', '&&='
+                    if self.input.cur() == Some('=') {
+                        self.input.bump();
+                        return Ok(Some(AssignOp(match token {
+                            BitAnd => AndAssign,
+                            BitOr => OrAssign,
+                            _ => unreachable!(),
+                        })));
+                    }
+
                     return Ok(Some(BinOp(match token {
                         BitAnd => LogicalAnd,
                         BitOr => LogicalOr,
@@ -419,6 +430,25 @@ impl<'a, I: Input> Lexer<'a, I> {
         Ok(Some(token))
     }
 
+    /// Creates a numeric literal token which started at `start`, storing the
+    /// source text of the literal.
+    fn num_token(&mut self, start: BytePos, v: Either<f64, BigIntValue>) -> Token {
+        let end = self.cur_pos();
+        let raw = self.input.slice(start, end);
+
+        match v {
+            Left(value) => Num {
+                value,
+                raw: raw.into(),
+            },
+            Right(value) => BigInt {
+                value,
+                // Strip `n`
+                raw: raw[..raw.len() - 1].into(),
+            },
+        }
+    }
+
     /// `#`
     fn read_token_number_sign(&mut self) -> LexResult<Option<Token>> {
         debug_assert!(self.cur().is_some());
@@ -553,6 +553,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn raw() {
+        fn token(s: &'static str) -> Token {
+            lex(s, |l| l.next().unwrap().token)
+        }
+
+        assert_eq!(
+            token("0x1F"),
+            Num {
+                value: 31.0,
+                raw: "0x1F".into(),
+            }
+        );
+        assert_eq!(
+            token("1_000"),
+            Num {
+                value: 1000.0,
+                raw: "1_000".into(),
+            }
+        );
+        assert_eq!(
+            token("0b11n"),
+            BigInt {
+                value: 3.into(),
+                raw: "0b11".into(),
+            }
+        );
+    }
+
     /// Valid even on strict mode.
     const VALID_CASES: &[&str] = &[".0", "0.e-1", "0e8", ".8e1", "0.8e1", "1.18e1"];
     const INVALID_CASES_ON_STRICT: &[&str] = &["08e1", "08.1", "08.8e1", "08", "01"];
@@ -592,9 +621,21 @@ mod tests {
                 };
                 assert_eq!(vec.len(), 1);
                 let token = vec.into_iter().next().unwrap();
-                assert_eq!(Num(expected), token);
+                assert_eq!(
+                    Num {
+                        value: expected,
+                        raw: (*case).into(),
+                    },
+                    token
+                );
             } else if let Ok(vec) = vec {
-                assert_ne!(vec![Num(expected)], vec)
+                assert_ne!(
+                    vec![Num {
+                        value: expected,
+                        raw: (*case).into(),
+                    }],
+                    vec
+                )
             }
         }
     }
@@ -1,5 +1,5 @@
 use super::{Context, Input, Lexer};
-use crate::{input::Tokens, lexer::util::CharExt, token::*, JscTarget, Syntax};
+use crate::{error::Error, input::Tokens, lexer::util::CharExt, token::*, JscTarget, Syntax};
 use enum_kind::Kind;
 use log::trace;
 use std::mem;
@@ -83,12 +83,12 @@ impl<'a> From<&'a Token> for TokenType {
             _ => TokenType::Other {
                 before_expr: t.before_expr(),
                 can_have_trailing_comment: match *t {
-                    Token::Num(..)
+                    Token::Num { .. }
                     | Token::Str { .. }
                     | Token::Word(Word::Ident(..))
                     | Token::DollarLBrace
                     | Token::Regex(..)
-                    | Token::BigInt(..)
+                    | Token::BigInt { .. }
                     | Token::JSXText { .. }
                     | Token::RBrace => true,
 
@@ -129,6 +129,14 @@ impl<I: Input> Tokens for Lexer<'_, I> {
     fn set_token_context(&mut self, c: TokenContexts) {
         self.state.context = c;
     }
+
+    fn add_error(&self, error: Error) {
+        self.errors.borrow_mut().push(error);
+    }
+
+    fn take_errors(&mut self) -> Vec<Error> {
+        mem::replace(&mut *self.errors.borrow_mut(), Default::default())
+    }
 }
 
 impl<'a, I: Input> Iterator for Lexer<'a, I> {
@@ -179,7 +187,7 @@ impl<'a, I: Input> Iterator for Lexer<'a, I> {
 
             self.state.start = start;
 
-            if self.syntax.typescript() && self.ctx.in_type {
+            if self.syntax.typed() && self.ctx.in_type {
                 if c == '<' {
                     self.input.bump();
                     return Ok(Some(tok!('<')));
@@ -233,6 +241,12 @@ impl<'a, I: Input> Iterator for Lexer<'a, I> {
             }
         })();
 
+        if res.is_err() && self.ctx.recover && self.cur_pos() == start && self.input.cur().is_some()
+        {
+            // Skip the invalid character so that lexing can continue.
+            self.input.bump();
+        }
+
         let token = match res.map_err(Token::Error).map_err(Some) {
             Ok(t) => t,
             Err(e) => e,
@@ -77,12 +77,18 @@ impl WithSpan for Token {
 }
 impl WithSpan for usize {
     fn into_token(self) -> Token {
-        Num(self as f64)
+        Num {
+            value: self as f64,
+            raw: self.to_string().into(),
+        }
     }
 }
 impl WithSpan for f64 {
     fn into_token(self) -> Token {
-        Num(self)
+        Num {
+            value: self,
+            raw: self.to_string().into(),
+        }
     }
 }
 impl<'a> WithSpan for &'a str {
@@ -168,7 +174,12 @@ fn test262_lexer_error_0001() {
     assert_eq!(
         lex(Syntax::default(), "123..a(1)"),
         vec![
-            123f64.span(0..4).lb(),
+            Num {
+                value: 123.0,
+                raw: "123.".into(),
+            }
+            .span(0..4)
+            .lb(),
             Dot.span(4..5),
             "a".span(5..6),
             LParen.span(6..7),
@@ -470,6 +481,24 @@ fn complex_divide() {
     )
 }
 
+#[test]
+fn logical_assign() {
+    assert_eq!(
+        lex_tokens(Syntax::default(), "a &&= b ||= c ??= d ?? e"),
+        vec![
+            Word(Word::Ident("a".into())),
+            AssignOp(AndAssign),
+            Word(Word::Ident("b".into())),
+            AssignOp(OrAssign),
+            Word(Word::Ident("c".into())),
+            AssignOp(NullishAssign),
+            Word(Word::Ident("d".into())),
+            BinOp(NullishCoalescing),
+            Word(Word::Ident("e".into())),
+        ]
+    )
+}
+
 // ---------- Tests from tc39 spec
 
 #[test]
@@ -6,7 +6,7 @@
 //!
 //! [babylon/util/identifier.js]:https://github.com/babel/babel/blob/master/packages/babylon/src/util/identifier.js
 use super::{input::Input, Char, LexResult, Lexer};
-use crate::error::{ErrorToDiag, SyntaxError};
+use crate::error::{Error, ErrorToDiag, SyntaxError};
 use std::char;
 use swc_common::{
     comments::{Comment, CommentKind},
@@ -115,6 +115,11 @@ impl<'a, I: Input> Lexer<'a, I> {
 
     #[cold]
     pub(super) fn emit_error_span(&mut self, span: Span, kind: SyntaxError) {
+        if self.ctx.recover {
+            self.errors.borrow_mut().push(Error { span, error: kind });
+            return;
+        }
+
         let err = ErrorToDiag {
             handler: self.session.handler,
             span,
@@ -98,6 +98,7 @@
 #![deny(unsafe_code)]
 
 pub use self::{
+    error::{Error, SyntaxError},
     lexer::input::{Input, SourceFileInput},
     parser::*,
 };
@@ -119,6 +120,8 @@ pub enum Syntax {
     Es(EsConfig),
     #[serde(rename = "typescript")]
     Typescript(TsConfig),
+    #[serde(rename = "flow")]
+    Flow(FlowConfig),
 }
 
 impl Default for Syntax {
@@ -132,7 +135,8 @@ impl Syntax {
     pub fn jsx(self) -> bool {
         match self {
             Syntax::Es(EsConfig { jsx: true, .. })
-            | Syntax::Typescript(TsConfig { tsx: true, .. }) => true,
+            | Syntax::Typescript(TsConfig { tsx: true, .. })
+            | Syntax::Flow(FlowConfig { jsx: true, .. }) => true,
             _ => false,
         }
     }
@@ -150,6 +154,10 @@ impl Syntax {
             | Syntax::Typescript(TsConfig {
                 dynamic_import: true,
                 ..
+            })
+            | Syntax::Flow(FlowConfig {
+                dynamic_import: true,
+                ..
             }) => true,
             _ => false,
         }
@@ -173,6 +181,9 @@ impl Syntax {
             })
             | Syntax::Typescript(TsConfig {
                 decorators: true, ..
+            })
+            | Syntax::Flow(FlowConfig {
+                decorators: true, ..
             }) => true,
             _ => false,
         }
@@ -188,7 +199,8 @@ impl Syntax {
                 class_private_props: true,
                 ..
             })
-            | Syntax::Typescript(..) => true,
+            | Syntax::Typescript(..)
+            | Syntax::Flow(..) => true,
             _ => false,
         }
     }
@@ -216,6 +228,25 @@ impl Syntax {
         }
     }
 
+    /// Should we parse flow?
+    pub fn flow(self) -> bool {
+        match self {
+            Syntax::Flow(..) => true,
+            _ => false,
+        }
+    }
+
+    /// Type annotations are allowed.
+    ///
+    /// Flow is parsed into the typescript ast, so most of the typescript
+    /// parser is shared between the two.
+    pub(crate) fn typed(self) -> bool {
+        match self {
+            Syntax::Typescript(..) | Syntax::Flow(..) => true,
+            _ => false,
+        }
+    }
+
     pub fn export_default_from(self) -> bool {
         match self {
             Syntax::Es(EsConfig {
@@ -232,7 +263,8 @@ impl Syntax {
                 export_namespace_from: true,
                 ..
             })
-            | Syntax::Typescript(..) => true,
+            | Syntax::Typescript(..)
+            | Syntax::Flow(..) => true,
             _ => false,
         }
     }
@@ -246,7 +278,8 @@ impl Syntax {
             Syntax::Es(EsConfig {
                 import_meta: true, ..
             })
-            | Syntax::Typescript(..) => true,
+            | Syntax::Typescript(..)
+            | Syntax::Flow(..) => true,
 
             _ => false,
         }
@@ -258,7 +291,8 @@ impl Syntax {
                 top_level_await: true,
                 ..
             })
-            | Syntax::Typescript(..) => true,
+            | Syntax::Typescript(..)
+            | Syntax::Flow(..) => true,
 
             _ => false,
         }
@@ -274,7 +308,7 @@ impl Syntax {
     pub(crate) fn early_errors(self) -> bool {
         match self {
             Syntax::Typescript(t) => !t.no_early_errors,
-            Syntax::Es(..) => true,
+            Syntax::Es(..) | Syntax::Flow(..) => true,
         }
     }
 }
@@ -299,6 +333,19 @@ pub struct TsConfig {
     pub no_early_errors: bool,
 }
 
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct FlowConfig {
+    #[serde(default)]
+    pub jsx: bool,
+
+    #[serde(default)]
+    pub decorators: bool,
+
+    #[serde(default)]
+    pub dynamic_import: bool,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialOrd, Ord, PartialEq, Eq)]
 pub enum JscTarget {
     #[serde(rename = "es3")]
@@ -317,6 +364,11 @@ pub enum JscTarget {
     Es2019,
     #[serde(rename = "es2020")]
     Es2020,
+    #[serde(rename = "es2021")]
+    Es2021,
+    /// Latest ecmascript, including stage 3 proposals.
+    #[serde(rename = "esnext")]
+    EsNext,
 }
 
 impl Default for JscTarget {
@@ -427,6 +479,14 @@ pub struct Context {
 
     /// If true, `:` should not be treated as a type annotation.
     in_case_cond: bool,
+
+    /// Flow: if true, `T => U` is not parsed as a function type. Used for
+    /// return types of arrow functions.
+    no_anon_fn_type: bool,
+
+    /// If true, errors are stored instead of being emitted, and the parser
+    /// recovers from fatal errors.
+    recover: bool,
 }
 
 #[derive(Clone, Copy)]
@@ -32,6 +32,9 @@ macro_rules! tok {
     ('|') => {
         crate::token::Token::BinOp(crate::token::BinOpToken::BitOr)
     };
+    ("||") => {
+        crate::token::Token::BinOp(crate::token::BinOpToken::LogicalOr)
+    };
 
     (',') => {
         crate::token::Token::Comma
@@ -347,6 +350,16 @@ macro_rules! tok {
     ("type") => {
         crate::token::Token::Word(crate::token::Word::Ident(swc_atoms::js_word!("type")))
     };
+
+    // ----------
+    // Flow
+    // ----------
+    ("opaque") => {
+        crate::token::Token::Word(crate::token::Word::Ident(swc_atoms::js_word!("opaque")))
+    };
+    ("checks") => {
+        crate::token::Token::Word(crate::token::Word::Ident(swc_atoms::js_word!("checks")))
+    };
 }
 
 macro_rules! token_including_semi {
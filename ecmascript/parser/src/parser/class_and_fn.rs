@@ -89,7 +89,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 p.emit_err(span, SyntaxError::TS2414);
             }
 
-            let type_params = if p.input.syntax().typescript() {
+            let type_params = if p.input.syntax().typed() {
                 p.try_parse_ts_type_params()?
             } else {
                 None
@@ -97,7 +97,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
             let (mut super_class, mut super_type_params) = if eat!("extends") {
                 let super_class = p.parse_lhs_expr().map(Some)?;
-                let super_type_params = if p.input.syntax().typescript() && is!('<') {
+                let super_type_params = if p.input.syntax().typed() && is!('<') {
                     Some(p.parse_ts_type_args()?)
                 } else {
                     None
@@ -121,12 +121,12 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 p.emit_err(make_span(p.input.prev_span()), SyntaxError::TS1172);
 
                 p.parse_lhs_expr()?;
-                if p.input.syntax().typescript() && is!('<') {
+                if p.input.syntax().typed() && is!('<') {
                     p.parse_ts_type_args()?;
                 }
             };
 
-            let implements = if p.input.syntax().typescript() && eat!("implements") {
+            let implements = if p.input.syntax().typed() && eat!("implements") {
                 p.parse_ts_heritage_clause()?
             } else {
                 vec![]
@@ -146,7 +146,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 p.emit_err(make_span(p.input.prev_span()), SyntaxError::TS1175);
 
                 let sc = p.parse_lhs_expr()?;
-                let type_params = if p.input.syntax().typescript() && is!('<') {
+                let type_params = if p.input.syntax().typed() && is!('<') {
                     p.parse_ts_type_args().map(Some)?
                 } else {
                     None
@@ -252,7 +252,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     }
 
     fn parse_maybe_decorator_args(&mut self, expr: Box<Expr>) -> PResult<'a, Box<Expr>> {
-        let type_args = if self.input.syntax().typescript() && is!('<') {
+        let type_args = if self.input.syntax().typed() && is!('<') {
             Some(self.parse_ts_type_args()?)
         } else {
             None
@@ -311,7 +311,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                     js_word!("declare"),
                     span!(start),
                 )));
-                let is_optional = self.input.syntax().typescript() && eat!('?');
+                let is_optional = self.input.syntax().typed() && eat!('?');
                 return self.make_method(
                     |p| p.parse_unique_formal_params(),
                     MakeMethodArgs {
@@ -334,7 +334,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                     js_word!("declare"),
                     span!(start),
                 )));
-                let is_optional = self.input.syntax().typescript() && eat!('?');
+                let is_optional = self.input.syntax().typed() && eat!('?');
                 return self.make_property(
                     start,
                     decorators,
@@ -372,7 +372,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                     js_word!("static"),
                     static_token,
                 )));
-                let is_optional = self.input.syntax().typescript() && eat!('?');
+                let is_optional = self.input.syntax().typed() && eat!('?');
                 return self.make_method(
                     |p| p.parse_unique_formal_params(),
                     MakeMethodArgs {
@@ -395,7 +395,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                     js_word!("static"),
                     static_token,
                 )));
-                let is_optional = self.input.syntax().typescript() && eat!('?');
+                let is_optional = self.input.syntax().typed() && eat!('?');
                 return self.make_property(
                     start,
                     decorators,
@@ -436,8 +436,12 @@ impl<'a, I: Tokens> Parser<'a, I> {
             _ => (false, false),
         };
 
-        if self.input.syntax().typescript() && !is_abstract && !is_static && accessibility.is_none()
-        {
+        if self.input.syntax().flow() && is_one_of!('+', '-') {
+            // Variance sigils of flow are ignored.
+            bump!();
+        }
+
+        if self.input.syntax().typed() && !is_abstract && !is_static && accessibility.is_none() {
             let idx = self.try_parse_ts_index_signature(start, readonly)?;
             if let Some(idx) = idx {
                 return Ok(idx.into());
@@ -480,7 +484,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
             Either::Right(PropName::Ident(..)) => true,
             _ => false,
         };
-        let is_optional = self.input.syntax().typescript() && eat!('?');
+        let is_optional = self.input.syntax().typed() && eat!('?');
 
         if self.is_class_method()? {
             // handle a(){} / get(){} / set(){} / async(){}
@@ -491,7 +495,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
             let is_constructor = is_constructor(&key);
 
             if is_constructor {
-                if self.syntax().typescript() && is!('<') {
+                if self.syntax().typed() && is!('<') {
                     let start = cur_pos!();
                     if peeked_is!('>') {
                         assert_and_bump!('<');
@@ -515,7 +519,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 let params = self.parse_constructor_params()?;
                 expect!(')');
 
-                if self.syntax().typescript() && is!(':') {
+                if self.syntax().typed() && is!(':') {
                     let start = cur_pos!();
                     let type_ann = self.parse_ts_type_ann(true, start)?;
 
@@ -528,7 +532,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 };
                 let body: Option<_> = self.with_ctx(ctx).parse_fn_body(false, false)?;
 
-                if self.syntax().typescript() && body.is_none() {
+                if self.syntax().typed() && body.is_none() {
                     // Declare constructors cannot have assignment pattern in parameters
                     for p in &params {
                         // TODO: Search deeply for assignment pattern using a Visitor
@@ -800,11 +804,13 @@ impl<'a, I: Tokens> Parser<'a, I> {
     }
 
     fn is_class_method(&mut self) -> PResult<'a, bool> {
-        Ok(is!('(') || (self.input.syntax().typescript() && is!('<')))
+        Ok(is!('(') || (self.input.syntax().typed() && is!('<')))
     }
 
     fn is_class_property(&mut self) -> PResult<'a, bool> {
-        Ok((self.input.syntax().typescript() && is_one_of!('!', ':')) || is_one_of!('=', ';', '}'))
+        Ok((self.input.syntax().typescript() && is!('!'))
+            || (self.input.syntax().typed() && is!(':'))
+            || is_one_of!('=', ';', '}'))
     }
 
     fn parse_fn<T>(
@@ -898,7 +904,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         };
 
         self.with_ctx(ctx).parse_with(|p| {
-            let type_params = if p.syntax().typescript() && is!('<') {
+            let type_params = if p.syntax().typed() && is!('<') {
                 //
                 Some(p.parse_ts_type_params()?)
             } else {
@@ -917,7 +923,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
             expect!(')');
 
             // typescript extension
-            let return_type = if p.syntax().typescript() && is!(':') {
+            let return_type = if p.syntax().typed() && is!(':') {
                 p.parse_ts_type_or_type_predicate_ann(&tok!(':'))
                     .map(Some)?
             } else {
@@ -926,7 +932,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
             let body: Option<_> = p.parse_fn_body(is_async, is_generator)?;
 
-            if p.syntax().typescript() && body.is_none() {
+            if p.syntax().typed() && body.is_none() {
                 // Declare functions cannot have assignment pattern in parameters
                 for param in &params {
                     // TODO: Search deeply for assignment pattern using a Visitor
@@ -967,7 +973,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     where
         Self: FnBodyParser<'a, T>,
     {
-        if self.ctx().in_declare && self.syntax().typescript() && is!('{') {
+        if self.ctx().in_declare && self.syntax().typed() && is!('{') {
             //            self.emit_err(
             //                self.ctx().span_of_fn_name.expect("we are not in function"),
             //                SyntaxError::TS1183,
@@ -1193,7 +1199,7 @@ impl<'a, I: Tokens> FnBodyParser<'a, BlockStmtOrExpr> for Parser<'a, I> {
 impl<'a, I: Tokens> FnBodyParser<'a, Option<BlockStmt>> for Parser<'a, I> {
     fn parse_fn_body_inner(&mut self) -> PResult<'a, Option<BlockStmt>> {
         // allow omitting body and allow placing `{` on next line
-        if self.input.syntax().typescript() && !is!('{') && eat!(';') {
+        if self.input.syntax().typed() && !is!('{') && eat!(';') {
             return Ok(None);
         }
         self.include_in_expr(true).parse_block(true).map(Some)
@@ -36,7 +36,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     pub(super) fn parse_assignment_expr(&mut self) -> PResult<'a, Box<Expr>> {
         trace_cur!(parse_assignment_expr);
 
-        if self.input.syntax().typescript() {
+        if self.input.syntax().typed() {
             // Note: When the JSX plugin is on, type assertions (`<T> x`) aren't valid
             // syntax.
 
@@ -67,10 +67,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
             }
         }
 
-        if self.input.syntax().typescript()
-            && (is_one_of!('<', JSXTagStart))
-            && peeked_is!(IdentName)
-        {
+        if self.input.syntax().typed() && (is_one_of!('<', JSXTagStart)) && peeked_is!(IdentName) {
             let res = self.try_parse_ts(|p| {
                 let type_parameters = p.parse_ts_type_params()?;
                 let mut arrow = p.parse_assignment_expr_base()?;
@@ -246,7 +243,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 return self.parse_async_fn_expr();
             }
 
-            if can_be_arrow && self.input.syntax().typescript() && peeked_is!('<') {
+            if can_be_arrow && self.input.syntax().typed() && peeked_is!('<') {
                 // try parsing `async<T>() => {}`
                 if let Some(res) = self.try_parse_ts(|p| {
                     let start = cur_pos!();
@@ -285,8 +282,8 @@ impl<'a, I: Tokens> Parser<'a, I> {
             Ok(&tok!("null"))
             | Ok(&tok!("true"))
             | Ok(&tok!("false"))
-            | Ok(&Token::Num(..))
-            | Ok(&Token::BigInt(..))
+            | Ok(&Token::Num { .. })
+            | Ok(&Token::BigInt { .. })
             | Ok(Token::Str { .. }) => true,
             _ => false,
         } {
@@ -432,6 +429,8 @@ impl<'a, I: Tokens> Parser<'a, I> {
             unexpected!();
         };
 
+        self.check_target(span!(meta.span.lo()), "import.meta", JscTarget::Es2020);
+
         Ok(MetaPropExpr { meta, prop })
     }
 
@@ -460,7 +459,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
             let callee = self.parse_member_expr_or_new_expr(is_new_expr)?;
             return_if_arrow!(callee);
 
-            let type_args = if self.input.syntax().typescript() && is!('<') {
+            let type_args = if self.input.syntax().typed() && is!('<') {
                 self.try_parse_ts(|p| {
                     let args = p.parse_ts_type_args()?;
                     if !is!('(') {
@@ -576,6 +575,12 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
         let expr_start = async_span.map(|x| x.lo()).unwrap_or(cur_pos!());
 
+        if self.input.syntax().flow() && async_span.is_none() {
+            if let Some(expr) = self.try_parse_flow_type_cast() {
+                return Ok(expr);
+            }
+        }
+
         // At this point, we can't know if it's parenthesized
         // expression or head of arrow function.
         // But as all patterns of javascript is subset of
@@ -588,11 +593,17 @@ impl<'a, I: Tokens> Parser<'a, I> {
         });
 
         // This is slow path. We handle arrow in conditional expression.
-        if self.syntax().typescript() && self.ctx().in_cond_expr && is!(':') {
+        if self.syntax().typed() && self.ctx().in_cond_expr && is!(':') {
             // TODO: Remove clone
             let items_ref = &paren_items;
             if let Some(expr) = self.try_parse_ts(|p| {
-                let return_type = p.parse_ts_type_or_type_predicate_ann(&tok!(':'))?;
+                let ctx = Context {
+                    no_anon_fn_type: true,
+                    ..p.ctx()
+                };
+                let return_type = p
+                    .with_ctx(ctx)
+                    .parse_ts_type_or_type_predicate_ann(&tok!(':'))?;
 
                 expect!("=>");
 
@@ -618,11 +629,18 @@ impl<'a, I: Tokens> Parser<'a, I> {
         }
 
         let return_type = if !self.ctx().in_cond_expr
-            && self.input.syntax().typescript()
+            && self.input.syntax().typed()
             && is!(':')
             && !self.ctx().in_case_cond
         {
-            Some(self.parse_ts_type_or_type_predicate_ann(&tok!(':'))?)
+            let ctx = Context {
+                no_anon_fn_type: true,
+                ..self.ctx()
+            };
+            Some(
+                self.with_ctx(ctx)
+                    .parse_ts_type_or_type_predicate_ann(&tok!(':'))?,
+            )
         } else {
             None
         };
@@ -872,8 +890,11 @@ impl<'a, I: Tokens> Parser<'a, I> {
         let _ = cur!(false);
         let start = obj.span().lo();
 
-        if self.input.syntax().typescript() {
-            if !self.input.had_line_break_before_cur() && is!('!') {
+        if self.input.syntax().typed() {
+            if self.input.syntax().typescript()
+                && !self.input.had_line_break_before_cur()
+                && is!('!')
+            {
                 self.input.set_expr_allowed(false);
                 assert_and_bump!('!');
 
@@ -1093,7 +1114,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         let callee = self.parse_new_expr()?;
         return_if_arrow!(callee);
 
-        let type_args = if self.input.syntax().typescript() && is!('<') {
+        let type_args = if self.input.syntax().typed() && is!('<') {
             self.try_parse_ts(|p| {
                 let type_args = p.parse_ts_type_args()?;
                 if is!('(') {
@@ -1193,7 +1214,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
             let pat_start = cur_pos!();
 
             let mut arg = {
-                if self.input.syntax().typescript()
+                if self.input.syntax().typed()
                     && (is!(IdentRef) || (is!("...") && peeked_is!(IdentRef)))
                 {
                     let spread = if eat!("...") {
@@ -1222,7 +1243,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 }
             };
 
-            let optional = if self.input.syntax().typescript() {
+            let optional = if self.input.syntax().typed() {
                 if is!('?') {
                     if peeked_is!(',') || peeked_is!(':') || peeked_is!(')') || peeked_is!('=') {
                         assert_and_bump!('?');
@@ -1278,12 +1299,12 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 false
             };
 
-            if optional || (self.input.syntax().typescript() && is!(':')) {
+            if optional || (self.input.syntax().typed() && is!(':')) {
                 let start = cur_pos!();
 
                 // TODO: `async(...args?: any[]) : any => {}`
                 //
-                // if self.input.syntax().typescript() && optional && arg.spread.is_some() {
+                // if self.input.syntax().typed() && optional && arg.spread.is_some() {
                 //     self.emit_err(self.input.prev_span(), SyntaxError::TS1047)
                 // }
 
@@ -1500,18 +1521,25 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 }),
                 _ => unreachable!(),
             },
-            Token::Num(..) => match bump!() {
-                Token::Num(value) => Lit::Num(Number {
+            Token::Num { .. } => match bump!() {
+                Token::Num { value, raw } => Lit::Num(Number {
                     span: span!(start),
                     value,
+                    raw: Some(raw),
                 }),
                 _ => unreachable!(),
             },
-            Token::BigInt(..) => match bump!() {
-                Token::BigInt(value) => Lit::BigInt(BigInt {
-                    span: span!(start),
-                    value,
-                }),
+            Token::BigInt { .. } => match bump!() {
+                Token::BigInt { value, raw } => {
+                    let span = span!(start);
+                    self.check_target(span, "BigInt literal", JscTarget::Es2020);
+
+                    Lit::BigInt(BigInt {
+                        span,
+                        value,
+                        raw: Some(raw),
+                    })
+                }
                 _ => unreachable!(),
             },
             _ => unreachable!("parse_lit should not be called"),
@@ -0,0 +1,580 @@
+//! Flow is parsed into the typescript ast. Only syntax which cannot be
+//! handled by the typescript parser lives here.
+use super::*;
+use either::Either;
+use swc_common::Spanned;
+
+#[parser]
+impl<'a, I: Tokens> Parser<'a, I> {
+    /// Parses `T => U` in addition to types handled by
+    /// `parse_ts_union_type_or_higher`.
+    pub(super) fn parse_flow_non_conditional_type(&mut self) -> PResult<'a, Box<TsType>> {
+        debug_assert!(self.input.syntax().flow());
+
+        let start = cur_pos!();
+
+        let ty = self.parse_ts_union_type_or_higher()?;
+        if self.ctx().no_anon_fn_type || !is!("=>") {
+            return Ok(ty);
+        }
+
+        // `string => void`
+        let params = vec![TsFnParam::Ident(unnamed_param(0, ty))];
+        let type_ann = self.parse_ts_type_or_type_predicate_ann(&tok!("=>"))?;
+
+        Ok(Box::new(TsType::TsFnOrConstructorType(
+            TsFnOrConstructorType::TsFnType(TsFnType {
+                span: span!(start),
+                params,
+                type_params: None,
+                type_ann,
+            }),
+        )))
+    }
+
+    /// Returns `None` if the type should be parsed by
+    /// `parse_ts_non_array_type`.
+    pub(super) fn parse_flow_non_array_type(&mut self) -> PResult<'a, Option<Box<TsType>>> {
+        debug_assert!(self.input.syntax().flow());
+
+        let start = cur_pos!();
+
+        if eat!('?') {
+            // `?T` is `T | null | void`
+            let ty = self.parse_ts_type_operator_or_higher()?;
+            let ty_span = ty.span();
+            let keyword = |kind| {
+                Box::new(TsType::TsKeywordType(TsKeywordType {
+                    span: ty_span,
+                    kind,
+                }))
+            };
+
+            return Ok(Some(Box::new(TsType::TsUnionOrIntersectionType(
+                TsUnionOrIntersectionType::TsUnionType(TsUnionType {
+                    span: span!(start),
+                    types: vec![
+                        ty,
+                        keyword(TsKeywordTypeKind::TsNullKeyword),
+                        keyword(TsKeywordTypeKind::TsUndefinedKeyword),
+                    ],
+                }),
+            ))));
+        }
+
+        if eat!('*') {
+            // Existential type
+            return Ok(Some(Box::new(TsType::TsKeywordType(TsKeywordType {
+                span: span!(start),
+                kind: TsKeywordTypeKind::TsAnyKeyword,
+            }))));
+        }
+
+        if is!('{') {
+            let ctx = Context {
+                no_anon_fn_type: false,
+                ..self.ctx()
+            };
+            return self.with_ctx(ctx).parse_flow_object_type().map(Some);
+        }
+
+        if is_one_of!('(', '<') {
+            return self.parse_flow_fn_or_parenthesized_type().map(Some);
+        }
+
+        Ok(None)
+    }
+
+    /// `{ a: A, ...B }` is parsed as `B & { a: A }`.
+    fn parse_flow_object_type(&mut self) -> PResult<'a, Box<TsType>> {
+        let start = cur_pos!();
+
+        let (members, mut spreads) = self.parse_flow_object_type_members()?;
+        let lit = Box::new(TsType::TsTypeLit(TsTypeLit {
+            span: span!(start),
+            members,
+        }));
+        if spreads.is_empty() {
+            return Ok(lit);
+        }
+
+        spreads.push(lit);
+        Ok(Box::new(TsType::TsUnionOrIntersectionType(
+            TsUnionOrIntersectionType::TsIntersectionType(TsIntersectionType {
+                span: span!(start),
+                types: spreads,
+            }),
+        )))
+    }
+
+    /// Parses members of an object type, which can be exact (`{| |}`).
+    ///
+    /// Returns members and spread types.
+    pub(super) fn parse_flow_object_type_members(
+        &mut self,
+    ) -> PResult<'a, (Vec<TsTypeElement>, Vec<Box<TsType>>)> {
+        debug_assert!(self.input.syntax().flow());
+
+        expect!('{');
+        if eat!("||") {
+            expect!('}');
+            return Ok((vec![], vec![]));
+        }
+        let exact = eat!('|');
+
+        let mut members = vec![];
+        let mut spreads = vec![];
+        loop {
+            if exact {
+                if eat!('|') {
+                    break;
+                }
+            } else if eof!() || is!('}') {
+                break;
+            }
+
+            let start = cur_pos!();
+            if eat!("...") {
+                // `...` alone makes the object explicitly inexact.
+                if !is_one_of!('}', ',', ';', '|') {
+                    spreads.push(self.parse_ts_type()?);
+                }
+                if !is!('}') {
+                    self.parse_ts_type_member_semicolon()?;
+                }
+                continue;
+            }
+
+            // Variance sigils have no typescript equivalent.
+            if !eat!('+') {
+                eat!('-');
+            }
+
+            if is!('[') && !self.ts_look_ahead(|p| p.is_ts_unambiguously_index_signature())? {
+                members.push(self.parse_flow_indexer(start)?.into());
+                continue;
+            }
+
+            members.push(self.parse_ts_type_member()?);
+        }
+        expect!('}');
+
+        Ok((members, spreads))
+    }
+
+    /// `[K]: V`, which is an indexer without the name of the key.
+    fn parse_flow_indexer(&mut self, start: BytePos) -> PResult<'a, TsIndexSignature> {
+        expect!('[');
+        let key = self.parse_ts_type()?;
+        expect!(']');
+
+        let type_ann = self.try_parse_ts_type_ann()?;
+        self.parse_ts_type_member_semicolon()?;
+
+        Ok(TsIndexSignature {
+            span: span!(start),
+            readonly: false,
+            params: vec![TsFnParam::Ident(unnamed_param(0, key))],
+            type_ann,
+        })
+    }
+
+    /// Parses `(A, b: B) => C`, `<T>(t: T) => T` and `(A)`.
+    fn parse_flow_fn_or_parenthesized_type(&mut self) -> PResult<'a, Box<TsType>> {
+        let start = cur_pos!();
+        let no_anon_fn_type = self.ctx().no_anon_fn_type;
+        let ctx = Context {
+            no_anon_fn_type: false,
+            ..self.ctx()
+        };
+
+        let type_params = self.try_parse_ts_type_params()?;
+        expect!('(');
+        let mut params = vec![];
+        while !eof!() && !is!(')') {
+            params.push(self.with_ctx(ctx).parse_flow_fn_type_param(params.len())?);
+            if !is!(')') {
+                expect!(',');
+            }
+        }
+        expect!(')');
+
+        // In the return type of an arrow function, `(A) => ...` is a grouped
+        // type followed by the body.
+        let is_grouped = type_params.is_none()
+            && (no_anon_fn_type || !is!("=>"))
+            && match &*params {
+                [Either::Right(..)] => true,
+                _ => false,
+            };
+        if is_grouped {
+            let type_ann = match params.pop() {
+                Some(Either::Right(ty)) => ty,
+                _ => unreachable!(),
+            };
+            return Ok(Box::new(TsType::TsParenthesizedType(TsParenthesizedType {
+                span: span!(start),
+                type_ann,
+            })));
+        }
+
+        let params = params
+            .into_iter()
+            .enumerate()
+            .map(|(idx, param)| match param {
+                Either::Left(param) => param,
+                Either::Right(ty) => TsFnParam::Ident(unnamed_param(idx, ty)),
+            })
+            .collect();
+        let type_ann = self.parse_ts_type_or_type_predicate_ann(&tok!("=>"))?;
+
+        Ok(Box::new(TsType::TsFnOrConstructorType(
+            TsFnOrConstructorType::TsFnType(TsFnType {
+                span: span!(start),
+                params,
+                type_params,
+                type_ann,
+            }),
+        )))
+    }
+
+    /// Returns `Either::Right` for a parameter without a name.
+    fn parse_flow_fn_type_param(
+        &mut self,
+        idx: usize,
+    ) -> PResult<'a, Either<TsFnParam, Box<TsType>>> {
+        let start = cur_pos!();
+
+        if eat!("...") {
+            let dot3_token = span!(start);
+            let mut arg = match self.parse_flow_fn_type_param(idx)? {
+                Either::Left(TsFnParam::Ident(i)) => i,
+                Either::Right(ty) => unnamed_param(idx, ty),
+                _ => unexpected!(),
+            };
+            let type_ann = arg.type_ann.take();
+
+            return Ok(Either::Left(TsFnParam::Rest(RestPat {
+                span: span!(start),
+                dot3_token,
+                arg: Box::new(Pat::Ident(arg)),
+                type_ann,
+            })));
+        }
+
+        if is!(IdentName) && (peeked_is!(':') || peeked_is!('?')) {
+            let mut ident = self.parse_ident_name()?;
+            ident.optional = eat!('?');
+            let type_ann_start = cur_pos!();
+            expect!(':');
+            ident.type_ann = Some(self.parse_ts_type_ann(/* eat_colon */ false, type_ann_start)?);
+            ident.span = span!(start);
+
+            return Ok(Either::Left(TsFnParam::Ident(ident)));
+        }
+
+        self.parse_ts_type().map(Either::Right)
+    }
+
+    /// `opaque type A: Super = T;`
+    ///
+    /// `declare opaque type A;` does not have a body, so the supertype (or
+    /// `any`) is used instead.
+    pub(super) fn parse_flow_opaque_type_decl(
+        &mut self,
+        start: BytePos,
+    ) -> PResult<'a, TsTypeAliasDecl> {
+        debug_assert!(self.input.syntax().flow());
+
+        let id = self.parse_ident_name()?;
+        let type_params = self.try_parse_ts_type_params()?;
+        let super_type = self.eat_then_parse_ts_type(&tok!(':'))?;
+        let type_ann = match self.eat_then_parse_ts_type(&tok!('='))? {
+            Some(ty) => ty,
+            None => super_type.unwrap_or_else(|| {
+                Box::new(TsType::TsKeywordType(TsKeywordType {
+                    span: id.span,
+                    kind: TsKeywordTypeKind::TsAnyKeyword,
+                }))
+            }),
+        };
+        expect!(';');
+
+        Ok(TsTypeAliasDecl {
+            span: span!(start),
+            declare: false,
+            id,
+            type_params,
+            type_ann,
+        })
+    }
+
+    /// `(expr: T)`
+    pub(super) fn try_parse_flow_type_cast(&mut self) -> Option<Box<Expr>> {
+        debug_assert!(self.input.syntax().flow());
+
+        let in_cond_expr = self.ctx().in_cond_expr;
+        self.try_parse_ts(|p| {
+            let start = cur_pos!();
+            expect!('(');
+            let expr = p.include_in_expr(true).parse_assignment_expr()?;
+            if !is!(':') {
+                return Ok(None);
+            }
+            let type_ann_start = cur_pos!();
+            let type_ann = p.parse_ts_type_ann(/* eat_colon */ true, type_ann_start)?;
+            expect!(')');
+
+            // `(a: T) => a` and `(a: T): U => a` are arrow functions.
+            if is!("=>") || (!in_cond_expr && is!(':')) {
+                return Ok(None);
+            }
+
+            let span = span!(start);
+            Ok(Some(Box::new(Expr::Paren(ParenExpr {
+                span,
+                expr: Box::new(Expr::TsTypeCast(TsTypeCastExpr {
+                    span,
+                    expr,
+                    type_ann,
+                })),
+            }))))
+        })
+    }
+}
+
+/// Flow allows omitting names of parameters in function types, but the
+/// typescript ast requires them.
+fn unnamed_param(idx: usize, ty: Box<TsType>) -> Ident {
+    let span = ty.span();
+    Ident {
+        span,
+        sym: format!("_{}", idx).into(),
+        type_ann: Some(TsTypeAnn { span, type_ann: ty }),
+        optional: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{lexer::Lexer, test_parser, FlowConfig, Parser, Syntax};
+    use swc_common::DUMMY_SP;
+    use swc_ecma_ast::*;
+    use testing::assert_eq_ignore_span;
+
+    fn syntax() -> Syntax {
+        Syntax::Flow(FlowConfig {
+            jsx: true,
+            ..Default::default()
+        })
+    }
+
+    fn module(src: &'static str) -> Module {
+        test_parser(src, syntax(), |p| {
+            p.parse_module().map_err(|mut e| e.emit())
+        })
+    }
+
+    /// Returns the errors emitted while parsing `src` as a script.
+    fn errors(src: &'static str) -> String {
+        crate::with_test_sess(src, |sess, input| {
+            let lexer = Lexer::new(sess, syntax(), Default::default(), input, None);
+            Parser::new_from(sess, lexer)
+                .parse_script()
+                .map_err(|mut e| {
+                    e.emit();
+                })?;
+
+            // Make the handler return the output.
+            Err(())
+        })
+        .expect_err("errors(): output should be returned")
+        .to_string()
+    }
+
+    fn type_alias(src: &'static str) -> Box<TsType> {
+        match module(src).body.pop() {
+            Some(ModuleItem::Stmt(Stmt::Decl(Decl::TsTypeAlias(decl)))) => decl.type_ann,
+            item => panic!("expected a type alias, got {:?}", item),
+        }
+    }
+
+    fn keyword(kind: TsKeywordTypeKind) -> Box<TsType> {
+        box TsType::TsKeywordType(TsKeywordType {
+            span: DUMMY_SP,
+            kind,
+        })
+    }
+
+    #[test]
+    fn maybe_type() {
+        assert_eq_ignore_span!(
+            type_alias("type A = ?string;"),
+            box TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsUnionType(
+                TsUnionType {
+                    span: DUMMY_SP,
+                    types: vec![
+                        keyword(TsKeywordTypeKind::TsStringKeyword),
+                        keyword(TsKeywordTypeKind::TsNullKeyword),
+                        keyword(TsKeywordTypeKind::TsUndefinedKeyword),
+                    ],
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn exact_object() {
+        assert_eq_ignore_span!(
+            type_alias("type A = {||};"),
+            box TsType::TsTypeLit(TsTypeLit {
+                span: DUMMY_SP,
+                members: vec![],
+            })
+        );
+
+        match *type_alias("type A = {| +a: string, b?: ?number |};") {
+            TsType::TsTypeLit(TsTypeLit { ref members, .. }) => assert_eq!(members.len(), 2),
+            ref ty => panic!("expected a type literal, got {:?}", ty),
+        }
+    }
+
+    #[test]
+    fn object_spread() {
+        match *type_alias("type A = { ...B, [string]: number, ... };") {
+            TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsIntersectionType(
+                TsIntersectionType { ref types, .. },
+            )) => assert_eq!(types.len(), 2),
+            ref ty => panic!("expected an intersection type, got {:?}", ty),
+        }
+    }
+
+    #[test]
+    fn fn_type() {
+        match *type_alias("type F = (string, b?: number, ...Array<boolean>) => void;") {
+            TsType::TsFnOrConstructorType(TsFnOrConstructorType::TsFnType(TsFnType {
+                ref params,
+                ..
+            })) => assert_eq!(params.len(), 3),
+            ref ty => panic!("expected a function type, got {:?}", ty),
+        }
+
+        match *type_alias("type F = string => void;") {
+            TsType::TsFnOrConstructorType(TsFnOrConstructorType::TsFnType(..)) => {}
+            ref ty => panic!("expected a function type, got {:?}", ty),
+        }
+
+        match *type_alias("type F = (string);") {
+            TsType::TsParenthesizedType(..) => {}
+            ref ty => panic!("expected a parenthesized type, got {:?}", ty),
+        }
+    }
+
+    #[test]
+    fn type_params() {
+        module("type A<+T: Object = {}, -U> = T; class B<T: string> { +x: T; }");
+    }
+
+    #[test]
+    fn opaque_type() {
+        module("opaque type A = string; export opaque type B: string = string;");
+        module("declare opaque type C;");
+    }
+
+    #[test]
+    fn type_cast() {
+        let m = module("(x: any); const y = ((a + b: any): number);");
+        match m.body[0] {
+            ModuleItem::Stmt(Stmt::Expr(ExprStmt { ref expr, .. })) => match **expr {
+                Expr::Paren(ParenExpr { ref expr, .. }) => match **expr {
+                    Expr::TsTypeCast(..) => {}
+                    ref e => panic!("expected a type cast, got {:?}", e),
+                },
+                ref e => panic!("expected a paren expression, got {:?}", e),
+            },
+            ref item => panic!("expected an expression statement, got {:?}", item),
+        }
+    }
+
+    #[test]
+    fn arrow_fn() {
+        module(
+            "const f = (x: number): number => x; const g = async <T>(x: T): Promise<T> => x;
+             const h = (x: number): (string => void) => () => {};",
+        );
+    }
+
+    #[test]
+    fn checks() {
+        module("function isString(x: mixed): boolean %checks { return typeof x === 'string'; }");
+    }
+
+    #[test]
+    fn import_type() {
+        let m = module(
+            "import type A from 'a'; import typeof B from 'b'; import { type C, typeof D } from \
+             'c'; import { type E, F } from 'e';",
+        );
+        let type_only = m
+            .body
+            .iter()
+            .map(|item| match *item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl { type_only, .. })) => {
+                    type_only
+                }
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(type_only, vec![true, true, true, false]);
+    }
+
+    #[test]
+    fn jsx() {
+        module("const a = <div>{(x: any)}</div>;");
+    }
+
+    #[test]
+    fn typescript_only_syntax() {
+        for src in &[
+            "enum E { A }",
+            "const enum E { A }",
+            "namespace N {}",
+            "module M {}",
+            "declare namespace N {}",
+            "abstract class A {}",
+            "x!;",
+            "x as T;",
+            "let x!: number;",
+            "class A { public x; }",
+            "class A { constructor(private x) {} }",
+            "import a = require('a');",
+        ] {
+            assert_ne!(errors(src), "", "flow should reject `{}`", src);
+        }
+    }
+
+    #[test]
+    fn with_stmt() {
+        assert!(!errors("with (a) {}").contains("TS2410"));
+    }
+
+    #[test]
+    fn declare_module() {
+        module("declare module 'a' {}");
+    }
+
+    #[test]
+    fn await_ident() {
+        let script = test_parser("var await;", syntax(), |p| {
+            p.parse_script().map_err(|mut e| e.emit())
+        });
+        match script.body[0] {
+            Stmt::Decl(Decl::Var(ref v)) => match v.decls[0].name {
+                Pat::Ident(ref i) => assert_eq!(i.sym, *"await"),
+                ref pat => panic!("expected an ident, got {:?}", pat),
+            },
+            ref stmt => panic!("expected a var decl, got {:?}", stmt),
+        }
+    }
+}
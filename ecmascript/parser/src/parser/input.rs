@@ -1,5 +1,6 @@
 use super::Parser;
 use crate::{
+    error::Error,
     lexer::{self},
     token::*,
     Context, JscTarget, Syntax,
@@ -18,6 +19,12 @@ pub trait Tokens: Clone + Iterator<Item = TokenAndSpan> {
     fn token_context(&self) -> &lexer::TokenContexts;
     fn token_context_mut(&mut self) -> &mut lexer::TokenContexts;
     fn set_token_context(&mut self, _c: lexer::TokenContexts);
+
+    /// Stores an error, which is used by the recovery mode.
+    fn add_error(&self, error: Error);
+
+    /// Takes errors stored by [add_error](Tokens::add_error).
+    fn take_errors(&mut self) -> Vec<Error>;
 }
 
 #[derive(Clone)]
@@ -27,6 +34,7 @@ pub struct TokensInput {
     syntax: Syntax,
     target: JscTarget,
     token_ctx: TokenContexts,
+    errors: Rc<RefCell<Vec<Error>>>,
 }
 
 impl TokensInput {
@@ -37,6 +45,7 @@ impl TokensInput {
             syntax,
             target,
             token_ctx: Default::default(),
+            errors: Default::default(),
         }
     }
 }
@@ -78,6 +87,14 @@ impl Tokens for TokensInput {
     fn set_token_context(&mut self, c: TokenContexts) {
         self.token_ctx = c;
     }
+
+    fn add_error(&self, error: Error) {
+        self.errors.borrow_mut().push(error);
+    }
+
+    fn take_errors(&mut self) -> Vec<Error> {
+        mem::replace(&mut *self.errors.borrow_mut(), Default::default())
+    }
 }
 
 /// Note: Lexer need access to parser's context to lex correctly.
@@ -168,6 +185,14 @@ impl<I: Tokens> Tokens for Capturing<I> {
     fn set_token_context(&mut self, c: TokenContexts) {
         self.inner.set_token_context(c)
     }
+
+    fn add_error(&self, error: Error) {
+        self.inner.add_error(error)
+    }
+
+    fn take_errors(&mut self) -> Vec<Error> {
+        self.inner.take_errors()
+    }
 }
 
 /// This struct is responsible for managing current token and peeked token.
@@ -392,4 +417,12 @@ impl<I: Tokens> Buffer<I> {
     pub(crate) fn set_token_context(&mut self, c: lexer::TokenContexts) {
         self.iter.set_token_context(c)
     }
+
+    pub(crate) fn add_error(&self, error: Error) {
+        self.iter.add_error(error)
+    }
+
+    pub(crate) fn take_errors(&mut self) -> Vec<Error> {
+        self.iter.take_errors()
+    }
 }
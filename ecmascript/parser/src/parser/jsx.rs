@@ -203,7 +203,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     ) -> PResult<'a, JSXOpeningElement> {
         debug_assert!(self.input.syntax().jsx());
 
-        let type_args = if self.input.syntax().typescript() && is!('<') {
+        let type_args = if self.input.syntax().typed() && is!('<') {
             self.try_parse_ts(|p| p.parse_ts_type_args().map(Some))
         } else {
             None
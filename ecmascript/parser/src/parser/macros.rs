@@ -192,33 +192,24 @@ macro_rules! cur {
         if is_err_token {
             match $p.input.bump() {
                 $crate::token::Token::Error(e) => {
-                    let err =
-                        ::swc_common::errors::DiagnosticBuilder::from($crate::error::ErrorToDiag {
-                            handler: &$p.session.handler,
-                            span: e.span,
-                            error: e.error,
-                        });
+                    let err = $p.make_error(e.span, e.error);
                     return Err(err.into());
                 }
                 _ => unreachable!(),
             }
         }
 
+        if $required && $p.input.cur().is_none() {
+            let err = $p.make_error(last, $crate::error::SyntaxError::Eof);
+            return Err(err.into());
+        }
+
         match $p.input.cur() {
             Some(c) => Ok(c),
-            None => {
-                if $required {
-                    let err = ::swc_common::errors::DiagnosticBuilder::from($crate::error::Eof {
-                        last,
-                        handler: &$p.session.handler,
-                    });
-                    return Err(err.into());
-                }
-                Err($crate::error::Eof {
-                    last,
-                    handler: &$p.session.handler,
-                })
-            }
+            None => Err($crate::error::Eof {
+                last,
+                handler: &$p.session.handler,
+            }),
         }
     }};
 }
@@ -234,16 +225,12 @@ Current token is {:?}",
 
         let pos = cur_pos!($p);
         let last = Span::new(pos, pos, Default::default());
-        match $p.input.peek() {
-            Some(c) => Ok(c),
-            None => {
-                let err = ::swc_common::errors::DiagnosticBuilder::from($crate::error::Eof {
-                    //TODO: Use whole span
-                    last,
-                    handler: &$p.session.handler,
-                });
-                Err(err)
-            }
+        if $p.input.peek().is_some() {
+            Ok($p.input.peek().unwrap())
+        } else {
+            //TODO: Use whole span
+            let err = $p.make_error(last, $crate::error::SyntaxError::Eof);
+            Err(err)
         }
     }};
 }
@@ -312,11 +299,7 @@ macro_rules! span {
 
 macro_rules! make_error {
     ($p:expr, $span:expr, $err:expr) => {{
-        ::swc_common::errors::DiagnosticBuilder::from($crate::error::ErrorToDiag {
-            handler: $p.session.handler,
-            span: $span,
-            error: $err,
-        })
+        $p.make_error($span, $err)
     }};
 }
 
@@ -3,14 +3,19 @@
 pub use self::input::{Capturing, Tokens, TokensInput};
 use self::{input::Buffer, util::ParseObject};
 use crate::{
-    error::{ErrorToDiag, SyntaxError},
+    error::{Error, ErrorToDiag, SyntaxError},
     lexer::Lexer,
     token::{Token, Word},
     Context, JscTarget, Session, Syntax,
 };
-use std::ops::{Deref, DerefMut};
+use std::{
+    cell::RefCell,
+    ops::{Deref, DerefMut},
+};
 use swc_atoms::JsWord;
-use swc_common::{comments::Comments, errors::DiagnosticBuilder, input::Input, BytePos, Span};
+use swc_common::{
+    comments::Comments, errors::DiagnosticBuilder, input::Input, BytePos, Span, DUMMY_SP,
+};
 use swc_ecma_ast::*;
 use swc_ecma_parser_macros::parser;
 #[cfg(test)]
@@ -22,6 +27,7 @@ use test::Bencher;
 mod macros;
 mod class_and_fn;
 mod expr;
+mod flow;
 mod ident;
 pub mod input;
 mod jsx;
@@ -42,6 +48,11 @@ pub struct Parser<'a, I: Tokens> {
     session: Session<'a>,
     state: State,
     input: Buffer<I>,
+    /// Last fatal error. Used by the recovery mode.
+    last_fatal: RefCell<Option<Error>>,
+    /// If true, a warning is emitted for syntax which is newer than the
+    /// target and cannot be lowered.
+    target_diagnostics: bool,
 }
 
 #[derive(Clone, Default)]
@@ -74,9 +85,18 @@ impl<'a, I: Tokens> Parser<'a, I> {
             session,
             input: Buffer::new(input),
             state: Default::default(),
+            last_fatal: Default::default(),
+            target_diagnostics: false,
         }
     }
 
+    /// Enables the diagnostic mode, which warns if the input uses syntax newer
+    /// than the target while there's no pass to lower it.
+    pub fn with_target_diagnostics(mut self) -> Self {
+        self.target_diagnostics = true;
+        self
+    }
+
     pub(crate) fn target(&self) -> JscTarget {
         self.input.target()
     }
@@ -104,7 +124,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     pub fn parse_typescript_module(&mut self) -> PResult<'a, Module> {
         trace_cur!(parse_typescript_module);
 
-        debug_assert!(self.syntax().typescript());
+        debug_assert!(self.syntax().typed());
 
         //TODO: parse() -> PResult<'a, Program>
         let ctx = Context {
@@ -145,6 +165,128 @@ impl<'a, I: Tokens> Parser<'a, I> {
         })
     }
 
+    /// Parses a script while recovering from syntax errors.
+    ///
+    /// Statements which cannot be parsed are replaced with
+    /// [Expr::Invalid], and all syntax errors are returned instead of being
+    /// emitted.
+    pub fn parse_script_with_recovery(&mut self) -> (Script, Vec<Error>) {
+        let start = cur_pos!();
+        let ctx = Context {
+            recover: true,
+            ..self.ctx()
+        };
+        self.set_ctx(ctx);
+
+        let script = match self.parse_script() {
+            Ok(script) => script,
+            Err(err) => {
+                self.store_fatal_error(err);
+                Script {
+                    span: Span::new(start, start.max(last_pos!()), Default::default()),
+                    body: vec![],
+                    shebang: None,
+                }
+            }
+        };
+
+        (script, self.take_errors())
+    }
+
+    /// Parses a module while recovering from syntax errors.
+    ///
+    /// Statements which cannot be parsed are replaced with
+    /// [Expr::Invalid], and all syntax errors are returned instead of being
+    /// emitted.
+    pub fn parse_module_with_recovery(&mut self) -> (Module, Vec<Error>) {
+        let start = cur_pos!();
+        let ctx = Context {
+            recover: true,
+            ..self.ctx()
+        };
+        self.set_ctx(ctx);
+
+        let module = match self.parse_module() {
+            Ok(module) => module,
+            Err(err) => {
+                self.store_fatal_error(err);
+                Module {
+                    span: Span::new(start, start.max(last_pos!()), Default::default()),
+                    body: vec![],
+                    shebang: None,
+                }
+            }
+        };
+
+        (module, self.take_errors())
+    }
+
+    /// Returns errors stored in the recovery mode, sorted by position.
+    fn take_errors(&mut self) -> Vec<Error> {
+        let mut errors = self.input.take_errors();
+        errors.sort_by_key(|e| e.span.lo());
+        errors.dedup();
+        errors
+    }
+
+    /// Stores `err` as an error of the recovery mode and cancels it.
+    fn store_fatal_error(&mut self, mut err: DiagnosticBuilder<'a>) {
+        let span = err.span.primary_span().unwrap_or(DUMMY_SP);
+        let error = match self.last_fatal.borrow_mut().take() {
+            Some(e) if e.span == span => e,
+            _ => Error {
+                span,
+                error: SyntaxError::Unexpected { got: err.message() },
+            },
+        };
+        err.cancel();
+
+        self.input.add_error(error);
+    }
+
+    /// Creates a fatal error.
+    ///
+    /// In the recovery mode, the error is remembered so that it can be
+    /// returned to the caller.
+    fn make_error(&self, span: Span, error: SyntaxError) -> DiagnosticBuilder<'a> {
+        if self.ctx().recover {
+            *self.last_fatal.borrow_mut() = Some(Error {
+                span,
+                error: error.clone(),
+            });
+        }
+
+        DiagnosticBuilder::from(ErrorToDiag {
+            handler: self.session.handler,
+            span,
+            error,
+        })
+    }
+
+    /// Warns if `syntax`, which is available since `since`, is used while
+    /// targeting an older version.
+    ///
+    /// This should be called only for syntax which cannot be lowered.
+    fn check_target(&self, span: Span, syntax: &str, since: JscTarget) {
+        if !self.emit_err || !self.target_diagnostics || self.target() >= since {
+            return;
+        }
+
+        self.session
+            .handler
+            .struct_span_warn(
+                span,
+                &format!(
+                    "{} requires {:?} or newer, but the target is {:?}. It will not be lowered \
+                     and may not work.",
+                    syntax,
+                    since,
+                    self.target()
+                ),
+            )
+            .emit();
+    }
+
     fn parse_shebang(&mut self) -> PResult<'a, Option<JsWord>> {
         match cur!(false) {
             Ok(&Token::Shebang(..)) => match bump!() {
@@ -164,6 +306,11 @@ impl<'a, I: Tokens> Parser<'a, I> {
             return;
         }
 
+        if self.ctx().recover {
+            self.input.add_error(Error { span, error });
+            return;
+        }
+
         DiagnosticBuilder::from(ErrorToDiag {
             handler: self.session.handler,
             span,
@@ -215,3 +362,66 @@ where
         Ok(())
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EsConfig;
+
+    /// Returns the warnings emitted while parsing `s` in the diagnostic mode.
+    fn target_warnings(s: &'static str, target: JscTarget) -> String {
+        crate::with_test_sess(s, |sess, input| {
+            let syntax = Syntax::Es(EsConfig {
+                import_meta: true,
+                top_level_await: true,
+                ..Default::default()
+            });
+            let lexer = Lexer::new(sess, syntax, target, input, None);
+            let mut p = Parser::new_from(sess, lexer).with_target_diagnostics();
+            p.parse_module().map_err(|mut e| {
+                e.emit();
+            })?;
+
+            // Make the handler return the output.
+            Err(())
+        })
+        .expect_err("test_parser(): output should be returned")
+        .to_string()
+    }
+
+    #[test]
+    fn target_diagnostics_big_int() {
+        assert!(target_warnings("1n;", JscTarget::Es2019).contains("BigInt literal"));
+        assert!(!target_warnings("1n;", JscTarget::Es2020).contains("BigInt literal"));
+    }
+
+    #[test]
+    fn target_diagnostics_import_meta() {
+        assert!(target_warnings("import.meta.url;", JscTarget::Es5).contains("import.meta"));
+        assert!(!target_warnings("import.meta.url;", JscTarget::EsNext).contains("import.meta"));
+    }
+
+    #[test]
+    fn target_diagnostics_top_level_await() {
+        assert!(target_warnings("await foo;", JscTarget::Es2021).contains("Top level await"));
+        assert!(!target_warnings("await foo;", JscTarget::EsNext).contains("Top level await"));
+    }
+
+    #[test]
+    fn target_diagnostics_disabled() {
+        let warnings = crate::with_test_sess("1n;", |sess, input| {
+            let lexer = Lexer::new(sess, Syntax::default(), JscTarget::Es5, input, None);
+            Parser::new_from(sess, lexer)
+                .parse_module()
+                .map_err(|mut e| {
+                    e.emit();
+                })?;
+
+            Err(())
+        })
+        .expect_err("test_parser(): output should be returned")
+        .to_string();
+
+        assert!(!warnings.contains("BigInt literal"));
+    }
+}
@@ -55,10 +55,11 @@ impl<'a, I: Tokens> Parser<'a, I> {
                     }),
                     _ => unreachable!(),
                 },
-                Token::Num(_) => match bump!() {
-                    Token::Num(value) => PropName::Num(Number {
+                Token::Num { .. } => match bump!() {
+                    Token::Num { value, raw } => PropName::Num(Number {
                         span: span!(start),
                         value,
+                        raw: Some(raw),
                     }),
                     _ => unreachable!(),
                 },
@@ -72,7 +73,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
                     let mut expr = p.include_in_expr(true).parse_assignment_expr()?;
 
-                    if p.syntax().typescript() && is!(',') {
+                    if p.syntax().typed() && is!(',') {
                         let mut exprs = vec![expr];
 
                         while eat!(',') {
@@ -153,9 +154,9 @@ impl<'a, I: Tokens> ParseObject<'a, Box<Expr>> for Parser<'a, I> {
 
         let key = self.parse_prop_name()?;
 
-        if self.input.syntax().typescript()
+        if self.input.syntax().typed()
             && !is_one_of!('(', '[', ':', ',', '?', '=', '*', IdentName)
-            && !(self.input.syntax().typescript() && is!('<'))
+            && !(self.input.syntax().typed() && is!('<'))
             && !(is!('}')
                 && match key {
                     PropName::Ident(..) => true,
@@ -182,7 +183,7 @@ impl<'a, I: Tokens> ParseObject<'a, Box<Expr>> for Parser<'a, I> {
         }
 
         // Handle `a(){}` (and async(){} / get(){} / set(){})
-        if (self.input.syntax().typescript() && is!('<')) || is!('(') {
+        if (self.input.syntax().typed() && is!('<')) || is!('(') {
             return self
                 .parse_fn_args_body(
                     // no decorator in an object literal
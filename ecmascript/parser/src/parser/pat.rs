@@ -10,7 +10,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     pub(super) fn parse_opt_binding_ident(&mut self) -> PResult<'a, Option<Ident>> {
         trace_cur!(parse_opt_binding_ident);
 
-        if is!(BindingIdent) || (self.input.syntax().typescript() && is!("this")) {
+        if is!(BindingIdent) || (self.input.syntax().typed() && is!("this")) {
             self.parse_binding_ident().map(Some)
         } else {
             Ok(None)
@@ -159,7 +159,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         let mut pat = self.parse_binding_element()?;
         let mut opt = false;
 
-        if self.input.syntax().typescript() {
+        if self.input.syntax().typed() {
             if eat!('?') {
                 match pat {
                     Pat::Ident(Ident {
@@ -273,7 +273,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 let dot3_token = span!(pat_start);
 
                 let pat = self.parse_binding_pat_or_ident()?;
-                let type_ann = if self.input.syntax().typescript() && is!(':') {
+                let type_ann = if self.input.syntax().typed() && is!(':') {
                     let cur_pos = cur_pos!();
                     Some(self.parse_ts_type_ann(/* eat_colon */ true, cur_pos)?)
                 } else {
@@ -386,7 +386,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                     .into();
                 }
 
-                let type_ann = if self.input.syntax().typescript() && is!(':') {
+                let type_ann = if self.input.syntax().typed() && is!(':') {
                     let cur_pos = cur_pos!();
                     let ty = self.parse_ts_type_ann(/* eat_colon */ true, cur_pos)?;
                     Some(ty)
@@ -1,5 +1,8 @@
 use super::{pat::PatType, *};
-use crate::{error::SyntaxError, make_span};
+use crate::{
+    error::{Error, SyntaxError},
+    make_span,
+};
 use swc_atoms::js_word;
 use swc_common::Spanned;
 #[cfg(test)]
@@ -22,13 +25,37 @@ impl<'a, I: Tokens> Parser<'a, I> {
         trace_cur!(parse_block_body);
 
         let old_ctx = self.ctx();
+        // Speculative parsing should not be affected by the recovery mode.
+        let recover = old_ctx.recover && self.emit_err;
 
         let mut stmts = vec![];
         while {
+            if recover {
+                self.skip_error_tokens();
+            }
             let c = cur!(false).ok();
             c != end
         } {
-            let stmt = self.parse_stmt_like(true, top_level)?;
+            if recover && eof!() {
+                // `end` is missing.
+                let pos = last_pos!();
+                self.input.add_error(Error {
+                    span: Span::new(pos, pos, Default::default()),
+                    error: SyntaxError::Eof,
+                });
+                self.set_ctx(old_ctx);
+                return Ok(stmts);
+            }
+
+            let start = cur_pos!();
+            let stmt = match self.parse_stmt_like(true, top_level) {
+                Ok(stmt) => stmt,
+                Err(err) if recover => {
+                    self.store_fatal_error(err);
+                    self.recover_stmt(start).into()
+                }
+                Err(err) => return Err(err),
+            };
             if allow_directives {
                 allow_directives = false;
                 if stmt.is_use_strict() {
@@ -60,6 +87,70 @@ impl<'a, I: Tokens> Parser<'a, I> {
         Ok(stmts)
     }
 
+    /// Stores errors from the lexer and skips them.
+    fn skip_error_tokens(&mut self) {
+        while let Some(&Token::Error(..)) = self.input.cur() {
+            match self.input.bump() {
+                Token::Error(err) => self.input.add_error(err),
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    /// Skips tokens until a statement boundary or a closing brace, and returns
+    /// a placeholder for the statement which cannot be parsed.
+    fn recover_stmt(&mut self, start: BytePos) -> Stmt {
+        let mut depth = 0usize;
+        let mut progressed = last_pos!() > start;
+
+        loop {
+            self.skip_error_tokens();
+
+            let had_line_break = self.input.had_line_break_before_cur();
+            let token = match self.input.cur() {
+                Some(token) => token.clone(),
+                None => break,
+            };
+
+            match token {
+                Token::Semi if depth == 0 => {
+                    self.input.bump();
+                    break;
+                }
+                Token::LBrace => depth += 1,
+                Token::RBrace if depth == 0 => {
+                    // This is the end of the enclosing block, unless we are stuck at it.
+                    if !progressed {
+                        self.input.bump();
+                    }
+                    break;
+                }
+                Token::RBrace => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.input.bump();
+                        break;
+                    }
+                }
+                Word(Word::Keyword(keyword))
+                    if depth == 0 && progressed && had_line_break && keyword.starts_stmt() =>
+                {
+                    break;
+                }
+                _ => {}
+            }
+
+            self.input.bump();
+            progressed = true;
+        }
+
+        let span = Span::new(start, start.max(last_pos!()), Default::default());
+        Stmt::Expr(ExprStmt {
+            span,
+            expr: Box::new(Expr::Invalid(Invalid { span })),
+        })
+    }
+
     pub fn parse_stmt(&mut self, top_level: bool) -> PResult<'a, Stmt> {
         trace_cur!(parse_stmt);
         self.parse_stmt_like(false, top_level)
@@ -105,6 +196,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
             if !valid {
                 self.emit_err(self.input.cur_span(), SyntaxError::TopLevelAwait);
             }
+            self.check_target(self.input.cur_span(), "Top level await", JscTarget::EsNext);
 
             let expr = self.parse_await_expr()?;
             eat!(';');
@@ -309,7 +401,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 }));
             }
 
-            if self.input.syntax().typescript() {
+            if self.input.syntax().typed() {
                 if let Some(decl) = self.parse_ts_expr_stmt(decorators, ident.clone())? {
                     return Ok(Stmt::Decl(decl));
                 }
@@ -579,7 +671,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         if eat!('(') {
             let pat = self.parse_binding_pat_or_ident()?;
 
-            if self.syntax().typescript() && eat!(':') {
+            if self.syntax().typed() && eat!(':') {
                 let ctx = Context {
                     in_type: true,
                     ..self.ctx()
@@ -606,7 +698,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         let var_span = span!(start);
         let should_include_in = kind != VarDeclKind::Var || !for_loop;
 
-        if self.syntax().typescript() && for_loop {
+        if self.syntax().typed() && for_loop {
             let res = if is_one_of!("in", "of") {
                 self.ts_look_ahead(|p| {
                     //
@@ -715,7 +807,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         };
 
         // Typescript extension
-        if self.input.syntax().typescript() && is!(':') {
+        if self.input.syntax().typed() && is!(':') {
             let type_annotation = self.try_parse_ts_type_ann()?;
             match name {
                 Pat::Array(ArrayPat {
@@ -1722,4 +1814,91 @@ export default function waitUntil(callback, options = {}) {
         assert!(trailing.is_empty());
         assert_eq!(leading.len(), 1);
     }
+
+    fn module_with_recovery(s: &'static str) -> (Module, Vec<Error>) {
+        test_parser(s, Syntax::default(), |p| Ok(p.parse_module_with_recovery()))
+    }
+
+    fn is_invalid(item: &ModuleItem) -> bool {
+        match item {
+            ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. })) => match **expr {
+                Expr::Invalid(..) => true,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn recovery_collects_all_errors() {
+        let (module, errors) = module_with_recovery(
+            "let a = ;
+foo();
+let b = 1 +;
+bar();",
+        );
+
+        assert_eq!(module.body.len(), 4);
+        assert!(is_invalid(&module.body[0]));
+        assert!(!is_invalid(&module.body[1]));
+        assert!(is_invalid(&module.body[2]));
+        assert!(!is_invalid(&module.body[3]));
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].span.lo() < errors[1].span.lo());
+    }
+
+    #[test]
+    fn recovery_in_block() {
+        let (module, errors) = module_with_recovery(
+            "function f() {
+    a(;
+    b();
+}
+c();",
+        );
+
+        assert_eq!(module.body.len(), 2);
+        match &module.body[0] {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Fn(f))) => {
+                let body = &f.function.body.as_ref().unwrap().stmts;
+                assert_eq!(body.len(), 2);
+                assert!(is_invalid(&ModuleItem::Stmt(body[0].clone())));
+            }
+            item => panic!("expected a function declaration, got {:?}", item),
+        }
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn recovery_from_lexer_error() {
+        let (module, errors) = module_with_recovery(
+            "a = 1 ¬ 2;
+b();",
+        );
+
+        assert_eq!(module.body.len(), 2);
+        assert!(is_invalid(&module.body[0]));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, SyntaxError::UnexpectedChar { c: '¬' });
+    }
+
+    #[test]
+    fn recovery_at_eof() {
+        let (module, errors) = module_with_recovery(
+            "function f() {
+    a();",
+        );
+
+        assert_eq!(module.body.len(), 1);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, SyntaxError::Eof);
+    }
+
+    #[test]
+    fn recovery_without_errors() {
+        let (module, errors) = module_with_recovery("import foo from 'foo'; foo();");
+
+        assert_eq!(module.body.len(), 2);
+        assert!(errors.is_empty());
+    }
 }
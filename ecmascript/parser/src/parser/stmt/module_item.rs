@@ -60,12 +60,16 @@ impl<'a, I: Tokens> Parser<'a, I> {
             .map(ModuleItem::from);
         }
 
-        let type_only = self.input.syntax().typescript()
+        let mut type_only = self.input.syntax().typed()
             && is!("type")
             && (peeked_is!('{') || !peeked_is!("from") && !peeked_is!(','));
 
         if type_only {
             assert_and_bump!("type");
+        } else if self.input.syntax().flow() && is!("typeof") {
+            // `import typeof A from 'a'`
+            assert_and_bump!("typeof");
+            type_only = true;
         }
 
         let mut specifiers = vec![];
@@ -93,6 +97,8 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 }));
             } else if eat!('{') {
                 let mut first = true;
+                // Flow allows marking each specifier as a type import.
+                let mut all_types = self.input.syntax().flow() && specifiers.is_empty();
                 while !eof!() && !is!('}') {
                     if first {
                         first = false;
@@ -100,9 +106,23 @@ impl<'a, I: Tokens> Parser<'a, I> {
                         break;
                     }
 
+                    let is_type = self.input.syntax().flow()
+                        && is_one_of!("type", "typeof")
+                        && peeked_is!(IdentName)
+                        && !peeked_is!("as");
+                    if is_type {
+                        bump!();
+                    }
+                    all_types &= is_type;
+
                     specifiers.push(self.parse_import_specifier()?);
                 }
                 expect!('}');
+
+                // `import { type A, typeof B } from 'a'` is a type-only import.
+                if all_types && !specifiers.is_empty() {
+                    type_only = true;
+                }
             }
         }
 
@@ -185,7 +205,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
             }
         }
 
-        if self.input.syntax().typescript() && is!(IdentName) {
+        if self.input.syntax().typed() && is!(IdentName) {
             let sym = match *cur!(true)? {
                 Token::Word(ref w) => w.clone().into(),
                 _ => unreachable!(),
@@ -259,7 +279,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
             }
         }
 
-        let type_only = self.input.syntax().typescript() && eat!("type");
+        let type_only = self.input.syntax().typed() && eat!("type");
 
         // Some("default") if default is exported from 'src'
         let mut export_default = None;
@@ -8,7 +8,7 @@ use swc_common::{Spanned, SyntaxContext};
 impl<'a, I: Tokens> Parser<'a, I> {
     /// `tsNextTokenCanFollowModifier`
     fn ts_next_token_can_follow_modifier(&mut self) -> PResult<'a, bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         // Note: TypeScript's implementation is much more complicated because
         // more things are considered modifiers there.
@@ -56,7 +56,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     /// `tsIsListTerminator`
 
     fn is_ts_list_terminator(&mut self, kind: ParsingContext) -> PResult<'a, bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         Ok(match kind {
             ParsingContext::EnumMembers | ParsingContext::TypeMembers => is!('}'),
@@ -77,7 +77,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     where
         F: FnMut(&mut Self) -> PResult<'a, T>,
     {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let mut buf = vec![];
         while !self.is_ts_list_terminator(kind)? {
@@ -113,7 +113,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     where
         F: FnMut(&mut Self) -> PResult<'a, (BytePos, T)>,
     {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let mut buf = vec![];
 
@@ -164,7 +164,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     where
         F: FnMut(&mut Self) -> PResult<'a, T>,
     {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         if !skip_first_token {
             if bracket {
@@ -187,7 +187,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseEntityName`
     fn parse_ts_entity_name(&mut self, allow_reserved_words: bool) -> PResult<'a, TsEntityName> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let init = self.parse_ident_name()?;
         match init {
@@ -230,7 +230,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseTypeReference`
     fn parse_ts_type_ref(&mut self) -> PResult<'a, TsTypeRef> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let start = cur_pos!();
 
@@ -261,7 +261,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         has_asserts_keyword: bool,
         lhs: TsThisType,
     ) -> PResult<'a, TsTypePredicate> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let param_name = TsThisTypeOrIdent::TsThisType(lhs);
         let type_ann = if eat!("is") {
@@ -284,7 +284,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseThisTypeNode`
     fn parse_ts_this_type_node(&mut self) -> PResult<'a, TsThisType> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         expect!("this");
 
@@ -337,7 +337,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseTypeQuery`
     fn parse_ts_type_query(&mut self) -> PResult<'a, TsTypeQuery> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let start = cur_pos!();
         expect!("typeof");
@@ -359,12 +359,23 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseTypeParameter`
     fn parse_ts_type_param(&mut self) -> PResult<'a, TsTypeParam> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let start = cur_pos!();
 
+        if self.input.syntax().flow() {
+            // Variance sigils (`+T`, `-T`) have no typescript equivalent.
+            if !eat!('+') {
+                eat!('-');
+            }
+        }
+
         let name = self.parse_ident_name()?;
-        let constraint = self.eat_then_parse_ts_type(&tok!("extends"))?;
+        let constraint = if self.input.syntax().flow() {
+            self.eat_then_parse_ts_type(&tok!(':'))?
+        } else {
+            self.eat_then_parse_ts_type(&tok!("extends"))?
+        };
         let default = self.eat_then_parse_ts_type(&tok!('='))?;
 
         Ok(TsTypeParam {
@@ -403,9 +414,9 @@ impl<'a, I: Tokens> Parser<'a, I> {
         &mut self,
         return_token: &'static Token,
     ) -> PResult<'a, TsTypeAnn> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
-        self.in_type().parse_with(|p| {
+        let type_ann = self.in_type().parse_with(|p| {
             let return_token_start = cur_pos!();
             if !p.input.eat(return_token) {
                 let cur = format!("{:?}", cur!(false).ok());
@@ -455,7 +466,15 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 span: span!(return_token_start),
                 type_ann: node,
             })
-        })
+        })?;
+
+        if self.input.syntax().flow() && is!('%') && peeked_is!("checks") {
+            // Predicate functions are not checked by us.
+            bump!();
+            bump!();
+        }
+
+        Ok(type_ann)
     }
 
     /// `tsTryParse`
@@ -463,7 +482,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     where
         F: FnOnce(&mut Self) -> PResult<'a, Option<bool>>,
     {
-        if !self.input.syntax().typescript() {
+        if !self.input.syntax().typed() {
             return Ok(false);
         }
         let mut cloned = self.clone();
@@ -488,7 +507,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     where
         F: FnOnce(&mut Self) -> PResult<'a, Option<T>>,
     {
-        if !self.input.syntax().typescript() {
+        if !self.input.syntax().typed() {
             return None;
         }
         let mut cloned = self.clone();
@@ -513,7 +532,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         eat_colon: bool,
         start: BytePos,
     ) -> PResult<'a, TsTypeAnn> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         self.in_type().parse_with(|p| {
             if eat_colon {
@@ -530,7 +549,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     }
 
     /// `tsEatThenParseType`
-    fn eat_then_parse_ts_type(
+    pub(super) fn eat_then_parse_ts_type(
         &mut self,
         token_to_eat: &'static Token,
     ) -> PResult<'a, Option<Box<TsType>>> {
@@ -545,7 +564,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsExpectThenParseType`
     fn expect_then_parse_ts_type(&mut self, token: &'static Token) -> PResult<'a, Box<TsType>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         self.in_type().parse_with(|p| {
             if !p.input.eat(token) {
@@ -558,7 +577,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsNextThenParseType`
     pub(super) fn next_then_parse_ts_type(&mut self) -> PResult<'a, Box<TsType>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         self.in_type().parse_with(|p| {
             bump!();
@@ -569,7 +588,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseEnumMember`
     fn parse_ts_enum_member(&mut self) -> PResult<'a, TsEnumMember> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let start = cur_pos!();
         // Computed property names are grammar errors in an enum, so accept just string
@@ -579,7 +598,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 Lit::Str(s) => TsEnumMemberId::Str(s),
                 _ => unreachable!(),
             })?,
-            Token::Num(v) => {
+            Token::Num { value: v, .. } => {
                 bump!();
                 let span = span!(start);
 
@@ -633,7 +652,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         start: BytePos,
         is_const: bool,
     ) -> PResult<'a, TsEnumDecl> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let id = self.parse_ident_name()?;
         expect!('{');
@@ -654,7 +673,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     fn parse_ts_module_block(&mut self) -> PResult<'a, TsModuleBlock> {
         trace_cur!(parse_ts_module_block);
 
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let start = cur_pos!();
         expect!('{');
@@ -674,7 +693,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseModuleOrNamespaceDeclaration`
     fn parse_ts_module_or_ns_decl(&mut self, start: BytePos) -> PResult<'a, TsModuleDecl> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let id = self.parse_ident_name()?;
         let body: TsNamespaceBody = if eat!('.') {
@@ -709,7 +728,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         &mut self,
         start: BytePos,
     ) -> PResult<'a, TsModuleDecl> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let (global, id) = if is!("global") {
             let id = self.parse_ident_name()?;
@@ -744,7 +763,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     }
 
     pub fn parse_type(&mut self) -> PResult<'a, Box<TsType>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         self.in_type().parse_ts_type()
     }
@@ -755,7 +774,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     pub(super) fn parse_ts_type(&mut self) -> PResult<'a, Box<TsType>> {
         trace_cur!(parse_ts_type);
 
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         // Need to set `state.inType` so that we don't parse JSX in a type context.
         debug_assert!(self.ctx().in_type);
@@ -791,7 +810,11 @@ impl<'a, I: Tokens> Parser<'a, I> {
     fn parse_ts_non_conditional_type(&mut self) -> PResult<'a, Box<TsType>> {
         trace_cur!(parse_ts_non_conditional_type);
 
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
+
+        if self.input.syntax().flow() {
+            return self.parse_flow_non_conditional_type();
+        }
 
         if self.is_ts_start_of_fn_type()? {
             return self
@@ -811,7 +834,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     }
 
     fn is_ts_start_of_fn_type(&mut self) -> PResult<'a, bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         if is!('<') {
             return Ok(true);
@@ -825,7 +848,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         &mut self,
         start: BytePos,
     ) -> PResult<'a, TsTypeAssertion> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         // Not actually necessary to set state.inType because we never reach here if JSX
         // plugin is enabled, but need `tsInType` to satisfy the assertion in
@@ -842,7 +865,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseHeritageClause`
     pub(super) fn parse_ts_heritage_clause(&mut self) -> PResult<'a, Vec<TsExprWithTypeArgs>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         self.parse_ts_delimited_list(ParsingContext::HeritageClauseElement, |p| {
             p.parse_expr_with_type_args()
@@ -851,7 +874,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseExpressionWithTypeArguments`
     fn parse_expr_with_type_args(&mut self) -> PResult<'a, TsExprWithTypeArgs> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let start = cur_pos!();
         // Note: TS uses parseLeftHandSideExpressionOrHigher,
@@ -875,7 +898,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         &mut self,
         start: BytePos,
     ) -> PResult<'a, TsInterfaceDecl> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let id = self.parse_ident_name()?;
         match id.sym {
@@ -934,7 +957,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseTypeAliasDeclaration`
     fn parse_ts_type_alias_decl(&mut self, start: BytePos) -> PResult<'a, TsTypeAliasDecl> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let id = self.parse_ident_name()?;
         let type_params = self.try_parse_ts_type_params()?;
@@ -955,7 +978,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         start: BytePos,
         is_export: bool,
     ) -> PResult<'a, TsImportEqualsDecl> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let id = self.parse_ident_name()?;
         expect!('=');
@@ -973,14 +996,14 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsIsExternalModuleReference`
     fn is_ts_external_module_ref(&mut self) -> PResult<'a, bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         Ok(is!("require") && peeked_is!('('))
     }
 
     /// `tsParseModuleReference`
     fn parse_ts_module_ref(&mut self) -> PResult<'a, TsModuleRef> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         if self.is_ts_external_module_ref()? {
             self.parse_ts_external_module_ref().map(From::from)
@@ -993,7 +1016,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     /// `tsParseExternalModuleReference`
     #[allow(clippy::cognitive_complexity)]
     fn parse_ts_external_module_ref(&mut self) -> PResult<'a, TsExternalModuleRef> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let start = cur_pos!();
         expect!("require");
@@ -1017,7 +1040,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     where
         F: FnOnce(&mut Self) -> PResult<'a, T>,
     {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let mut cloned = self.clone();
         cloned.emit_err = false;
@@ -1027,7 +1050,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsIsUnambiguouslyStartOfFunctionType`
     fn is_ts_unambiguously_start_of_fn_type(&mut self) -> PResult<'a, bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         assert_and_bump!('(');
         if is_one_of!(')', "...") {
@@ -1053,7 +1076,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsSkipParameterStart`
     fn skip_ts_parameter_start(&mut self) -> PResult<'a, bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let _ = self.eat_any_ts_modifier()?;
 
@@ -1096,10 +1119,10 @@ impl<'a, I: Tokens> Parser<'a, I> {
     }
 
     /// `tsParseTypeMemberSemicolon`
-    fn parse_ts_type_member_semicolon(&mut self) -> PResult<'a, ()> {
-        debug_assert!(self.input.syntax().typescript());
+    pub(super) fn parse_ts_type_member_semicolon(&mut self) -> PResult<'a, ()> {
+        debug_assert!(self.input.syntax().typed());
 
-        if !eat!(',') {
+        if !eat!(',') && !(self.input.syntax().flow() && is!('|') && peeked_is!('}')) {
             expect!(';');
         }
 
@@ -1111,7 +1134,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         &mut self,
         kind: SignatureParsingMode,
     ) -> PResult<'a, Either<TsCallSignatureDecl, TsConstructSignatureDecl>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let start = cur_pos!();
 
@@ -1153,8 +1176,8 @@ impl<'a, I: Tokens> Parser<'a, I> {
     }
 
     /// `tsIsUnambiguouslyIndexSignature`
-    fn is_ts_unambiguously_index_signature(&mut self) -> PResult<'a, bool> {
-        debug_assert!(self.input.syntax().typescript());
+    pub(super) fn is_ts_unambiguously_index_signature(&mut self) -> PResult<'a, bool> {
+        debug_assert!(self.input.syntax().typed());
 
         // Note: babel's comment is wrong
         assert_and_bump!('['); // Skip '['
@@ -1210,7 +1233,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         start: BytePos,
         readonly: bool,
     ) -> PResult<'a, Either<TsPropertySignature, TsMethodSignature>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         // ----- inlined self.parsePropertyName(node);
         let (computed, key) = if eat!('[') {
@@ -1225,7 +1248,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
             self.with_ctx(ctx).parse_with(|p| {
                 // We check if it's valid for it to be a private name when we push it.
                 let key = match *cur!(true)? {
-                    Token::Num(..) | Token::Str { .. } => p.parse_new_expr(),
+                    Token::Num { .. } | Token::Str { .. } => p.parse_new_expr(),
                     _ => p.parse_maybe_private_name().map(|e| match e {
                         Either::Left(_) => unreachable!(
                             "private name inside parse_ts_property_or_method_signature"
@@ -1283,8 +1306,8 @@ impl<'a, I: Tokens> Parser<'a, I> {
     }
 
     /// `tsParseTypeMember`
-    fn parse_ts_type_member(&mut self) -> PResult<'a, TsTypeElement> {
-        debug_assert!(self.input.syntax().typescript());
+    pub(super) fn parse_ts_type_member(&mut self) -> PResult<'a, TsTypeElement> {
+        debug_assert!(self.input.syntax().typed());
 
         fn into_type_elem(
             e: Either<TsCallSignatureDecl, TsConstructSignatureDecl>,
@@ -1322,7 +1345,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsIsStartOfConstructSignature`
     fn is_ts_start_of_construct_signature(&mut self) -> PResult<'a, bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         bump!();
 
@@ -1331,7 +1354,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseTypeLiteral`
     fn parse_ts_type_lit(&mut self) -> PResult<'a, TsTypeLit> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let start = cur_pos!();
         let members = self.parse_ts_object_type_members()?;
@@ -1343,7 +1366,12 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseObjectTypeMembers`
     fn parse_ts_object_type_members(&mut self) -> PResult<'a, Vec<TsTypeElement>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
+
+        if self.input.syntax().flow() {
+            let (members, _) = self.parse_flow_object_type_members()?;
+            return Ok(members);
+        }
 
         expect!('{');
         let members =
@@ -1354,7 +1382,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsIsStartOfMappedType`
     fn is_ts_start_of_mapped_type(&mut self) -> PResult<'a, bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         bump!();
         if eat!('+') || eat!('-') {
@@ -1377,7 +1405,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseMappedTypeParameter`
     fn parse_ts_mapped_type_param(&mut self) -> PResult<'a, TsTypeParam> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let start = cur_pos!();
         let name = self.parse_ident_name()?;
@@ -1394,7 +1422,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     /// `tsParseMappedType`
     #[allow(clippy::cognitive_complexity)]
     fn parse_ts_mapped_type(&mut self) -> PResult<'a, TsMappedType> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let start = cur_pos!();
         expect!('{');
@@ -1443,7 +1471,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseTupleType`
     fn parse_ts_tuple_type(&mut self) -> PResult<'a, TsTupleType> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let start = cur_pos!();
         let elem_types = self.parse_ts_bracketed_list(
@@ -1484,7 +1512,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseTupleElementType`
     fn parse_ts_tuple_element_type(&mut self) -> PResult<'a, Box<TsType>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         // parses `...TsType[]`
         let start = cur_pos!();
@@ -1512,7 +1540,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseParenthesizedType`
     fn parse_ts_parenthesized_type(&mut self) -> PResult<'a, TsParenthesizedType> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let start = cur_pos!();
         expect!('(');
@@ -1531,7 +1559,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     ) -> PResult<'a, TsFnOrConstructorType> {
         trace_cur!(parse_ts_fn_or_constructor_type);
 
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let start = cur_pos!();
         if !is_fn_type {
@@ -1564,7 +1592,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseLiteralTypeNode`
     fn parse_ts_lit_type_node(&mut self) -> PResult<'a, TsLitType> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let start = cur_pos!();
 
@@ -1593,7 +1621,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseBindingListForSignature`
     fn parse_ts_binding_list_for_signature(&mut self) -> PResult<'a, Vec<TsFnParam>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let params = self.parse_formal_params()?;
         let mut list = vec![];
@@ -1650,7 +1678,13 @@ impl<'a, I: Tokens> Parser<'a, I> {
     /// `tsParseNonArrayType`
     #[allow(clippy::cognitive_complexity)]
     fn parse_ts_non_array_type(&mut self) -> PResult<'a, Box<TsType>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
+
+        if self.input.syntax().flow() {
+            if let Some(ty) = self.parse_flow_non_array_type()? {
+                return Ok(ty);
+            }
+        }
 
         let start = cur_pos!();
 
@@ -1723,7 +1757,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 let start = cur_pos!();
                 bump!();
                 if match *cur!(true)? {
-                    Token::Num(..) => false,
+                    Token::Num { .. } => false,
                     _ => true,
                 } {
                     unexpected!()
@@ -1733,6 +1767,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                     Lit::Num(num) => TsLit::Number(Number {
                         span: num.span,
                         value: -num.value,
+                        raw: None,
                     }),
                     _ => unreachable!(),
                 };
@@ -1789,7 +1824,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseArrayTypeOrHigher`
     fn parse_ts_array_type_or_higher(&mut self, readonly: bool) -> PResult<'a, Box<TsType>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let mut ty = self.parse_ts_non_array_type()?;
 
@@ -1816,7 +1851,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseTypeOperator`
     fn parse_ts_type_operator(&mut self, op: TsTypeOperatorOp) -> PResult<'a, TsTypeOperator> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let start = cur_pos!();
         match op {
@@ -1835,7 +1870,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseInferType`
     fn parse_ts_infer_type(&mut self) -> PResult<'a, TsInferType> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let start = cur_pos!();
         expect!("infer");
@@ -1853,8 +1888,8 @@ impl<'a, I: Tokens> Parser<'a, I> {
     }
 
     /// `tsParseTypeOperatorOrHigher`
-    fn parse_ts_type_operator_or_higher(&mut self) -> PResult<'a, Box<TsType>> {
-        debug_assert!(self.input.syntax().typescript());
+    pub(super) fn parse_ts_type_operator_or_higher(&mut self) -> PResult<'a, Box<TsType>> {
+        debug_assert!(self.input.syntax().typed());
 
         let operator = if is!("keyof") {
             Some(TsTypeOperatorOp::KeyOf)
@@ -1994,7 +2029,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                     .map(Some);
             }
 
-            if is!("const") && peeked_is!("enum") {
+            if p.input.syntax().typescript() && is!("const") && peeked_is!("enum") {
                 assert_and_bump!("const");
                 let _ = cur!(true);
                 assert_and_bump!("enum");
@@ -2019,7 +2054,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                     .map(Some);
             }
 
-            if is!("global") {
+            if p.input.syntax().typescript() && is!("global") {
                 return p
                     .parse_ts_ambient_external_module_decl(start)
                     .map(Decl::from)
@@ -2071,7 +2106,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         next: bool,
     ) -> PResult<'a, Option<Decl>> {
         match value {
-            js_word!("abstract") => {
+            js_word!("abstract") if self.input.syntax().typescript() => {
                 if next || is!("class") {
                     if next {
                         bump!();
@@ -2092,7 +2127,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 }
             }
 
-            js_word!("enum") => {
+            js_word!("enum") if self.input.syntax().typescript() => {
                 if next || is!(IdentRef) {
                     if next {
                         bump!();
@@ -2117,7 +2152,8 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 }
             }
 
-            js_word!("module") => {
+            // Flow only has `declare module`.
+            js_word!("module") if self.input.syntax().typescript() || self.ctx().in_declare => {
                 if next {
                     bump!();
                 }
@@ -2138,7 +2174,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 }
             }
 
-            js_word!("namespace") => {
+            js_word!("namespace") if self.input.syntax().typescript() => {
                 if next || is!(IdentRef) {
                     if next {
                         bump!();
@@ -2162,6 +2198,19 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 }
             }
 
+            js_word!("opaque") if self.input.syntax().flow() => {
+                if next || is!("type") {
+                    if next {
+                        bump!();
+                    }
+                    expect!("type");
+                    return self
+                        .parse_flow_opaque_type_decl(start)
+                        .map(From::from)
+                        .map(Some);
+                }
+            }
+
             _ => {}
         }
 
@@ -2184,7 +2233,11 @@ impl<'a, I: Tokens> Parser<'a, I> {
                     .map(|p| p.pat)
                     .collect();
                 expect!(')');
-                let return_type = p.try_parse_ts_type_or_type_predicate_ann()?;
+                let ctx = Context {
+                    no_anon_fn_type: true,
+                    ..p.ctx()
+                };
+                let return_type = p.with_ctx(ctx).try_parse_ts_type_or_type_predicate_ann()?;
                 expect!("=>");
 
                 Ok(Some((type_params, params, return_type)))
@@ -2222,10 +2275,16 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseTypeArguments`
     pub fn parse_ts_type_args(&mut self) -> PResult<'a, TsTypeParamInstantiation> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let start = cur_pos!();
-        let params = self.in_type().parse_with(|p| {
+        let ctx = Context {
+            in_type: true,
+            // `T => U` is not ambiguous in type arguments.
+            no_anon_fn_type: false,
+            ..self.ctx()
+        };
+        let params = self.with_ctx(ctx).parse_with(|p| {
             // Temporarily remove a JSX parsing context, which makes us scan different
             // tokens.
             p.ts_in_no_context(|p| {
@@ -2248,7 +2307,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
     /// `tsParseIntersectionTypeOrHigher`
     fn parse_ts_intersection_type_or_higher(&mut self) -> PResult<'a, Box<TsType>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         self.parse_ts_union_or_intersection_type(
             UnionOrIntersection::Intersection,
@@ -2258,8 +2317,8 @@ impl<'a, I: Tokens> Parser<'a, I> {
     }
 
     /// `tsParseUnionTypeOrHigher`
-    fn parse_ts_union_type_or_higher(&mut self) -> PResult<'a, Box<TsType>> {
-        debug_assert!(self.input.syntax().typescript());
+    pub(super) fn parse_ts_union_type_or_higher(&mut self) -> PResult<'a, Box<TsType>> {
+        debug_assert!(self.input.syntax().typed());
 
         self.parse_ts_union_or_intersection_type(
             UnionOrIntersection::Union,
@@ -2278,16 +2337,17 @@ impl<'a, I: Tokens> Parser<'a, I> {
     where
         F: FnMut(&mut Self) -> PResult<'a, Box<TsType>>,
     {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let start = cur_pos!(); // include the leading operator in the start
         self.input.eat(operator);
 
         let ty = parse_constituent_type(self)?;
-        if self.input.is(&operator) {
+        if self.is_ts_union_or_intersection_op(operator)? {
             let mut types = vec![ty];
 
-            while self.input.eat(operator) {
+            while self.is_ts_union_or_intersection_op(operator)? {
+                bump!();
                 types.push(parse_constituent_type(self)?);
             }
 
@@ -2307,6 +2367,11 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
         Ok(ty)
     }
+
+    fn is_ts_union_or_intersection_op(&mut self, operator: &'static Token) -> PResult<'a, bool> {
+        // `|}` closes an exact object type in flow.
+        Ok(self.input.is(operator) && !(self.input.syntax().flow() && peeked_is!('}')))
+    }
 }
 
 impl<'a, I: Tokens> Parser<'a, I> {
@@ -2315,7 +2380,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
     where
         F: FnOnce(&mut Self) -> PResult<'a, T>,
     {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typed());
 
         let cloned = self.input.token_context().clone();
         self.input
@@ -2394,6 +2459,7 @@ mod tests {
                         lit: TsLit::Number(Number {
                             span: DUMMY_SP,
                             value: -1.0,
+                            raw: None,
                         }),
                     }),
                 })));
@@ -2429,6 +2495,7 @@ mod tests {
                             arg: box Expr::Lit(Lit::Num(Number {
                                 span: DUMMY_SP,
                                 value: 1.0,
+                                raw: Some("1".into()),
                             })),
                         })),
                         definite: false,
@@ -121,12 +121,22 @@ pub enum Token {
     #[kind(starts_expr)]
     Regex(JsWord, JsWord),
 
-    /// TODO: Make Num as enum and separate decimal, binary, ..etc
+    /// Numeric literal.
     #[kind(starts_expr)]
-    Num(f64),
+    Num {
+        value: f64,
+        /// Source text of the literal.
+        raw: JsWord,
+    },
 
+    /// BigInt literal.
     #[kind(starts_expr)]
-    BigInt(#[cfg_attr(feature = "fold", fold(ignore))] BigIntValue),
+    BigInt {
+        #[cfg_attr(feature = "fold", fold(ignore))]
+        value: BigIntValue,
+        /// Source text of the literal, without `n`.
+        raw: JsWord,
+    },
 
     JSXName {
         name: JsWord,
@@ -372,46 +382,58 @@ impl Debug for Word {
 /// Keywords
 #[derive(Kind, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "fold", derive(Fold))]
-#[kind(function(before_expr = "bool", starts_expr = "bool"))]
+#[kind(function(before_expr = "bool", starts_expr = "bool", starts_stmt = "bool"))]
 pub enum Keyword {
     /// Spec says this might be identifier.
     #[kind(before_expr, starts_expr)]
     Await,
 
+    #[kind(starts_stmt)]
     Break,
     #[kind(before_expr)]
     Case,
     Catch,
+    #[kind(starts_stmt)]
     Continue,
+    #[kind(starts_stmt)]
     Debugger,
     #[kind(before_expr)]
     Default_,
-    #[kind(before_expr)]
+    #[kind(before_expr, starts_stmt)]
     Do,
     #[kind(before_expr)]
     Else,
 
     Finally,
+    #[kind(starts_stmt)]
     For,
 
-    #[kind(starts_expr)]
+    #[kind(starts_expr, starts_stmt)]
     Function,
 
+    #[kind(starts_stmt)]
     If,
 
-    #[kind(before_expr)]
+    #[kind(before_expr, starts_stmt)]
     Return,
 
+    #[kind(starts_stmt)]
     Switch,
 
-    #[kind(before_expr, starts_expr)]
+    #[kind(before_expr, starts_expr, starts_stmt)]
     Throw,
 
+    #[kind(starts_stmt)]
     Try,
+    #[kind(starts_stmt)]
     Var,
+    #[kind(starts_stmt)]
     Let,
+    #[kind(starts_stmt)]
     Const,
+    #[kind(starts_stmt)]
     While,
+    #[kind(starts_stmt)]
     With,
 
     #[kind(before_expr, starts_expr)]
@@ -421,14 +443,15 @@ pub enum Keyword {
     #[kind(starts_expr)]
     Super,
 
-    #[kind(starts_expr)]
+    #[kind(starts_expr, starts_stmt)]
     Class,
 
     #[kind(before_expr)]
     Extends,
 
+    #[kind(starts_stmt)]
     Export,
-    #[kind(starts_expr)]
+    #[kind(starts_expr, starts_stmt)]
     Import,
 
     /// Spec says this might be identifier.
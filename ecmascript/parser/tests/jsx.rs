@@ -245,3 +245,9 @@ impl Fold<PatOrExpr> for Normalizer {
         }
     }
 }
+
+impl Fold<Number> for Normalizer {
+    fn fold(&mut self, node: Number) -> Number {
+        Number { raw: None, ..node }
+    }
+}
@@ -370,6 +370,24 @@ impl Fold<Str> for Normalizer {
         }
     }
 }
+impl Fold<Number> for Normalizer {
+    fn fold(&mut self, n: Number) -> Number {
+        Number {
+            span: Default::default(),
+            raw: None,
+            ..n
+        }
+    }
+}
+impl Fold<BigInt> for Normalizer {
+    fn fold(&mut self, n: BigInt) -> BigInt {
+        BigInt {
+            span: Default::default(),
+            raw: None,
+            ..n
+        }
+    }
+}
 impl Fold<Expr> for Normalizer {
     fn fold(&mut self, e: Expr) -> Expr {
         let e = e.fold_children(self);
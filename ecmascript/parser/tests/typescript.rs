@@ -302,7 +302,14 @@ impl Fold<Number> for Normalizer {
     fn fold(&mut self, mut node: Number) -> Number {
         node.value =
             serde_json::from_str(&serde_json::to_string(&node.value).unwrap()).unwrap_or(f64::NAN);
+        node.raw = None;
 
         node
     }
 }
+
+impl Fold<BigInt> for Normalizer {
+    fn fold(&mut self, node: BigInt) -> BigInt {
+        BigInt { raw: None, ..node }
+    }
+}
@@ -0,0 +1,39 @@
+// Generates `src/browserslist/data.json`.
+//
+// Usage: node scripts/browserslist-data-gen.js > src/browserslist/data.json
+const browserslist = require('browserslist');
+const envs = require('node-releases/data/processed/envs.json');
+const schedule = require('node-releases/data/release-schedule/release-schedule.json');
+
+const agents = {};
+for (const name in browserslist.data) {
+    const agent = browserslist.data[name];
+
+    agents[name] = {
+        versions: agent.versions.map(function (version) {
+            return {
+                version,
+                release_date: agent.releaseDate[version] || null,
+                usage: browserslist.usage.global[name + ' ' + version] || 0,
+            };
+        }),
+    };
+}
+
+const nodeSchedule = {};
+for (const key in schedule) {
+    nodeSchedule[key.replace(/^v/, '')] = {
+        start: Date.parse(schedule[key].start) / 1000,
+        end: Date.parse(schedule[key].end) / 1000,
+    };
+}
+
+console.log(JSON.stringify({
+    agents,
+    node: {
+        versions: envs.map(function (env) {
+            return env.version;
+        }),
+        schedule: nodeSchedule,
+    },
+}));
@@ -0,0 +1,216 @@
+//! Discovery of `.browserslistrc`, `browserslist` and `package.json`.
+
+use super::Error;
+use fxhash::FxHashMap;
+use serde::Deserialize;
+use serde_json::Value;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// Loads queries from the environment variables or the nearest config file of
+/// `path`.
+///
+/// Returns `Ok(None)` if there's no config.
+pub fn find_config(path: &Path) -> Result<Option<Vec<String>>, Error> {
+    if let Ok(queries) = env::var("BROWSERSLIST") {
+        return Ok(Some(vec![queries]));
+    }
+
+    let env = env::var("BROWSERSLIST_ENV")
+        .or_else(|_| env::var("NODE_ENV"))
+        .unwrap_or_else(|_| "production".into());
+
+    if let Ok(file) = env::var("BROWSERSLIST_CONFIG") {
+        let file = PathBuf::from(file);
+        let content = fs::read_to_string(&file).map_err(Error::Io)?;
+        let config = if file
+            .file_name()
+            .map(|s| s == "package.json")
+            .unwrap_or(false)
+        {
+            parse_package_json(&file, &content)?
+        } else {
+            Some(parse_config(&content))
+        };
+
+        return Ok(config.and_then(|config| pick_env(config, &env)));
+    }
+
+    for dir in path.ancestors() {
+        let mut found = None;
+
+        for name in &["browserslist", ".browserslistrc"] {
+            let file = dir.join(name);
+            if !file.is_file() {
+                continue;
+            }
+            if found.is_some() {
+                return Err(Error::InvalidConfig {
+                    path: dir.to_path_buf(),
+                    msg: "`browserslist` and `.browserslistrc` should not exist in the same \
+                          directory"
+                        .into(),
+                });
+            }
+
+            let content = fs::read_to_string(&file).map_err(Error::Io)?;
+            found = Some(parse_config(&content));
+        }
+
+        let file = dir.join("package.json");
+        if file.is_file() {
+            let content = fs::read_to_string(&file).map_err(Error::Io)?;
+            if let Some(config) = parse_package_json(&file, &content)? {
+                if found.is_some() {
+                    return Err(Error::InvalidConfig {
+                        path: dir.to_path_buf(),
+                        msg: "`package.json` has a `browserslist` field but a browserslist config \
+                              file exists in the same directory"
+                            .into(),
+                    });
+                }
+                found = Some(config);
+            }
+        }
+
+        if let Some(config) = found {
+            return Ok(pick_env(config, &env));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Queries of each environment. Queries not in a section are stored as
+/// `defaults`.
+type Config = FxHashMap<String, Vec<String>>;
+
+fn pick_env(mut config: Config, env: &str) -> Option<Vec<String>> {
+    config.remove(env).or_else(|| config.remove("defaults"))
+}
+
+/// Parses `.browserslistrc`.
+///
+/// ```text
+/// # comment
+/// > 1%
+///
+/// [development production]
+/// last 1 chrome version
+/// ```
+fn parse_config(content: &str) -> Config {
+    let mut config = Config::default();
+    let mut sections = vec!["defaults".to_string()];
+
+    for line in content.lines() {
+        let line = match line.find('#') {
+            Some(idx) => &line[..idx],
+            None => line,
+        }
+        .trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with('[') && line.ends_with(']') {
+            sections = line[1..line.len() - 1]
+                .split(' ')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect();
+            continue;
+        }
+
+        for section in &sections {
+            config
+                .entry(section.clone())
+                .or_insert_with(Default::default)
+                .push(line.to_string());
+        }
+    }
+
+    config
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Queries {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl From<Queries> for Vec<String> {
+    fn from(q: Queries) -> Self {
+        match q {
+            Queries::Single(s) => vec![s],
+            Queries::Multiple(v) => v,
+        }
+    }
+}
+
+/// Reads `browserslist` field of `package.json`.
+pub(super) fn parse_package_json(path: &Path, content: &str) -> Result<Option<Config>, Error> {
+    let invalid = |msg: String| Error::InvalidConfig {
+        path: path.to_path_buf(),
+        msg,
+    };
+
+    let mut pkg: FxHashMap<String, Value> = serde_json::from_str(content)
+        .map_err(|err| invalid(format!("failed to parse package.json: {}", err)))?;
+
+    let value = match pkg.remove("browserslist") {
+        Some(v) => v,
+        None => return Ok(None),
+    };
+
+    let mut config = Config::default();
+    match value {
+        Value::Object(map) => {
+            for (env, queries) in map {
+                let queries: Queries = serde_json::from_value(queries)
+                    .map_err(|err| invalid(format!("invalid `browserslist.{}`: {}", env, err)))?;
+                config.insert(env, queries.into());
+            }
+        }
+        value => {
+            let queries: Queries = serde_json::from_value(value)
+                .map_err(|err| invalid(format!("invalid `browserslist`: {}", err)))?;
+            config.insert("defaults".into(), queries.into());
+        }
+    }
+
+    Ok(Some(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sections() {
+        let config = parse_config(
+            "# comment
+> 1%
+
+[development production]
+last 1 chrome version # trailing comment
+
+[test]
+ie 11",
+        );
+
+        assert_eq!(
+            pick_env(config.clone(), "production"),
+            Some(vec!["last 1 chrome version".to_string()])
+        );
+        assert_eq!(
+            pick_env(config.clone(), "test"),
+            Some(vec!["ie 11".to_string()])
+        );
+        assert_eq!(pick_env(config, "staging"), Some(vec!["> 1%".to_string()]));
+    }
+}
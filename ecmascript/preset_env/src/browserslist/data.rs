@@ -0,0 +1,60 @@
+//! Snapshot of `caniuse-lite` and `node-releases`.
+//!
+//! Generated by `scripts/browserslist-data-gen.js`.
+
+use fxhash::FxHashMap;
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+
+pub static DATA: Lazy<Data> = Lazy::new(|| {
+    serde_json::from_str(include_str!("data.json")).expect("failed parse browserslist data.json")
+});
+
+#[derive(Debug, Deserialize)]
+pub struct Data {
+    pub agents: FxHashMap<String, Agent>,
+    pub node: NodeData,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Agent {
+    /// Sorted from the oldest to the newest.
+    pub versions: Vec<VersionDetail>,
+}
+
+impl Agent {
+    /// Versions which are already released.
+    pub fn released(&self) -> impl DoubleEndedIterator<Item = &VersionDetail> {
+        self.versions.iter().filter(|v| v.release_date.is_some())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VersionDetail {
+    /// e.g. `78`, `12.2-12.4`, `all`, `TP`
+    pub version: String,
+
+    /// Unix timestamp in seconds. `None` if the version is not released yet.
+    pub release_date: Option<i64>,
+
+    /// Global usage in percent.
+    pub usage: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeData {
+    /// Sorted from the oldest to the newest.
+    pub versions: Vec<String>,
+
+    /// Release schedule of each release line, keyed by `major` (or
+    /// `major.minor` for `0.x`).
+    pub schedule: FxHashMap<String, ReleaseSchedule>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReleaseSchedule {
+    /// Unix timestamp in seconds.
+    pub start: i64,
+    /// Unix timestamp in seconds.
+    pub end: i64,
+}
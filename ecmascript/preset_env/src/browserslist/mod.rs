@@ -0,0 +1,690 @@
+//! Rust port of [browserslist](https://github.com/browserslist/browserslist).
+//!
+//! Usage and release data is embedded, so resolving queries does not require
+//! node.js.
+
+pub use self::config::find_config;
+use self::data::{Agent, VersionDetail, DATA};
+use std::{
+    cmp::Ordering,
+    collections::BTreeSet,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+mod config;
+mod data;
+
+/// Queries used if there's no query and no config.
+const DEFAULTS: &[&str] = &["> 0.5%", "last 2 versions", "Firefox ESR", "not dead"];
+
+/// Browsers which are not officially supported or updated for 24 months.
+const DEAD: &[&str] = &[
+    "ie <= 10",
+    "ie_mob <= 10",
+    "bb <= 10",
+    "op_mob <= 12.1",
+    "samsung 4",
+];
+
+const FIREFOX_ESR: &[&str] = &["68"];
+
+#[derive(Debug)]
+pub enum Error {
+    UnknownQuery(String),
+    UnknownBrowser(String),
+    UnknownVersion {
+        browser: String,
+        version: String,
+    },
+    /// The first query cannot be `not ...`.
+    NotAtStart(String),
+    InvalidConfig {
+        path: PathBuf,
+        msg: String,
+    },
+    Io(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnknownQuery(q) => write!(f, "unknown browser query `{}`", q),
+            Error::UnknownBrowser(b) => write!(f, "unknown browser `{}`", b),
+            Error::UnknownVersion { browser, version } => {
+                write!(f, "unknown version `{}` of {}", version, browser)
+            }
+            Error::NotAtStart(q) => write!(f, "`{}` cannot be the first query", q),
+            Error::InvalidConfig { path, msg } => write!(f, "{}: {}", path.display(), msg),
+            Error::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+/// Resolves queries to a list of browsers like `chrome 78` or `ios_saf
+/// 12.2-12.4`.
+///
+/// If `queries` is empty, queries are loaded from the config of `path` and
+/// `defaults` is used if there's no config.
+pub fn resolve<S>(queries: &[S], path: &Path) -> Result<Vec<String>, Error>
+where
+    S: AsRef<str>,
+{
+    let queries: Vec<String> = queries
+        .iter()
+        .map(|s| s.as_ref().trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    let queries = if queries.is_empty() {
+        match find_config(path)? {
+            Some(queries) => queries,
+            None => DEFAULTS.iter().map(|s| s.to_string()).collect(),
+        }
+    } else {
+        queries
+    };
+
+    Resolver { path }
+        .resolve(&queries)
+        .map(|v| v.into_iter().collect())
+}
+
+struct Resolver<'a> {
+    /// Used to find shareable configs.
+    path: &'a Path,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Combinator {
+    Or,
+    And,
+}
+
+impl Resolver<'_> {
+    fn resolve<S>(&self, queries: &[S]) -> Result<BTreeSet<String>, Error>
+    where
+        S: AsRef<str>,
+    {
+        let mut result = BTreeSet::new();
+
+        for (i, (combinator, query)) in split(queries).into_iter().enumerate() {
+            let is_not = query
+                .get(..4)
+                .map_or(false, |s| s.eq_ignore_ascii_case("not "));
+            let (is_not, query) = if is_not {
+                (true, query[4..].trim())
+            } else {
+                (false, query)
+            };
+            if is_not && i == 0 {
+                return Err(Error::NotAtStart(query.to_string()));
+            }
+
+            let browsers = self.query(query)?;
+            result = match (combinator, is_not) {
+                (_, true) => result.difference(&browsers).cloned().collect(),
+                (Combinator::Or, false) => result.union(&browsers).cloned().collect(),
+                (Combinator::And, false) => result.intersection(&browsers).cloned().collect(),
+            };
+        }
+
+        Ok(result)
+    }
+
+    /// Resolves a query without combinators.
+    fn query(&self, query: &str) -> Result<BTreeSet<String>, Error> {
+        let lower = query.to_ascii_lowercase();
+        let words: Vec<&str> = lower.split_whitespace().collect();
+
+        let result = match &*words {
+            ["defaults"] => return self.resolve(DEFAULTS),
+            ["dead"] => return self.resolve(DEAD),
+
+            // Package names are case sensitive.
+            ["extends", _] => return self.extends(query[7..].trim()),
+
+            ["last", n, "versions"] | ["last", n, "version"] => {
+                let n = parse_count(query, n)?;
+                all_agents(|name, agent| last(name, agent, n))
+            }
+            ["last", n, "major", "versions"] | ["last", n, "major", "version"] => {
+                let n = parse_count(query, n)?;
+                all_agents(|name, agent| last_major(name, agent, n))
+            }
+            ["last", n, browser, "versions"] | ["last", n, browser, "version"] => {
+                let n = parse_count(query, n)?;
+                let (name, agent) = agent(browser)?;
+                last(name, agent, n)
+            }
+            ["last", n, browser, "major", "versions"]
+            | ["last", n, browser, "major", "version"] => {
+                let n = parse_count(query, n)?;
+                let (name, agent) = agent(browser)?;
+                last_major(name, agent, n)
+            }
+
+            ["unreleased", "versions"] => all_agents(unreleased),
+            ["unreleased", browser, "versions"] | ["unreleased", browser, "version"] => {
+                let (name, agent) = agent(browser)?;
+                unreleased(name, agent)
+            }
+
+            [op, percent] if percent.ends_with('%') && is_cmp_op(op) => {
+                let usage: f64 = percent[..percent.len() - 1]
+                    .parse()
+                    .map_err(|_| Error::UnknownQuery(query.to_string()))?;
+
+                all_agents(|name, agent| {
+                    agent
+                        .versions
+                        .iter()
+                        .filter(|v| compare(v.usage, *op, usage))
+                        .map(|v| format!("{} {}", name, v.version))
+                        .collect()
+                })
+            }
+
+            ["since", date] => {
+                let since = parse_date(date).ok_or_else(|| Error::UnknownQuery(query.into()))?;
+                all_agents(|name, agent| {
+                    agent
+                        .released()
+                        .filter(|v| v.release_date.unwrap() >= since)
+                        .map(|v| format!("{} {}", name, v.version))
+                        .collect()
+                })
+            }
+
+            ["firefox", "esr"] | ["ff", "esr"] | ["fx", "esr"] => FIREFOX_ESR
+                .iter()
+                .map(|v| format!("firefox {}", v))
+                .collect(),
+
+            ["opera", "mini", "all"] | ["operamini", "all"] | ["op_mini", "all"] => {
+                Some("op_mini all".to_string()).into_iter().collect()
+            }
+
+            ["maintained", "node", "versions"] => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs() as i64)
+                    .unwrap_or(0);
+
+                DATA.node
+                    .schedule
+                    .iter()
+                    .filter(|(_, s)| s.start < now && now < s.end)
+                    .filter_map(|(line, _)| latest_node(line))
+                    .map(|v| format!("node {}", v))
+                    .collect()
+            }
+
+            ["node", op, version] if is_cmp_op(op) => {
+                let version = parse_node_version(query, version)?;
+                DATA.node
+                    .versions
+                    .iter()
+                    .filter(|v| {
+                        compare_node(v, &version).map_or(false, |ord| match *op {
+                            ">" => ord == Ordering::Greater,
+                            ">=" => ord != Ordering::Less,
+                            "<" => ord == Ordering::Less,
+                            _ => ord != Ordering::Greater,
+                        })
+                    })
+                    .map(|v| format!("node {}", v))
+                    .collect()
+            }
+
+            ["node", version] => {
+                let v = latest_node(version).ok_or_else(|| Error::UnknownVersion {
+                    browser: "node".into(),
+                    version: version.to_string(),
+                })?;
+                Some(format!("node {}", v)).into_iter().collect()
+            }
+
+            [browser, op, version] if is_cmp_op(op) => {
+                let (name, agent) = agent(browser)?;
+                let version =
+                    parse_float(version).ok_or_else(|| Error::UnknownQuery(query.to_string()))?;
+
+                agent
+                    .released()
+                    .filter(|v| parse_float(&v.version).map_or(false, |v| compare(v, *op, version)))
+                    .map(|v| format!("{} {}", name, v.version))
+                    .collect()
+            }
+
+            [browser, version] => {
+                let (name, agent) = agent(browser)?;
+
+                match version.find('-') {
+                    // chrome 60-70
+                    Some(idx) if agent.versions.iter().all(|v| v.version != *version) => {
+                        let from = parse_float(&version[..idx]);
+                        let to = parse_float(&version[idx + 1..]);
+                        let (from, to) = match (from, to) {
+                            (Some(from), Some(to)) => (from, to),
+                            _ => return Err(Error::UnknownQuery(query.to_string())),
+                        };
+
+                        agent
+                            .released()
+                            .filter(|v| {
+                                parse_float(&v.version).map_or(false, |v| from <= v && v <= to)
+                            })
+                            .map(|v| format!("{} {}", name, v.version))
+                            .collect()
+                    }
+
+                    _ => {
+                        let v = normalize_version(agent, version).ok_or_else(|| {
+                            Error::UnknownVersion {
+                                browser: name.to_string(),
+                                version: version.to_string(),
+                            }
+                        })?;
+                        Some(format!("{} {}", name, v.version))
+                            .into_iter()
+                            .collect()
+                    }
+                }
+            }
+
+            _ => return Err(Error::UnknownQuery(query.to_string())),
+        };
+
+        Ok(result)
+    }
+
+    /// `extends browserslist-config-foo`
+    ///
+    /// Shareable configs are resolved from `node_modules`. As javascript cannot
+    /// be executed, only the `browserslist` field of `package.json` and json
+    /// entry points are supported.
+    fn extends(&self, name: &str) -> Result<BTreeSet<String>, Error> {
+        let pkg_name = name.split('/').last().unwrap_or(name);
+        if !pkg_name.starts_with("browserslist-config-")
+            && !(name.starts_with('@') && pkg_name == "browserslist-config")
+        {
+            return Err(Error::UnknownQuery(format!(
+                "extends {}: browserslist config needs `browserslist-config-` prefix",
+                name
+            )));
+        }
+
+        for dir in self.path.ancestors() {
+            let pkg_dir = dir.join("node_modules").join(name);
+            let pkg_json = pkg_dir.join("package.json");
+            if !pkg_json.is_file() {
+                continue;
+            }
+
+            let content = fs::read_to_string(&pkg_json).map_err(Error::Io)?;
+            if let Some(mut config) = config::parse_package_json(&pkg_json, &content)? {
+                if let Some(queries) = config.remove("defaults") {
+                    return self.resolve(&queries);
+                }
+            }
+
+            let pkg: serde_json::Value =
+                serde_json::from_str(&content).map_err(|err| Error::InvalidConfig {
+                    path: pkg_json.clone(),
+                    msg: err.to_string(),
+                })?;
+            let main = pkg_dir.join(
+                pkg.get("main")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("index.json"),
+            );
+            if main.extension().map(|ext| ext == "json").unwrap_or(false) {
+                let content = fs::read_to_string(&main).map_err(Error::Io)?;
+                let queries: Vec<String> =
+                    serde_json::from_str(&content).map_err(|err| Error::InvalidConfig {
+                        path: main.clone(),
+                        msg: err.to_string(),
+                    })?;
+                return self.resolve(&queries);
+            }
+
+            return Err(Error::InvalidConfig {
+                path: main,
+                msg: "shareable configs written in javascript are not supported".into(),
+            });
+        }
+
+        Err(Error::UnknownQuery(format!(
+            "extends {}: cannot find the package",
+            name
+        )))
+    }
+}
+
+/// Splits queries by `,`, ` or ` and ` and `.
+fn split<S>(queries: &[S]) -> Vec<(Combinator, &str)>
+where
+    S: AsRef<str>,
+{
+    let mut result = vec![];
+
+    for query in queries {
+        for or in query.as_ref().split(',').flat_map(|s| split_word(s, "or")) {
+            for (i, and) in split_word(or, "and").into_iter().enumerate() {
+                let combinator = if i == 0 {
+                    Combinator::Or
+                } else {
+                    Combinator::And
+                };
+
+                result.push((combinator, and));
+            }
+        }
+    }
+
+    result
+}
+
+/// Splits `s` by a case-insensitive keyword surrounded by whitespaces.
+fn split_word<'a>(s: &'a str, word: &str) -> Vec<&'a str> {
+    let mut result = vec![];
+    let mut start = 0;
+    let lower = s.to_ascii_lowercase();
+    let pat = format!(" {} ", word);
+
+    while let Some(idx) = lower[start..].find(&pat) {
+        result.push(s[start..start + idx].trim());
+        start += idx + pat.len();
+    }
+    result.push(s[start..].trim());
+
+    result.retain(|s| !s.is_empty());
+    result
+}
+
+fn agent(name: &str) -> Result<(&'static str, &'static Agent), Error> {
+    let name = match name {
+        "fx" | "ff" => "firefox",
+        "ios" => "ios_saf",
+        "explorer" => "ie",
+        "blackberry" => "bb",
+        "explorermobile" => "ie_mob",
+        "operamini" => "op_mini",
+        "operamobile" => "op_mob",
+        "chromeandroid" => "and_chr",
+        "firefoxandroid" => "and_ff",
+        "ucandroid" => "and_uc",
+        "qqandroid" => "and_qq",
+        _ => name,
+    };
+
+    DATA.agents
+        .get_key_value(name)
+        .map(|(k, v)| (&**k, v))
+        .ok_or_else(|| Error::UnknownBrowser(name.to_string()))
+}
+
+fn all_agents<F>(mut op: F) -> BTreeSet<String>
+where
+    F: FnMut(&str, &Agent) -> BTreeSet<String>,
+{
+    DATA.agents
+        .iter()
+        .flat_map(|(name, agent)| op(name, agent))
+        .collect()
+}
+
+fn last(name: &str, agent: &Agent, n: usize) -> BTreeSet<String> {
+    agent
+        .released()
+        .rev()
+        .take(n)
+        .map(|v| format!("{} {}", name, v.version))
+        .collect()
+}
+
+fn last_major(name: &str, agent: &Agent, n: usize) -> BTreeSet<String> {
+    let mut majors: Vec<&str> = agent.released().map(|v| major(&v.version)).collect();
+    majors.dedup();
+    let majors = &majors[majors.len().saturating_sub(n)..];
+
+    agent
+        .released()
+        .filter(|v| majors.contains(&major(&v.version)))
+        .map(|v| format!("{} {}", name, v.version))
+        .collect()
+}
+
+fn unreleased(name: &str, agent: &Agent) -> BTreeSet<String> {
+    agent
+        .versions
+        .iter()
+        .filter(|v| v.release_date.is_none())
+        .map(|v| format!("{} {}", name, v.version))
+        .collect()
+}
+
+fn major(v: &str) -> &str {
+    v.split(|c| c == '.' || c == '-').next().unwrap_or(v)
+}
+
+/// Finds the version which matches `version`.
+///
+/// `ios 12.3` matches `ios_saf 12.2-12.4` and `and_chr 70` matches the only
+/// version of `and_chr`.
+fn normalize_version<'a>(agent: &'a Agent, version: &str) -> Option<&'a VersionDetail> {
+    if let Some(v) = agent.versions.iter().find(|v| v.version == version) {
+        return Some(v);
+    }
+
+    let target = parse_float(version)?;
+    if let Some(v) = agent.versions.iter().find(|v| {
+        let mut range = v.version.split('-');
+        let from = range.next().and_then(parse_float);
+        let to = range.next().and_then(parse_float).or(from);
+
+        match (from, to) {
+            (Some(from), Some(to)) => from <= target && target <= to,
+            _ => false,
+        }
+    }) {
+        return Some(v);
+    }
+
+    if agent.versions.len() == 1 {
+        return agent.versions.first();
+    }
+
+    None
+}
+
+/// Works like `parseFloat` of javascript. Returns `None` for `NaN`.
+fn parse_float(s: &str) -> Option<f64> {
+    let mut end = 0;
+    let mut seen_dot = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            '0'..='9' => end = i + 1,
+            '.' if !seen_dot => seen_dot = true,
+            _ => break,
+        }
+    }
+
+    s[..end].parse().ok()
+}
+
+fn parse_count(query: &str, n: &str) -> Result<usize, Error> {
+    n.parse()
+        .map_err(|_| Error::UnknownQuery(query.to_string()))
+}
+
+fn is_cmp_op(op: &str) -> bool {
+    match op {
+        ">" | ">=" | "<" | "<=" => true,
+        _ => false,
+    }
+}
+
+fn compare(l: f64, op: &str, r: f64) -> bool {
+    match op {
+        ">" => l > r,
+        ">=" => l >= r,
+        "<" => l < r,
+        "<=" => l <= r,
+        _ => unreachable!("invalid operator: {}", op),
+    }
+}
+
+/// Parses `YYYY`, `YYYY-MM` or `YYYY-MM-DD` as an unix timestamp in utc.
+fn parse_date(s: &str) -> Option<i64> {
+    let mut parts = s.split('-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next().map(|s| s.parse().ok()).unwrap_or(Some(1))?;
+    let day: i64 = parts.next().map(|s| s.parse().ok()).unwrap_or(Some(1))?;
+    if parts.next().is_some() || month < 1 || month > 12 || day < 1 || day > 31 {
+        return None;
+    }
+
+    // http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+
+    Some(days * 86400)
+}
+
+fn parse_node_version(query: &str, v: &str) -> Result<Vec<u32>, Error> {
+    v.split('.')
+        .map(|s| s.parse())
+        .collect::<Result<_, _>>()
+        .map_err(|_| Error::UnknownQuery(query.to_string()))
+}
+
+/// Compares `v` with `target`, only using components specified in `target`.
+fn compare_node(v: &str, target: &[u32]) -> Option<Ordering> {
+    let v: Vec<u32> = v
+        .split('.')
+        .map(|s| s.parse().ok())
+        .collect::<Option<_>>()?;
+
+    Some(v.iter().take(target.len()).cmp(target.iter()))
+}
+
+/// `12` -> `12.13.0`
+fn latest_node(version: &str) -> Option<&'static str> {
+    let target: Vec<u32> = version
+        .split('.')
+        .map(|s| s.parse().ok())
+        .collect::<Option<_>>()?;
+
+    DATA.node
+        .versions
+        .iter()
+        .rev()
+        .find(|v| compare_node(v, &target) == Some(Ordering::Equal))
+        .map(|v| &**v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(queries: &[&str]) -> Vec<String> {
+        resolve(queries, Path::new("/")).unwrap()
+    }
+
+    #[test]
+    fn version() {
+        assert_eq!(run(&["chrome 78"]), vec!["chrome 78"]);
+        assert_eq!(run(&["ios 12.3"]), vec!["ios_saf 12.2-12.4"]);
+        assert_eq!(run(&["ChromeAndroid 70"]), vec!["and_chr 78"]);
+    }
+
+    #[test]
+    fn version_range() {
+        assert_eq!(
+            run(&["firefox 60-62"]),
+            vec!["firefox 60", "firefox 61", "firefox 62"]
+        );
+    }
+
+    #[test]
+    fn comparison() {
+        assert_eq!(run(&["ie >= 10"]), vec!["ie 10", "ie 11"]);
+        assert_eq!(run(&["edge > 17"]), vec!["edge 18"]);
+        assert_eq!(
+            run(&["safari < 5"]),
+            vec!["safari 3.1", "safari 3.2", "safari 4"]
+        );
+    }
+
+    #[test]
+    fn last_versions() {
+        assert_eq!(run(&["last 2 edge versions"]), vec!["edge 17", "edge 18"]);
+        assert_eq!(
+            run(&["last 1 ios major versions"]),
+            vec!["ios_saf 13.0-13.1", "ios_saf 13.2"]
+        );
+    }
+
+    #[test]
+    fn combinators() {
+        assert_eq!(
+            run(&["ie >= 9, edge 18 or not ie 10"]),
+            vec!["edge 18", "ie 11", "ie 9"]
+        );
+        assert_eq!(run(&["ie >= 9 and ie <= 10"]), vec!["ie 10", "ie 9"]);
+        assert_eq!(run(&["last 2 ie versions", "not dead"]), vec!["ie 11"]);
+    }
+
+    #[test]
+    fn not_at_start() {
+        assert!(resolve(&["not ie 11"], Path::new("/")).is_err());
+    }
+
+    #[test]
+    fn usage() {
+        let result = run(&["> 5%"]);
+        assert_eq!(result, vec!["and_chr 78", "chrome 77"]);
+    }
+
+    #[test]
+    fn since() {
+        assert_eq!(
+            run(&["edge > 12 and since 2018"]),
+            vec!["edge 17", "edge 18"]
+        );
+        assert_eq!(parse_date("1970-01-02"), Some(86400));
+        assert_eq!(parse_date("2019-10-22"), Some(1_571_702_400));
+    }
+
+    #[test]
+    fn node() {
+        assert_eq!(run(&["node 12"]), vec!["node 12.13.0"]);
+        assert_eq!(run(&["node 10.4"]), vec!["node 10.4.0"]);
+        assert!(run(&["node >= 13"])
+            .iter()
+            .all(|v| v.starts_with("node 13.")));
+    }
+
+    #[test]
+    fn defaults() {
+        let result = run(&["defaults"]);
+        assert!(result.contains(&"firefox 68".to_string()));
+        assert!(result.contains(&"ie 11".to_string()));
+        assert!(!result.contains(&"ie 10".to_string()));
+        assert!(!result.contains(&"chrome 80".to_string()));
+    }
+
+    #[test]
+    fn unknown() {
+        assert!(resolve(&["foo 11"], Path::new("/")).is_err());
+        assert!(resolve(&["chrome 1000"], Path::new("/")).is_err());
+    }
+}
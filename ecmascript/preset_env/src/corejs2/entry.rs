@@ -1,9 +1,10 @@
 use super::builtin::BUILTINS;
-use crate::{version::should_enable, Versions};
+use crate::{util::require_src, version::should_enable, Versions};
 use fxhash::FxHashSet;
 use swc_atoms::js_word;
 use swc_common::{util::move_map::MoveMap, Fold, FoldWith, DUMMY_SP};
 use swc_ecma_ast::*;
+use swc_ecma_transforms::util::StmtLike;
 
 #[derive(Debug)]
 pub struct Entry {
@@ -60,51 +61,35 @@ impl Entry {
             self.imports.insert(feature);
         }
     }
+
+    /// Removes `require('@swc/polyfill');` and friends at the top level.
+    fn fold_items<T>(&mut self, items: Vec<T>) -> Vec<T>
+    where
+        T: StmtLike + FoldWith<Self>,
+    {
+        items.move_flat_map(|item| {
+            let item = item.fold_with(self);
+
+            match item.as_stmt().and_then(require_src) {
+                Some(src) if self.add_all(src) => None,
+                _ => Some(item),
+            }
+        })
+    }
 }
 
 impl Fold<Vec<ModuleItem>> for Entry {
     fn fold(&mut self, items: Vec<ModuleItem>) -> Vec<ModuleItem> {
-        items.move_flat_map(|item| {
-            let item: ModuleItem = item.fold_with(self);
-
-            match item {
-                ModuleItem::Stmt(Stmt::Expr(ExprStmt {
-                    expr:
-                        box Expr::Call(CallExpr {
-                            callee:
-                                ExprOrSuper::Expr(box Expr::Ident(Ident {
-                                    sym: js_word!("require"),
-                                    ..
-                                })),
-                            ref args,
-                            ..
-                        }),
-                    ..
-                })) => {
-                    if args.len() == 1
-                        && match args[0] {
-                            ExprOrSpread {
-                                spread: None,
-                                expr: box Expr::Lit(Lit::Str(ref s)),
-                            } => {
-                                s.value == *"core-js"
-                                    || s.value == *"@swc/polyfill"
-                                    || s.value == *"@babel/polyfill"
-                            }
-                            _ => false,
-                        }
-                    {
-                        if self.add_all("@swc/polyfill") {
-                            return None;
-                        }
-                    }
-                }
-
-                _ => {}
-            }
+        self.fold_items(items)
+    }
+}
 
-            Some(item)
-        })
+impl Fold<Script> for Entry {
+    fn fold(&mut self, s: Script) -> Script {
+        Script {
+            body: self.fold_items(s.body),
+            ..s
+        }
     }
 }
 
@@ -1,10 +1,11 @@
-use super::compat::DATA as CORE_JS_COMPAT_DATA;
-use crate::{version::should_enable, Version, Versions};
+use super::{compat::DATA as CORE_JS_COMPAT_DATA, is_available};
+use crate::{util::require_src, version::should_enable, Version, Versions};
 use fxhash::{FxHashMap, FxHashSet};
 use once_cell::sync::Lazy;
 use swc_atoms::js_word;
-use swc_common::{Fold, FoldWith, DUMMY_SP};
+use swc_common::{util::move_map::MoveMap, Fold, FoldWith, DUMMY_SP};
 use swc_ecma_ast::*;
+use swc_ecma_transforms::util::StmtLike;
 
 static ENTRIES: Lazy<FxHashMap<String, Vec<&'static str>>> = Lazy::new(|| {
     serde_json::from_str::<FxHashMap<String, Vec<String>>>(include_str!("entries.json"))
@@ -21,21 +22,6 @@ static ENTRIES: Lazy<FxHashMap<String, Vec<&'static str>>> = Lazy::new(|| {
         .collect()
 });
 
-static MODULES_BY_VERSION: Lazy<FxHashMap<Version, Vec<&'static str>>> = Lazy::new(|| {
-    serde_json::from_str::<FxHashMap<_, _>>(include_str!("modules-by-versions.json"))
-        .expect("failed to parse modules-by-versions.json")
-        .into_iter()
-        .map(|(k, v): (Version, Vec<String>)| {
-            (
-                k,
-                v.into_iter()
-                    .map(|s: String| &*Box::leak(s.into_boxed_str()))
-                    .collect::<Vec<_>>(),
-            )
-        })
-        .collect()
-});
-
 #[derive(Debug)]
 pub struct Entry {
     is_any_target: bool,
@@ -87,13 +73,8 @@ impl Entry {
 
                 //                println!("{} -> {}", src, f);
 
-                for (_, features) in MODULES_BY_VERSION
-                    .iter()
-                    .filter(|(version, _features)| *corejs_version < **version)
-                {
-                    if features.contains(&*f) {
-                        return None;
-                    }
+                if !is_available(f, *corejs_version) {
+                    return None;
                 }
 
                 Some(f)
@@ -104,6 +85,36 @@ impl Entry {
             false
         }
     }
+
+    /// Removes `require('core-js/stable');` and friends at the top level.
+    fn fold_items<T>(&mut self, items: Vec<T>) -> Vec<T>
+    where
+        T: StmtLike + FoldWith<Self>,
+    {
+        items.move_flat_map(|item| {
+            let item = item.fold_with(self);
+
+            match item.as_stmt().and_then(require_src) {
+                Some(src) if self.add(src) => None,
+                _ => Some(item),
+            }
+        })
+    }
+}
+
+impl Fold<Vec<ModuleItem>> for Entry {
+    fn fold(&mut self, items: Vec<ModuleItem>) -> Vec<ModuleItem> {
+        self.fold_items(items)
+    }
+}
+
+impl Fold<Script> for Entry {
+    fn fold(&mut self, s: Script) -> Script {
+        Script {
+            body: self.fold_items(s.body),
+            ..s
+        }
+    }
 }
 
 impl Fold<ImportDecl> for Entry {
@@ -1,6 +1,34 @@
 pub(crate) use self::{entry::Entry, usage::UsageVisitor};
+use crate::Version;
+use fxhash::FxHashMap;
+use once_cell::sync::Lazy;
 
 mod compat;
 mod data;
 mod entry;
 mod usage;
+
+/// Maps a version of core js to the modules added in the version.
+static MODULES_BY_VERSION: Lazy<FxHashMap<Version, Vec<&'static str>>> = Lazy::new(|| {
+    serde_json::from_str::<FxHashMap<_, _>>(include_str!("modules-by-versions.json"))
+        .expect("failed to parse modules-by-versions.json")
+        .into_iter()
+        .map(|(k, v): (Version, Vec<String>)| {
+            (
+                k,
+                v.into_iter()
+                    .map(|s: String| &*Box::leak(s.into_boxed_str()))
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect()
+});
+
+/// Returns false if `module` is added in a version of core js newer than
+/// `corejs_version`.
+fn is_available(module: &str, corejs_version: Version) -> bool {
+    MODULES_BY_VERSION
+        .iter()
+        .filter(|(version, _)| corejs_version < **version)
+        .all(|(_, modules)| modules.iter().all(|m| *m != module))
+}
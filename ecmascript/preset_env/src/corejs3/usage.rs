@@ -6,10 +6,11 @@ use crate::{
             COMMON_ITERATORS, INSTANCE_PROPERTIES, POSSIBLE_GLOBAL_OBJECTS, PROMISE_DEPENDENCIES,
             STATIC_PROPERTIES,
         },
+        is_available,
     },
     util::DataMapExt,
     version::should_enable,
-    Versions,
+    Version, Versions,
 };
 use fxhash::FxHashSet;
 use swc_atoms::{js_word, JsWord};
@@ -20,11 +21,12 @@ pub(crate) struct UsageVisitor {
     shipped_proposals: bool,
     is_any_target: bool,
     target: Versions,
+    corejs_version: Version,
     pub required: FxHashSet<&'static str>,
 }
 
 impl UsageVisitor {
-    pub fn new(target: Versions, shipped_proposals: bool) -> Self {
+    pub fn new(target: Versions, corejs_version: Version, shipped_proposals: bool) -> Self {
         //        let mut v = Self { required: vec![] };
         //
         //
@@ -48,6 +50,7 @@ impl UsageVisitor {
             shipped_proposals,
             is_any_target: target.is_any_target(),
             target,
+            corejs_version,
             required: Default::default(),
         }
     }
@@ -58,6 +61,7 @@ impl UsageVisitor {
             shipped_proposals,
             is_any_target,
             target,
+            corejs_version,
             ..
         } = self;
 
@@ -76,6 +80,10 @@ impl UsageVisitor {
                 }
             }
 
+            if !is_available(f, *corejs_version) {
+                return None;
+            }
+
             Some(f)
         }));
     }
@@ -8,34 +8,45 @@ pub use self::{transform_data::Feature, version::Version};
 use dashmap::DashMap;
 use fxhash::{FxHashMap, FxHashSet};
 use once_cell::sync::Lazy;
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 use st_map::StaticMap;
 use std::{
     convert::{TryFrom, TryInto},
+    env, error, fmt,
     process::Command,
 };
 use swc_atoms::{js_word, JsWord};
 use swc_common::{chain, Fold, FoldWith, FromVariant, Mark, VisitWith, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_transforms::{
-    compat::{es2015, es2016, es2017, es2018, es2020, es3},
+    compat::{es2015, es2016, es2017, es2018, es2020, es2021, es3},
     pass::{noop, Optional, Pass},
     util::prepend_stmts,
 };
 
 #[macro_use]
 mod util;
+pub mod browserslist;
 mod corejs2;
 mod corejs3;
 mod regenerator;
 mod transform_data;
 mod version;
 
-pub fn preset_env(global_mark: Mark, c: Config) -> impl Pass {
+pub fn preset_env(global_mark: Mark, c: Config) -> Result<impl Pass, Error> {
     let loose = c.loose;
-    let targets: Versions = c.targets.try_into().expect("failed to parse targets");
+    let targets: Versions = c.targets.try_into()?;
     let is_any_target = targets.is_any_target();
 
+    let corejs = c.core_js.unwrap_or(Version {
+        major: 3,
+        minor: 0,
+        patch: 0,
+    });
+    if c.mode.is_some() && corejs.major != 2 && corejs.major != 3 {
+        return Err(Error::UnsupportedCoreJs(corejs));
+    }
+
     let (include, included_modules) = FeatureOrModule::split(c.include);
     let (exclude, excluded_modules) = FeatureOrModule::split(c.exclude);
 
@@ -67,13 +78,51 @@ pub fn preset_env(global_mark: Mark, c: Config) -> impl Pass {
         }};
     }
 
+    // ES2021
+    let pass = add!(
+        pass,
+        LogicalAssignmentOperators,
+        es2021::logical_assignments()
+    );
+    let pass = add!(pass, NumericSeparator, es2021::numeric_separator());
+
     // ES2020
 
     let pass = add!(pass, NullishCoalescing, es2020::nullish_coalescing());
     let pass = add!(pass, OptionalChaining, es2020::optional_chaining());
-    let pass = add!(pass, ClassProperties, es2020::class_properties());
+
+    // Private methods are lowered by the pass for class properties.
+    let class_properties = should_enable!(ClassProperties, false);
+    let private_methods = should_enable!(PrivateMethods, false);
+    if c.debug {
+        println!(
+            "{}: {:?}",
+            Feature::ClassProperties.as_str(),
+            class_properties
+        );
+        println!(
+            "{}: {:?}",
+            Feature::PrivateMethods.as_str(),
+            private_methods
+        );
+    }
+    let pass = chain!(
+        pass,
+        Optional::new(
+            es2020::class_properties(),
+            class_properties || private_methods
+        )
+    );
 
     // ES2018
+    let pass = add!(pass, AsyncGeneratorFunctions, es2018::async_generator());
+    let pass = add!(pass, UnicodePropertyRegex, es2018::unicode_property_regex());
+    let pass = add!(pass, DotAllRegex, es2018::dot_all_regex());
+    let pass = add!(
+        pass,
+        NamedCapturingGroupsRegex,
+        es2018::named_capturing_groups_regex()
+    );
     let pass = add!(pass, ObjectRestSpread, es2018::object_rest_spread());
     let pass = add!(pass, OptionalCatchBinding, es2018::optional_catch_binding());
 
@@ -91,7 +140,11 @@ pub fn preset_env(global_mark: Mark, c: Config) -> impl Pass {
         es2015::TemplateLiteral::default(),
         true
     );
+    let pass = add!(pass, Literals, es2015::literals());
+    let pass = add!(pass, UnicodeEscapes, es2015::unicode_escapes());
+    let pass = add!(pass, NewTarget, es2015::new_target());
     let pass = add!(pass, Classes, es2015::Classes::default());
+    let pass = add!(pass, ObjectSuper, es2015::object_super());
     let pass = add!(
         pass,
         Spread,
@@ -101,8 +154,9 @@ pub fn preset_env(global_mark: Mark, c: Config) -> impl Pass {
     let pass = add!(pass, FunctionName, es2015::function_name());
     let pass = add!(pass, ArrowFunctions, es2015::arrow());
     let pass = add!(pass, DuplicateKeys, es2015::duplicate_keys());
+    let pass = add!(pass, UnicodeRegex, es2015::unicode_regex());
     let pass = add!(pass, StickyRegex, es2015::StickyRegex);
-    // TODO:    InstanceOf,
+    let pass = add!(pass, InstanceOf, es2015::InstanceOf);
     let pass = add!(pass, TypeOfSymbol, es2015::TypeOfSymbol);
     let pass = add!(pass, ShorthandProperties, es2015::Shorthand);
     let pass = add!(pass, Parameters, es2015::parameters());
@@ -129,16 +183,8 @@ pub fn preset_env(global_mark: Mark, c: Config) -> impl Pass {
     let pass = add!(pass, Regenerator, es2015::regenerator(global_mark), true);
     let pass = add!(pass, BlockScoping, es2015::block_scoping(), true);
 
-    // TODO:
-    //    Literals,
-    //    ObjectSuper,
-    //    DotAllRegex,
-    //    UnicodeRegex,
-    //    NewTarget,
-    //    AsyncGeneratorFunctions,
-    //    UnicodePropertyRegex,
-    //    JsonStrings,
-    //    NamedCapturingGroupsRegex,
+    // `JsonStrings` doesn't need a pass, as the code generator escapes line
+    // terminators in strings.
 
     // ES 3
     let pass = add!(pass, PropertyLiterals, es3::PropertyLiteral);
@@ -155,22 +201,19 @@ pub fn preset_env(global_mark: Mark, c: Config) -> impl Pass {
         println!("Targets: {:?}", targets);
     }
 
-    chain!(
+    Ok(chain!(
         pass,
         Polyfills {
             mode: c.mode,
             regenerator: should_enable!(Regenerator, true),
-            corejs: c.core_js.unwrap_or(Version {
-                major: 3,
-                minor: 0,
-                patch: 0
-            }),
+            corejs,
+            script_loader: c.script_loader,
             shipped_proposals: c.shipped_proposals,
             targets,
             includes: included_modules,
             excludes: excluded_modules,
         }
-    )
+    ))
 }
 
 /// A map without allocation.
@@ -212,57 +255,67 @@ struct Polyfills {
     shipped_proposals: bool,
     corejs: Version,
     regenerator: bool,
+    script_loader: Option<JsWord>,
     includes: FxHashSet<String>,
     excludes: FxHashSet<String>,
 }
 
-impl Fold<Module> for Polyfills {
-    fn fold(&mut self, mut m: Module) -> Module {
-        let span = m.span;
-
-        let required = match self.mode {
-            None => Default::default(),
+impl Polyfills {
+    /// Collects polyfills required by `n` and returns the sources of them.
+    fn collect<T>(&self, n: T) -> (T, Vec<JsWord>)
+    where
+        T: VisitWith<corejs2::UsageVisitor>
+            + VisitWith<corejs3::UsageVisitor>
+            + VisitWith<regenerator::RegeneratorVisitor>
+            + FoldWith<corejs2::Entry>
+            + FoldWith<corejs3::Entry>,
+    {
+        let (n, required) = match self.mode {
+            None => (n, Default::default()),
             Some(Mode::Usage) => {
                 let mut r = match self.corejs {
                     Version { major: 2, .. } => {
                         let mut v = corejs2::UsageVisitor::new(self.targets);
-                        m.visit_with(&mut v);
+                        n.visit_with(&mut v);
 
                         v.required
                     }
                     Version { major: 3, .. } => {
-                        let mut v =
-                            corejs3::UsageVisitor::new(self.targets, self.shipped_proposals);
-                        m.visit_with(&mut v);
+                        let mut v = corejs3::UsageVisitor::new(
+                            self.targets,
+                            self.corejs,
+                            self.shipped_proposals,
+                        );
+                        n.visit_with(&mut v);
                         v.required
                     }
 
-                    _ => unimplemented!("corejs version other than 2 / 3"),
+                    _ => unreachable!("core js version is validated by preset_env"),
                 };
 
-                if regenerator::is_required(&m) {
+                if self.regenerator && regenerator::is_required(&n) {
                     r.insert("regenerator-runtime/runtime".into());
                 }
 
-                r
+                (n, r)
             }
             Some(Mode::Entry) => match self.corejs {
                 Version { major: 2, .. } => {
                     let mut v = corejs2::Entry::new(self.targets, self.regenerator);
-                    m = m.fold_with(&mut v);
-                    v.imports
+                    let n = n.fold_with(&mut v);
+                    (n, v.imports)
                 }
 
                 Version { major: 3, .. } => {
                     let mut v = corejs3::Entry::new(self.targets, self.corejs, !self.regenerator);
-                    m = m.fold_with(&mut v);
-                    v.imports
+                    let n = n.fold_with(&mut v);
+                    (n, v.imports)
                 }
 
-                _ => unimplemented!("corejs version other than 2 / 3"),
+                _ => unreachable!("core js version is validated by preset_env"),
             },
         };
-        let required = required
+        let mut required = required
             .into_iter()
             .filter(|s| !self.excludes.contains(&**s))
             .map(|s| -> JsWord {
@@ -282,41 +335,34 @@ impl Fold<Module> for Polyfills {
             .collect::<Vec<_>>();
 
         if cfg!(debug_assertions) {
-            let mut v = required.into_iter().collect::<Vec<_>>();
-            v.sort();
-            prepend_stmts(
-                &mut m.body,
-                v.into_iter().map(|src| {
-                    ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
-                        span,
-                        specifiers: vec![],
-                        src: Str {
-                            span: DUMMY_SP,
-                            value: src,
-                            has_escape: false,
-                        },
-                        type_only: false,
-                    }))
-                }),
-            );
-        } else {
-            prepend_stmts(
-                &mut m.body,
-                required.into_iter().map(|src| {
-                    ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
-                        span,
-                        specifiers: vec![],
-                        src: Str {
-                            span: DUMMY_SP,
-                            value: src,
-                            has_escape: false,
-                        },
-                        type_only: false,
-                    }))
-                }),
-            );
+            required.sort();
         }
 
+        (n, required)
+    }
+}
+
+impl Fold<Module> for Polyfills {
+    fn fold(&mut self, m: Module) -> Module {
+        let span = m.span;
+        let (mut m, required) = self.collect(m);
+
+        prepend_stmts(
+            &mut m.body,
+            required.into_iter().map(|src| {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                    span,
+                    specifiers: vec![],
+                    src: Str {
+                        span: DUMMY_SP,
+                        value: src,
+                        has_escape: false,
+                    },
+                    type_only: false,
+                }))
+            }),
+        );
+
         m.body.retain(|item| match item {
             ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
                 src:
@@ -334,9 +380,44 @@ impl Fold<Module> for Polyfills {
     }
 }
 
+/// Scripts can't import polyfills, so they are loaded using `require` or the
+/// configured script loader.
 impl Fold<Script> for Polyfills {
-    fn fold(&mut self, _: Script) -> Script {
-        unimplemented!("automatic polyfill for scripts")
+    fn fold(&mut self, s: Script) -> Script {
+        let span = s.span;
+        let (mut s, required) = self.collect(s);
+
+        let loader = self
+            .script_loader
+            .clone()
+            .unwrap_or_else(|| js_word!("require"));
+
+        prepend_stmts(
+            &mut s.body,
+            required.into_iter().map(|src| {
+                Stmt::Expr(ExprStmt {
+                    span,
+                    expr: box Expr::Call(CallExpr {
+                        span: DUMMY_SP,
+                        callee: ExprOrSuper::Expr(box Expr::Ident(Ident::new(
+                            loader.clone(),
+                            DUMMY_SP,
+                        ))),
+                        args: vec![ExprOrSpread {
+                            spread: None,
+                            expr: box Expr::Lit(Lit::Str(Str {
+                                span: DUMMY_SP,
+                                value: src,
+                                has_escape: false,
+                            })),
+                        }],
+                        type_args: None,
+                    }),
+                })
+            }),
+        );
+
+        s
     }
 }
 
@@ -355,44 +436,57 @@ impl BrowserData<Option<Version>> {
         self.iter().all(|(_, v)| v.is_none())
     }
 
-    pub fn parse_versions<'a>(lines: impl Iterator<Item = &'a str>) -> Result<Self, &'a str> {
-        fn remap(key: &str) -> String {
-            match key {
-                "and_chr" => "chrome".into(),
-                "and_ff" => "firefox".into(),
-                "ie_mob" => "ie".into(),
-                "ios_saf" => "ios".into(),
-                "op_mob" => "opera".into(),
-                _ => key.into(),
+    /// Returns false if `browser` is not a known browser.
+    fn set(&mut self, browser: &str, version: Version) -> bool {
+        for (k, v) in self.iter_mut() {
+            if browser == k {
+                *v = Some(version);
+                return true;
             }
         }
 
-        let browsers = lines.map(|v| {
-            let mut v = v.split(' ');
-            (remap(v.next().unwrap()), v.next().unwrap().to_string())
-        });
+        false
+    }
 
+    /// Maps a browserslist name of a browser to the name used by compat data.
+    fn remap(key: &str) -> &str {
+        match key {
+            "and_chr" => "chrome",
+            "and_ff" => "firefox",
+            "ie_mob" => "ie",
+            "ios_saf" => "ios",
+            "op_mob" => "opera",
+            _ => key,
+        }
+    }
+
+    /// Returns the invalid line on error.
+    pub fn parse_versions<'a>(lines: impl Iterator<Item = &'a str>) -> Result<Self, &'a str> {
         let mut data: Versions = BrowserData::default();
-        for (browser, version) in browsers {
-            match &*browser {
+        for line in lines {
+            let mut v = line.split(' ');
+            let (browser, version) = match (v.next(), v.next()) {
+                (Some(browser), Some(version)) => (Self::remap(browser), version),
+                _ => return Err(line),
+            };
+
+            match browser {
                 "and_qq" | "and_uc" | "baidu" | "bb" | "kaios" | "op_mini" => continue,
 
                 _ => {}
             }
 
-            let version = if version.contains("-") {
-                version.split('-').next().unwrap().parse().unwrap()
-            } else {
-                version.parse().unwrap()
-            };
+            // `ios_saf 12.2-12.4` is a range.
+            let version: Version = version
+                .split('-')
+                .next()
+                .unwrap_or(version)
+                .parse()
+                .map_err(|_| line)?;
 
             // lowest version
-            if data[&browser].is_none() || data[&browser].unwrap() > version {
-                for (k, v) in data.iter_mut() {
-                    if browser == k {
-                        *v = Some(version);
-                    }
-                }
+            if data[browser].is_none() || data[browser].unwrap() > version {
+                data.set(browser, version);
             }
         }
 
@@ -429,9 +523,18 @@ pub struct Config {
     pub exclude: Vec<FeatureOrModule>,
 
     /// The version of the used core js.
+    ///
+    /// Modules added in later versions of core js are not injected.
     #[serde(default)]
     pub core_js: Option<Version>,
 
+    /// Name of the function used to load polyfills in scripts, like
+    /// `importScripts`.
+    ///
+    /// Defaults to `require`.
+    #[serde(default)]
+    pub script_loader: Option<JsWord>,
+
     #[serde(default = "default_targets")]
     pub targets: Option<Targets>,
 
@@ -481,6 +584,7 @@ pub enum Targets {
 }
 
 #[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct EsModules {
     esmodules: bool,
 }
@@ -490,6 +594,8 @@ pub struct EsModules {
 pub enum QueryOrVersion {
     Query(Query),
     Version(Version),
+    /// `esmodules: true` or `node: true`
+    Bool(bool),
 }
 
 #[derive(Debug, Clone, Deserialize, FromVariant, Eq, PartialEq, PartialOrd, Ord, Hash)]
@@ -499,38 +605,93 @@ pub enum Query {
     Multiple(Vec<String>),
 }
 
-type QueryResult = Result<Versions, ()>;
+/// Error returned while resolving [Targets].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TargetsError {
+    /// Failed to run a browserslist query.
+    Query(String),
+    UnknownBrowser(String),
+    InvalidVersion {
+        browser: String,
+        version: String,
+    },
+    /// Failed to resolve `node: "current"`.
+    CurrentNode(String),
+}
+
+impl fmt::Display for TargetsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TargetsError::Query(err) => write!(f, "browserslist: {}", err),
+            TargetsError::UnknownBrowser(b) => write!(f, "unknown target `{}`", b),
+            TargetsError::InvalidVersion { browser, version } => {
+                write!(f, "invalid version `{}` of {}", version, browser)
+            }
+            TargetsError::CurrentNode(err) => {
+                write!(f, "failed to resolve the current version of node: {}", err)
+            }
+        }
+    }
+}
+
+impl error::Error for TargetsError {}
+
+/// Error returned by [preset_env] for an invalid [Config].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Targets(TargetsError),
+    /// Only core js 2 and 3 are supported.
+    UnsupportedCoreJs(Version),
+}
+
+impl From<TargetsError> for Error {
+    fn from(err: TargetsError) -> Self {
+        Error::Targets(err)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Targets(err) => write!(f, "failed to resolve targets: {}", err),
+            Error::UnsupportedCoreJs(v) => write!(
+                f,
+                "core js {}.{} is not supported; only core js 2 and 3 are supported",
+                v.major, v.minor
+            ),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+type QueryResult = Result<Versions, TargetsError>;
 
 impl Query {
     fn exec(&self) -> QueryResult {
         fn query<T>(s: &[T]) -> QueryResult
         where
-            T: AsRef<str> + Serialize,
+            T: AsRef<str>,
         {
-            let output = {
-                let output = Command::new("node")
-                    .arg("-e")
-                    .arg(include_str!("query.js"))
-                    .arg(serde_json::to_string(&s).expect("failed to serialize with serde"))
-                    .output()
-                    .expect("failed to collect output");
-                if !output.status.success() {
-                    println!("query.js: Status {:?}", output.status,);
-                    println!(
-                        "{}\n{}",
-                        String::from_utf8_lossy(&output.stdout),
-                        String::from_utf8_lossy(&output.stderr),
-                    );
-                    return Err(());
-                }
-
-                output.stdout
-            };
-
-            let browsers: Vec<String> =
-                serde_json::from_slice(&output).expect("failed to read browser data output");
-            let versions = BrowserData::parse_versions(browsers.iter().map(|s| &**s))
-                .expect("failed to parse browser version");
+            let queries = s
+                .iter()
+                .map(|s| s.as_ref())
+                .filter(|s| !s.starts_with("esmodules"))
+                .collect::<Vec<_>>();
+
+            let dir = env::current_dir().map_err(|err| {
+                TargetsError::Query(format!("failed to get current directory: {}", err))
+            })?;
+            let browsers = browserslist::resolve(&queries, &dir)
+                .map_err(|err| TargetsError::Query(err.to_string()))?;
+
+            let versions = BrowserData::parse_versions(
+                browsers
+                    .iter()
+                    .map(|s| &**s)
+                    .filter(|s| !s.ends_with(" TP")),
+            )
+            .map_err(|line| TargetsError::Query(format!("invalid browser version `{}`", line)))?;
 
             Ok(versions)
         }
@@ -538,10 +699,7 @@ impl Query {
         static CACHE: Lazy<DashMap<Query, QueryResult>> = Lazy::new(Default::default);
 
         if let Some(v) = CACHE.get(self) {
-            return match &*v {
-                Ok(v) => Ok(*v),
-                Err(err) => Err(*err),
-            };
+            return v.clone();
         }
 
         let result = match *self {
@@ -549,48 +707,154 @@ impl Query {
             Query::Multiple(ref s) => query(&s),
         };
 
-        CACHE.insert(self.clone(), result);
+        CACHE.insert(self.clone(), result.clone());
 
         result
     }
 }
 
+/// Browsers supporting `<script type="module">`.
+const ES_MODULES: &[(&str, &str)] = &[
+    ("chrome", "61"),
+    ("edge", "16"),
+    ("firefox", "60"),
+    ("safari", "10.1"),
+    ("ios", "10.3"),
+    ("samsung", "8.2"),
+    ("opera", "48"),
+    ("android", "61"),
+];
+
+fn es_modules() -> Versions {
+    let mut versions = Versions::default();
+    for &(browser, version) in ES_MODULES {
+        versions.set(browser, version.parse().unwrap());
+    }
+    versions
+}
+
+/// Resolves `node: "current"`.
+///
+/// `SWC_NODE_VERSION` is used if it's set, and `node --version` is used
+/// otherwise.
+fn current_node() -> Result<Version, TargetsError> {
+    let version = match env::var("SWC_NODE_VERSION") {
+        Ok(v) => v,
+        Err(..) => {
+            let output = Command::new("node")
+                .arg("--version")
+                .output()
+                .map_err(|err| TargetsError::CurrentNode(err.to_string()))?;
+            if !output.status.success() {
+                return Err(TargetsError::CurrentNode(
+                    String::from_utf8_lossy(&output.stderr).into_owned(),
+                ));
+            }
+
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        }
+    };
+
+    let version = version.trim();
+    let version = version.trim_start_matches('v');
+    version
+        .parse()
+        .map_err(|_| TargetsError::CurrentNode(format!("invalid version `{}`", version)))
+}
+
+/// Resolves a version of a target map like `{ "chrome": "58" }`.
+fn resolve_version(browser: &str, v: QueryOrVersion) -> Result<Version, TargetsError> {
+    let invalid = |version: String| TargetsError::InvalidVersion {
+        browser: browser.into(),
+        version,
+    };
+
+    match v {
+        QueryOrVersion::Version(v) => Ok(v),
+        QueryOrVersion::Bool(true) if browser == "node" => current_node(),
+        QueryOrVersion::Query(Query::Single(s)) => match (browser, &*s) {
+            ("node", "current") => current_node(),
+            // Technology preview is newer than any released version.
+            ("safari", "tp") => Query::Single("last 1 safari version".into())
+                .exec()?
+                .safari
+                .ok_or_else(|| invalid(s.clone())),
+            _ => s.parse().map_err(|_| invalid(s.clone())),
+        },
+        QueryOrVersion::Query(Query::Multiple(q)) => Err(invalid(q.join(", "))),
+        QueryOrVersion::Bool(b) => Err(invalid(b.to_string())),
+    }
+}
+
 impl TryFrom<Option<Targets>> for Versions {
-    type Error = ();
+    type Error = TargetsError;
 
     fn try_from(v: Option<Targets>) -> Result<Self, Self::Error> {
         match v {
             None => Ok(Default::default()),
             Some(Targets::Versions(v)) => Ok(v),
             Some(Targets::Query(q)) => q.exec(),
+            Some(Targets::EsModules(EsModules { esmodules: true })) => Ok(es_modules()),
+            Some(Targets::EsModules(EsModules { esmodules: false })) => {
+                default_targets().try_into()
+            }
             Some(Targets::HashMap(mut map)) => {
-                let q = map.remove("browsers").map(|q| match q {
-                    QueryOrVersion::Query(q) => q.exec().expect("failed to run query"),
-                    _ => unreachable!(),
-                });
-
-                let node = map.remove("node").map(|q| match q {
-                    QueryOrVersion::Version(v) => v,
-                    QueryOrVersion::Query(..) => unreachable!(),
-                });
-
-                if map.is_empty() {
-                    if let Some(mut q) = q {
-                        q.node = node;
-                        return Ok(q);
+                let esmodules = match map.remove("esmodules") {
+                    None | Some(QueryOrVersion::Bool(false)) => false,
+                    Some(QueryOrVersion::Bool(true)) => true,
+                    Some(v) => {
+                        return Err(TargetsError::InvalidVersion {
+                            browser: "esmodules".into(),
+                            version: format!("{:?}", v),
+                        })
+                    }
+                };
+
+                let browsers = match map.remove("browsers") {
+                    None => None,
+                    Some(QueryOrVersion::Query(q)) => Some(q.exec()?),
+                    Some(v) => {
+                        return Err(TargetsError::Query(format!("invalid query: {:?}", v)));
+                    }
+                };
+
+                // Like babel, `esmodules` takes precedence over `browsers`.
+                let mut versions = if esmodules {
+                    es_modules()
+                } else {
+                    browsers.unwrap_or_default()
+                };
+
+                // Versions of each browser take precedence over queries.
+                for (key, v) in map {
+                    let browser = Versions::remap(&key);
+                    let version = resolve_version(browser, v)?;
+
+                    if !versions.set(browser, version) {
+                        return Err(TargetsError::UnknownBrowser(key));
                     }
                 }
 
-                unimplemented!("Targets: {:?}", map)
+                Ok(versions)
             }
-            _ => unimplemented!("Option<Targets>: {:?}", v),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Query;
+    use super::{preset_env, Config, Error, Mode, Query, Targets, TargetsError, Version, Versions};
+    use std::{convert::TryInto, env};
+    use swc_common::Mark;
+
+    fn targets(s: &str) -> Result<Versions, TargetsError> {
+        let targets: Targets = serde_json::from_str(s).expect("failed to parse targets");
+        Some(targets).try_into()
+    }
+
+    fn version(s: &str) -> Option<Version> {
+        Some(s.parse().unwrap())
+    }
 
     #[test]
     fn test_empty() {
@@ -600,4 +864,121 @@ mod tests {
             "empty query should return non-empty result"
         );
     }
+
+    #[test]
+    fn test_browser_versions() {
+        let res = targets(r#"{ "chrome": "58", "ie": 11, "safari": 10.1 }"#).unwrap();
+        assert_eq!(res.chrome, version("58"));
+        assert_eq!(res.ie, version("11"));
+        assert_eq!(res.safari, version("10.1"));
+        assert_eq!(res.firefox, None);
+    }
+
+    #[test]
+    fn test_safari_tp() {
+        let res = targets(r#"{ "chrome": "58", "safari": "tp" }"#).unwrap();
+        assert_eq!(res.chrome, version("58"));
+        assert!(res.safari.unwrap() >= version("13").unwrap());
+    }
+
+    #[test]
+    fn test_browser_alias() {
+        let res = targets(r#"{ "ios_saf": "12.2" }"#).unwrap();
+        assert_eq!(res.ios, version("12.2"));
+    }
+
+    #[test]
+    fn test_esmodules() {
+        let res = targets(r#"{ "esmodules": true }"#).unwrap();
+        assert_eq!(res.chrome, version("61"));
+        assert_eq!(res.ie, None);
+
+        let res = targets(r#"{ "esmodules": true, "browsers": "ie 11", "node": "12" }"#).unwrap();
+        assert_eq!(res.chrome, version("61"));
+        assert_eq!(res.ie, None);
+        assert_eq!(res.node, version("12"));
+    }
+
+    #[test]
+    fn test_node_current() {
+        env::set_var("SWC_NODE_VERSION", "v12.16.1");
+
+        let res = targets(r#"{ "node": "current" }"#).unwrap();
+        assert_eq!(res.node, version("12.16.1"));
+    }
+
+    #[test]
+    fn test_query_and_versions() {
+        let res = targets(r#"{ "browsers": ["last 2 ie versions"], "chrome": "60" }"#).unwrap();
+        assert_eq!(res.ie, version("10"));
+        assert_eq!(res.chrome, version("60"));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            targets(r#"{ "chrome": "58", "foo": "1" }"#).unwrap_err(),
+            TargetsError::UnknownBrowser("foo".into())
+        );
+        assert_eq!(
+            targets(r#"{ "chrome": "latest" }"#).unwrap_err(),
+            TargetsError::InvalidVersion {
+                browser: "chrome".into(),
+                version: "latest".into()
+            }
+        );
+        assert!(match targets(r#"{ "browsers": "foo bar" }"#) {
+            Err(TargetsError::Query(..)) => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn test_parse_versions() {
+        let res =
+            Versions::parse_versions(vec!["chrome 80", "ios_saf 12.2-12.4"].into_iter()).unwrap();
+        assert_eq!(res.chrome, version("80"));
+        assert_eq!(res.ios, version("12.2"));
+
+        assert_eq!(
+            Versions::parse_versions(vec!["chrome 80", "chrome"].into_iter()).unwrap_err(),
+            "chrome"
+        );
+        assert_eq!(
+            Versions::parse_versions(vec!["firefox x.y"].into_iter()).unwrap_err(),
+            "firefox x.y"
+        );
+    }
+
+    #[test]
+    fn test_invalid_query() {
+        let res = preset_env(
+            Mark::root(),
+            Config {
+                targets: Some(Targets::Query(Query::Single("foo bar".into()))),
+                ..Default::default()
+            },
+        );
+        assert!(match res {
+            Err(Error::Targets(TargetsError::Query(..))) => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn test_unsupported_core_js() {
+        let res = preset_env(
+            Mark::root(),
+            Config {
+                mode: Some(Mode::Usage),
+                core_js: version("4"),
+                targets: None,
+                ..Default::default()
+            },
+        );
+        assert!(match res {
+            Err(Error::UnsupportedCoreJs(v)) => v.major == 4,
+            _ => false,
+        });
+    }
 }
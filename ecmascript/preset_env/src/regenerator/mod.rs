@@ -1,4 +1,5 @@
-use swc_common::VisitWith;
+use swc_common::{Visit, VisitWith};
+use swc_ecma_ast::*;
 
 pub(super) fn is_required<T: VisitWith<RegeneratorVisitor>>(node: &T) -> bool {
     let mut v = RegeneratorVisitor { found: false };
@@ -9,3 +10,22 @@ pub(super) fn is_required<T: VisitWith<RegeneratorVisitor>>(node: &T) -> bool {
 pub(super) struct RegeneratorVisitor {
     found: bool,
 }
+
+/// Generators and async functions are lowered to code using
+/// `regeneratorRuntime` before polyfills are injected.
+impl Visit<Ident> for RegeneratorVisitor {
+    fn visit(&mut self, i: &Ident) {
+        if i.sym == *"regeneratorRuntime" {
+            self.found = true;
+        }
+    }
+}
+
+impl Visit<MemberExpr> for RegeneratorVisitor {
+    fn visit(&mut self, e: &MemberExpr) {
+        e.obj.visit_with(self);
+        if e.computed {
+            e.prop.visit_with(self);
+        }
+    }
+}
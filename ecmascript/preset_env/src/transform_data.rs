@@ -2,6 +2,7 @@ use crate::{version::should_enable, BrowserData, Version, Versions};
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use string_enum::StringEnum;
+use swc_ecma_parser::JscTarget;
 
 impl Feature {
     pub fn should_enable(self, target: Versions, default: bool) -> bool {
@@ -9,6 +10,65 @@ impl Feature {
 
         should_enable(target, *f, default)
     }
+
+    /// Returns the first version of ecmascript which supports the feature
+    /// natively.
+    ///
+    /// Passes for the feature should be enabled if `target` is older than
+    /// this.
+    pub fn target(self) -> JscTarget {
+        match self {
+            Feature::PropertyLiterals
+            | Feature::MemberExpressionLiterals
+            | Feature::ReservedWords => JscTarget::Es5,
+
+            Feature::TemplateLiterals
+            | Feature::Literals
+            | Feature::FunctionName
+            | Feature::ArrowFunctions
+            | Feature::BlockScopedFunctions
+            | Feature::Classes
+            | Feature::ObjectSuper
+            | Feature::ShorthandProperties
+            | Feature::DuplicateKeys
+            | Feature::ComputedProperties
+            | Feature::ForOf
+            | Feature::StickyRegex
+            | Feature::UnicodeRegex
+            | Feature::Spread
+            | Feature::Parameters
+            | Feature::Destructuring
+            | Feature::BlockScoping
+            | Feature::TypeOfSymbol
+            | Feature::InstanceOf
+            | Feature::NewTarget
+            | Feature::Regenerator
+            | Feature::UnicodeEscapes => JscTarget::Es2015,
+
+            Feature::ExponentiationOperator => JscTarget::Es2016,
+
+            Feature::AsyncToGenerator => JscTarget::Es2017,
+
+            Feature::AsyncGeneratorFunctions
+            | Feature::ObjectRestSpread
+            | Feature::UnicodePropertyRegex
+            | Feature::DotAllRegex
+            | Feature::NamedCapturingGroupsRegex => JscTarget::Es2018,
+
+            Feature::JsonStrings | Feature::OptionalCatchBinding => JscTarget::Es2019,
+
+            Feature::NullishCoalescing | Feature::OptionalChaining => JscTarget::Es2020,
+
+            Feature::LogicalAssignmentOperators | Feature::NumericSeparator => JscTarget::Es2021,
+
+            Feature::ClassProperties | Feature::PrivateMethods => JscTarget::EsNext,
+        }
+    }
+
+    /// Returns true if the feature should be lowered for `target`.
+    pub fn should_enable_for(self, target: JscTarget) -> bool {
+        target < self.target()
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, StringEnum, Hash)]
@@ -70,6 +130,9 @@ pub enum Feature {
     /// `transform-typeof-symbol`
     TypeOfSymbol,
 
+    /// `transform-instanceof`
+    InstanceOf,
+
     /// `transform-new-target`
     NewTarget,
 
@@ -112,6 +175,9 @@ pub enum Feature {
     /// `proposal-nullish-coalescing-operator`
     NullishCoalescing,
 
+    /// `proposal-logical-assignment-operators`
+    LogicalAssignmentOperators,
+
     /// `proposal-optional-chaining`
     OptionalChaining,
 
@@ -159,6 +225,15 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn jsc_target() {
+        assert!(Feature::LogicalAssignmentOperators.should_enable_for(JscTarget::Es2020));
+        assert!(!Feature::LogicalAssignmentOperators.should_enable_for(JscTarget::Es2021));
+        assert!(Feature::PrivateMethods.should_enable_for(JscTarget::Es2021));
+        assert!(!Feature::PrivateMethods.should_enable_for(JscTarget::EsNext));
+        assert!(!Feature::ReservedWords.should_enable_for(JscTarget::Es5));
+    }
+
     #[test]
     fn tpl_lit() {
         assert!(!Feature::TemplateLiterals.should_enable(
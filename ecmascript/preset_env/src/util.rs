@@ -1,3 +1,6 @@
+use swc_atoms::{js_word, JsWord};
+use swc_ecma_ast::*;
+
 macro_rules! val {
     (& $v:expr) => {
         &$v
@@ -236,3 +239,29 @@ impl<T> DataMapExt<T> for DataMap<T> {
         self
     }
 }
+
+/// Returns the source of `require('foo');`.
+pub(crate) fn require_src(stmt: &Stmt) -> Option<&JsWord> {
+    match stmt {
+        Stmt::Expr(ExprStmt {
+            expr:
+                box Expr::Call(CallExpr {
+                    callee:
+                        ExprOrSuper::Expr(box Expr::Ident(Ident {
+                            sym: js_word!("require"),
+                            ..
+                        })),
+                    args,
+                    ..
+                }),
+            ..
+        }) if args.len() == 1 => match &args[0] {
+            ExprOrSpread {
+                spread: None,
+                expr: box Expr::Lit(Lit::Str(s)),
+            } => Some(&s.value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -15,9 +15,7 @@ impl FromStr for Version {
     fn from_str(v: &str) -> Result<Self, Self::Err> {
         if !v.contains(".") {
             return Ok(Version {
-                major: v
-                    .parse()
-                    .unwrap_or_else(|err| panic!("failed to parse `{}` as a version: {}", v, err)),
+                major: v.parse().map_err(|_| ())?,
                 minor: 0,
                 patch: 0,
             });
@@ -26,15 +24,13 @@ impl FromStr for Version {
         if v.split(".").count() == 2 {
             let mut s = v.split(".");
             return Ok(Version {
-                major: s.next().unwrap().parse().unwrap(),
-                minor: s.next().unwrap().parse().unwrap(),
+                major: s.next().unwrap().parse().map_err(|_| ())?,
+                minor: s.next().unwrap().parse().map_err(|_| ())?,
                 patch: 0,
             });
         }
 
-        let v = v
-            .parse::<semver::Version>()
-            .unwrap_or_else(|err| panic!("failed to parse {} as semver: {}", v, err));
+        let v = v.parse::<semver::Version>().map_err(|_| ())?;
 
         Ok(Version {
             major: v.major as _,
@@ -114,11 +110,8 @@ impl<'de> Visitor<'de> for SerdeVisitor {
     where
         E: de::Error,
     {
-        Ok(Version {
-            major: v.floor() as _,
-            minor: v.fract() as _,
-            patch: 0,
-        })
+        // `10.1` -> `10.1.0`
+        self.visit_str(&v.to_string())
     }
 
     fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
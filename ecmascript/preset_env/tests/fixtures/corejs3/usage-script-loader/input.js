@@ -0,0 +1,8 @@
+require("foo");
+
+const x = new Promise(resolve => {
+  const p = [];
+
+  if (p.includes("a")) {
+  }
+});
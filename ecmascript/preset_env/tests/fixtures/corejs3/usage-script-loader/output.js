@@ -0,0 +1,13 @@
+importScripts("core-js/modules/es.array.includes");
+
+importScripts("core-js/modules/es.object.to-string");
+
+importScripts("core-js/modules/es.promise");
+
+require("foo");
+
+var x = new Promise(function (resolve) {
+  var p = [];
+
+  if (p.includes("a")) {}
+});
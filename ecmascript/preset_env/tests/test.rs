@@ -58,6 +58,9 @@ struct PresetConfig {
     #[serde(default)]
     pub shipped_proposals: bool,
 
+    #[serde(default)]
+    pub script_loader: Option<String>,
+
     #[serde(default)]
     pub config_path: String,
 
@@ -131,10 +134,14 @@ fn load() -> Result<Vec<TestDescAndFn>, Error> {
             continue;
         }
 
-        match e.path().join("input.mjs").metadata() {
-            Ok(e) if e.is_file() => {}
-            _ => continue,
-        }
+        // `.js` files are parsed as scripts.
+        let is_script = match e.path().join("input.mjs").metadata() {
+            Ok(e) if e.is_file() => false,
+            _ => match e.path().join("input.js").metadata() {
+                Ok(e) if e.is_file() => true,
+                _ => continue,
+            },
+        };
 
         let cfg: BabelOptions = serde_json::from_reader(File::open(e.path().join("options.json"))?)
             .map_err(|err| Error::Msg(format!("failed to parse options.json: {}", err)))?;
@@ -160,7 +167,7 @@ fn load() -> Result<Vec<TestDescAndFn>, Error> {
             },
             testfn: TestFn::DynTestFn(box move || {
                 //
-                exec(cfg, e.path().to_path_buf()).expect("failed to run test")
+                exec(cfg, e.path().to_path_buf(), is_script).expect("failed to run test")
             }),
         });
     }
@@ -168,7 +175,7 @@ fn load() -> Result<Vec<TestDescAndFn>, Error> {
     Ok(tests)
 }
 
-fn exec(c: PresetConfig, dir: PathBuf) -> Result<(), Error> {
+fn exec(c: PresetConfig, dir: PathBuf, is_script: bool) -> Result<(), Error> {
     println!("Config: {:?}", c);
 
     Tester::new()
@@ -197,15 +204,22 @@ fn exec(c: PresetConfig, dir: PathBuf) -> Result<(), Error> {
                     force_all_transforms: c.force_all_transforms,
                     shipped_proposals: c.shipped_proposals,
                     targets: c.targets,
+                    script_loader: c.script_loader.map(From::from),
                 },
-            );
+            )
+            .expect("invalid preset_env config");
+
+            let ext = if is_script { "js" } else { "mjs" };
 
-            let print = |m: &Module| {
+            let print = |p: &Program| {
                 let mut buf = vec![];
                 {
                     let handlers = box MyHandlers;
                     let mut emitter = Emitter {
-                        cfg: swc_ecma_codegen::Config { minify: false },
+                        cfg: swc_ecma_codegen::Config {
+                            minify: false,
+                            ..Default::default()
+                        },
                         comments: None,
                         cm: cm.clone(),
                         wr: box swc_ecma_codegen::text_writer::JsWriter::new(
@@ -217,13 +231,13 @@ fn exec(c: PresetConfig, dir: PathBuf) -> Result<(), Error> {
                         handlers,
                     };
 
-                    emitter.emit_module(m).expect("failed to emit module");
+                    emitter.emit_program(p).expect("failed to emit program");
                 }
                 unsafe { String::from_utf8_unchecked(buf) }
             };
 
             let fm = cm
-                .load_file(&dir.join("input.mjs"))
+                .load_file(&dir.join(format!("input.{}", ext)))
                 .expect("failed to load file");
             let mut p = Parser::new(
                 Session { handler: &handler },
@@ -235,8 +249,12 @@ fn exec(c: PresetConfig, dir: PathBuf) -> Result<(), Error> {
                 None,
             );
 
-            let module = p.parse_module().map_err(|mut e| e.emit())?;
-            let actual = module.fold_with(&mut pass);
+            let program = if is_script {
+                Program::Script(p.parse_script().map_err(|mut e| e.emit())?)
+            } else {
+                Program::Module(p.parse_module().map_err(|mut e| e.emit())?)
+            };
+            let actual = program.fold_with(&mut pass);
 
             // debug mode?
             if dir.join("stdout.txt").exists() {
@@ -253,7 +271,7 @@ fn exec(c: PresetConfig, dir: PathBuf) -> Result<(), Error> {
             // It's normal transform test.
             let expected = {
                 let fm = cm
-                    .load_file(&dir.join("output.mjs"))
+                    .load_file(&dir.join(format!("output.{}", ext)))
                     .expect("failed to load output file");
 
                 let mut p = Parser::new(
@@ -266,30 +284,46 @@ fn exec(c: PresetConfig, dir: PathBuf) -> Result<(), Error> {
                     None,
                 );
 
-                let mut m = p.parse_module().map_err(|mut e| e.emit())?;
-
-                m.body.sort_by(|a, b| match *a {
-                    ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
-                        ref specifiers,
-                        ref src,
-                        ..
-                    })) if specifiers.is_empty() && src.value.starts_with("core-js/modules") => {
-                        match *b {
-                            ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
-                                specifiers: ref rs,
-                                src: ref rsrc,
-                                ..
-                            })) if rs.is_empty() && rsrc.value.starts_with("core-js/modules") => {
-                                src.value.cmp(&rsrc.value)
-                            }
+                if is_script {
+                    let mut s = p.parse_script().map_err(|mut e| e.emit())?;
 
+                    s.body
+                        .sort_by(|a, b| match (polyfill_src(a), polyfill_src(b)) {
+                            (Some(l), Some(r)) => l.cmp(r),
                             _ => Ordering::Equal,
+                        });
+
+                    Program::Script(s)
+                } else {
+                    let mut m = p.parse_module().map_err(|mut e| e.emit())?;
+
+                    m.body.sort_by(|a, b| match *a {
+                        ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                            ref specifiers,
+                            ref src,
+                            ..
+                        })) if specifiers.is_empty()
+                            && src.value.starts_with("core-js/modules") =>
+                        {
+                            match *b {
+                                ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                                    specifiers: ref rs,
+                                    src: ref rsrc,
+                                    ..
+                                })) if rs.is_empty()
+                                    && rsrc.value.starts_with("core-js/modules") =>
+                                {
+                                    src.value.cmp(&rsrc.value)
+                                }
+
+                                _ => Ordering::Equal,
+                            }
                         }
-                    }
-                    _ => Ordering::Equal,
-                });
+                        _ => Ordering::Equal,
+                    });
 
-                m
+                    Program::Module(m)
+                }
             };
 
             let actual_src = print(&actual);
@@ -310,6 +344,28 @@ fn exec(c: PresetConfig, dir: PathBuf) -> Result<(), Error> {
     Ok(())
 }
 
+/// Returns the source of `require("core-js/modules/foo");`
+fn polyfill_src(stmt: &Stmt) -> Option<&str> {
+    match *stmt {
+        Stmt::Expr(ExprStmt {
+            expr:
+                box Expr::Call(CallExpr {
+                    callee: ExprOrSuper::Expr(box Expr::Ident(ref callee)),
+                    ref args,
+                    ..
+                }),
+            ..
+        }) if callee.sym == *"require" && args.len() == 1 => match args[0] {
+            ExprOrSpread {
+                spread: None,
+                expr: box Expr::Lit(Lit::Str(ref s)),
+            } if s.value.starts_with("core-js/modules") => Some(&s.value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 fn read(p: &Path) -> String {
     let mut buf = String::new();
     let mut f = File::open(p).expect("failed to open file");
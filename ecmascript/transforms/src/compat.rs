@@ -7,5 +7,7 @@ pub mod es2016;
 pub mod es2017;
 pub mod es2018;
 pub mod es2020;
+pub mod es2021;
 pub mod es3;
+mod regexp;
 pub mod reserved_words;
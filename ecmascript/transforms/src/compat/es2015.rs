@@ -2,10 +2,12 @@ pub use self::{
     arrow::arrow, block_scoped_fn::BlockScopedFns, block_scoping::block_scoping, classes::Classes,
     computed_props::computed_properties, destructuring::destructuring,
     duplicate_keys::duplicate_keys, for_of::for_of, function_name::function_name,
-    instanceof::InstanceOf, parameters::parameters, regenerator::regenerator,
-    shorthand_property::Shorthand, spread::spread, sticky_regex::StickyRegex,
-    template_literal::TemplateLiteral, typeof_symbol::TypeOfSymbol,
+    instanceof::InstanceOf, literals::literals, new_target::new_target, object_super::object_super,
+    parameters::parameters, regenerator::regenerator, shorthand_property::Shorthand,
+    spread::spread, sticky_regex::StickyRegex, template_literal::TemplateLiteral,
+    typeof_symbol::TypeOfSymbol, unicode_escapes::unicode_escapes,
 };
+pub use super::regexp::unicode_regex;
 use crate::pass::Pass;
 use serde::Deserialize;
 use swc_common::{chain, Mark};
@@ -21,6 +23,9 @@ mod duplicate_keys;
 pub mod for_of;
 mod function_name;
 mod instanceof;
+mod literals;
+mod new_target;
+mod object_super;
 mod parameters;
 mod regenerator;
 mod shorthand_property;
@@ -28,6 +33,7 @@ pub mod spread;
 mod sticky_regex;
 mod template_literal;
 mod typeof_symbol;
+mod unicode_escapes;
 
 fn exprs() -> impl Pass {
     chain_at!(
@@ -45,10 +51,15 @@ fn exprs() -> impl Pass {
 pub fn es2015(global_mark: Mark, c: Config) -> impl Pass {
     chain!(
         BlockScopedFns,
+        literals(),
+        unicode_escapes(),
         TemplateLiteral::default(),
+        new_target(),
         Classes::default(),
+        object_super(),
         spread(c.spread),
         function_name(),
+        unicode_regex(),
         exprs(),
         parameters(),
         for_of(c.for_of),
@@ -665,7 +665,7 @@ impl Classes {
                         box Expr::Lit(Lit::Str(quote_str!(i.span, i.sym.clone())))
                     }
                     PropName::Str(ref s) => box Expr::Lit(Lit::Str(s.clone())),
-                    PropName::Num(n) => box Expr::Lit(Lit::Num(n)),
+                    PropName::Num(ref n) => box Expr::Lit(Lit::Num(n.clone())),
                     PropName::Computed(ref c) => c.expr.clone(),
                 },
             })
@@ -25,6 +25,8 @@ pub(crate) struct SuperFieldAccessFolder<'a> {
     pub vars: &'a mut Vec<VarDeclarator>,
     /// Mark for the `_this`. Used only when folding constructor.
     pub constructor_this_mark: Option<Mark>,
+    /// If true, `class_name` itself is used as the home object instead of
+    /// `class_name.prototype`.
     pub is_static: bool,
 
     pub folding_constructor: bool,
@@ -129,6 +131,7 @@ impl<'a> Fold<Expr> for SuperCalleeFolder<'a> {
                         box Expr::Lit(Lit::Num(Number {
                             span: DUMMY_SP,
                             value: 1.0,
+                            raw: None,
                         })),
                     )
                 }
@@ -274,8 +277,14 @@ impl<'a> SuperCalleeFolder<'a> {
             }
         }
 
-        let proto_arg =
-            get_prototype_of(self.class_name.clone().member(quote_ident!("prototype"))).as_arg();
+        let proto_arg = get_prototype_of(if self.is_static {
+            // Foo
+            Expr::Ident(self.class_name.clone())
+        } else {
+            // Foo.prototype
+            self.class_name.clone().member(quote_ident!("prototype"))
+        })
+        .as_arg();
 
         let prop_arg = match *prop {
             Expr::Ident(Ident {
@@ -339,6 +348,9 @@ impl<'a> SuperCalleeFolder<'a> {
                         op!("&=") => op!("&"),
                         op!("^=") => op!("^"),
                         op!("**=") => op!("**"),
+                        op!("&&=") | op!("||=") | op!("??=") => {
+                            unreachable!("logical assignments should be lowered first")
+                        }
                     },
                     right: rhs,
                 }
@@ -114,6 +114,7 @@ impl Actual {
                 init: Some(box Expr::Lit(Lit::Num(Number {
                     span: DUMMY_SP,
                     value: 0f64,
+                    raw: None,
                 }))),
                 definite: false,
             });
@@ -0,0 +1,76 @@
+use crate::pass::Pass;
+use swc_common::Fold;
+use swc_ecma_ast::*;
+
+/// `@babel/plugin-transform-literals`
+///
+/// String literals are printed using their cooked values, so only binary and
+/// octal numeric literals are modified.
+///
+///# Example
+///## In
+///
+/// ```js
+/// 0b11;
+/// 0o7;
+/// ```
+///
+///## Out
+///
+/// ```js
+/// 3;
+/// 7;
+/// ```
+pub fn literals() -> impl Pass {
+    Literals
+}
+
+struct Literals;
+
+noop_fold_type!(Literals);
+
+impl Fold<Number> for Literals {
+    fn fold(&mut self, n: Number) -> Number {
+        let is_es2015 = match n.raw {
+            Some(ref raw) => {
+                raw.starts_with("0b")
+                    || raw.starts_with("0B")
+                    || raw.starts_with("0o")
+                    || raw.starts_with("0O")
+            }
+            None => false,
+        };
+
+        if is_es2015 {
+            // Printed using the value.
+            Number { raw: None, ..n }
+        } else {
+            n
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swc_common::DUMMY_SP;
+
+    fn num(value: f64, raw: &str) -> Number {
+        Number {
+            span: DUMMY_SP,
+            value,
+            raw: Some(raw.into()),
+        }
+    }
+
+    #[test]
+    fn binary_and_octal() {
+        assert_eq!(Literals.fold(num(3.0, "0b11")).raw, None);
+        assert_eq!(Literals.fold(num(7.0, "0O7")).raw, None);
+    }
+
+    #[test]
+    fn hex() {
+        assert_eq!(Literals.fold(num(31.0, "0x1F")).raw, Some("0x1F".into()));
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{pass::Pass, util::undefined};
+use std::mem::replace;
+use swc_common::{Fold, FoldWith, DUMMY_SP};
+use swc_ecma_ast::*;
+
+/// `@babel/plugin-transform-new-target`
+///
+/// This should run before classes are lowered.
+///
+///# Example
+///## In
+///
+/// ```js
+/// function Foo() {
+///     console.log(new.target);
+/// }
+///
+/// class Bar {
+///     constructor() {
+///         console.log(new.target);
+///     }
+/// }
+/// ```
+///
+///## Out
+///
+/// ```js
+/// function Foo() {
+///     console.log(this instanceof Foo ? this.constructor : void 0);
+/// }
+///
+/// class Bar {
+///     constructor() {
+///         console.log(this.constructor);
+///     }
+/// }
+/// ```
+pub fn new_target() -> impl Pass {
+    NewTarget::default()
+}
+
+#[derive(Default)]
+struct NewTarget {
+    ctx: Ctx,
+    /// True if `new.target` is used in the current function.
+    used: bool,
+}
+
+noop_fold_type!(NewTarget);
+
+#[derive(Clone)]
+enum Ctx {
+    /// `new.target` is invalid here.
+    Top,
+    /// `this instanceof Foo ? this.constructor : void 0`
+    Fn(Ident),
+    /// `this.constructor`
+    Constructor,
+    /// `void 0`
+    Method,
+}
+
+impl Default for Ctx {
+    fn default() -> Self {
+        Ctx::Top
+    }
+}
+
+impl NewTarget {
+    /// Folds `n` with `ctx` and returns true if `new.target` is used in it.
+    fn fold_in<T>(&mut self, ctx: Ctx, n: T) -> (T, bool)
+    where
+        T: FoldWith<Self>,
+    {
+        let old_ctx = replace(&mut self.ctx, ctx);
+        let old_used = replace(&mut self.used, false);
+
+        let n = n.fold_children(self);
+
+        self.ctx = old_ctx;
+        let used = replace(&mut self.used, old_used);
+
+        (n, used)
+    }
+}
+
+impl Fold<FnDecl> for NewTarget {
+    fn fold(&mut self, f: FnDecl) -> FnDecl {
+        let ctx = Ctx::Fn(f.ident.clone());
+        self.fold_in(ctx, f).0
+    }
+}
+
+impl Fold<FnExpr> for NewTarget {
+    fn fold(&mut self, f: FnExpr) -> FnExpr {
+        let ident = f.ident.clone().unwrap_or_else(|| private_ident!("_target"));
+
+        let (mut f, used) = self.fold_in(Ctx::Fn(ident.clone()), f);
+
+        // The function should be named to be referenced.
+        if used && f.ident.is_none() {
+            f.ident = Some(ident);
+        }
+
+        f
+    }
+}
+
+impl Fold<Constructor> for NewTarget {
+    fn fold(&mut self, c: Constructor) -> Constructor {
+        self.fold_in(Ctx::Constructor, c).0
+    }
+}
+
+macro_rules! method {
+    ($T:tt) => {
+        impl Fold<$T> for NewTarget {
+            fn fold(&mut self, n: $T) -> $T {
+                self.fold_in(Ctx::Method, n).0
+            }
+        }
+    };
+}
+
+method!(ClassMethod);
+method!(ClassProp);
+method!(MethodProp);
+method!(GetterProp);
+method!(SetterProp);
+
+impl Fold<Expr> for NewTarget {
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::MetaProp(MetaPropExpr {
+                ref meta, ref prop, ..
+            }) if meta.sym == *"new" && prop.sym == *"target" => {
+                let span = meta.span.with_hi(prop.span.hi());
+
+                let this_constructor = || {
+                    box Expr::Member(MemberExpr {
+                        span,
+                        obj: ExprOrSuper::Expr(box Expr::This(ThisExpr { span })),
+                        prop: box Expr::Ident(quote_ident!("constructor")),
+                        computed: false,
+                    })
+                };
+
+                match self.ctx {
+                    Ctx::Top => e,
+                    Ctx::Fn(ref name) => {
+                        self.used = true;
+
+                        Expr::Cond(CondExpr {
+                            span,
+                            test: box Expr::Bin(BinExpr {
+                                span: DUMMY_SP,
+                                op: op!("instanceof"),
+                                left: box Expr::This(ThisExpr { span }),
+                                right: box Expr::Ident(name.clone()),
+                            }),
+                            cons: this_constructor(),
+                            alt: undefined(span),
+                        })
+                    }
+                    Ctx::Constructor => *this_constructor(),
+                    Ctx::Method => *undefined(span),
+                }
+            }
+
+            _ => e.fold_children(self),
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use super::classes::SuperFieldAccessFolder;
+use crate::{
+    pass::Pass,
+    util::{prepend, StmtLike},
+};
+use std::mem::replace;
+use swc_common::{util::move_map::MoveMap, Fold, FoldWith, Visit, VisitWith, DUMMY_SP};
+use swc_ecma_ast::*;
+
+/// `@babel/plugin-transform-object-super`
+///
+/// This should run after classes are lowered.
+///
+///# Example
+///## In
+///
+/// ```js
+/// var obj = {
+///     foo() {
+///         return super.foo();
+///     }
+/// };
+/// ```
+///
+///## Out
+///
+/// ```js
+/// var _obj;
+/// var obj = _obj = {
+///     foo() {
+///         return _get(_getPrototypeOf(_obj), "foo", this).call(this);
+///     }
+/// };
+/// ```
+pub fn object_super() -> impl Pass {
+    ObjectSuper::default()
+}
+
+#[derive(Default)]
+struct ObjectSuper {
+    vars: Vec<VarDeclarator>,
+}
+
+noop_fold_type!(ObjectSuper);
+
+impl<T> Fold<Vec<T>> for ObjectSuper
+where
+    T: FoldWith<Self> + StmtLike,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        let mut buf = Vec::with_capacity(stmts.len() + 2);
+
+        for stmt in stmts {
+            let stmt = stmt.fold_with(self);
+
+            if !self.vars.is_empty() {
+                buf.push(T::from_stmt(Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    decls: replace(&mut self.vars, Default::default()),
+                    declare: false,
+                }))));
+            }
+
+            buf.push(stmt);
+        }
+
+        buf
+    }
+}
+
+impl Fold<Expr> for ObjectSuper {
+    fn fold(&mut self, e: Expr) -> Expr {
+        // Nested objects have their own home object.
+        let e = e.fold_children(self);
+
+        let ObjectLit { span, props } = match e {
+            Expr::Object(obj) => obj,
+            _ => return e,
+        };
+
+        let home = private_ident!("_obj");
+        let mut uses_super = false;
+
+        let props = props.move_map(|prop| match prop {
+            PropOrSpread::Prop(box Prop::Method(MethodProp { key, mut function })) => {
+                if contains_super(&function) {
+                    uses_super = true;
+                    function = fold_super(&home, function, |f| &mut f.body);
+                }
+
+                PropOrSpread::Prop(box Prop::Method(MethodProp { key, function }))
+            }
+            PropOrSpread::Prop(box Prop::Getter(mut getter)) => {
+                if contains_super(&getter.body) {
+                    uses_super = true;
+                    getter = fold_super(&home, getter, |g| &mut g.body);
+                }
+
+                PropOrSpread::Prop(box Prop::Getter(getter))
+            }
+            PropOrSpread::Prop(box Prop::Setter(mut setter)) => {
+                if contains_super(&setter.param) || contains_super(&setter.body) {
+                    uses_super = true;
+                    setter = fold_super(&home, setter, |s| &mut s.body);
+                }
+
+                PropOrSpread::Prop(box Prop::Setter(setter))
+            }
+            _ => prop,
+        });
+
+        let obj = Expr::Object(ObjectLit { span, props });
+        if !uses_super {
+            return obj;
+        }
+
+        self.vars.push(VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(home.clone()),
+            init: None,
+            definite: false,
+        });
+
+        // _obj = { ... }
+        Expr::Assign(AssignExpr {
+            span,
+            left: PatOrExpr::Pat(box Pat::Ident(home)),
+            op: op!("="),
+            right: box obj,
+        })
+    }
+}
+
+/// Replaces `super.foo` in `n` with `_get(_getPrototypeOf(home), "foo",
+/// this)`.
+///
+/// Temporary variables are declared in the body returned by `body`.
+fn fold_super<T, F>(home: &Ident, n: T, body: F) -> T
+where
+    T: for<'a> FoldWith<SuperFieldAccessFolder<'a>>,
+    F: FnOnce(&mut T) -> &mut Option<BlockStmt>,
+{
+    let mut vars = vec![];
+    let mut n = n.fold_with(&mut SuperFieldAccessFolder {
+        class_name: home,
+        vars: &mut vars,
+        constructor_this_mark: None,
+        // `_getPrototypeOf(_obj)` instead of `_getPrototypeOf(_obj.prototype)`
+        is_static: true,
+        folding_constructor: false,
+        in_nested_scope: false,
+        in_injected_define_property_call: false,
+        this_alias_mark: None,
+    });
+
+    if !vars.is_empty() {
+        if let Some(body) = body(&mut n) {
+            prepend(
+                &mut body.stmts,
+                Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    declare: false,
+                    decls: vars,
+                })),
+            );
+        }
+    }
+
+    n
+}
+
+fn contains_super<N>(n: &N) -> bool
+where
+    N: VisitWith<SuperVisitor>,
+{
+    let mut v = SuperVisitor { found: false };
+    n.visit_with(&mut v);
+    v.found
+}
+
+struct SuperVisitor {
+    found: bool,
+}
+
+noop_visit_type!(SuperVisitor);
+
+impl Visit<ExprOrSuper> for SuperVisitor {
+    fn visit(&mut self, n: &ExprOrSuper) {
+        match n {
+            ExprOrSuper::Super(..) => self.found = true,
+            ExprOrSuper::Expr(e) => e.visit_with(self),
+        }
+    }
+}
@@ -99,6 +99,7 @@ impl Params {
                                 right: box Expr::Lit(Lit::Num(Number {
                                     span,
                                     value: i as f64,
+                                    raw: None,
                                 })),
                             })
                             .into();
@@ -115,11 +116,16 @@ impl Params {
                                     right: box Expr::Lit(Lit::Num(Number {
                                         span,
                                         value: i as _,
+                                        raw: None,
                                     })),
                                 }
                                 .into(),
                                 cons: box bin,
-                                alt: box Expr::Lit(Lit::Num(Number { span, value: 0.0 })),
+                                alt: box Expr::Lit(Lit::Num(Number {
+                                    span,
+                                    value: 0.0,
+                                    raw: None
+                                })),
                             }))
                         }
                     };
@@ -159,6 +165,7 @@ impl Params {
                                     init: Some(box Expr::Lit(Lit::Num(Number {
                                         span,
                                         value: i as f64,
+                                        raw: None,
                                     }))),
                                     definite: false,
                                 },
@@ -23,6 +23,7 @@ impl Loc {
         Expr::Lit(Lit::Num(Number {
             span: DUMMY_SP,
             value: self.stmt_index as _,
+            raw: None,
         }))
     }
 
@@ -402,6 +403,7 @@ impl CaseHandler<'_> {
                                         box Lit::Num(Number {
                                             span: DUMMY_SP,
                                             value: 0.0,
+                                            raw: None,
                                         })
                                         .into(),
                                         box callee,
@@ -722,6 +724,7 @@ impl CaseHandler<'_> {
                 test: Some(box Expr::Lit(Lit::Num(Number {
                     span: DUMMY_SP,
                     value: i as _,
+                    raw: None,
                 }))),
                 cons: vec![],
             };
@@ -1412,6 +1415,7 @@ impl Fold<Expr> for UnmarkedInvalidHandler {
             Expr::Invalid(Invalid { span }) => Expr::Lit(Lit::Num(Number {
                 span,
                 value: self.case_id as _,
+                raw: None,
             })),
             _ => e,
         }
@@ -1438,6 +1442,7 @@ impl Fold<Expr> for InvalidToLit<'_> {
                         return Expr::Lit(Lit::Num(Number {
                             span: DUMMY_SP,
                             value: (*stmt_index) as _,
+                            raw: None,
                         }));
                     }
                 }
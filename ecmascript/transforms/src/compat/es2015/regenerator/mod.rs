@@ -344,6 +344,7 @@ impl Regenerator {
             test: Some(box Expr::Lit(Lit::Num(Number {
                 span: DUMMY_SP,
                 value: handler.final_loc() as _,
+                raw: None,
             }))),
             // fallthrough
             cons: vec![],
@@ -373,6 +374,7 @@ impl Regenerator {
             test: box Expr::Lit(Lit::Num(Number {
                 span: DUMMY_SP,
                 value: 1.0,
+                raw: None,
             })),
             body: box SwitchStmt {
                 span: DUMMY_SP,
@@ -0,0 +1,101 @@
+use crate::pass::Pass;
+use std::fmt::Write;
+use swc_common::{Fold, FoldWith};
+use swc_ecma_ast::*;
+
+/// `@babel/plugin-transform-unicode-escapes`
+///
+/// String literals and identifiers are printed using their cooked values, so
+/// only raw strings of template literals are modified.
+///
+///# Example
+///## In
+///
+/// ```js
+/// `\u{1F600}`;
+/// ```
+///
+///## Out
+///
+/// ```js
+/// `\uD83D\uDE00`;
+/// ```
+pub fn unicode_escapes() -> impl Pass {
+    UnicodeEscapes
+}
+
+struct UnicodeEscapes;
+
+noop_fold_type!(UnicodeEscapes);
+
+impl Fold<Tpl> for UnicodeEscapes {
+    fn fold(&mut self, tpl: Tpl) -> Tpl {
+        let mut tpl = tpl.fold_children(self);
+
+        for quasi in &mut tpl.quasis {
+            if quasi.raw.value.contains("\\u{") {
+                quasi.raw.value = lower_code_point_escapes(&quasi.raw.value).into();
+            }
+        }
+
+        tpl
+    }
+}
+
+/// Converts `\u{XXXXX}` to `\uXXXX` or a surrogate pair of it.
+fn lower_code_point_escapes(s: &str) -> String {
+    let mut buf = String::with_capacity(s.len() + 8);
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        buf.push(c);
+        if c != '\\' {
+            continue;
+        }
+
+        match chars.next() {
+            Some('u') if chars.peek() == Some(&'{') => {
+                chars.next();
+
+                let hex: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                let cp = u32::from_str_radix(&hex, 16).unwrap_or(0);
+
+                buf.push('u');
+                if cp <= 0xffff {
+                    write!(buf, "{:04X}", cp).unwrap();
+                } else {
+                    let cp = cp - 0x10000;
+                    let high = 0xd800 + (cp >> 10);
+                    let low = 0xdc00 + (cp & 0x3ff);
+                    write!(buf, "{:04X}\\u{:04X}", high, low).unwrap();
+                }
+            }
+            // `\\` should not be used as a start of an escape.
+            Some(c) => buf.push(c),
+            None => {}
+        }
+    }
+
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| unicode_escapes(),
+        code_point,
+        r#"`\u{1F600} \u{61} \\u{61} ${a}`;"#,
+        r#"`\uD83D\uDE00 \u0061 \\u{61} ${a}`;"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| unicode_escapes(),
+        tagged,
+        r#"String.raw`\u{1F600}`;"#,
+        r#"String.raw`\u{1F600}`;"#
+    );
+}
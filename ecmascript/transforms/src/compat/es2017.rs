@@ -1,4 +1,5 @@
 pub use self::async_to_generator::async_to_generator;
+pub(crate) use self::async_to_generator::MethodFolder;
 use crate::pass::Pass;
 
 mod async_to_generator;
@@ -80,7 +80,7 @@ impl Fold<MethodProp> for Actual {
         let prop = validate!(prop);
         let prop = prop.fold_children(self);
 
-        if !prop.function.is_async {
+        if !prop.function.is_async || prop.function.is_generator {
             return prop;
         }
         let params = prop.function.params;
@@ -149,8 +149,8 @@ impl Fold<MethodProp> for Actual {
 ///     }
 /// }
 /// ```
-struct MethodFolder {
-    vars: Vec<VarDeclarator>,
+pub(crate) struct MethodFolder {
+    pub vars: Vec<VarDeclarator>,
 }
 
 impl MethodFolder {
@@ -341,7 +341,7 @@ impl Fold<ClassMethod> for Actual {
         if m.function.body.is_none() {
             return m;
         }
-        if m.kind != MethodKind::Method || !m.function.is_async {
+        if m.kind != MethodKind::Method || !m.function.is_async || m.function.is_generator {
             return m;
         }
         let params = m.function.params.clone();
@@ -423,7 +423,7 @@ impl Fold<Expr> for Actual {
                 args,
                 type_args,
             }) => {
-                if !fn_expr.function.is_async {
+                if !fn_expr.function.is_async || fn_expr.function.is_generator {
                     return Expr::Call(CallExpr {
                         span,
                         callee: ExprOrSuper::Expr(box Expr::Fn(fn_expr)),
@@ -473,6 +473,7 @@ impl Fold<Expr> for Actual {
                     function:
                         Function {
                             is_async: true,
+                            is_generator: false,
                             body: Some(..),
                             ..
                         },
@@ -509,7 +510,7 @@ impl Fold<Expr> for Actual {
 impl Fold<FnDecl> for Actual {
     fn fold(&mut self, f: FnDecl) -> FnDecl {
         let f = f.fold_children(self);
-        if !f.function.is_async {
+        if !f.function.is_async || f.function.is_generator {
             return f;
         }
 
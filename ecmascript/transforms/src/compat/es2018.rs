@@ -1,12 +1,22 @@
 pub use self::{
-    object_rest_spread::object_rest_spread, optional_catch_binding::optional_catch_binding,
+    async_generator::async_generator, object_rest_spread::object_rest_spread,
+    optional_catch_binding::optional_catch_binding,
 };
+pub use super::regexp::{dot_all_regex, named_capturing_groups_regex, unicode_property_regex};
 use crate::pass::Pass;
 use swc_common::chain;
 
+mod async_generator;
 mod object_rest_spread;
 mod optional_catch_binding;
 
 pub fn es2018() -> impl Pass {
-    chain!(object_rest_spread(), optional_catch_binding())
+    chain!(
+        async_generator(),
+        unicode_property_regex(),
+        dot_all_regex(),
+        named_capturing_groups_regex(),
+        object_rest_spread(),
+        optional_catch_binding()
+    )
 }
@@ -0,0 +1,553 @@
+use crate::{
+    compat::es2017::MethodFolder,
+    pass::Pass,
+    util::{ExprFactory, StmtLike},
+};
+use std::iter;
+use swc_common::{Fold, FoldWith, Span, Visit, VisitWith, DUMMY_SP};
+use swc_ecma_ast::*;
+
+/// `@babel/plugin-proposal-async-generator-functions`
+///
+/// ## In
+///
+/// ```js
+/// async function* foo() {
+///   await 1;
+///   yield 2;
+///   yield* bar();
+///   for await (const x of y) {}
+/// }
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// function foo() {
+///   return _wrapAsyncGenerator(function* () {
+///     yield _awaitAsyncGenerator(1);
+///     yield 2;
+///     yield* _asyncGeneratorDelegate(_asyncIterator(bar()), _awaitAsyncGenerator);
+///     var _iteratorNormalCompletion = true, _didIteratorError = false, _iteratorError;
+///     try {
+///       for (var _iterator = _asyncIterator(y), _step, _value; _step = yield _awaitAsyncGenerator(_iterator.next()), _iteratorNormalCompletion = _step.done, _value = yield _awaitAsyncGenerator(_step.value), !_iteratorNormalCompletion; _iteratorNormalCompletion = true) {
+///         const x = _value;
+///       }
+///     } catch (err) {
+///       ...
+///     }
+///   }).apply(this, arguments);
+/// }
+/// ```
+///
+/// `for await` in async functions is lowered to a `for` loop using `await`,
+/// which can be handled by `async_to_generator`.
+pub fn async_generator() -> impl Pass {
+    AsyncGenerator
+}
+
+#[derive(Default, Clone)]
+struct AsyncGenerator;
+
+noop_fold_type!(AsyncGenerator);
+
+impl<T> Fold<Vec<T>> for AsyncGenerator
+where
+    T: StmtLike + VisitWith<AsyncGeneratorVisitor>,
+    Vec<T>: FoldWith<Self>,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        if !contains_async_generator(&stmts) {
+            return stmts;
+        }
+
+        stmts.fold_children(self)
+    }
+}
+
+impl Fold<Function> for AsyncGenerator {
+    fn fold(&mut self, f: Function) -> Function {
+        let f = f.fold_children(self);
+        if !f.is_async || !f.is_generator || f.body.is_none() {
+            return f;
+        }
+
+        wrap(f, vec![])
+    }
+}
+
+impl Fold<ClassMethod> for AsyncGenerator {
+    fn fold(&mut self, m: ClassMethod) -> ClassMethod {
+        if !m.function.is_async || !m.function.is_generator || m.function.body.is_none() {
+            return m.fold_children(self);
+        }
+
+        let function = m.function.fold_children(self);
+        // `super` is not allowed in the wrapped generator.
+        let mut folder = MethodFolder { vars: vec![] };
+        let function = function.fold_children(&mut folder);
+
+        ClassMethod {
+            function: wrap(function, folder.vars),
+            ..m
+        }
+    }
+}
+
+impl Fold<Stmt> for AsyncGenerator {
+    fn fold(&mut self, stmt: Stmt) -> Stmt {
+        let stmt = stmt.fold_children(self);
+
+        match stmt {
+            Stmt::Labeled(LabeledStmt {
+                span,
+                label,
+                body: box Stmt::ForOf(s),
+            }) if s.await_token.is_some() => fold_for_await(Some((span, label)), s),
+            Stmt::ForOf(s) if s.await_token.is_some() => fold_for_await(None, s),
+            _ => stmt,
+        }
+    }
+}
+
+/// Creates
+///
+/// ```js
+/// function foo() {
+///     return _wrapAsyncGenerator(function* () {}).apply(this, arguments);
+/// }
+/// ```
+///
+/// from `async function* foo() {}`.
+fn wrap(f: Function, hoisted_vars: Vec<VarDeclarator>) -> Function {
+    let span = f.span;
+    // Parameters are moved to the generator, but the length of the function
+    // should be preserved.
+    let params = {
+        let mut done = false;
+        f.params
+            .iter()
+            .filter_map(|p| {
+                if done {
+                    None
+                } else {
+                    match p.pat {
+                        Pat::Ident(..) => Some(p.clone()),
+                        Pat::Array(..) | Pat::Object(..) => Some(Param {
+                            pat: Pat::Ident(private_ident!("_")),
+                            ..p.clone()
+                        }),
+                        _ => {
+                            done = true;
+                            None
+                        }
+                    }
+                }
+            })
+            .collect()
+    };
+
+    let body = f.body.fold_with(&mut AwaitToYield);
+    let generator = Expr::Fn(FnExpr {
+        ident: None,
+        function: Function {
+            span: DUMMY_SP,
+            body,
+            is_async: false,
+            is_generator: true,
+            decorators: Default::default(),
+            ..f
+        },
+    });
+
+    let wrapped = Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: helper!(wrap_async_generator, "wrapAsyncGenerator"),
+        args: vec![generator.as_arg()],
+        type_args: Default::default(),
+    });
+
+    let hoisted = if hoisted_vars.is_empty() {
+        None
+    } else {
+        Some(Stmt::Decl(Decl::Var(VarDecl {
+            span: DUMMY_SP,
+            kind: VarDeclKind::Var,
+            decls: hoisted_vars,
+            declare: false,
+        })))
+    };
+
+    Function {
+        span,
+        params,
+        body: Some(BlockStmt {
+            span: DUMMY_SP,
+            stmts: hoisted
+                .into_iter()
+                .chain(iter::once(Stmt::Return(ReturnStmt {
+                    span: DUMMY_SP,
+                    arg: Some(box wrapped.apply(
+                        DUMMY_SP,
+                        box Expr::This(ThisExpr { span: DUMMY_SP }),
+                        vec![quote_ident!("arguments").as_arg()],
+                    )),
+                })))
+                .collect(),
+        }),
+        decorators: Default::default(),
+        is_async: false,
+        is_generator: false,
+        type_params: Default::default(),
+        return_type: Default::default(),
+    }
+}
+
+/// Converts `await` and `yield*` in the body of an async generator.
+struct AwaitToYield;
+
+macro_rules! noop {
+    ($T:path) => {
+        impl Fold<$T> for AwaitToYield {
+            /// Don't recurse into function.
+            fn fold(&mut self, f: $T) -> $T {
+                f
+            }
+        }
+    };
+}
+noop!(Function);
+noop!(Constructor);
+noop!(ArrowExpr);
+
+impl Fold<Expr> for AwaitToYield {
+    fn fold(&mut self, expr: Expr) -> Expr {
+        let expr = expr.fold_children(self);
+
+        match expr {
+            Expr::Await(AwaitExpr { span, arg }) => Expr::Yield(YieldExpr {
+                span,
+                delegate: false,
+                arg: Some(box Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: helper!(await_async_generator, "awaitAsyncGenerator"),
+                    args: vec![arg.as_arg()],
+                    type_args: Default::default(),
+                })),
+            }),
+
+            // yield* _asyncGeneratorDelegate(_asyncIterator(arg), _awaitAsyncGenerator)
+            Expr::Yield(YieldExpr {
+                span,
+                delegate: true,
+                arg: Some(arg),
+            }) => {
+                let iterator = Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: helper!(async_iterator, "asyncIterator"),
+                    args: vec![arg.as_arg()],
+                    type_args: Default::default(),
+                });
+
+                Expr::Yield(YieldExpr {
+                    span,
+                    delegate: true,
+                    arg: Some(box Expr::Call(CallExpr {
+                        span: DUMMY_SP,
+                        callee: helper!(async_generator_delegate, "asyncGeneratorDelegate"),
+                        args: vec![
+                            iterator.as_arg(),
+                            helper_expr!(await_async_generator, "awaitAsyncGenerator").as_arg(),
+                        ],
+                        type_args: Default::default(),
+                    })),
+                })
+            }
+
+            _ => expr,
+        }
+    }
+}
+
+/// Creates
+///
+/// ```js
+/// {
+///     var _iteratorNormalCompletion = true, _didIteratorError = false, _iteratorError;
+///     try {
+///         for (var _iterator = _asyncIterator(y), _step, _value; _step = await _iterator.next(), _iteratorNormalCompletion = _step.done, _value = await _step.value, !_iteratorNormalCompletion; _iteratorNormalCompletion = true) {
+///             const x = _value;
+///         }
+///     } catch (err) {
+///         _didIteratorError = true;
+///         _iteratorError = err;
+///     } finally {
+///         try {
+///             if (!_iteratorNormalCompletion && _iterator.return != null) {
+///                 await _iterator.return();
+///             }
+///         } finally {
+///             if (_didIteratorError) {
+///                 throw _iteratorError;
+///             }
+///         }
+///     }
+/// }
+/// ```
+///
+/// from `for await (const x of y) {}`.
+fn fold_for_await(label: Option<(Span, Ident)>, s: ForOfStmt) -> Stmt {
+    let normal_completion = private_ident!("_iteratorNormalCompletion");
+    let did_error = private_ident!("_didIteratorError");
+    let error = private_ident!("_iteratorError");
+    let iterator = private_ident!("_iterator");
+    let step = private_ident!("_step");
+    let value = private_ident!("_value");
+    let err = private_ident!("err");
+
+    let assign = |left: &Ident, right: Expr| {
+        Expr::Assign(AssignExpr {
+            span: DUMMY_SP,
+            left: PatOrExpr::Pat(box Pat::Ident(left.clone())),
+            op: op!("="),
+            right: box right,
+        })
+    };
+    let bool_lit = |value| {
+        Expr::Lit(Lit::Bool(Bool {
+            span: DUMMY_SP,
+            value,
+        }))
+    };
+    let await_expr = |arg: Expr| {
+        Expr::Await(AwaitExpr {
+            span: DUMMY_SP,
+            arg: box arg,
+        })
+    };
+    let var = |name: &Ident, init: Option<Expr>| VarDeclarator {
+        span: DUMMY_SP,
+        name: Pat::Ident(name.clone()),
+        init: init.map(Box::new),
+        definite: false,
+    };
+
+    let mut body = match *s.body {
+        Stmt::Block(block) => block,
+        body => BlockStmt {
+            span: DUMMY_SP,
+            stmts: vec![body],
+        },
+    };
+    body.stmts.insert(
+        0,
+        match s.left {
+            VarDeclOrPat::VarDecl(mut var) => {
+                assert_eq!(var.decls.len(), 1);
+                Stmt::Decl(Decl::Var(VarDecl {
+                    decls: vec![VarDeclarator {
+                        init: Some(box Expr::Ident(value.clone())),
+                        ..var.decls.pop().unwrap()
+                    }],
+                    ..var
+                }))
+            }
+            VarDeclOrPat::Pat(pat) => AssignExpr {
+                span: DUMMY_SP,
+                left: PatOrExpr::Pat(box pat),
+                op: op!("="),
+                right: box Expr::Ident(value.clone()),
+            }
+            .into_stmt(),
+        },
+    );
+
+    let for_stmt = Stmt::For(ForStmt {
+        span: s.span,
+        init: Some(VarDeclOrExpr::VarDecl(VarDecl {
+            span: DUMMY_SP,
+            kind: VarDeclKind::Var,
+            declare: false,
+            decls: vec![
+                var(
+                    &iterator,
+                    Some(Expr::Call(CallExpr {
+                        span: DUMMY_SP,
+                        callee: helper!(async_iterator, "asyncIterator"),
+                        args: vec![s.right.as_arg()],
+                        type_args: Default::default(),
+                    })),
+                ),
+                var(&step, None),
+                var(&value, None),
+            ],
+        })),
+        test: Some(box Expr::Seq(SeqExpr {
+            span: DUMMY_SP,
+            exprs: vec![
+                box assign(
+                    &step,
+                    await_expr(Expr::Call(CallExpr {
+                        span: DUMMY_SP,
+                        callee: iterator.clone().member(quote_ident!("next")).as_callee(),
+                        args: vec![],
+                        type_args: Default::default(),
+                    })),
+                ),
+                box assign(
+                    &normal_completion,
+                    step.clone().member(quote_ident!("done")),
+                ),
+                box assign(
+                    &value,
+                    await_expr(step.clone().member(quote_ident!("value"))),
+                ),
+                box Expr::Unary(UnaryExpr {
+                    span: DUMMY_SP,
+                    op: op!("!"),
+                    arg: box Expr::Ident(normal_completion.clone()),
+                }),
+            ],
+        })),
+        update: Some(box assign(&normal_completion, bool_lit(true))),
+        body: box Stmt::Block(body),
+    });
+
+    let for_stmt = match label {
+        Some((span, label)) => Stmt::Labeled(LabeledStmt {
+            span,
+            label,
+            body: box for_stmt,
+        }),
+        None => for_stmt,
+    };
+
+    let iterator_return = iterator.member(quote_ident!("return"));
+
+    let try_stmt = Stmt::Try(TryStmt {
+        span: DUMMY_SP,
+        block: BlockStmt {
+            span: DUMMY_SP,
+            stmts: vec![for_stmt],
+        },
+        handler: Some(CatchClause {
+            span: DUMMY_SP,
+            param: Some(Pat::Ident(err.clone())),
+            body: BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![
+                    assign(&did_error, bool_lit(true)).into_stmt(),
+                    assign(&error, Expr::Ident(err)).into_stmt(),
+                ],
+            },
+        }),
+        finalizer: Some(BlockStmt {
+            span: DUMMY_SP,
+            stmts: vec![Stmt::Try(TryStmt {
+                span: DUMMY_SP,
+                // if (!_iteratorNormalCompletion && _iterator.return != null) {
+                //     await _iterator.return();
+                // }
+                block: BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![Stmt::If(IfStmt {
+                        span: DUMMY_SP,
+                        test: box Expr::Bin(BinExpr {
+                            span: DUMMY_SP,
+                            left: box Expr::Unary(UnaryExpr {
+                                span: DUMMY_SP,
+                                op: op!("!"),
+                                arg: box Expr::Ident(normal_completion.clone()),
+                            }),
+                            op: op!("&&"),
+                            right: box Expr::Bin(BinExpr {
+                                span: DUMMY_SP,
+                                left: box iterator_return.clone(),
+                                op: op!("!="),
+                                right: box Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+                            }),
+                        }),
+                        cons: box Stmt::Block(BlockStmt {
+                            span: DUMMY_SP,
+                            stmts: vec![await_expr(Expr::Call(CallExpr {
+                                span: DUMMY_SP,
+                                callee: iterator_return.as_callee(),
+                                args: vec![],
+                                type_args: Default::default(),
+                            }))
+                            .into_stmt()],
+                        }),
+                        alt: None,
+                    })],
+                },
+                handler: None,
+                // if (_didIteratorError) {
+                //     throw _iteratorError;
+                // }
+                finalizer: Some(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![Stmt::If(IfStmt {
+                        span: DUMMY_SP,
+                        test: box Expr::Ident(did_error.clone()),
+                        cons: box Stmt::Block(BlockStmt {
+                            span: DUMMY_SP,
+                            stmts: vec![Stmt::Throw(ThrowStmt {
+                                span: DUMMY_SP,
+                                arg: box Expr::Ident(error.clone()),
+                            })],
+                        }),
+                        alt: None,
+                    })],
+                }),
+            })],
+        }),
+    });
+
+    Stmt::Block(BlockStmt {
+        span: DUMMY_SP,
+        stmts: vec![
+            Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                declare: false,
+                decls: vec![
+                    var(&normal_completion, Some(bool_lit(true))),
+                    var(&did_error, Some(bool_lit(false))),
+                    var(&error, None),
+                ],
+            })),
+            try_stmt,
+        ],
+    })
+}
+
+fn contains_async_generator<N>(node: &N) -> bool
+where
+    N: VisitWith<AsyncGeneratorVisitor>,
+{
+    let mut v = AsyncGeneratorVisitor { found: false };
+    node.visit_with(&mut v);
+    v.found
+}
+
+struct AsyncGeneratorVisitor {
+    found: bool,
+}
+
+impl Visit<Function> for AsyncGeneratorVisitor {
+    fn visit(&mut self, f: &Function) {
+        if f.is_async && f.is_generator {
+            self.found = true;
+        }
+        f.visit_children(self);
+    }
+}
+
+impl Visit<ForOfStmt> for AsyncGeneratorVisitor {
+    fn visit(&mut self, s: &ForOfStmt) {
+        if s.await_token.is_some() {
+            self.found = true;
+        }
+        s.visit_children(self);
+    }
+}
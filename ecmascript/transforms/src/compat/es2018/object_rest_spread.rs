@@ -806,7 +806,7 @@ impl RestFolder {
                             let value = value.clone();
                             (key, box Expr::Ident(quote_ident!(span, value)))
                         }
-                        PropName::Num(Number { span, value }) => (
+                        PropName::Num(Number { span, value, .. }) => (
                             key,
                             box Expr::Lit(Lit::Str(Str {
                                 span,
@@ -925,7 +925,7 @@ fn object_without_properties(obj: Box<Expr>, excluded_props: Vec<Option<ExprOrSp
         .into_iter()
         .map(|v| {
             v.map(|v| match *v.expr {
-                Expr::Lit(Lit::Num(Number { span, value })) => ExprOrSpread {
+                Expr::Lit(Lit::Num(Number { span, value, .. })) => ExprOrSpread {
                     expr: box Expr::Lit(Lit::Str(Str {
                         span,
                         value: value.to_string().into(),
@@ -980,7 +980,7 @@ fn excluded_props(props: &[ObjectPatProp]) -> Vec<Option<ExprOrSpread>> {
                 })
                 .as_arg(),
                 PropName::Str(s) => Lit::Str(s.clone()).as_arg(),
-                PropName::Num(Number { span, value }) => Lit::Str(Str {
+                PropName::Num(Number { span, value, .. }) => Lit::Str(Str {
                     span: *span,
                     value: format!("{}", value).into(),
                     has_escape: false,
@@ -9,10 +9,10 @@ use crate::{
     pass::Pass,
     util::{
         alias_ident_for, alias_if_required, constructor::inject_after_super, default_constructor,
-        undefined, ExprFactory, ModuleItemLike, StmtLike,
+        prepend, undefined, ExprFactory, ModuleItemLike, StmtLike,
     },
 };
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use swc_atoms::JsWord;
 use swc_common::{Fold, FoldWith, Mark, Spanned, VisitWith, DUMMY_SP};
 use swc_ecma_ast::*;
@@ -266,11 +266,76 @@ impl ClassProperties {
         let mut used_names = vec![];
         let mut used_key_names = vec![];
         let mut statics = HashSet::default();
+        // Functions of private methods and accessors
+        let mut private_fns = vec![];
+        let mut private_methods = HashMap::default();
+        let mut private_accessors: Vec<PrivateAccessor> = vec![];
+        let fn_mark = Mark::fresh(Mark::root());
 
         for member in class.body {
             match member {
-                ClassMember::PrivateMethod(..) | ClassMember::TsIndexSignature(..) => {
-                    members.push(member)
+                ClassMember::TsIndexSignature(..) => members.push(member),
+
+                ClassMember::PrivateMethod(method) => {
+                    if method.function.body.is_none() {
+                        continue;
+                    }
+
+                    let sym = method.key.id.sym.clone();
+                    if method.is_static {
+                        statics.insert(sym.clone());
+                    }
+
+                    let fn_name = match method.kind {
+                        MethodKind::Method => format!("_{}_method", sym),
+                        MethodKind::Getter => format!("_get_{}", sym),
+                        MethodKind::Setter => format!("_set_{}", sym),
+                    };
+                    let fn_ident = Ident::new(fn_name.into(), method.key.span.apply_mark(fn_mark));
+
+                    let ident = Ident::new(
+                        format!("_{}", sym).into(),
+                        method.key.span.apply_mark(self.mark),
+                    );
+                    match method.kind {
+                        MethodKind::Method => {
+                            private_methods.insert(sym, fn_ident.clone());
+                            if !method.is_static {
+                                private_accessors.push(PrivateAccessor {
+                                    ident,
+                                    is_static: false,
+                                    is_method: true,
+                                    getter: None,
+                                    setter: None,
+                                });
+                            }
+                        }
+                        kind => {
+                            let idx = match private_accessors
+                                .iter()
+                                .position(|a| !a.is_method && a.ident.sym == ident.sym)
+                            {
+                                Some(idx) => idx,
+                                None => {
+                                    private_accessors.push(PrivateAccessor {
+                                        ident,
+                                        is_static: method.is_static,
+                                        is_method: false,
+                                        getter: None,
+                                        setter: None,
+                                    });
+                                    private_accessors.len() - 1
+                                }
+                            };
+                            if kind == MethodKind::Getter {
+                                private_accessors[idx].getter = Some(fn_ident.clone());
+                            } else {
+                                private_accessors[idx].setter = Some(fn_ident.clone());
+                            }
+                        }
+                    }
+
+                    private_fns.push((fn_ident, method.is_static, method.function));
                 }
 
                 ClassMember::Method(method) => {
@@ -475,19 +540,65 @@ impl ClassProperties {
             }
         }
 
+        // Private methods are initialized before fields.
+        let mut private_stmts = vec![];
+        let mut brand_exprs = vec![];
+        for accessor in private_accessors {
+            let (stmt, expr) = accessor.into_init();
+            private_stmts.push(stmt);
+            brand_exprs.extend(expr);
+        }
+        brand_exprs.extend(constructor_exprs);
+        let constructor_exprs = brand_exprs;
+
         let constructor =
             self.process_constructor(constructor, has_super, &used_names, constructor_exprs);
         if let Some(c) = constructor {
             members.push(ClassMember::Constructor(c));
         }
 
-        let members = members.fold_with(&mut FieldAccessFolder {
+        let mut folder = FieldAccessFolder {
             mark: self.mark,
             statics: &statics,
+            private_methods: &private_methods,
             vars: vec![],
             class_name: &ident,
             in_assign_pat: false,
-        });
+        };
+        let members = members.fold_with(&mut folder);
+        for (fn_ident, is_static, function) in private_fns {
+            // `super` is not allowed in a function declaration.
+            let mut vars = vec![];
+            let mut function = function.fold_with(&mut SuperFieldAccessFolder {
+                class_name: &ident,
+                vars: &mut vars,
+                constructor_this_mark: None,
+                is_static,
+                folding_constructor: false,
+                in_injected_define_property_call: false,
+                in_nested_scope: false,
+                this_alias_mark: None,
+            });
+            if !vars.is_empty() {
+                prepend(
+                    &mut function.body.as_mut().unwrap().stmts,
+                    Stmt::Decl(Decl::Var(VarDecl {
+                        span: DUMMY_SP,
+                        kind: VarDeclKind::Var,
+                        declare: false,
+                        decls: vars,
+                    })),
+                );
+            }
+
+            private_stmts.push(Stmt::Decl(Decl::Fn(FnDecl {
+                ident: fn_ident,
+                declare: false,
+                function: function.fold_with(&mut folder),
+            })));
+        }
+        private_stmts.extend(extra_stmts);
+        let extra_stmts = private_stmts;
 
         (
             vars,
@@ -538,3 +649,86 @@ impl ClassProperties {
         }
     }
 }
+
+/// A private method or accessor which requires a variable.
+struct PrivateAccessor {
+    ident: Ident,
+    is_static: bool,
+    /// `WeakSet` used for brand checks of instance methods.
+    is_method: bool,
+    getter: Option<Ident>,
+    setter: Option<Ident>,
+}
+
+impl PrivateAccessor {
+    /// Returns the declaration of the variable and an expression to
+    /// initialize the instance.
+    fn into_init(self) -> (Stmt, Option<Box<Expr>>) {
+        let new = |class: &str| {
+            box Expr::New(NewExpr {
+                span: DUMMY_SP,
+                callee: box Expr::Ident(quote_ident!(class)),
+                args: Some(vec![]),
+                type_args: Default::default(),
+            })
+        };
+
+        let (init, expr) = if self.is_method {
+            // _x.add(this)
+            let expr = box Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: self.ident.clone().member(quote_ident!("add")).as_callee(),
+                args: vec![ThisExpr { span: DUMMY_SP }.as_arg()],
+                type_args: Default::default(),
+            });
+
+            (new("WeakSet"), Some(expr))
+        } else {
+            // { get: _get_x, set: _set_x }
+            let desc = box Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props: vec![("get", self.getter), ("set", self.setter)]
+                    .into_iter()
+                    .map(|(key, value)| {
+                        PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                            key: PropName::Ident(quote_ident!(key)),
+                            value: match value {
+                                Some(value) => box Expr::Ident(value),
+                                None => undefined(DUMMY_SP),
+                            },
+                        }))
+                    })
+                    .collect(),
+            });
+
+            if self.is_static {
+                (desc, None)
+            } else {
+                // _x.set(this, { get: _get_x, set: _set_x })
+                let expr = box Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: self.ident.clone().member(quote_ident!("set")).as_callee(),
+                    args: vec![ThisExpr { span: DUMMY_SP }.as_arg(), desc.as_arg()],
+                    type_args: Default::default(),
+                });
+
+                (new("WeakMap"), Some(expr))
+            }
+        };
+
+        (
+            Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                declare: false,
+                decls: vec![VarDeclarator {
+                    span: DUMMY_SP,
+                    name: Pat::Ident(self.ident),
+                    init: Some(init),
+                    definite: false,
+                }],
+            })),
+            expr,
+        )
+    }
+}
@@ -1,7 +1,10 @@
-use crate::util::{alias_ident_for, alias_if_required, prepend, ExprFactory};
-use std::{collections::HashSet, iter, mem};
+use crate::util::{alias_ident_for, alias_if_required, prepend, undefined, ExprFactory};
+use std::{
+    collections::{HashMap, HashSet},
+    iter, mem,
+};
 use swc_atoms::JsWord;
-use swc_common::{Fold, FoldWith, Mark, Spanned, DUMMY_SP};
+use swc_common::{Fold, FoldWith, Mark, Span, Spanned, DUMMY_SP};
 use swc_ecma_ast::*;
 
 pub(super) struct FieldAccessFolder<'a> {
@@ -9,6 +12,8 @@ pub(super) struct FieldAccessFolder<'a> {
     pub class_name: &'a Ident,
     pub vars: Vec<VarDeclarator>,
     pub statics: &'a HashSet<JsWord>,
+    /// Private methods, mapped to the function declared for them.
+    pub private_methods: &'a HashMap<JsWord, Ident>,
     pub in_assign_pat: bool,
 }
 
@@ -49,6 +54,10 @@ impl<'a> Fold<Expr> for FieldAccessFolder<'a> {
                     ExprOrSuper::Expr(ref obj) => obj.clone(),
                 };
 
+                if self.private_methods.contains_key(&n.id.sym) {
+                    return self.private_method_set();
+                }
+
                 let is_static = self.statics.contains(&n.id.sym);
                 let ident = Ident::new(
                     format!("_{}", n.id.sym).into(),
@@ -118,6 +127,7 @@ impl<'a> Fold<Expr> for FieldAccessFolder<'a> {
                         right: box Expr::Lit(Lit::Num(Number {
                             span: DUMMY_SP,
                             value: 1.0,
+                            raw: None,
                         })),
                     }
                     .as_arg()
@@ -200,6 +210,17 @@ impl<'a> Fold<Expr> for FieldAccessFolder<'a> {
                     ExprOrSuper::Expr(ref obj) => obj.clone(),
                 };
 
+                match op {
+                    op!("&&=") | op!("||=") | op!("??=") => {
+                        return self.fold_private_logical_assign(span, obj, n, op, right)
+                    }
+                    _ => {}
+                }
+
+                if self.private_methods.contains_key(&n.id.sym) {
+                    return self.private_method_set();
+                }
+
                 let is_static = self.statics.contains(&n.id.sym);
                 let ident = Ident::new(
                     format!("_{}", n.id.sym).into(),
@@ -254,6 +275,7 @@ impl<'a> Fold<Expr> for FieldAccessFolder<'a> {
                             op!("&=") => op!("&"),
                             op!("^=") => op!("^"),
                             op!("**=") => op!("**"),
+                            op!("&&=") | op!("||=") | op!("??=") => unreachable!(),
                         },
                         right,
                     }
@@ -361,6 +383,107 @@ impl Fold<Pat> for FieldAccessFolder<'_> {
 }
 
 impl<'a> FieldAccessFolder<'a> {
+    /// Private methods are not writable.
+    fn private_method_set(&self) -> Expr {
+        Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: helper!(class_private_method_set, "classPrivateMethodSet"),
+            args: vec![],
+            type_args: Default::default(),
+        })
+    }
+
+    /// Lowers `obj.#x ||= value`, `obj.#x &&= value` and `obj.#x ??= value`.
+    ///
+    /// The field is set only if required, like `obj.#x || (obj.#x = value)`.
+    fn fold_private_logical_assign(
+        &mut self,
+        span: Span,
+        obj: Box<Expr>,
+        n: PrivateName,
+        op: AssignOp,
+        right: Box<Expr>,
+    ) -> Expr {
+        let is_static = self.statics.contains(&n.id.sym);
+        let (get, this) = self.fold_private_get(
+            MemberExpr {
+                span: DUMMY_SP,
+                obj: ExprOrSuper::Expr(obj.clone()),
+                prop: box Expr::PrivateName(n.clone()),
+                computed: false,
+            },
+            None,
+        );
+        // `this` is the alias of `obj` if it's aliased by the getter.
+        let obj = match this {
+            Some(this) if !is_static => box this,
+            _ => obj,
+        };
+        let right = right.fold_with(self);
+
+        let set = Expr::Assign(AssignExpr {
+            span: DUMMY_SP,
+            left: PatOrExpr::Expr(box Expr::Member(MemberExpr {
+                span: DUMMY_SP,
+                obj: ExprOrSuper::Expr(obj),
+                prop: box Expr::PrivateName(n),
+                computed: false,
+            })),
+            op: op!("="),
+            right,
+        })
+        .fold_with(self);
+
+        match op {
+            op!("&&=") | op!("||=") => Expr::Bin(BinExpr {
+                span,
+                left: box get,
+                op: if op == op!("&&=") {
+                    op!("&&")
+                } else {
+                    op!("||")
+                },
+                right: box set,
+            }),
+            _ => {
+                let (l, _) = alias_if_required(&get, "ref");
+                self.vars.push(VarDeclarator {
+                    span: DUMMY_SP,
+                    name: Pat::Ident(l.clone()),
+                    init: None,
+                    definite: false,
+                });
+
+                Expr::Cond(CondExpr {
+                    span,
+                    test: box Expr::Bin(BinExpr {
+                        span: DUMMY_SP,
+                        left: box Expr::Bin(BinExpr {
+                            span: DUMMY_SP,
+                            left: box Expr::Assign(AssignExpr {
+                                span: DUMMY_SP,
+                                op: op!("="),
+                                left: PatOrExpr::Pat(box Pat::Ident(l.clone())),
+                                right: box get,
+                            }),
+                            op: op!("!=="),
+                            right: box Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+                        }),
+                        op: op!("&&"),
+                        right: box Expr::Bin(BinExpr {
+                            span: DUMMY_SP,
+                            left: box Expr::Ident(l.clone()),
+                            op: op!("!=="),
+                            right: undefined(DUMMY_SP),
+                        }),
+                    }),
+                    cons: box Expr::Ident(l),
+                    alt: box set,
+                })
+            }
+        }
+    }
+
     /// Returns `(expr, thisObject)`
     ///
     ///   - `obj_alias`: If alias is already declared, this method will use
@@ -396,11 +519,25 @@ impl<'a> FieldAccessFolder<'a> {
             n.id.span.apply_mark(self.mark),
         );
 
+        let method = self.private_methods.get(&n.id.sym).cloned();
+
         if is_static {
-            let get = helper!(
-                class_static_private_field_spec_get,
-                "classStaticPrivateFieldSpecGet"
-            );
+            let (get, target) = match method {
+                Some(method) => (
+                    helper!(
+                        class_static_private_method_get,
+                        "classStaticPrivateMethodGet"
+                    ),
+                    method,
+                ),
+                None => (
+                    helper!(
+                        class_static_private_field_spec_get,
+                        "classStaticPrivateFieldSpecGet"
+                    ),
+                    ident,
+                ),
+            };
 
             (
                 Expr::Call(CallExpr {
@@ -409,7 +546,7 @@ impl<'a> FieldAccessFolder<'a> {
                     args: vec![
                         obj.as_arg(),
                         self.class_name.clone().as_arg(),
-                        ident.as_arg(),
+                        target.as_arg(),
                     ],
                     type_args: Default::default(),
                 }),
@@ -439,14 +576,24 @@ impl<'a> FieldAccessFolder<'a> {
                 };
             }
 
-            let get = helper!(class_private_field_get, "classPrivateFieldGet");
+            // For private methods, the map is a `WeakSet` used for brand checks.
+            let (get, extra_args) = match method {
+                Some(method) => (
+                    helper!(class_private_method_get, "classPrivateMethodGet"),
+                    vec![ident.as_arg(), method.as_arg()],
+                ),
+                None => (
+                    helper!(class_private_field_get, "classPrivateFieldGet"),
+                    vec![ident.as_arg()],
+                ),
+            };
 
             match *obj {
                 Expr::This(this) => (
                     CallExpr {
                         span: DUMMY_SP,
                         callee: get,
-                        args: vec![this.as_arg(), ident.as_arg()],
+                        args: iter::once(this.as_arg()).chain(extra_args).collect(),
 
                         type_args: Default::default(),
                     }
@@ -473,24 +620,23 @@ impl<'a> FieldAccessFolder<'a> {
                         CallExpr {
                             span: DUMMY_SP,
                             callee: get,
-                            args: vec![
-                                if is_alias_initialized {
-                                    var.clone().as_arg()
-                                } else {
-                                    if aliased {
-                                        AssignExpr {
-                                            span: DUMMY_SP,
-                                            left: PatOrExpr::Pat(box Pat::Ident(var.clone())),
-                                            op: op!("="),
-                                            right: obj,
-                                        }
-                                        .as_arg()
-                                    } else {
-                                        var.clone().as_arg()
+                            args: iter::once(if is_alias_initialized {
+                                var.clone().as_arg()
+                            } else {
+                                if aliased {
+                                    AssignExpr {
+                                        span: DUMMY_SP,
+                                        left: PatOrExpr::Pat(box Pat::Ident(var.clone())),
+                                        op: op!("="),
+                                        right: obj,
                                     }
-                                },
-                                ident.as_arg(),
-                            ],
+                                    .as_arg()
+                                } else {
+                                    var.clone().as_arg()
+                                }
+                            })
+                            .chain(extra_args)
+                            .collect(),
 
                             type_args: Default::default(),
                         }
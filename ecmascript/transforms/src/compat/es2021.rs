@@ -0,0 +1,4 @@
+pub use self::{logical_assignments::logical_assignments, numeric_separator::numeric_separator};
+
+mod logical_assignments;
+mod numeric_separator;
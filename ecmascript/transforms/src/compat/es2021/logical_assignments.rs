@@ -0,0 +1,266 @@
+use crate::{
+    pass::Pass,
+    util::{alias_ident_for, StmtLike},
+};
+use std::mem::replace;
+use swc_common::{Fold, FoldWith, DUMMY_SP};
+use swc_ecma_ast::*;
+
+/// `@babel/plugin-proposal-logical-assignment-operators`
+///
+///# Example
+///## In
+///
+/// ```js
+/// a ||= b;
+/// foo().x &&= c;
+/// ```
+///
+///## Out
+///
+/// ```js
+/// a || (a = b);
+/// var _ref;
+/// (_ref = foo()).x && (_ref.x = c);
+/// ```
+pub fn logical_assignments() -> impl Pass + 'static {
+    LogicalAssignments::default()
+}
+
+#[derive(Debug, Default)]
+struct LogicalAssignments {
+    vars: Vec<VarDeclarator>,
+}
+
+impl<T> Fold<Vec<T>> for LogicalAssignments
+where
+    T: FoldWith<Self> + StmtLike,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        let mut buf = Vec::with_capacity(stmts.len() + 2);
+
+        for stmt in stmts {
+            let stmt = stmt.fold_with(self);
+
+            if !self.vars.is_empty() {
+                buf.push(T::from_stmt(Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    decls: replace(&mut self.vars, Default::default()),
+                    declare: false,
+                }))));
+            }
+
+            buf.push(stmt);
+        }
+
+        buf
+    }
+}
+
+impl LogicalAssignments {
+    /// Returns `(init, alias)`, where `init` evaluates `e` and stores it to
+    /// `alias`. Identifiers, `this` and literals are not aliased.
+    fn alias(&mut self, e: Box<Expr>, default: &str) -> (Box<Expr>, Box<Expr>) {
+        match *e {
+            Expr::Ident(..) | Expr::This(..) | Expr::Lit(..) => (e.clone(), e),
+            _ => {
+                let alias = alias_ident_for(&e, default);
+                self.vars.push(VarDeclarator {
+                    span: DUMMY_SP,
+                    name: Pat::Ident(alias.clone()),
+                    init: None,
+                    definite: false,
+                });
+
+                (
+                    box Expr::Assign(AssignExpr {
+                        span: DUMMY_SP,
+                        op: op!("="),
+                        left: PatOrExpr::Pat(box Pat::Ident(alias.clone())),
+                        right: e,
+                    }),
+                    box Expr::Ident(alias),
+                )
+            }
+        }
+    }
+}
+
+impl Fold<Expr> for LogicalAssignments {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        let (span, op, left, right) = match e {
+            Expr::Assign(AssignExpr {
+                span,
+                op,
+                left,
+                right,
+            }) if op == op!("&&=") || op == op!("||=") || op == op!("??=") => {
+                (span, op, left, right)
+            }
+            _ => return e,
+        };
+
+        let (get, set) = match left {
+            PatOrExpr::Expr(box Expr::Ident(i)) | PatOrExpr::Pat(box Pat::Ident(i)) => (
+                box Expr::Ident(i.clone()),
+                PatOrExpr::Pat(box Pat::Ident(i)),
+            ),
+
+            PatOrExpr::Expr(box Expr::Member(MemberExpr {
+                span: member_span,
+                obj,
+                prop,
+                computed,
+            })) => {
+                let (get_obj, set_obj) = match obj {
+                    ExprOrSuper::Super(s) => (ExprOrSuper::Super(s), ExprOrSuper::Super(s)),
+                    ExprOrSuper::Expr(obj) => {
+                        let (init, alias) = self.alias(obj, "_ref");
+                        (ExprOrSuper::Expr(init), ExprOrSuper::Expr(alias))
+                    }
+                };
+                let (get_prop, set_prop) = if computed {
+                    self.alias(prop, "_key")
+                } else {
+                    (prop.clone(), prop)
+                };
+
+                (
+                    box Expr::Member(MemberExpr {
+                        span: member_span,
+                        obj: get_obj,
+                        prop: get_prop,
+                        computed,
+                    }),
+                    PatOrExpr::Expr(box Expr::Member(MemberExpr {
+                        span: member_span,
+                        obj: set_obj,
+                        prop: set_prop,
+                        computed,
+                    })),
+                )
+            }
+
+            left => {
+                // Not a valid assignment target.
+                return Expr::Assign(AssignExpr {
+                    span,
+                    op,
+                    left,
+                    right,
+                });
+            }
+        };
+
+        Expr::Bin(BinExpr {
+            span,
+            left: get,
+            op: match op {
+                op!("&&=") => op!("&&"),
+                op!("||=") => op!("||"),
+                _ => op!("??"),
+            },
+            right: box Expr::Assign(AssignExpr {
+                span: DUMMY_SP,
+                op: op!("="),
+                left: set,
+                right,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swc_common::chain;
+
+    fn syntax() -> ::swc_ecma_parser::Syntax {
+        Default::default()
+    }
+
+    test!(
+        syntax(),
+        |_| logical_assignments(),
+        ident,
+        "a ||= b; a &&= b; a ??= b;",
+        "a || (a = b); a && (a = b); a ?? (a = b);"
+    );
+
+    test!(
+        syntax(),
+        |_| logical_assignments(),
+        member,
+        "obj.x ||= 1; this.y &&= 2; foo().z ??= 3;",
+        "
+obj.x || (obj.x = 1);
+this.y && (this.y = 2);
+var _ref;
+(_ref = foo()).z ?? (_ref.z = 3);
+"
+    );
+
+    test!(
+        syntax(),
+        |_| logical_assignments(),
+        computed,
+        "obj[key()] ||= 1; a.b[c] &&= 2; a['d'] ??= 3;",
+        "
+var _key;
+obj[_key = key()] || (obj[_key] = 1);
+var _b;
+(_b = a.b)[c] && (_b[c] = 2);
+a['d'] ?? (a['d'] = 3);
+"
+    );
+
+    test!(
+        syntax(),
+        |_| logical_assignments(),
+        nested_fn,
+        "function foo() { return a.b.x ||= () => c.d.y ??= 1; }",
+        "
+function foo() {
+    var _d, _b;
+    return (_b = a.b).x || (_b.x = () => (_d = c.d).y ?? (_d.y = 1));
+}
+"
+    );
+
+    test_exec!(
+        syntax(),
+        |_| chain!(
+            logical_assignments(),
+            crate::compat::es2020::nullish_coalescing()
+        ),
+        exec,
+        r#"
+let calls = 0;
+const obj = {
+    get x() { calls++; return this._x; },
+    set x(v) { calls++; this._x = v; },
+    _x: 0,
+};
+const get = () => { calls++; return obj; };
+
+get().x ||= 1;
+expect(obj._x).toBe(1);
+expect(calls).toBe(3);
+
+get().x ||= 2;
+expect(obj._x).toBe(1);
+expect(calls).toBe(5);
+
+get().x &&= 0;
+expect(obj._x).toBe(0);
+
+let a = null;
+a ??= 4;
+a ??= 5;
+expect(a).toBe(4);
+"#
+    );
+}
@@ -0,0 +1,85 @@
+use crate::pass::Pass;
+use swc_atoms::JsWord;
+use swc_common::Fold;
+use swc_ecma_ast::*;
+
+/// `@babel/plugin-proposal-numeric-separator`
+///
+/// Numeric literals are printed using their raw source text if possible, so
+/// this removes separators from it.
+///
+///# Example
+///## In
+///
+/// ```js
+/// 1_000_000;
+/// 0xAE_BE_CEn;
+/// ```
+///
+///## Out
+///
+/// ```js
+/// 1000000;
+/// 0xAEBECEn;
+/// ```
+pub fn numeric_separator() -> impl Pass {
+    NumericSeparator
+}
+
+struct NumericSeparator;
+
+noop_fold_type!(NumericSeparator);
+
+impl Fold<Number> for NumericSeparator {
+    fn fold(&mut self, n: Number) -> Number {
+        Number {
+            raw: n.raw.map(strip_separators),
+            ..n
+        }
+    }
+}
+
+impl Fold<BigInt> for NumericSeparator {
+    fn fold(&mut self, n: BigInt) -> BigInt {
+        BigInt {
+            raw: n.raw.map(strip_separators),
+            ..n
+        }
+    }
+}
+
+fn strip_separators(raw: JsWord) -> JsWord {
+    if raw.contains('_') {
+        raw.replace('_', "").into()
+    } else {
+        raw
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swc_common::DUMMY_SP;
+
+    #[test]
+    fn number() {
+        let n = NumericSeparator.fold(Number {
+            span: DUMMY_SP,
+            value: 1000000.0,
+            raw: Some("1_000_000".into()),
+        });
+
+        assert_eq!(n.raw, Some("1000000".into()));
+    }
+
+    #[test]
+    fn bigint() {
+        let n = NumericSeparator.fold(BigInt {
+            span: DUMMY_SP,
+            value: 0xAEBECE.into(),
+            raw: Some("0xAE_BE_CE".into()),
+        });
+
+        assert_eq!(n.raw, Some("0xAEBECE".into()));
+    }
+}
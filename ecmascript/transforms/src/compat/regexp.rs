@@ -0,0 +1,671 @@
+//! Rewrites regular expression literals which cannot be used with old engines.
+//!
+//! A pattern is parsed, rewritten and then printed again, so characters of a
+//! rewritten pattern may be escaped differently from the source.
+
+use self::{
+    charset::CharSet,
+    parser::{parse, Alt, Class, ClassItem, Group, Property, Term},
+};
+use crate::{pass::Pass, util::ExprFactory};
+use std::fmt::Write;
+use swc_common::{Fold, FoldWith, DUMMY_SP};
+use swc_ecma_ast::*;
+
+mod charset;
+mod parser;
+
+/// Compiles the `s` flag.
+///
+///# Example
+///## In
+///
+/// ```js
+/// /a.c/s;
+/// ```
+///
+///## Out
+///
+/// ```js
+/// /a[\s\S]c/;
+/// ```
+pub fn dot_all_regex() -> impl Pass {
+    RegExp {
+        dot_all: true,
+        ..Default::default()
+    }
+}
+
+/// Compiles named capturing groups.
+///
+///# Example
+///## In
+///
+/// ```js
+/// /(?<year>\d{4})-\k<year>/;
+/// ```
+///
+///## Out
+///
+/// ```js
+/// _wrapRegExp(/(\d{4})-\1/, { year: 1 });
+/// ```
+pub fn named_capturing_groups_regex() -> impl Pass {
+    RegExp {
+        named_groups: true,
+        ..Default::default()
+    }
+}
+
+/// Compiles unicode property escapes (`\p{...}`) to character classes.
+///
+/// The `u` flag is preserved.
+pub fn unicode_property_regex() -> impl Pass {
+    RegExp {
+        unicode_property: true,
+        ..Default::default()
+    }
+}
+
+/// Compiles the `u` flag.
+///
+/// Astral code points are matched using surrogate pairs.
+///
+///# Example
+///## In
+///
+/// ```js
+/// /\u{1F600}+/u;
+/// ```
+///
+///## Out
+///
+/// ```js
+/// /(?:\uD83D\uDE00)+/;
+/// ```
+pub fn unicode_regex() -> impl Pass {
+    RegExp {
+        unicode: true,
+        ..Default::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct RegExp {
+    dot_all: bool,
+    named_groups: bool,
+    unicode_property: bool,
+    unicode: bool,
+}
+
+noop_fold_type!(RegExp);
+
+impl Fold<Expr> for RegExp {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Lit(Lit::Regex(regex)) => match self.rewrite(&regex) {
+                Some(e) => e,
+                None => Expr::Lit(Lit::Regex(regex)),
+            },
+            _ => e,
+        }
+    }
+}
+
+impl RegExp {
+    /// Returns `None` if `regex` does not need to be changed.
+    fn rewrite(&self, regex: &Regex) -> Option<Expr> {
+        let unicode = regex.flags.contains('u');
+        let dot_all = self.dot_all && regex.flags.contains('s');
+        let named_groups = self.named_groups && regex.exp.contains("(?<");
+        let lower_unicode = self.unicode && unicode;
+        let properties = self.unicode_property
+            && unicode
+            && (regex.exp.contains("\\p") || regex.exp.contains("\\P"));
+
+        if !dot_all && !named_groups && !lower_unicode && !properties {
+            return None;
+        }
+
+        let mut pattern = parse(&regex.exp, unicode)?;
+
+        if dot_all {
+            pattern.for_each_mut(&mut |term| {
+                if let Term::Dot = term {
+                    *term = Term::Class(Class {
+                        negated: false,
+                        items: vec![ClassItem::Escape('s'), ClassItem::Escape('S')],
+                    });
+                }
+            });
+        }
+
+        let groups = if named_groups {
+            remove_group_names(&mut pattern)
+        } else {
+            vec![]
+        };
+
+        if properties || lower_unicode {
+            let mut ok = true;
+            pattern.for_each_mut(&mut |term| match term {
+                Term::Property(p) => match CharSet::property(p) {
+                    Some(set) => {
+                        *term = Term::Class(Class {
+                            negated: false,
+                            items: set
+                                .ranges()
+                                .iter()
+                                .map(|&(lo, hi)| ClassItem::Range(lo, hi))
+                                .collect(),
+                        })
+                    }
+                    None => ok = false,
+                },
+                Term::Class(class) => ok &= resolve_properties(class).is_some(),
+                _ => {}
+            });
+            if !ok {
+                return None;
+            }
+        }
+
+        let mut w = Writer {
+            buf: String::with_capacity(regex.exp.len()),
+            lower_unicode,
+            unicode: unicode && !lower_unicode,
+        };
+        w.write_alt(&pattern);
+
+        let flags: String = regex
+            .flags
+            .chars()
+            .filter(|&c| !(dot_all && c == 's') && !(lower_unicode && c == 'u'))
+            .collect();
+
+        let lit = Expr::Lit(Lit::Regex(Regex {
+            span: regex.span,
+            exp: w.buf.into(),
+            flags: flags.into(),
+        }));
+
+        if groups.is_empty() {
+            return Some(lit);
+        }
+
+        Some(Expr::Call(CallExpr {
+            span: regex.span,
+            callee: helper!(wrap_reg_exp, "wrapRegExp"),
+            args: vec![
+                lit.as_arg(),
+                Expr::Object(ObjectLit {
+                    span: DUMMY_SP,
+                    props: groups
+                        .into_iter()
+                        .map(|(name, idx)| {
+                            PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                                key: PropName::Ident(quote_ident!(name)),
+                                value: box Expr::Lit(Lit::Num(Number {
+                                    span: DUMMY_SP,
+                                    value: idx as f64,
+                                    raw: None,
+                                })),
+                            }))
+                        })
+                        .collect(),
+                })
+                .as_arg(),
+            ],
+            type_args: Default::default(),
+        }))
+    }
+}
+
+/// Converts named groups to unnamed groups and returns the names with the
+/// indices of the groups.
+fn remove_group_names(pattern: &mut Alt) -> Vec<(String, usize)> {
+    let mut groups = vec![];
+    let mut idx = 0;
+    pattern.for_each_mut(&mut |term| {
+        if let Term::Group(Group::Capture(name), _) = term {
+            idx += 1;
+            if let Some(name) = name.take() {
+                groups.push((name, idx));
+            }
+        }
+    });
+
+    pattern.for_each_mut(&mut |term| {
+        if let Term::NamedBackref(name) = term {
+            if let Some(&(_, idx)) = groups.iter().find(|(n, _)| n == name) {
+                *term = Term::Raw(format!("\\{}", idx));
+            }
+        }
+    });
+
+    groups
+}
+
+fn resolve_properties(class: &mut Class) -> Option<()> {
+    let mut items = Vec::with_capacity(class.items.len());
+    for item in class.items.drain(..) {
+        match item {
+            ClassItem::Property(p) => items.extend(
+                CharSet::property(&p)?
+                    .ranges()
+                    .iter()
+                    .map(|&(lo, hi)| ClassItem::Range(lo, hi)),
+            ),
+            _ => items.push(item),
+        }
+    }
+    class.items = items;
+
+    Some(())
+}
+
+struct Writer {
+    buf: String,
+    /// If true, code points are matched using surrogate pairs.
+    lower_unicode: bool,
+    /// True if the `u` flag is preserved.
+    unicode: bool,
+}
+
+impl Writer {
+    fn write_alt(&mut self, alt: &Alt) {
+        for (i, terms) in alt.0.iter().enumerate() {
+            if i != 0 {
+                self.buf.push('|');
+            }
+
+            for (i, term) in terms.iter().enumerate() {
+                let quantified = match terms.get(i + 1) {
+                    Some(Term::Quantifier(..)) => true,
+                    _ => false,
+                };
+                self.write_term(term, quantified);
+            }
+        }
+    }
+
+    fn write_term(&mut self, term: &Term, quantified: bool) {
+        match *term {
+            Term::Raw(ref s) | Term::Quantifier(ref s) => self.buf.push_str(s),
+            Term::Char(c) if self.lower_unicode && c > 0xffff => {
+                let (hi, lo) = surrogates(c);
+                if quantified {
+                    self.buf.push_str("(?:");
+                }
+                let _ = write!(self.buf, "\\u{:04X}\\u{:04X}", hi, lo);
+                if quantified {
+                    self.buf.push(')');
+                }
+            }
+            Term::Char(c) => self.write_char(c, false),
+            Term::Dot if self.lower_unicode => self.write_set(&CharSet::dot()),
+            Term::Dot => self.buf.push('.'),
+            Term::Escape(c) if self.lower_unicode && c.is_ascii_uppercase() => {
+                self.write_set(&CharSet::escape(c))
+            }
+            Term::Escape(c) => {
+                self.buf.push('\\');
+                self.buf.push(c);
+            }
+            Term::Property(ref p) => self.write_property(p),
+            Term::Class(ref class) if self.lower_unicode => {
+                let set = class.items.iter().fold(CharSet::default(), |set, item| {
+                    set.union(&match *item {
+                        ClassItem::Char(c) => CharSet::from_ranges(vec![(c, c)]),
+                        ClassItem::Range(lo, hi) => CharSet::from_ranges(vec![(lo, hi)]),
+                        ClassItem::Escape(c) => CharSet::escape(c),
+                        ClassItem::Property(..) => {
+                            unreachable!("properties should be resolved before lowering")
+                        }
+                    })
+                });
+
+                if class.negated {
+                    self.write_set(&set.negate())
+                } else {
+                    self.write_set(&set)
+                }
+            }
+            Term::Class(ref class) => self.write_class(class),
+            Term::Group(ref group, ref body) => {
+                self.buf.push('(');
+                match *group {
+                    Group::Capture(Some(ref name)) => {
+                        let _ = write!(self.buf, "?<{}>", name);
+                    }
+                    Group::Capture(None) => {}
+                    Group::NonCapture => self.buf.push_str("?:"),
+                    Group::Look(s) => self.buf.push_str(s),
+                }
+                self.write_alt(body);
+                self.buf.push(')');
+            }
+            Term::NamedBackref(ref name) => {
+                let _ = write!(self.buf, "\\k<{}>", name);
+            }
+        }
+    }
+
+    fn write_class(&mut self, class: &Class) {
+        self.buf.push('[');
+        if class.negated {
+            self.buf.push('^');
+        }
+        for item in &class.items {
+            match *item {
+                ClassItem::Char(c) => self.write_char(c, true),
+                ClassItem::Range(lo, hi) => self.write_range(lo, hi),
+                ClassItem::Escape(c) => {
+                    self.buf.push('\\');
+                    self.buf.push(c);
+                }
+                ClassItem::Property(ref p) => self.write_property(p),
+            }
+        }
+        self.buf.push(']');
+    }
+
+    fn write_range(&mut self, lo: u32, hi: u32) {
+        self.write_char(lo, true);
+        if hi > lo + 1 {
+            self.buf.push('-');
+        }
+        if hi > lo {
+            self.write_char(hi, true);
+        }
+    }
+
+    fn write_property(&mut self, p: &Property) {
+        self.buf.push_str(if p.negated { "\\P{" } else { "\\p{" });
+        self.buf.push_str(&p.name);
+        if let Some(ref value) = p.value {
+            self.buf.push('=');
+            self.buf.push_str(value);
+        }
+        self.buf.push('}');
+    }
+
+    /// Writes a pattern which matches a code point in `set` without the `u`
+    /// flag.
+    ///
+    /// Surrogate pairs are tried before lone surrogates.
+    fn write_set(&mut self, set: &CharSet) {
+        let mut bmp = vec![];
+        let mut astral = vec![];
+        for &(lo, hi) in set.ranges() {
+            if lo <= 0xffff {
+                bmp.push((lo, hi.min(0xffff)));
+            }
+            if hi > 0xffff {
+                astral.push((lo.max(0x10000), hi));
+            }
+        }
+
+        if astral.is_empty() {
+            self.write_bmp_set(&bmp);
+            return;
+        }
+
+        self.buf.push_str("(?:");
+        for (i, &(lo, hi)) in astral.iter().enumerate() {
+            if i != 0 {
+                self.buf.push('|');
+            }
+            self.write_astral_range(lo, hi);
+        }
+        if !bmp.is_empty() {
+            self.buf.push('|');
+            self.write_bmp_set(&bmp);
+        }
+        self.buf.push(')');
+    }
+
+    fn write_bmp_set(&mut self, ranges: &[(u32, u32)]) {
+        if let &[(lo, hi)] = ranges {
+            if lo == hi {
+                self.write_char(lo, false);
+                return;
+            }
+        }
+
+        self.buf.push('[');
+        for &(lo, hi) in ranges {
+            self.write_range(lo, hi);
+        }
+        self.buf.push(']');
+    }
+
+    fn write_astral_range(&mut self, lo: u32, hi: u32) {
+        let (lo_hi, lo_lo) = surrogates(lo);
+        let (hi_hi, hi_lo) = surrogates(hi);
+
+        if lo_hi == hi_hi {
+            self.write_units(lo_hi, lo_hi);
+            self.write_units(lo_lo, hi_lo);
+            return;
+        }
+
+        let mut parts = vec![];
+        let mut start = lo_hi;
+        if lo_lo != 0xdc00 {
+            parts.push((lo_hi, lo_hi, lo_lo, 0xdfff));
+            start += 1;
+        }
+        let mut end = hi_hi;
+        let last = if hi_lo != 0xdfff {
+            end -= 1;
+            Some((hi_hi, hi_hi, 0xdc00, hi_lo))
+        } else {
+            None
+        };
+        if start <= end {
+            parts.push((start, end, 0xdc00, 0xdfff));
+        }
+        parts.extend(last);
+
+        for (i, (hi_start, hi_end, lo_start, lo_end)) in parts.into_iter().enumerate() {
+            if i != 0 {
+                self.buf.push('|');
+            }
+            self.write_units(hi_start, hi_end);
+            self.write_units(lo_start, lo_end);
+        }
+    }
+
+    fn write_units(&mut self, lo: u32, hi: u32) {
+        if lo == hi {
+            let _ = write!(self.buf, "\\u{:04X}", lo);
+        } else {
+            let _ = write!(self.buf, "[\\u{:04X}-\\u{:04X}]", lo, hi);
+        }
+    }
+
+    fn write_char(&mut self, c: u32, in_class: bool) {
+        match c {
+            0x9 => self.buf.push_str("\\t"),
+            0xa => self.buf.push_str("\\n"),
+            0xb => self.buf.push_str("\\v"),
+            0xc => self.buf.push_str("\\f"),
+            0xd => self.buf.push_str("\\r"),
+            0..=0x1f | 0x7f => {
+                let _ = write!(self.buf, "\\x{:02X}", c);
+            }
+            0x20..=0x7e => {
+                let c = c as u8 as char;
+                let escape = match c {
+                    '\\' | '/' | ']' | '[' | '^' => true,
+                    '-' => in_class,
+                    '$' | '.' | '*' | '+' | '?' | '(' | ')' | '{' | '}' | '|' => !in_class,
+                    _ => false,
+                };
+                if escape {
+                    self.buf.push('\\');
+                }
+                self.buf.push(c);
+            }
+            0x80..=0xffff => {
+                let _ = write!(self.buf, "\\u{:04X}", c);
+            }
+            _ if self.unicode => {
+                let _ = write!(self.buf, "\\u{{{:X}}}", c);
+            }
+            _ => {
+                // An astral character in a pattern without the `u` flag.
+                self.buf.extend(std::char::from_u32(c));
+            }
+        }
+    }
+}
+
+/// Splits an astral code point into a surrogate pair.
+fn surrogates(c: u32) -> (u32, u32) {
+    let c = c - 0x10000;
+    (0xd800 + (c >> 10), 0xdc00 + (c & 0x3ff))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swc_common::chain;
+
+    fn syntax() -> ::swc_ecma_parser::Syntax {
+        Default::default()
+    }
+
+    test!(
+        syntax(),
+        |_| dot_all_regex(),
+        dot_all,
+        r#"var re = /a.c/s; var re2 = /a.c/;"#,
+        r#"var re = /a[\s\S]c/; var re2 = /a.c/;"#
+    );
+
+    test!(
+        syntax(),
+        |_| dot_all_regex(),
+        dot_all_in_class,
+        r#"var re = /[.]./gsi;"#,
+        r#"var re = /[.][\s\S]/gi;"#
+    );
+
+    test!(
+        syntax(),
+        |_| named_capturing_groups_regex(),
+        named_groups,
+        r#"var re = /(?<year>\d{4})-(?<month>\d{2})-\k<year>/;"#,
+        r#"var re = _wrapRegExp(/(\d{4})-(\d{2})-\1/, { year: 1, month: 2 });"#
+    );
+
+    test!(
+        syntax(),
+        |_| named_capturing_groups_regex(),
+        named_groups_nested,
+        r#"var re = /(a)(?:(?<b>b(c))(?<d>d))(?<=e)/g;"#,
+        r#"var re = _wrapRegExp(/(a)(?:(b(c))(d))(?<=e)/g, { b: 2, d: 4 });"#
+    );
+
+    test!(
+        syntax(),
+        |_| named_capturing_groups_regex(),
+        named_groups_lookbehind_only,
+        r#"var re = /(?<=a)b(?<!c)/;"#,
+        r#"var re = /(?<=a)b(?<!c)/;"#
+    );
+
+    test!(
+        syntax(),
+        |_| unicode_property_regex(),
+        unicode_property,
+        r#"var re = /\p{ASCII_Hex_Digit}+\P{ASCII}/u;"#,
+        r#"var re = /[0-9A-Fa-f]+[\u0080-\u{10FFFF}]/u;"#
+    );
+
+    test!(
+        syntax(),
+        |_| unicode_property_regex(),
+        unicode_property_in_class,
+        r#"var re = /[\p{ASCII_Hex_Digit}_]/u;"#,
+        r#"var re = /[0-9A-Fa-f_]/u;"#
+    );
+
+    test!(
+        syntax(),
+        |_| unicode_property_regex(),
+        unicode_property_unknown,
+        r#"var re = /\p{Foo}/u;"#,
+        r#"var re = /\p{Foo}/u;"#
+    );
+
+    test!(
+        syntax(),
+        |_| unicode_regex(),
+        unicode_astral,
+        r#"var re = /\u{1F600}+a/u;"#,
+        r#"var re = /(?:\uD83D\uDE00)+a/;"#
+    );
+
+    test!(
+        syntax(),
+        |_| unicode_regex(),
+        unicode_class,
+        r#"var re = /[a\u{1F600}-\u{1F64F}]/u;"#,
+        r#"var re = /(?:\uD83D[\uDE00-\uDE4F]|a)/;"#
+    );
+
+    test!(
+        syntax(),
+        |_| unicode_regex(),
+        unicode_class_across_surrogates,
+        r#"var re = /[\u{10000}-\u{10500}]/u;"#,
+        r#"var re = /(?:\uD800[\uDC00-\uDFFF]|\uD801[\uDC00-\uDD00])/;"#
+    );
+
+    test!(
+        syntax(),
+        |_| unicode_regex(),
+        unicode_dot,
+        r#"var re = /^.$/u;"#,
+        r#"var re = /^(?:[\uD800-\uDBFF][\uDC00-\uDFFF]|[\x00-\t\v\f\x0E-\u2027\u202A-\uFFFF])$/;"#
+    );
+
+    test!(
+        syntax(),
+        |_| unicode_regex(),
+        unicode_non_unicode,
+        r#"var re = /^.$/;"#,
+        r#"var re = /^.$/;"#
+    );
+
+    test_exec!(
+        syntax(),
+        |_| named_capturing_groups_regex(),
+        named_groups_exec,
+        r#"
+var re = /(?<year>\d{4})-(?<month>\d{2})/;
+var result = re.exec("2020-04");
+expect(result.groups.year).toBe("2020");
+expect(result.groups.month).toBe("04");
+expect("2020-04".replace(re, "$<month>/$<year>")).toBe("04/2020");
+"#
+    );
+
+    test_exec!(
+        syntax(),
+        |_| chain!(unicode_property_regex(), unicode_regex()),
+        unicode_exec,
+        r#"
+var re = /^\p{Script=Gothic}.$/u;
+expect(re.test("𐌰𐌱")).toBe(true);
+expect(re.test("𐌰a")).toBe(true);
+expect(re.test("a𐌰")).toBe(false);
+expect(/^[^a]$/u.test("😀")).toBe(true);
+"#
+    );
+}
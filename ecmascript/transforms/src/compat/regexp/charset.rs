@@ -0,0 +1,170 @@
+use super::parser::Property;
+use regex_syntax::{
+    hir::{self, HirKind},
+    ParserBuilder,
+};
+
+pub(super) const MAX_CODE_POINT: u32 = 0x10ffff;
+
+/// A set of code points.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(super) struct CharSet {
+    /// Sorted, non-overlapping and non-adjacent inclusive ranges.
+    ranges: Vec<(u32, u32)>,
+}
+
+impl CharSet {
+    pub fn from_ranges(mut ranges: Vec<(u32, u32)>) -> Self {
+        ranges.sort_unstable();
+
+        let mut merged: Vec<(u32, u32)> = Vec::with_capacity(ranges.len());
+        for (lo, hi) in ranges {
+            match merged.last_mut() {
+                Some(last) if lo <= last.1.saturating_add(1) => last.1 = last.1.max(hi),
+                _ => merged.push((lo, hi)),
+            }
+        }
+
+        CharSet { ranges: merged }
+    }
+
+    pub fn ranges(&self) -> &[(u32, u32)] {
+        &self.ranges
+    }
+
+    pub fn union(&self, other: &CharSet) -> CharSet {
+        CharSet::from_ranges(
+            self.ranges
+                .iter()
+                .chain(other.ranges.iter())
+                .copied()
+                .collect(),
+        )
+    }
+
+    pub fn negate(&self) -> CharSet {
+        let mut ranges = vec![];
+        let mut next = 0;
+        for &(lo, hi) in &self.ranges {
+            if lo > next {
+                ranges.push((next, lo - 1));
+            }
+            next = hi + 1;
+        }
+        if next <= MAX_CODE_POINT {
+            ranges.push((next, MAX_CODE_POINT));
+        }
+
+        CharSet { ranges }
+    }
+
+    /// Code points matched by `\d`, `\s`, `\w` or their negations.
+    pub fn escape(c: char) -> CharSet {
+        let ranges = match c.to_ascii_lowercase() {
+            'd' => vec![(0x30, 0x39)],
+            'w' => vec![(0x30, 0x39), (0x41, 0x5a), (0x5f, 0x5f), (0x61, 0x7a)],
+            's' => vec![
+                (0x9, 0xd),
+                (0x20, 0x20),
+                (0xa0, 0xa0),
+                (0x1680, 0x1680),
+                (0x2000, 0x200a),
+                (0x2028, 0x2029),
+                (0x202f, 0x202f),
+                (0x205f, 0x205f),
+                (0x3000, 0x3000),
+                (0xfeff, 0xfeff),
+            ],
+            _ => unreachable!("invalid character class escape: \\{}", c),
+        };
+        let set = CharSet { ranges };
+
+        if c.is_ascii_uppercase() {
+            set.negate()
+        } else {
+            set
+        }
+    }
+
+    /// Code points matched by `.` without the `s` flag.
+    pub fn dot() -> CharSet {
+        CharSet::from_ranges(vec![(0xa, 0xa), (0xd, 0xd), (0x2028, 0x2029)]).negate()
+    }
+
+    /// Returns `None` if the property is unknown.
+    pub fn property(p: &Property) -> Option<CharSet> {
+        let query = match p.value {
+            Some(ref value) => format!("\\p{{{}={}}}", p.name, value),
+            None => format!("\\p{{{}}}", p.name),
+        };
+
+        let hir = ParserBuilder::new().build().parse(&query).ok()?;
+        let set = match hir.into_kind() {
+            HirKind::Class(hir::Class::Unicode(class)) => CharSet::from_ranges(
+                class
+                    .iter()
+                    .map(|r| (r.start() as u32, r.end() as u32))
+                    .collect(),
+            ),
+            _ => return None,
+        };
+
+        Some(if p.negated { set.negate() } else { set })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge() {
+        assert_eq!(
+            CharSet::from_ranges(vec![(5, 8), (0, 2), (3, 3), (7, 10)]).ranges(),
+            &[(0, 3), (5, 10)]
+        );
+    }
+
+    #[test]
+    fn negate() {
+        assert_eq!(
+            CharSet::from_ranges(vec![(0, 9), (0x20, 0x20)])
+                .negate()
+                .ranges(),
+            &[(10, 0x1f), (0x21, MAX_CODE_POINT)]
+        );
+        assert_eq!(
+            CharSet::from_ranges(vec![(0, MAX_CODE_POINT)])
+                .negate()
+                .ranges(),
+            &[]
+        );
+    }
+
+    #[test]
+    fn property() {
+        let greek = CharSet::property(&Property {
+            negated: false,
+            name: "Script".into(),
+            value: Some("Greek".into()),
+        })
+        .unwrap();
+        assert!(greek
+            .ranges()
+            .iter()
+            .any(|&(lo, hi)| lo <= 0x3b1 && 0x3c9 <= hi));
+        assert!(!greek
+            .ranges()
+            .iter()
+            .any(|&(lo, hi)| lo <= 0x61 && 0x61 <= hi));
+
+        assert_eq!(
+            CharSet::property(&Property {
+                negated: false,
+                name: "NotAProperty".into(),
+                value: None,
+            }),
+            None
+        );
+    }
+}
@@ -0,0 +1,562 @@
+//! Parser for patterns of regular expression literals.
+//!
+//! Only the structure required to rewrite a pattern is preserved. Parts which
+//! are printed verbatim (assertions, quantifiers, backreferences) are stored as
+//! strings.
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Alt(pub Vec<Vec<Term>>);
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Term {
+    /// `^`, `$`, `\b`, `\B` and backreferences.
+    Raw(String),
+    /// `*`, `+?`, `{1,2}`, etc.
+    Quantifier(String),
+    /// A code point.
+    Char(u32),
+    Dot,
+    /// `\d`, `\D`, `\s`, `\S`, `\w` or `\W`.
+    Escape(char),
+    Property(Property),
+    Class(Class),
+    Group(Group, Alt),
+    /// `\k<name>`
+    NamedBackref(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Group {
+    Capture(Option<String>),
+    NonCapture,
+    /// `?=`, `?!`, `?<=` or `?<!`.
+    Look(&'static str),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Class {
+    pub negated: bool,
+    pub items: Vec<ClassItem>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum ClassItem {
+    Char(u32),
+    Range(u32, u32),
+    Escape(char),
+    Property(Property),
+}
+
+/// `\p{name}`, `\p{name=value}` or `\P{...}`.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Property {
+    pub negated: bool,
+    pub name: String,
+    pub value: Option<String>,
+}
+
+impl Alt {
+    /// Calls `op` for each term, including terms nested in groups, in source
+    /// order.
+    pub fn for_each_mut<F>(&mut self, op: &mut F)
+    where
+        F: FnMut(&mut Term),
+    {
+        for terms in &mut self.0 {
+            for term in terms {
+                op(term);
+
+                if let Term::Group(_, body) = term {
+                    body.for_each_mut(op);
+                }
+            }
+        }
+    }
+}
+
+/// Returns `None` if `pattern` is not a valid pattern.
+pub(super) fn parse(pattern: &str, unicode: bool) -> Option<Alt> {
+    let mut p = Parser {
+        chars: pattern.chars().collect(),
+        pos: 0,
+        unicode,
+    };
+
+    let alt = p.parse_alt()?;
+    if p.pos != p.chars.len() {
+        return None;
+    }
+
+    Some(alt)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    /// True if the `u` flag is present.
+    unicode: bool,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, n: usize) -> Option<char> {
+        self.chars.get(self.pos + n).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_str(&mut self, s: &str) -> bool {
+        let len = s.chars().count();
+        if self.pos + len <= self.chars.len()
+            && self.chars[self.pos..]
+                .iter()
+                .copied()
+                .take(len)
+                .eq(s.chars())
+        {
+            self.pos += len;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_digits(&mut self) -> bool {
+        let start = self.pos;
+        while self.peek().map_or(false, |c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        self.pos != start
+    }
+
+    fn slice(&self, start: usize) -> String {
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    /// Reads exactly `n` hex digits. Nothing is consumed on failure.
+    fn hex(&mut self, n: usize) -> Option<u32> {
+        let digits = self.chars.get(self.pos..self.pos + n)?;
+        let mut value = 0;
+        for c in digits {
+            value = value * 16 + c.to_digit(16)?;
+        }
+        self.pos += n;
+        Some(value)
+    }
+
+    fn parse_alt(&mut self) -> Option<Alt> {
+        let mut alts = vec![self.parse_terms()?];
+        while self.eat('|') {
+            alts.push(self.parse_terms()?);
+        }
+
+        Some(Alt(alts))
+    }
+
+    fn parse_terms(&mut self) -> Option<Vec<Term>> {
+        let mut terms = vec![];
+
+        while let Some(c) = self.peek() {
+            let term = match c {
+                '|' | ')' => break,
+                '^' | '$' => {
+                    self.pos += 1;
+                    Term::Raw(c.to_string())
+                }
+                '.' => {
+                    self.pos += 1;
+                    Term::Dot
+                }
+                '[' => Term::Class(self.parse_class()?),
+                '(' => self.parse_group()?,
+                '\\' => self.parse_atom_escape()?,
+                '*' | '+' | '?' => return None,
+                '{' | '}' | ']' if self.unicode => return None,
+                _ => {
+                    self.pos += 1;
+                    Term::Char(c as u32)
+                }
+            };
+            terms.push(term);
+
+            if let Some(q) = self.parse_quantifier() {
+                terms.push(Term::Quantifier(q));
+            }
+        }
+
+        Some(terms)
+    }
+
+    fn parse_quantifier(&mut self) -> Option<String> {
+        let start = self.pos;
+        match self.peek()? {
+            '*' | '+' | '?' => self.pos += 1,
+            '{' => {
+                self.pos += 1;
+                let valid = self.eat_digits() && {
+                    if self.eat(',') {
+                        self.eat_digits();
+                    }
+                    self.eat('}')
+                };
+                if !valid {
+                    // A literal `{` in annex B.
+                    self.pos = start;
+                    return None;
+                }
+            }
+            _ => return None,
+        }
+        self.eat('?');
+
+        Some(self.slice(start))
+    }
+
+    fn parse_group(&mut self) -> Option<Term> {
+        self.pos += 1;
+
+        let group = if self.eat_str("?:") {
+            Group::NonCapture
+        } else if self.eat_str("?=") {
+            Group::Look("?=")
+        } else if self.eat_str("?!") {
+            Group::Look("?!")
+        } else if self.eat_str("?<=") {
+            Group::Look("?<=")
+        } else if self.eat_str("?<!") {
+            Group::Look("?<!")
+        } else if self.eat_str("?<") {
+            Group::Capture(Some(self.parse_group_name()?))
+        } else {
+            Group::Capture(None)
+        };
+
+        let body = self.parse_alt()?;
+        if !self.eat(')') {
+            return None;
+        }
+
+        Some(Term::Group(group, body))
+    }
+
+    /// Parses `name>`.
+    fn parse_group_name(&mut self) -> Option<String> {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            match c {
+                '>' => break,
+                '$' | '_' | '\\' | '{' | '}' => {}
+                _ if c.is_alphanumeric() => {}
+                _ => return None,
+            }
+            self.pos += 1;
+        }
+
+        if self.pos == start {
+            return None;
+        }
+        let name = self.slice(start);
+        if !self.eat('>') {
+            return None;
+        }
+
+        Some(name)
+    }
+
+    fn parse_atom_escape(&mut self) -> Option<Term> {
+        self.pos += 1;
+
+        let c = self.peek()?;
+        match c {
+            'b' | 'B' => {
+                self.pos += 1;
+                Some(Term::Raw(format!("\\{}", c)))
+            }
+            '1'..='9' => {
+                let start = self.pos;
+                self.eat_digits();
+                Some(Term::Raw(format!("\\{}", self.slice(start))))
+            }
+            'k' if self.peek_at(1) == Some('<') => {
+                self.pos += 2;
+                Some(Term::NamedBackref(self.parse_group_name()?))
+            }
+            'd' | 'D' | 's' | 'S' | 'w' | 'W' => {
+                self.pos += 1;
+                Some(Term::Escape(c))
+            }
+            'p' | 'P' if self.unicode => Some(Term::Property(self.parse_property()?)),
+            _ => Some(Term::Char(self.parse_char_escape(false)?)),
+        }
+    }
+
+    /// Parses `p{...}` or `P{...}`.
+    fn parse_property(&mut self) -> Option<Property> {
+        let negated = self.peek()? == 'P';
+        self.pos += 1;
+        if !self.eat('{') {
+            return None;
+        }
+
+        let start = self.pos;
+        while self.peek()? != '}' {
+            self.pos += 1;
+        }
+        let body = self.slice(start);
+        self.pos += 1;
+
+        let (name, value) = match body.find('=') {
+            Some(idx) => (body[..idx].to_string(), Some(body[idx + 1..].to_string())),
+            None => (body, None),
+        };
+
+        Some(Property {
+            negated,
+            name,
+            value,
+        })
+    }
+
+    /// Parses the part of a character escape after `\`.
+    fn parse_char_escape(&mut self, in_class: bool) -> Option<u32> {
+        let c = self.peek()?;
+        self.pos += 1;
+
+        Some(match c {
+            'f' => 0xc,
+            'n' => 0xa,
+            'r' => 0xd,
+            't' => 0x9,
+            'v' => 0xb,
+            'b' if in_class => 0x8,
+            'c' => match self.peek() {
+                Some(l) if l.is_ascii_alphabetic() => {
+                    self.pos += 1;
+                    l as u32 % 32
+                }
+                _ if self.unicode => return None,
+                _ => {
+                    // `\c` is `\` followed by `c` in annex B.
+                    self.pos -= 1;
+                    '\\' as u32
+                }
+            },
+            '0' if !self.peek().map_or(false, |c| c.is_ascii_digit()) => 0,
+            '0'..='7' if !self.unicode => {
+                // Legacy octal escape.
+                let mut value = c.to_digit(8).unwrap();
+                for _ in 0..2 {
+                    match self.peek().and_then(|c| c.to_digit(8)) {
+                        Some(d) if value * 8 + d <= 0o377 => {
+                            value = value * 8 + d;
+                            self.pos += 1;
+                        }
+                        _ => break,
+                    }
+                }
+                value
+            }
+            'x' => match self.hex(2) {
+                Some(v) => v,
+                None if self.unicode => return None,
+                None => 'x' as u32,
+            },
+            'u' => self.parse_unicode_escape()?,
+            _ if self.unicode => match c {
+                '^' | '$' | '\\' | '.' | '*' | '+' | '?' | '(' | ')' | '[' | ']' | '{' | '}'
+                | '|' | '/' => c as u32,
+                '-' if in_class => c as u32,
+                _ => return None,
+            },
+            _ => c as u32,
+        })
+    }
+
+    /// Parses the part of an unicode escape after `\u`.
+    fn parse_unicode_escape(&mut self) -> Option<u32> {
+        if self.unicode && self.eat('{') {
+            let start = self.pos;
+            while self.peek().map_or(false, |c| c.is_ascii_hexdigit()) {
+                self.pos += 1;
+            }
+            let value = u32::from_str_radix(&self.slice(start), 16).ok()?;
+            if !self.eat('}') || value > 0x10ffff {
+                return None;
+            }
+            return Some(value);
+        }
+
+        match self.hex(4) {
+            Some(hi) => {
+                if self.unicode && (0xd800..0xdc00).contains(&hi) {
+                    let start = self.pos;
+                    if self.eat_str("\\u") {
+                        match self.hex(4) {
+                            Some(lo) if (0xdc00..0xe000).contains(&lo) => {
+                                return Some(0x10000 + ((hi - 0xd800) << 10) + (lo - 0xdc00));
+                            }
+                            _ => self.pos = start,
+                        }
+                    }
+                }
+
+                Some(hi)
+            }
+            None if self.unicode => None,
+            None => Some('u' as u32),
+        }
+    }
+
+    fn parse_class(&mut self) -> Option<Class> {
+        self.pos += 1;
+
+        let negated = self.eat('^');
+        let mut items = vec![];
+        loop {
+            if self.eat(']') {
+                break;
+            }
+            let item = self.parse_class_atom()?;
+
+            if self.peek() == Some('-') && self.peek_at(1).map_or(false, |c| c != ']') {
+                self.pos += 1;
+                let end = self.parse_class_atom()?;
+
+                match (item, end) {
+                    (ClassItem::Char(lo), ClassItem::Char(hi)) => {
+                        if lo > hi {
+                            return None;
+                        }
+                        items.push(ClassItem::Range(lo, hi));
+                    }
+                    _ if self.unicode => return None,
+                    // `[\d-z]` is `\d`, `-` and `z` in annex B.
+                    (item, end) => {
+                        items.push(item);
+                        items.push(ClassItem::Char('-' as u32));
+                        items.push(end);
+                    }
+                }
+                continue;
+            }
+
+            items.push(item);
+        }
+
+        Some(Class { negated, items })
+    }
+
+    fn parse_class_atom(&mut self) -> Option<ClassItem> {
+        let c = self.peek()?;
+        self.pos += 1;
+        if c != '\\' {
+            return Some(ClassItem::Char(c as u32));
+        }
+
+        let c = self.peek()?;
+        match c {
+            'd' | 'D' | 's' | 'S' | 'w' | 'W' => {
+                self.pos += 1;
+                Some(ClassItem::Escape(c))
+            }
+            'p' | 'P' if self.unicode => Some(ClassItem::Property(self.parse_property()?)),
+            _ => Some(ClassItem::Char(self.parse_char_escape(true)?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups() {
+        let alt = parse(r"(?<year>\d{4})-\k<year>|(?:a)+?", false).unwrap();
+
+        assert_eq!(
+            alt,
+            Alt(vec![
+                vec![
+                    Term::Group(
+                        Group::Capture(Some("year".into())),
+                        Alt(vec![vec![
+                            Term::Escape('d'),
+                            Term::Quantifier("{4}".into())
+                        ]])
+                    ),
+                    Term::Char('-' as u32),
+                    Term::NamedBackref("year".into()),
+                ],
+                vec![
+                    Term::Group(Group::NonCapture, Alt(vec![vec![Term::Char('a' as u32)]])),
+                    Term::Quantifier("+?".into()),
+                ],
+            ])
+        );
+    }
+
+    #[test]
+    fn annex_b() {
+        assert_eq!(
+            parse(r"a{,\c[\d-z]", false),
+            Some(Alt(vec![vec![
+                Term::Char('a' as u32),
+                Term::Char('{' as u32),
+                Term::Char(',' as u32),
+                Term::Char('\\' as u32),
+                Term::Char('c' as u32),
+                Term::Class(Class {
+                    negated: false,
+                    items: vec![
+                        ClassItem::Escape('d'),
+                        ClassItem::Char('-' as u32),
+                        ClassItem::Char('z' as u32),
+                    ]
+                }),
+            ]]))
+        );
+        assert_eq!(parse(r"a{,", true), None);
+    }
+
+    #[test]
+    fn unicode_escapes() {
+        assert_eq!(
+            parse(r"\u{1F600}😀[\p{Script=Greek}]", true),
+            Some(Alt(vec![vec![
+                Term::Char(0x1f600),
+                Term::Char(0x1f600),
+                Term::Class(Class {
+                    negated: false,
+                    items: vec![ClassItem::Property(Property {
+                        negated: false,
+                        name: "Script".into(),
+                        value: Some("Greek".into()),
+                    })]
+                }),
+            ]]))
+        );
+        assert_eq!(
+            parse(r"\u{1F600}", false),
+            Some(Alt(vec![vec![
+                Term::Char('u' as u32),
+                Term::Char('{' as u32),
+                Term::Char('1' as u32),
+                Term::Char('F' as u32),
+                Term::Char('6' as u32),
+                Term::Char('0' as u32),
+                Term::Char('0' as u32),
+                Term::Char('}' as u32),
+            ]]))
+        );
+    }
+}
@@ -0,0 +1,10 @@
+use crate::{pass::Pass, typescript};
+
+/// Strips flow type annotations out.
+///
+/// Flow is parsed into the typescript ast, so this is the same pass as
+/// [typescript::strip]. Imports used only as types are removed, as are
+/// `import type` and `import typeof` declarations.
+pub fn strip() -> impl Pass {
+    typescript::strip()
+}
@@ -162,6 +162,7 @@ define_helpers!(Helpers {
     class_private_method_set: (),
     class_static_private_field_spec_get: (),
     class_static_private_field_spec_set: (),
+    class_static_private_method_get: (),
     construct: (set_prototype_of),
     create_class: (),
     decorate: (to_array, to_property_key),
@@ -218,6 +219,7 @@ define_helpers!(Helpers {
         set_prototype_of,
         is_native_function
     ),
+    wrap_reg_exp: (wrap_native_super, inherits),
 
     class_private_field_destructure: (),
 });
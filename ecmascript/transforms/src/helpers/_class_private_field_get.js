@@ -1,7 +1,13 @@
 function _classPrivateFieldGet(receiver, privateMap) {
-  if (!privateMap.has(receiver)) {
+  var descriptor = privateMap.get(receiver);
+
+  if (!descriptor) {
     throw new TypeError("attempted to get private field on non-instance");
   }
 
-  return privateMap.get(receiver).value;
+  if (descriptor.get) {
+    return descriptor.get.call(receiver);
+  }
+
+  return descriptor.value;
 }
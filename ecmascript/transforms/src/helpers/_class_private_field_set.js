@@ -1,14 +1,19 @@
 function _classPrivateFieldSet(receiver, privateMap, value) {
-  if (!privateMap.has(receiver)) {
+  var descriptor = privateMap.get(receiver);
+
+  if (!descriptor) {
     throw new TypeError("attempted to set private field on non-instance");
   }
 
-  var descriptor = privateMap.get(receiver);
+  if (descriptor.set) {
+    descriptor.set.call(receiver, value);
+  } else {
+    if (!descriptor.writable) {
+      throw new TypeError("attempted to set read only private field");
+    }
 
-  if (!descriptor.writable) {
-    throw new TypeError("attempted to set read only private field");
+    descriptor.value = value;
   }
 
-  descriptor.value = value;
   return value;
 }
@@ -3,5 +3,9 @@ function _classStaticPrivateFieldSpecGet(receiver, classConstructor, descriptor)
     throw new TypeError("Private static access of wrong provenance");
   }
 
+  if (descriptor.get) {
+    return descriptor.get.call(receiver);
+  }
+
   return descriptor.value;
 }
@@ -10,7 +10,7 @@ use swc_atoms::JsWord;
 use swc_common::{chain, Fold, FoldWith, Span, SyntaxContext};
 use swc_ecma_ast::*;
 
-mod ops;
+pub(crate) mod ops;
 #[cfg(test)]
 mod tests;
 
@@ -162,7 +162,13 @@ impl<'a> Hygiene<'a> {
         if ops.is_empty() {
             return node;
         }
-        node.fold_with(&mut Operator(&ops))
+        let renames = ops
+            .iter()
+            .map(|op| match *op {
+                ScopeOp::Rename { ref from, ref to } => (from.clone(), to.clone()),
+            })
+            .collect();
+        node.fold_with(&mut Operator(&renames))
     }
 }
 
@@ -1,6 +1,8 @@
+use std::collections::HashMap;
 use swc_atoms::JsWord;
 use swc_common::{util::move_map::MoveMap, Fold, FoldWith, Spanned, SyntaxContext, DUMMY_SP};
 use swc_ecma_ast::*;
+use swc_ecma_utils::Id;
 
 #[derive(Debug)]
 pub(super) enum ScopeOp {
@@ -10,7 +12,12 @@ pub(super) enum ScopeOp {
     },
 }
 
-pub(super) struct Operator<'a>(pub &'a [ScopeOp]);
+/// Applies renames to a node.
+///
+/// Keys and values of properties, import / export specifiers and exported
+/// declarations are handled so that the public interface of a module is
+/// preserved.
+pub(crate) struct Operator<'a>(pub &'a HashMap<Id, JsWord>);
 
 noop_fold_type!(Operator<'_>);
 
@@ -253,22 +260,15 @@ impl<'a> Fold<KeyValueProp> for Operator<'a> {
 impl<'a> Operator<'a> {
     /// Returns `Ok(renamed_ident)` if ident should be renamed.
     fn rename_ident(&mut self, ident: Ident) -> Result<Ident, Ident> {
-        for op in self.0 {
-            match *op {
-                ScopeOp::Rename { ref from, ref to }
-                    if *from.0 == ident.sym && from.1 == ident.span.ctxt() =>
-                {
-                    return Ok(Ident {
-                        // Clear mark
-                        span: ident.span.with_ctxt(SyntaxContext::empty()),
-                        sym: to.clone(),
-                        ..ident
-                    });
-                }
-                _ => {}
-            }
+        match self.0.get(&(ident.sym.clone(), ident.span.ctxt())) {
+            Some(to) => Ok(Ident {
+                // Clear mark
+                span: ident.span.with_ctxt(SyntaxContext::empty()),
+                sym: to.clone(),
+                ..ident
+            }),
+            None => Err(ident),
         }
-        Err(ident)
     }
 }
 
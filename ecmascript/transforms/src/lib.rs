@@ -33,6 +33,8 @@ pub mod compat;
 mod const_modules;
 pub mod debug;
 mod fixer;
+pub mod flow;
+pub mod minify;
 pub mod modules;
 pub mod optimization;
 pub mod pass;
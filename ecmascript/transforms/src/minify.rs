@@ -0,0 +1,73 @@
+//! Identifier mangler and compressor.
+//!
+//! This module is meant to be applied to the **output** of other passes, so it
+//! does not rely on marks applied by previous passes. Instead it runs its own
+//! resolver.
+pub use self::{
+    compress::{compressor, CompressOptions},
+    mangle::{mangler, MangleOptions},
+};
+use crate::{
+    fixer, hygiene,
+    pass::{Optional, Pass},
+    resolver_with_mark,
+};
+use serde::{Deserialize, Serialize};
+use swc_common::{chain, Mark};
+use swc_ecma_parser::JscTarget;
+
+mod compress;
+mod mangle;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Config {
+    /// Use `null` to disable compression.
+    #[serde(default = "default_compress")]
+    pub compress: Option<CompressOptions>,
+
+    /// Use `null` to disable mangling.
+    #[serde(default = "default_mangle")]
+    pub mangle: Option<MangleOptions>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            compress: default_compress(),
+            mangle: default_mangle(),
+        }
+    }
+}
+
+fn default_compress() -> Option<CompressOptions> {
+    Some(Default::default())
+}
+
+fn default_mangle() -> Option<MangleOptions> {
+    Some(Default::default())
+}
+
+/// Shortens identifiers and compresses statements / expressions.
+///
+/// `target` is used to prevent the compressor from emitting syntax which is
+/// not supported by the target runtime.
+///
+/// Returned pass includes `hygiene` and `fixer`.
+pub fn minifier(target: JscTarget, config: Config) -> impl Pass {
+    let Config { compress, mangle } = config;
+
+    chain!(
+        resolver_with_mark(Mark::fresh(Mark::root())),
+        Optional::new(
+            mangler(mangle.clone().unwrap_or_default()),
+            mangle.is_some()
+        ),
+        Optional::new(
+            compressor(target, compress.clone().unwrap_or_default()),
+            compress.is_some()
+        ),
+        hygiene(),
+        fixer()
+    )
+}
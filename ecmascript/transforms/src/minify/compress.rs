@@ -0,0 +1,418 @@
+use crate::{pass::Pass, util::StmtLike};
+use serde::{Deserialize, Serialize};
+use std::mem::replace;
+use swc_atoms::js_word;
+use swc_common::{Fold, FoldWith, Spanned, SyntaxContext, DUMMY_SP};
+use swc_ecma_ast::*;
+use swc_ecma_parser::JscTarget;
+use swc_ecma_utils::undefined;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct CompressOptions {
+    /// `true` -> `!0`, `false` -> `!1`
+    #[serde(default = "true_by_default")]
+    pub booleans: bool,
+
+    /// `if` statements -> conditional / logical expressions
+    #[serde(default = "true_by_default")]
+    pub conditionals: bool,
+
+    /// Joins consecutive expression statements with the comma operator.
+    #[serde(default = "true_by_default")]
+    pub sequences: bool,
+
+    /// `{ a: a }` -> `{ a }`
+    ///
+    /// Ignored if the target does not support shorthand properties.
+    #[serde(default = "true_by_default")]
+    pub shorthand: bool,
+
+    /// `undefined` -> `void 0`
+    #[serde(default = "true_by_default")]
+    pub undefined: bool,
+}
+
+impl Default for CompressOptions {
+    fn default() -> Self {
+        CompressOptions {
+            booleans: true,
+            conditionals: true,
+            sequences: true,
+            shorthand: true,
+            undefined: true,
+        }
+    }
+}
+
+const fn true_by_default() -> bool {
+    true
+}
+
+/// Compresses statements and expressions.
+///
+/// This pass requires `resolver` to be applied and `fixer` should be applied
+/// after this pass.
+pub fn compressor(target: JscTarget, options: CompressOptions) -> impl Pass {
+    Compressor { target, options }
+}
+
+struct Compressor {
+    target: JscTarget,
+    options: CompressOptions,
+}
+
+noop_fold_type!(Compressor);
+
+impl<T> Fold<Vec<T>> for Compressor
+where
+    T: FoldWith<Self> + StmtLike,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        let mut buf = Vec::with_capacity(stmts.len());
+        let mut exprs: Vec<Box<Expr>> = vec![];
+
+        macro_rules! flush {
+            () => {{
+                if !exprs.is_empty() {
+                    let expr = join(replace(&mut exprs, vec![]));
+                    buf.push(T::from_stmt(Stmt::Expr(ExprStmt {
+                        span: expr.span(),
+                        expr,
+                    })));
+                }
+            }};
+        }
+
+        for stmt in stmts {
+            let stmt = stmt.fold_with(self);
+            if !self.options.sequences {
+                buf.push(stmt);
+                continue;
+            }
+
+            match stmt.try_into_stmt() {
+                // We don't touch directives.
+                Ok(Stmt::Expr(ExprStmt { expr, .. })) if !is_str_lit(&expr) => exprs.push(expr),
+
+                Ok(Stmt::Return(ReturnStmt {
+                    span,
+                    arg: Some(arg),
+                })) if !exprs.is_empty() => {
+                    exprs.push(arg);
+                    let arg = join(replace(&mut exprs, vec![]));
+                    buf.push(T::from_stmt(Stmt::Return(ReturnStmt {
+                        span,
+                        arg: Some(arg),
+                    })));
+                }
+
+                Ok(stmt) => {
+                    flush!();
+                    buf.push(T::from_stmt(stmt));
+                }
+                Err(item) => {
+                    flush!();
+                    buf.push(item);
+                }
+            }
+        }
+        flush!();
+
+        buf
+    }
+}
+
+impl Fold<Stmt> for Compressor {
+    fn fold(&mut self, stmt: Stmt) -> Stmt {
+        let stmt = stmt.fold_children(self);
+        if !self.options.conditionals {
+            return stmt;
+        }
+
+        match stmt {
+            Stmt::If(IfStmt {
+                span,
+                test,
+                cons,
+                alt,
+            }) => match (into_expr(*cons), alt.map(|alt| into_expr(*alt))) {
+                (Ok(cons), None) => Stmt::Expr(ExprStmt {
+                    span,
+                    expr: match *test {
+                        // if (!a) b(); -> a || b();
+                        Expr::Unary(UnaryExpr {
+                            op: op!("!"), arg, ..
+                        }) => box Expr::Bin(BinExpr {
+                            span,
+                            op: op!("||"),
+                            left: arg,
+                            right: cons,
+                        }),
+                        // if (a) b(); -> a && b();
+                        test => box Expr::Bin(BinExpr {
+                            span,
+                            op: op!("&&"),
+                            left: box test,
+                            right: cons,
+                        }),
+                    },
+                }),
+
+                // if (a) b(); else c(); -> a ? b() : c();
+                (Ok(cons), Some(Ok(alt))) => Stmt::Expr(ExprStmt {
+                    span,
+                    expr: box Expr::Cond(CondExpr {
+                        span,
+                        test,
+                        cons,
+                        alt,
+                    }),
+                }),
+
+                // if (a) return b; else return c; -> return a ? b : c;
+                (
+                    Err(Stmt::Return(ReturnStmt { arg: cons, .. })),
+                    Some(Err(Stmt::Return(ReturnStmt { arg: alt, .. }))),
+                ) if cons.is_some() || alt.is_some() => Stmt::Return(ReturnStmt {
+                    span,
+                    arg: Some(box Expr::Cond(CondExpr {
+                        span,
+                        test,
+                        cons: cons.unwrap_or_else(|| undefined(DUMMY_SP)),
+                        alt: alt.unwrap_or_else(|| undefined(DUMMY_SP)),
+                    })),
+                }),
+
+                (cons, alt) => Stmt::If(IfStmt {
+                    span,
+                    test,
+                    cons: box from_expr(cons),
+                    alt: alt.map(|alt| box from_expr(alt)),
+                }),
+            },
+
+            _ => stmt,
+        }
+    }
+}
+
+impl Fold<Expr> for Compressor {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Lit(Lit::Bool(Bool { span, value })) if self.options.booleans => {
+                Expr::Unary(UnaryExpr {
+                    span,
+                    op: op!("!"),
+                    arg: box Expr::Lit(Lit::Num(Number {
+                        span,
+                        value: if value { 0.0 } else { 1.0 },
+                        raw: None,
+                    })),
+                })
+            }
+
+            Expr::Ident(Ident {
+                span,
+                sym: js_word!("undefined"),
+                ..
+            }) if self.options.undefined && span.ctxt() == SyntaxContext::empty() => {
+                *undefined(span)
+            }
+
+            _ => e,
+        }
+    }
+}
+
+impl Fold<Prop> for Compressor {
+    fn fold(&mut self, p: Prop) -> Prop {
+        let p = p.fold_children(self);
+        if !self.options.shorthand || self.target < JscTarget::Es2015 {
+            return p;
+        }
+
+        match p {
+            Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(ref key),
+                value: box Expr::Ident(ref value),
+            }) if key.sym == value.sym && &*key.sym != "__proto__" => {
+                Prop::Shorthand(value.clone())
+            }
+            _ => p,
+        }
+    }
+}
+
+/// Assignment targets should not be modified.
+impl Fold<PatOrExpr> for Compressor {
+    fn fold(&mut self, n: PatOrExpr) -> PatOrExpr {
+        match n {
+            PatOrExpr::Expr(box Expr::Ident(..)) => n,
+            _ => n.fold_children(self),
+        }
+    }
+}
+
+/// Assignment targets should not be modified.
+impl Fold<UpdateExpr> for Compressor {
+    fn fold(&mut self, n: UpdateExpr) -> UpdateExpr {
+        match *n.arg {
+            Expr::Ident(..) => n,
+            _ => n.fold_children(self),
+        }
+    }
+}
+
+fn is_str_lit(e: &Expr) -> bool {
+    match *e {
+        Expr::Lit(Lit::Str(..)) => true,
+        _ => false,
+    }
+}
+
+fn join(mut exprs: Vec<Box<Expr>>) -> Box<Expr> {
+    if exprs.len() == 1 {
+        return exprs.pop().unwrap();
+    }
+
+    box Expr::Seq(SeqExpr {
+        span: DUMMY_SP,
+        exprs,
+    })
+}
+
+/// Returns `Ok` if `stmt` is an expression statement or a block which only
+/// contains an expression statement.
+fn into_expr(stmt: Stmt) -> Result<Box<Expr>, Stmt> {
+    match stmt {
+        Stmt::Expr(ExprStmt { expr, .. }) => Ok(expr),
+        Stmt::Block(BlockStmt { span, mut stmts }) => {
+            if stmts.len() == 1 {
+                match stmts.pop().unwrap() {
+                    Stmt::Expr(ExprStmt { expr, .. }) => return Ok(expr),
+                    // Unwrap blocks of return statements for simpler matching.
+                    stmt @ Stmt::Return(..) => return Err(stmt),
+                    stmt => stmts.push(stmt),
+                }
+            }
+
+            Err(Stmt::Block(BlockStmt { span, stmts }))
+        }
+        _ => Err(stmt),
+    }
+}
+
+fn from_expr(r: Result<Box<Expr>, Stmt>) -> Stmt {
+    match r {
+        Ok(expr) => Stmt::Expr(ExprStmt {
+            span: expr.span(),
+            expr,
+        }),
+        Err(stmt) => stmt,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resolver;
+    use swc_common::chain;
+
+    fn tr() -> impl Pass {
+        chain!(
+            resolver(),
+            compressor(JscTarget::Es2015, Default::default())
+        )
+    }
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(),
+        booleans,
+        "foo(true, false);",
+        "foo(!0, !1);"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(),
+        undefined_global,
+        "foo(undefined);",
+        "foo(void 0);"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(),
+        undefined_local,
+        "function foo(undefined) { return undefined; }",
+        "function foo(undefined) { return undefined; }"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(),
+        sequences,
+        "function foo() { a(); b(); return c; }",
+        "function foo() { return a(), b(), c; }"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(),
+        directives,
+        "function foo() { 'use strict'; a(); b(); }",
+        "function foo() { 'use strict'; a(), b(); }"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(),
+        if_to_and,
+        "if (a) { b(); }",
+        "a && b();"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(),
+        if_not_to_or,
+        "if (!a) b();",
+        "a || b();"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(),
+        if_else_to_cond,
+        "if (a) b(); else c();",
+        "a ? b() : c();"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(),
+        if_return,
+        "function foo() { if (a) { return b; } else return; }",
+        "function foo() { return a ? b : void 0; }"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(),
+        shorthand,
+        "var a = 1; foo({ a: a, b: a, __proto__: __proto__ });",
+        "var a = 1; foo({ a, b: a, __proto__: __proto__ });"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| compressor(JscTarget::Es5, Default::default()),
+        shorthand_es5,
+        "var a = 1; foo({ a: a });",
+        "var a = 1; foo({ a: a });"
+    );
+}
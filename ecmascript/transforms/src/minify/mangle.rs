@@ -0,0 +1,649 @@
+use crate::{hygiene::ops::Operator, pass::Pass, scope::ScopeKind};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+};
+use swc_atoms::{js_word, JsWord};
+use swc_common::{Fold, FoldWith, SyntaxContext, Visit, VisitWith};
+use swc_ecma_ast::*;
+use swc_ecma_utils::{find_ids, ident::IdentLike, Id};
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct MangleOptions {
+    /// Mangle names declared in the top level scope.
+    ///
+    /// Note that names exported from a module are preserved even if this is
+    /// enabled.
+    #[serde(default)]
+    pub top_level: bool,
+
+    /// Names which should not be used for / renamed by the mangler.
+    #[serde(default)]
+    pub reserved: Vec<JsWord>,
+}
+
+/// Shortens names of bindings.
+///
+/// This pass requires `resolver` to be applied and does not clear marks of
+/// identifiers which are not renamed.
+///
+/// If the code contains a direct call to `eval` or a `with` statement, names
+/// are not mangled at all as they can be observed at runtime.
+pub fn mangler(options: MangleOptions) -> impl Pass {
+    Mangler { options }
+}
+
+struct Mangler {
+    options: MangleOptions,
+}
+
+noop_fold_type!(Mangler);
+
+impl Fold<Module> for Mangler {
+    fn fold(&mut self, module: Module) -> Module {
+        let mut analyzer = Analyzer::default();
+        module.visit_with(&mut analyzer);
+
+        self.apply(&analyzer, module)
+    }
+}
+
+impl Fold<Script> for Mangler {
+    fn fold(&mut self, script: Script) -> Script {
+        let mut analyzer = Analyzer::default();
+        script.visit_with(&mut analyzer);
+
+        self.apply(&analyzer, script)
+    }
+}
+
+impl Mangler {
+    fn apply<N>(&self, analyzer: &Analyzer, node: N) -> N
+    where
+        for<'o> N: FoldWith<Operator<'o>>,
+    {
+        if analyzer.has_eval {
+            return node;
+        }
+
+        let renames = analyzer.assign_names(&self.options);
+
+        node.fold_with(&mut Operator(&renames))
+    }
+}
+
+#[derive(Debug)]
+struct ScopeData {
+    parent: Option<usize>,
+    kind: ScopeKind,
+    /// Bindings declared in this scope, in declaration order.
+    decls: Vec<Id>,
+    /// Bindings referenced from this scope or its descendants.
+    used: HashSet<Id>,
+}
+
+#[derive(Debug)]
+struct Analyzer {
+    /// Parents are always stored before their children.
+    scopes: Vec<ScopeData>,
+    cur: usize,
+    /// Index of the scope where a binding is declared.
+    decl_scope: HashMap<Id, usize>,
+    /// Number of occurrences of each binding.
+    counts: HashMap<Id, usize>,
+    /// Unresolved references.
+    globals: HashSet<JsWord>,
+    has_eval: bool,
+}
+
+impl Default for Analyzer {
+    fn default() -> Self {
+        Analyzer {
+            scopes: vec![ScopeData {
+                parent: None,
+                kind: ScopeKind::Fn,
+                decls: vec![],
+                used: Default::default(),
+            }],
+            cur: 0,
+            decl_scope: Default::default(),
+            counts: Default::default(),
+            globals: Default::default(),
+            has_eval: false,
+        }
+    }
+}
+
+impl Analyzer {
+    fn with_scope<F>(&mut self, kind: ScopeKind, op: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let parent = self.cur;
+        self.scopes.push(ScopeData {
+            parent: Some(parent),
+            kind,
+            decls: vec![],
+            used: Default::default(),
+        });
+        self.cur = self.scopes.len() - 1;
+
+        op(self);
+
+        self.cur = parent;
+    }
+
+    fn declare(&mut self, id: Id, hoisted: bool) {
+        if id.1 == SyntaxContext::empty() || self.decl_scope.contains_key(&id) {
+            return;
+        }
+
+        let mut idx = self.cur;
+        if hoisted {
+            while self.scopes[idx].kind != ScopeKind::Fn {
+                idx = match self.scopes[idx].parent {
+                    Some(parent) => parent,
+                    None => break,
+                };
+            }
+        }
+
+        self.scopes[idx].decls.push(id.clone());
+        self.decl_scope.insert(id, idx);
+    }
+
+    fn declare_pat<T>(&mut self, pat: &T, hoisted: bool)
+    where
+        T: for<'any> VisitWith<swc_ecma_utils::DestructuringFinder<'any, Id>>,
+    {
+        let ids: Vec<Id> = find_ids(pat);
+        for id in ids {
+            self.declare(id, hoisted);
+        }
+    }
+
+    fn add_usage(&mut self, i: &Ident) {
+        if i.span.ctxt() == SyntaxContext::empty() {
+            self.globals.insert(i.sym.clone());
+            return;
+        }
+
+        let id = i.to_id();
+        *self.counts.entry(id.clone()).or_default() += 1;
+
+        let mut cur = Some(self.cur);
+        while let Some(idx) = cur {
+            // Ancestors of a scope which uses the binding also use it.
+            if !self.scopes[idx].used.insert(id.clone()) {
+                break;
+            }
+            cur = self.scopes[idx].parent;
+        }
+    }
+
+    /// Computes new names of bindings.
+    ///
+    /// As parents are processed before their children, a binding only needs
+    /// to avoid names of outer bindings which are used in the scope of it.
+    fn assign_names(&self, options: &MangleOptions) -> HashMap<Id, JsWord> {
+        let mut forbidden: HashSet<JsWord> = self.globals.clone();
+        forbidden.extend(options.reserved.iter().cloned());
+
+        // Bindings we don't know about. (e.g. Identifiers generated by other passes)
+        forbidden.extend(
+            self.counts
+                .keys()
+                .filter(|id| !self.decl_scope.contains_key(*id))
+                .map(|id| id.0.clone()),
+        );
+        for id in self.decl_scope.keys() {
+            if options.reserved.contains(&id.0) {
+                forbidden.insert(id.0.clone());
+            }
+        }
+        if !options.top_level {
+            forbidden.extend(self.scopes[0].decls.iter().map(|id| id.0.clone()));
+        }
+
+        let mut names = HashMap::default();
+
+        for (idx, scope) in self.scopes.iter().enumerate() {
+            if idx == 0 && !options.top_level {
+                continue;
+            }
+
+            let taken: HashSet<JsWord> = scope
+                .used
+                .iter()
+                .filter(|id| self.decl_scope.get(*id) != Some(&idx))
+                .filter_map(|id| names.get(id).cloned())
+                .collect();
+
+            let mut decls = scope
+                .decls
+                .iter()
+                .filter(|id| !options.reserved.contains(&id.0))
+                .collect::<Vec<_>>();
+            // Frequently used bindings get shorter names.
+            decls.sort_by_key(|id| Reverse(self.counts.get(*id).copied().unwrap_or(0)));
+
+            let mut n = 0;
+            for id in decls {
+                let name = loop {
+                    let name = base54(n);
+                    n += 1;
+
+                    if is_reserved(&name) || forbidden.contains(&name) || taken.contains(&name) {
+                        continue;
+                    }
+                    break name;
+                };
+
+                names.insert(id.clone(), name);
+            }
+        }
+
+        names.retain(|id, name| id.0 != *name);
+        names
+    }
+}
+
+impl Visit<Function> for Analyzer {
+    fn visit(&mut self, f: &Function) {
+        f.decorators.visit_with(self);
+
+        self.with_scope(ScopeKind::Fn, |a| {
+            for param in &f.params {
+                a.declare_pat(&param.pat, false);
+            }
+            f.params.visit_with(a);
+
+            if let Some(ref body) = f.body {
+                body.stmts.visit_with(a);
+            }
+        })
+    }
+}
+
+impl Visit<ArrowExpr> for Analyzer {
+    fn visit(&mut self, f: &ArrowExpr) {
+        self.with_scope(ScopeKind::Fn, |a| {
+            a.declare_pat(&f.params, false);
+            f.params.visit_with(a);
+
+            match f.body {
+                BlockStmtOrExpr::BlockStmt(ref body) => body.stmts.visit_with(a),
+                BlockStmtOrExpr::Expr(ref body) => body.visit_with(a),
+            }
+        })
+    }
+}
+
+impl Visit<Constructor> for Analyzer {
+    fn visit(&mut self, c: &Constructor) {
+        c.key.visit_with(self);
+
+        self.with_scope(ScopeKind::Fn, |a| {
+            a.declare_pat(&c.params, false);
+            c.params.visit_with(a);
+
+            if let Some(ref body) = c.body {
+                body.stmts.visit_with(a);
+            }
+        })
+    }
+}
+
+impl Visit<SetterProp> for Analyzer {
+    fn visit(&mut self, f: &SetterProp) {
+        f.key.visit_with(self);
+
+        self.with_scope(ScopeKind::Fn, |a| {
+            a.declare_pat(&f.param, false);
+            f.param.visit_with(a);
+
+            if let Some(ref body) = f.body {
+                body.stmts.visit_with(a);
+            }
+        })
+    }
+}
+
+impl Visit<GetterProp> for Analyzer {
+    fn visit(&mut self, f: &GetterProp) {
+        f.key.visit_with(self);
+
+        self.with_scope(ScopeKind::Fn, |a| {
+            if let Some(ref body) = f.body {
+                body.stmts.visit_with(a);
+            }
+        })
+    }
+}
+
+impl Visit<BlockStmt> for Analyzer {
+    fn visit(&mut self, b: &BlockStmt) {
+        self.with_scope(ScopeKind::Block, |a| b.stmts.visit_with(a))
+    }
+}
+
+impl Visit<ForStmt> for Analyzer {
+    fn visit(&mut self, s: &ForStmt) {
+        self.with_scope(ScopeKind::Block, |a| s.visit_children(a))
+    }
+}
+
+impl Visit<ForInStmt> for Analyzer {
+    fn visit(&mut self, s: &ForInStmt) {
+        self.with_scope(ScopeKind::Block, |a| s.visit_children(a))
+    }
+}
+
+impl Visit<ForOfStmt> for Analyzer {
+    fn visit(&mut self, s: &ForOfStmt) {
+        self.with_scope(ScopeKind::Block, |a| s.visit_children(a))
+    }
+}
+
+impl Visit<CatchClause> for Analyzer {
+    fn visit(&mut self, c: &CatchClause) {
+        self.with_scope(ScopeKind::Block, |a| {
+            if let Some(ref param) = c.param {
+                a.declare_pat(param, false);
+                param.visit_with(a);
+            }
+
+            c.body.stmts.visit_with(a);
+        })
+    }
+}
+
+impl Visit<FnDecl> for Analyzer {
+    fn visit(&mut self, f: &FnDecl) {
+        self.declare(f.ident.to_id(), true);
+        f.ident.visit_with(self);
+
+        f.function.visit_with(self);
+    }
+}
+
+impl Visit<FnExpr> for Analyzer {
+    fn visit(&mut self, f: &FnExpr) {
+        match f.ident {
+            Some(ref ident) => self.with_scope(ScopeKind::Block, |a| {
+                a.declare(ident.to_id(), false);
+                ident.visit_with(a);
+
+                f.function.visit_with(a);
+            }),
+            None => f.function.visit_with(self),
+        }
+    }
+}
+
+impl Visit<ClassDecl> for Analyzer {
+    fn visit(&mut self, c: &ClassDecl) {
+        self.declare(c.ident.to_id(), false);
+        c.ident.visit_with(self);
+
+        c.class.visit_with(self);
+    }
+}
+
+impl Visit<ClassExpr> for Analyzer {
+    fn visit(&mut self, c: &ClassExpr) {
+        match c.ident {
+            Some(ref ident) => self.with_scope(ScopeKind::Block, |a| {
+                a.declare(ident.to_id(), false);
+                ident.visit_with(a);
+
+                c.class.visit_with(a);
+            }),
+            None => c.class.visit_with(self),
+        }
+    }
+}
+
+impl Visit<VarDecl> for Analyzer {
+    fn visit(&mut self, v: &VarDecl) {
+        for decl in &v.decls {
+            self.declare_pat(&decl.name, v.kind == VarDeclKind::Var);
+        }
+
+        v.visit_children(self)
+    }
+}
+
+impl Visit<ImportDecl> for Analyzer {
+    fn visit(&mut self, i: &ImportDecl) {
+        for s in &i.specifiers {
+            let local = match *s {
+                ImportSpecifier::Specific(ref s) => &s.local,
+                ImportSpecifier::Default(ref s) => &s.local,
+                ImportSpecifier::Namespace(ref s) => &s.local,
+            };
+
+            self.declare(local.to_id(), false);
+            self.add_usage(local);
+        }
+    }
+}
+
+impl Visit<NamedExport> for Analyzer {
+    fn visit(&mut self, e: &NamedExport) {
+        // Reexports does not reference local bindings.
+        if e.src.is_some() {
+            return;
+        }
+
+        for s in &e.specifiers {
+            match *s {
+                ExportSpecifier::Named(ref s) => self.add_usage(&s.orig),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Visit<Ident> for Analyzer {
+    fn visit(&mut self, i: &Ident) {
+        self.add_usage(i)
+    }
+}
+
+impl Visit<MemberExpr> for Analyzer {
+    fn visit(&mut self, e: &MemberExpr) {
+        e.obj.visit_with(self);
+
+        if e.computed {
+            e.prop.visit_with(self);
+        }
+    }
+}
+
+impl Visit<PropName> for Analyzer {
+    fn visit(&mut self, n: &PropName) {
+        match *n {
+            PropName::Computed(ref c) => c.visit_with(self),
+            _ => {}
+        }
+    }
+}
+
+impl Visit<PrivateName> for Analyzer {
+    fn visit(&mut self, _: &PrivateName) {}
+}
+
+impl Visit<LabeledStmt> for Analyzer {
+    fn visit(&mut self, s: &LabeledStmt) {
+        s.body.visit_with(self);
+    }
+}
+
+impl Visit<BreakStmt> for Analyzer {
+    fn visit(&mut self, _: &BreakStmt) {}
+}
+
+impl Visit<ContinueStmt> for Analyzer {
+    fn visit(&mut self, _: &ContinueStmt) {}
+}
+
+impl Visit<CallExpr> for Analyzer {
+    fn visit(&mut self, e: &CallExpr) {
+        match e.callee {
+            ExprOrSuper::Expr(box Expr::Ident(ref i))
+                if i.sym == js_word!("eval") && i.span.ctxt() == SyntaxContext::empty() =>
+            {
+                self.has_eval = true
+            }
+            _ => {}
+        }
+
+        e.visit_children(self)
+    }
+}
+
+impl Visit<WithStmt> for Analyzer {
+    fn visit(&mut self, s: &WithStmt) {
+        self.has_eval = true;
+
+        s.visit_children(self)
+    }
+}
+
+/// Port of `base54` of terser.
+fn base54(mut n: usize) -> JsWord {
+    const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ$_0123456789";
+
+    let mut buf = String::new();
+    let mut base = 54;
+    n += 1;
+    loop {
+        n -= 1;
+        buf.push(CHARS[n % base] as char);
+        n /= base;
+        base = 64;
+
+        if n == 0 {
+            break;
+        }
+    }
+
+    buf.into()
+}
+
+fn is_reserved(name: &JsWord) -> bool {
+    name.is_reserved_for_es3()
+        || match &**name {
+            "let" | "yield" | "await" | "arguments" | "eval" | "undefined" | "NaN" | "Infinity" => {
+                true
+            }
+            _ => false,
+        }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resolver;
+    use swc_common::chain;
+
+    fn tr(top_level: bool) -> impl Pass {
+        chain!(
+            resolver(),
+            mangler(MangleOptions {
+                top_level,
+                ..Default::default()
+            })
+        )
+    }
+
+    #[test]
+    fn base54_names() {
+        assert_eq!(&*base54(0), "a");
+        assert_eq!(&*base54(53), "_");
+        assert_eq!(&*base54(54), "aa");
+        assert_eq!(&*base54(55), "ba");
+    }
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(false),
+        function_params,
+        "function foo(first, second) { return first + second; }",
+        "function foo(a, b) { return a + b; }"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(false),
+        frequency,
+        "function foo(first, second) { return second + second + first; }",
+        "function foo(b, a) { return a + a + b; }"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(false),
+        preserve_globals,
+        "function foo(first) { return a(first); }",
+        "function foo(b) { return a(b); }"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(false),
+        outer_binding_used_in_inner_scope,
+        "function foo(first) {
+            return function (second) {
+                return first + second;
+            };
+        }",
+        "function foo(a) {
+            return function (b) {
+                return a + b;
+            };
+        }"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(false),
+        reuse_names_in_siblings,
+        "function foo(first) { return first; }
+        function bar(second) { return second; }",
+        "function foo(a) { return a; }
+        function bar(a) { return a; }"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(false),
+        shorthand,
+        "function foo(first) { return { first }; }",
+        "function foo(a) { return { first: a }; }"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(false),
+        eval,
+        "function foo(first) { return eval('first'); }",
+        "function foo(first) { return eval('first'); }"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(true),
+        top_level_export,
+        "export function foo(first) { return first; }
+        const bar = 1;
+        export { bar };",
+        "function b(a) { return a; }
+        export { b as foo };
+        const a = 1;
+        export { a as bar };"
+    );
+}
@@ -2,5 +2,7 @@
 pub mod util;
 pub mod amd;
 pub mod common_js;
+pub mod es6;
 pub mod import_analysis;
+pub mod system_js;
 pub mod umd;
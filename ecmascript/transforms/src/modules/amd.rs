@@ -1,6 +1,7 @@
 use super::util::{
     self, define_es_module, define_property, has_use_strict, initialize_to_undefined,
-    local_name_for_src, make_descriptor, use_strict, Exports, ModulePass, Scope,
+    local_name_for_src, make_descriptor, make_dynamic_import_amd, report_ts_module_decl,
+    use_strict, Exports, ModulePass, Scope,
 };
 use crate::{
     pass::Pass,
@@ -10,7 +11,7 @@ use fxhash::FxHashSet;
 use serde::{Deserialize, Serialize};
 use std::iter;
 use swc_atoms::js_word;
-use swc_common::{Fold, FoldWith, Mark, VisitWith, DUMMY_SP};
+use swc_common::{Fold, FoldWith, Mark, Span, VisitWith, DUMMY_SP};
 use swc_ecma_ast::*;
 
 pub fn amd(config: Config) -> impl Pass {
@@ -19,6 +20,7 @@ pub fn amd(config: Config) -> impl Pass {
         in_top_level: Default::default(),
         scope: Default::default(),
         exports: Default::default(),
+        require: Default::default(),
     }
 }
 
@@ -27,6 +29,8 @@ struct Amd {
     in_top_level: bool,
     scope: Scope,
     exports: Exports,
+    /// Local `require`, used by dynamic imports.
+    require: Option<Ident>,
 }
 
 noop_fold_type!(Amd);
@@ -391,9 +395,11 @@ impl Fold<Module> for Amd {
                     }
                 }
 
-                ModuleDecl::TsImportEquals(..)
-                | ModuleDecl::TsExportAssignment(..)
-                | ModuleDecl::TsNamespaceExport(..) => {}
+                ModuleDecl::TsImportEquals(..) | ModuleDecl::TsExportAssignment(..) => {
+                    report_ts_module_decl(&decl, "amd")
+                }
+
+                ModuleDecl::TsNamespaceExport(..) => {}
             }
         }
 
@@ -408,7 +414,17 @@ impl Fold<Module> for Amd {
             elems: vec![],
         };
 
-        let mut factory_params = Vec::with_capacity(self.scope.imports.len() + 1);
+        let mut factory_params = Vec::with_capacity(self.scope.imports.len() + 2);
+        if let Some(require) = self.require.take() {
+            define_deps_arg
+                .elems
+                .push(Some(Lit::Str(quote_str!("require")).as_arg()));
+            factory_params.push(Param {
+                span: DUMMY_SP,
+                decorators: Default::default(),
+                pat: Pat::Ident(require),
+            });
+        }
         if has_export {
             define_deps_arg
                 .elems
@@ -618,5 +634,14 @@ impl ModulePass for Amd {
     fn scope_mut(&mut self) -> &mut Scope {
         &mut self.scope
     }
+
+    fn make_dynamic_import(&mut self, span: Span, args: Vec<ExprOrSpread>) -> Expr {
+        let require = self
+            .require
+            .get_or_insert_with(|| private_ident!("_require"))
+            .clone();
+
+        make_dynamic_import_amd(span, require, args, self.config.config.no_interop)
+    }
 }
 mark_as_nested!(Amd);
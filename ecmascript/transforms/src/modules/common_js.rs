@@ -1,7 +1,7 @@
 pub use super::util::Config;
 use super::util::{
     define_es_module, define_property, has_use_strict, initialize_to_undefined, make_descriptor,
-    make_require_call, use_strict, ModulePass, Scope,
+    make_dynamic_import_require, make_require_call, use_strict, ModulePass, Scope,
 };
 use crate::{
     pass::Pass,
@@ -9,7 +9,7 @@ use crate::{
 };
 use fxhash::FxHashSet;
 use swc_atoms::js_word;
-use swc_common::{Fold, FoldWith, Mark, VisitWith, DUMMY_SP};
+use swc_common::{Fold, FoldWith, Mark, Span, VisitWith, DUMMY_SP};
 use swc_ecma_ast::*;
 
 pub fn common_js(root_mark: Mark, config: Config) -> impl Pass {
@@ -423,6 +423,70 @@ impl Fold<Vec<ModuleItem>> for CommonJs {
                         _ => unreachable!(),
                     }
                 }
+
+                ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(TsImportEqualsDecl {
+                    span,
+                    is_export,
+                    id,
+                    module_ref:
+                        TsModuleRef::TsExternalModuleRef(TsExternalModuleRef { expr: src, .. }),
+                    ..
+                })) => {
+                    // import foo = require('foo');
+                    //  -> var foo = require('foo');
+                    self.scope
+                        .declared_vars
+                        .push((id.sym.clone(), id.span.ctxt()));
+
+                    extra_stmts.push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(VarDecl {
+                        span,
+                        kind: VarDeclKind::Var,
+                        decls: vec![VarDeclarator {
+                            span: DUMMY_SP,
+                            name: Pat::Ident(id.clone()),
+                            init: Some(box make_require_call(self.root_mark, src.value)),
+                            definite: false,
+                        }],
+                        declare: false,
+                    }))));
+
+                    if is_export {
+                        if !self.config.strict && !emitted_esmodule {
+                            emitted_esmodule = true;
+
+                            stmts.push(ModuleItem::Stmt(define_es_module(quote_ident!("exports"))));
+                        }
+
+                        extra_stmts.push(
+                            AssignExpr {
+                                span: DUMMY_SP,
+                                left: PatOrExpr::Expr(
+                                    box quote_ident!("exports").member(id.clone()),
+                                ),
+                                op: op!("="),
+                                right: box id.into(),
+                            }
+                            .into_stmt()
+                            .into(),
+                        );
+                    }
+                }
+
+                ModuleItem::ModuleDecl(ModuleDecl::TsExportAssignment(export)) => {
+                    // export = foo;
+                    //  -> module.exports = foo;
+                    extra_stmts.push(
+                        AssignExpr {
+                            span: export.span,
+                            left: PatOrExpr::Expr(member_expr!(DUMMY_SP, module.exports)),
+                            op: op!("="),
+                            right: export.expr.fold_with(self),
+                        }
+                        .into_stmt()
+                        .into(),
+                    );
+                }
+
                 _ => extra_stmts.push(item.fold_with(self)),
             }
         }
@@ -665,6 +729,15 @@ impl ModulePass for CommonJs {
     fn scope_mut(&mut self) -> &mut Scope {
         &mut self.scope
     }
+
+    fn make_dynamic_import(&mut self, span: Span, args: Vec<ExprOrSpread>) -> Expr {
+        make_dynamic_import_require(
+            span,
+            quote_ident!(DUMMY_SP.apply_mark(self.root_mark), "require"),
+            args,
+            self.config.no_interop,
+        )
+    }
 }
 
 mark_as_nested!(CommonJs);
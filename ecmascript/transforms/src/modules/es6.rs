@@ -0,0 +1,37 @@
+use super::util::report_ts_module_decl;
+use crate::pass::Pass;
+use swc_common::Fold;
+use swc_ecma_ast::*;
+
+/// Keeps es modules as-is, but reports `import foo = require('foo')` and
+/// `export = foo` as they cannot be expressed using es modules.
+pub fn es6() -> impl Pass {
+    Es6
+}
+
+struct Es6;
+
+noop_fold_type!(Es6);
+
+impl Fold<Vec<ModuleItem>> for Es6 {
+    fn fold(&mut self, items: Vec<ModuleItem>) -> Vec<ModuleItem> {
+        items
+            .into_iter()
+            .filter(|item| match item {
+                ModuleItem::ModuleDecl(
+                    decl
+                    @
+                    ModuleDecl::TsImportEquals(TsImportEqualsDecl {
+                        module_ref: TsModuleRef::TsExternalModuleRef(..),
+                        ..
+                    }),
+                )
+                | ModuleItem::ModuleDecl(decl @ ModuleDecl::TsExportAssignment(..)) => {
+                    report_ts_module_decl(decl, "es6");
+                    false
+                }
+                _ => true,
+            })
+            .collect()
+    }
+}
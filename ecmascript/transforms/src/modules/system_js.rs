@@ -0,0 +1,778 @@
+use super::util::{fn_expr, local_name_for_src, report_ts_module_decl, use_strict};
+use crate::{
+    pass::Pass,
+    util::{find_ids, ident::IdentLike, undefined, ExprFactory, Id},
+};
+use fxhash::FxHashMap;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::iter;
+use swc_atoms::{js_word, JsWord};
+use swc_common::{Fold, FoldWith, Mark, DUMMY_SP};
+use swc_ecma_ast::*;
+
+/// `@babel/plugin-transform-modules-systemjs`
+///
+///# Example
+///## In
+///
+/// ```js
+/// import { foo } from 'foo';
+/// export let count = 0;
+/// export function inc() {
+///     count++;
+/// }
+/// ```
+///
+///## Out
+///
+/// ```js
+/// System.register(['foo'], function (_export, _context) {
+///     'use strict';
+///     var foo, count;
+///     function inc() {
+///         _export('count', count = +count + 1), count - 1;
+///     }
+///     _export('inc', inc);
+///     return {
+///         setters: [function (_foo) {
+///             foo = _foo.foo;
+///         }],
+///         execute: function () {
+///             _export('count', count = 0);
+///         }
+///     };
+/// });
+/// ```
+pub fn system_js(config: Config) -> impl Pass {
+    SystemJs {
+        config,
+        export: private_ident!("_export"),
+        context: private_ident!("_context"),
+        exports: Default::default(),
+        in_top_level: Default::default(),
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Config {
+    /// If false, top-level `this` is replaced with `undefined`.
+    #[serde(default)]
+    pub allow_top_level_this: bool,
+}
+
+struct SystemJs {
+    config: Config,
+    /// `_export`
+    export: Ident,
+    /// `_context`
+    context: Ident,
+    /// Local bindings to the names they are exported as.
+    exports: FxHashMap<Id, Vec<JsWord>>,
+    in_top_level: bool,
+}
+
+noop_fold_type!(SystemJs);
+
+/// A dependency of the module.
+struct Dep {
+    /// Parameter of the setter.
+    module: Ident,
+    setter: Vec<Stmt>,
+}
+
+impl Fold<Module> for SystemJs {
+    fn fold(&mut self, module: Module) -> Module {
+        let items = module.body;
+        self.in_top_level = true;
+
+        // Imported bindings, used to handle `export { foo }` of an import.
+        let mut imported: FxHashMap<Id, (JsWord, Option<JsWord>)> = Default::default();
+        for item in &items {
+            self.collect_exports(item, &mut imported);
+        }
+
+        let mut deps: IndexMap<JsWord, Dep> = Default::default();
+        let mut vars = vec![];
+        let mut fns = vec![];
+        let mut execute = vec![];
+
+        macro_rules! dep {
+            ($src:expr) => {{
+                let src: &JsWord = $src;
+                deps.entry(src.clone()).or_insert_with(|| Dep {
+                    module: Ident::new(
+                        local_name_for_src(src),
+                        DUMMY_SP.apply_mark(Mark::fresh(Mark::root())),
+                    ),
+                    setter: vec![],
+                })
+            }};
+        }
+
+        for item in items {
+            let decl = match item {
+                ModuleItem::Stmt(Stmt::Decl(decl)) => {
+                    self.hoist_decl(decl, &mut vars, &mut fns, &mut execute);
+                    continue;
+                }
+                ModuleItem::Stmt(stmt) => {
+                    execute.push(stmt.fold_with(self));
+                    continue;
+                }
+                ModuleItem::ModuleDecl(decl) => decl,
+            };
+
+            match decl {
+                ModuleDecl::Import(import) => {
+                    let dep = dep!(&import.src.value);
+
+                    for specifier in import.specifiers {
+                        let (local, value) = match specifier {
+                            ImportSpecifier::Namespace(s) => {
+                                (s.local, Expr::Ident(dep.module.clone()))
+                            }
+                            ImportSpecifier::Default(s) => {
+                                (s.local, dep.module.clone().member(quote_ident!("default")))
+                            }
+                            ImportSpecifier::Named(s) => {
+                                let imported = s.imported.unwrap_or_else(|| s.local.clone());
+                                (s.local, dep.module.clone().member(imported))
+                            }
+                        };
+
+                        vars.push(local.clone());
+                        dep.setter.push(
+                            AssignExpr {
+                                span: DUMMY_SP,
+                                left: PatOrExpr::Pat(box Pat::Ident(local)),
+                                op: op!("="),
+                                right: box value,
+                            }
+                            .into_stmt(),
+                        );
+                    }
+                }
+
+                ModuleDecl::ExportAll(export) => {
+                    let dep = dep!(&export.src.value);
+                    let stmts = export_all(&self.export, &dep.module);
+                    dep.setter.extend(stmts);
+                }
+
+                ModuleDecl::ExportNamed(NamedExport {
+                    src: Some(src),
+                    specifiers,
+                    ..
+                }) => {
+                    let dep = dep!(&src.value);
+
+                    let props = specifiers
+                        .into_iter()
+                        .map(|specifier| {
+                            let (exported, value) = match specifier {
+                                ExportSpecifier::Namespace(s) => {
+                                    (s.name, Expr::Ident(dep.module.clone()))
+                                }
+                                ExportSpecifier::Default(s) => (
+                                    s.exported,
+                                    dep.module.clone().member(quote_ident!("default")),
+                                ),
+                                ExportSpecifier::Named(s) => {
+                                    let exported = s.exported.unwrap_or_else(|| s.orig.clone());
+                                    (exported, dep.module.clone().member(s.orig))
+                                }
+                            };
+
+                            PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                                key: PropName::Str(quote_str!(exported.sym)),
+                                value: box value,
+                            }))
+                        })
+                        .collect();
+
+                    dep.setter.push(
+                        CallExpr {
+                            span: DUMMY_SP,
+                            callee: self.export.clone().as_callee(),
+                            args: vec![ObjectLit {
+                                span: DUMMY_SP,
+                                props,
+                            }
+                            .as_arg()],
+                            type_args: Default::default(),
+                        }
+                        .into_stmt(),
+                    );
+                }
+
+                ModuleDecl::ExportNamed(NamedExport { specifiers, .. }) => {
+                    // Local bindings are exported where they are assigned.
+                    for specifier in specifiers {
+                        let s = match specifier {
+                            ExportSpecifier::Named(s) => s,
+                            _ => continue,
+                        };
+
+                        if let Some((src, name)) = imported.get(&s.orig.to_id()) {
+                            let dep = dep!(src);
+                            let value = match name {
+                                Some(name) => dep
+                                    .module
+                                    .clone()
+                                    .member(Ident::new(name.clone(), DUMMY_SP)),
+                                None => Expr::Ident(dep.module.clone()),
+                            };
+                            let exported = s.exported.unwrap_or(s.orig);
+                            let stmt = self.export_call(exported.sym, value).into_stmt();
+                            dep.setter.push(stmt);
+                        }
+                    }
+                }
+
+                ModuleDecl::ExportDecl(ExportDecl { decl, .. }) => {
+                    self.hoist_decl(decl, &mut vars, &mut fns, &mut execute)
+                }
+
+                ModuleDecl::ExportDefaultDecl(ExportDefaultDecl { decl, .. }) => match decl {
+                    DefaultDecl::Class(ClassExpr { ident, class }) => {
+                        let ident = self.default_ident(ident);
+                        self.hoist_decl(
+                            Decl::Class(ClassDecl {
+                                ident,
+                                declare: false,
+                                class,
+                            }),
+                            &mut vars,
+                            &mut fns,
+                            &mut execute,
+                        )
+                    }
+                    DefaultDecl::Fn(FnExpr { ident, function }) => {
+                        let ident = self.default_ident(ident);
+                        self.hoist_decl(
+                            Decl::Fn(FnDecl {
+                                ident,
+                                declare: false,
+                                function,
+                            }),
+                            &mut vars,
+                            &mut fns,
+                            &mut execute,
+                        )
+                    }
+                    DefaultDecl::TsInterfaceDecl(..) => {}
+                },
+
+                ModuleDecl::ExportDefaultExpr(ExportDefaultExpr { expr, .. }) => {
+                    let expr = expr.fold_with(self);
+                    execute.push(self.export_call(js_word!("default"), *expr).into_stmt());
+                }
+
+                ModuleDecl::TsImportEquals(..) | ModuleDecl::TsExportAssignment(..) => {
+                    report_ts_module_decl(&decl, "systemjs")
+                }
+
+                ModuleDecl::TsNamespaceExport(..) => {}
+            }
+        }
+
+        // ====================
+        //  Emit
+        // ====================
+
+        let mut stmts = vec![use_strict()];
+        if !vars.is_empty() {
+            stmts.push(Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                decls: vars
+                    .into_iter()
+                    .map(|name| VarDeclarator {
+                        span: DUMMY_SP,
+                        name: Pat::Ident(name),
+                        init: None,
+                        definite: false,
+                    })
+                    .collect(),
+                declare: false,
+            })));
+        }
+
+        // Functions are hoisted, so they are exported before any module is executed.
+        let mut fn_exports = vec![];
+        for f in &fns {
+            if let Stmt::Decl(Decl::Fn(FnDecl { ref ident, .. })) = f {
+                let expr = self.wrap_export(&ident.to_id(), box Expr::Ident(ident.clone()));
+                if let Expr::Call(..) = *expr {
+                    fn_exports.push(Stmt::Expr(ExprStmt {
+                        span: DUMMY_SP,
+                        expr,
+                    }));
+                }
+            }
+        }
+        stmts.extend(fns);
+        stmts.extend(fn_exports);
+
+        let (deps, setters): (Vec<_>, Vec<_>) = deps
+            .into_iter()
+            .map(|(src, dep)| {
+                let setter = fn_expr(vec![dep.module], dep.setter);
+                (
+                    Some(Lit::Str(quote_str!(src)).as_arg()),
+                    Some(setter.as_arg()),
+                )
+            })
+            .unzip();
+
+        stmts.push(Stmt::Return(ReturnStmt {
+            span: DUMMY_SP,
+            arg: Some(box Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props: vec![
+                    PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                        key: PropName::Ident(quote_ident!("setters")),
+                        value: box Expr::Array(ArrayLit {
+                            span: DUMMY_SP,
+                            elems: setters,
+                        }),
+                    })),
+                    PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                        key: PropName::Ident(quote_ident!("execute")),
+                        value: box fn_expr(vec![], execute),
+                    })),
+                ],
+            })),
+        }));
+
+        Module {
+            body: vec![CallExpr {
+                span: DUMMY_SP,
+                callee: member_expr!(DUMMY_SP, System.register).as_callee(),
+                args: vec![
+                    ArrayLit {
+                        span: DUMMY_SP,
+                        elems: deps,
+                    }
+                    .as_arg(),
+                    fn_expr(vec![self.export.clone(), self.context.clone()], stmts).as_arg(),
+                ],
+                type_args: Default::default(),
+            }
+            .into_stmt()
+            .into()],
+            ..module
+        }
+    }
+}
+
+impl SystemJs {
+    /// Records exported local bindings.
+    fn collect_exports(
+        &mut self,
+        item: &ModuleItem,
+        imported: &mut FxHashMap<Id, (JsWord, Option<JsWord>)>,
+    ) {
+        let decl = match *item {
+            ModuleItem::ModuleDecl(ref decl) => decl,
+            _ => return,
+        };
+
+        let mut add =
+            |i: &Ident, name: JsWord| self.exports.entry(i.to_id()).or_default().push(name);
+
+        match *decl {
+            ModuleDecl::Import(ref import) => {
+                for specifier in &import.specifiers {
+                    let (local, name) = match *specifier {
+                        ImportSpecifier::Namespace(ref s) => (&s.local, None),
+                        ImportSpecifier::Default(ref s) => (&s.local, Some(js_word!("default"))),
+                        ImportSpecifier::Named(ref s) => (
+                            &s.local,
+                            Some(s.imported.as_ref().unwrap_or(&s.local).sym.clone()),
+                        ),
+                    };
+                    imported.insert(local.to_id(), (import.src.value.clone(), name));
+                }
+            }
+
+            ModuleDecl::ExportDecl(ExportDecl { ref decl, .. }) => match *decl {
+                Decl::Class(ClassDecl { ref ident, .. }) | Decl::Fn(FnDecl { ref ident, .. }) => {
+                    add(ident, ident.sym.clone())
+                }
+                Decl::Var(ref var) => {
+                    let ids: Vec<Ident> = find_ids(&var.decls);
+                    for i in &ids {
+                        add(i, i.sym.clone());
+                    }
+                }
+                _ => {}
+            },
+
+            ModuleDecl::ExportDefaultDecl(ExportDefaultDecl { ref decl, .. }) => match *decl {
+                DefaultDecl::Class(ClassExpr {
+                    ident: Some(ref ident),
+                    ..
+                })
+                | DefaultDecl::Fn(FnExpr {
+                    ident: Some(ref ident),
+                    ..
+                }) => add(ident, js_word!("default")),
+                _ => {}
+            },
+
+            ModuleDecl::ExportNamed(NamedExport {
+                src: None,
+                ref specifiers,
+                ..
+            }) => {
+                for specifier in specifiers {
+                    if let ExportSpecifier::Named(ref s) = *specifier {
+                        let exported = s.exported.as_ref().unwrap_or(&s.orig);
+                        add(&s.orig, exported.sym.clone())
+                    }
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    /// Declares a binding for an anonymous default export.
+    fn default_ident(&mut self, ident: Option<Ident>) -> Ident {
+        ident.unwrap_or_else(|| {
+            let ident = private_ident!("_default");
+            self.exports
+                .insert(ident.to_id(), vec![js_word!("default")]);
+            ident
+        })
+    }
+
+    /// Hoists a top-level declaration out of the `execute` function, so that
+    /// the binding is visible to setters and hoisted functions.
+    fn hoist_decl(
+        &mut self,
+        decl: Decl,
+        vars: &mut Vec<Ident>,
+        fns: &mut Vec<Stmt>,
+        execute: &mut Vec<Stmt>,
+    ) {
+        match decl {
+            Decl::Fn(f) => fns.push(Stmt::Decl(Decl::Fn(f.fold_with(self)))),
+
+            Decl::Class(ClassDecl { ident, class, .. }) => {
+                vars.push(ident.clone());
+
+                let expr = Expr::Assign(AssignExpr {
+                    span: DUMMY_SP,
+                    left: PatOrExpr::Pat(box Pat::Ident(ident.clone())),
+                    op: op!("="),
+                    right: box Expr::Class(ClassExpr {
+                        ident: Some(ident),
+                        class,
+                    }),
+                });
+                execute.push(expr.fold_with(self).into_stmt());
+            }
+
+            Decl::Var(var) => {
+                let ids: Vec<Ident> = find_ids(&var.decls);
+                vars.extend(ids);
+
+                for decl in var.decls {
+                    let init = match decl.init {
+                        Some(init) => init,
+                        None => {
+                            // `export let x;` still has to define the export.
+                            if let Pat::Ident(ref ident) = decl.name {
+                                if self.exports.contains_key(&ident.to_id()) {
+                                    let expr =
+                                        self.wrap_export(&ident.to_id(), undefined(DUMMY_SP));
+                                    execute.push(Stmt::Expr(ExprStmt {
+                                        span: decl.span,
+                                        expr,
+                                    }));
+                                }
+                            }
+                            continue;
+                        }
+                    };
+
+                    let expr = Expr::Assign(AssignExpr {
+                        span: decl.span,
+                        left: PatOrExpr::Pat(box decl.name),
+                        op: op!("="),
+                        right: init,
+                    });
+                    execute.push(expr.fold_with(self).into_stmt());
+                }
+            }
+
+            Decl::TsInterface(..)
+            | Decl::TsTypeAlias(..)
+            | Decl::TsEnum(..)
+            | Decl::TsModule(..) => execute.push(Stmt::Decl(decl)),
+        }
+    }
+
+    /// `_export('name', value)`
+    fn export_call(&self, name: JsWord, value: Expr) -> Expr {
+        Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: self.export.clone().as_callee(),
+            args: vec![Lit::Str(quote_str!(name)).as_arg(), value.as_arg()],
+            type_args: Default::default(),
+        })
+    }
+
+    /// Wraps `value` with `_export` calls if `id` is exported.
+    fn wrap_export(&self, id: &Id, value: Box<Expr>) -> Box<Expr> {
+        match self.exports.get(id) {
+            Some(names) => names.iter().fold(value, |value, name| {
+                box self.export_call(name.clone(), *value)
+            }),
+            None => value,
+        }
+    }
+}
+
+impl Fold<Expr> for SystemJs {
+    fn fold(&mut self, expr: Expr) -> Expr {
+        let expr = expr.fold_children(self);
+
+        match expr {
+            // import.meta
+            Expr::MetaProp(MetaPropExpr {
+                meta:
+                    Ident {
+                        sym: js_word!("import"),
+                        ..
+                    },
+                prop,
+            }) if &*prop.sym == "meta" => self.context.clone().member(prop),
+
+            // import('foo')
+            Expr::Call(CallExpr {
+                span,
+                callee:
+                    ExprOrSuper::Expr(box Expr::Ident(Ident {
+                        sym: js_word!("import"),
+                        ..
+                    })),
+                args,
+                type_args,
+            }) => Expr::Call(CallExpr {
+                span,
+                callee: self
+                    .context
+                    .clone()
+                    .member(quote_ident!("import"))
+                    .as_callee(),
+                args,
+                type_args,
+            }),
+
+            Expr::This(ThisExpr { span })
+                if self.in_top_level && !self.config.allow_top_level_this =>
+            {
+                *undefined(span)
+            }
+
+            Expr::Assign(AssignExpr {
+                left: PatOrExpr::Pat(box Pat::Ident(ref i)),
+                ..
+            })
+            | Expr::Assign(AssignExpr {
+                left: PatOrExpr::Expr(box Expr::Ident(ref i)),
+                ..
+            }) => {
+                let id = i.to_id();
+                *self.wrap_export(&id, box expr)
+            }
+
+            Expr::Assign(AssignExpr {
+                left: PatOrExpr::Pat(ref pat),
+                ..
+            }) => {
+                let ids: Vec<Ident> = find_ids(&**pat);
+                let exports: Vec<_> = ids
+                    .into_iter()
+                    .filter(|i| self.exports.contains_key(&i.to_id()))
+                    .map(|i| self.wrap_export(&i.to_id(), box Expr::Ident(i)))
+                    .collect();
+                if exports.is_empty() {
+                    return expr;
+                }
+
+                Expr::Seq(SeqExpr {
+                    span: DUMMY_SP,
+                    exprs: iter::once(box expr).chain(exports).collect(),
+                })
+            }
+
+            Expr::Update(UpdateExpr {
+                span,
+                prefix,
+                op,
+                arg: box Expr::Ident(arg),
+            }) => {
+                let id = arg.to_id();
+                if !self.exports.contains_key(&id) {
+                    return Expr::Update(UpdateExpr {
+                        span,
+                        prefix,
+                        op,
+                        arg: box Expr::Ident(arg),
+                    });
+                }
+
+                // x++ -> (x = +x + 1, x - 1)
+                let value = box Expr::Assign(AssignExpr {
+                    span,
+                    left: PatOrExpr::Pat(box Pat::Ident(arg.clone())),
+                    op: op!("="),
+                    right: box Expr::Bin(BinExpr {
+                        span: DUMMY_SP,
+                        left: box Expr::Unary(UnaryExpr {
+                            span: DUMMY_SP,
+                            op: op!(unary, "+"),
+                            arg: box Expr::Ident(arg.clone()),
+                        }),
+                        op: match op {
+                            op!("++") => op!(bin, "+"),
+                            op!("--") => op!(bin, "-"),
+                        },
+                        right: box Expr::Lit(Lit::Num(Number {
+                            span: DUMMY_SP,
+                            value: 1.0,
+                            raw: None,
+                        })),
+                    }),
+                });
+                let value = self.wrap_export(&id, value);
+
+                if prefix {
+                    *value
+                } else {
+                    Expr::Seq(SeqExpr {
+                        span: DUMMY_SP,
+                        exprs: vec![
+                            value,
+                            box Expr::Bin(BinExpr {
+                                span: DUMMY_SP,
+                                left: box Expr::Ident(arg),
+                                op: match op {
+                                    op!("++") => op!(bin, "-"),
+                                    op!("--") => op!(bin, "+"),
+                                },
+                                right: box Expr::Lit(Lit::Num(Number {
+                                    span: DUMMY_SP,
+                                    value: 1.0,
+                                    raw: None,
+                                })),
+                            }),
+                        ],
+                    })
+                }
+            }
+
+            _ => expr,
+        }
+    }
+}
+
+/// Creates
+///
+/// ```js
+/// var _exportObj = {};
+/// for (var _key in _foo) {
+///     if (_key !== 'default' && _key !== '__esModule') _exportObj[_key] = _foo[_key];
+/// }
+/// _export(_exportObj);
+/// ```
+fn export_all(export: &Ident, module: &Ident) -> Vec<Stmt> {
+    let obj = private_ident!("_exportObj");
+    let key = private_ident!("_key");
+
+    let is_not = |name: &str| {
+        box Expr::Bin(BinExpr {
+            span: DUMMY_SP,
+            left: box Expr::Ident(key.clone()),
+            op: op!("!=="),
+            right: box Expr::Lit(Lit::Str(quote_str!(name))),
+        })
+    };
+
+    vec![
+        Stmt::Decl(Decl::Var(VarDecl {
+            span: DUMMY_SP,
+            kind: VarDeclKind::Var,
+            decls: vec![VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(obj.clone()),
+                init: Some(box Expr::Object(ObjectLit {
+                    span: DUMMY_SP,
+                    props: vec![],
+                })),
+                definite: false,
+            }],
+            declare: false,
+        })),
+        Stmt::ForIn(ForInStmt {
+            span: DUMMY_SP,
+            left: VarDeclOrPat::VarDecl(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                decls: vec![VarDeclarator {
+                    span: DUMMY_SP,
+                    name: Pat::Ident(key.clone()),
+                    init: None,
+                    definite: false,
+                }],
+                declare: false,
+            }),
+            right: box Expr::Ident(module.clone()),
+            body: box Stmt::Block(BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![Stmt::If(IfStmt {
+                    span: DUMMY_SP,
+                    test: box Expr::Bin(BinExpr {
+                        span: DUMMY_SP,
+                        left: is_not("default"),
+                        op: op!("&&"),
+                        right: is_not("__esModule"),
+                    }),
+                    cons: box AssignExpr {
+                        span: DUMMY_SP,
+                        left: PatOrExpr::Expr(box Expr::Member(MemberExpr {
+                            span: DUMMY_SP,
+                            obj: ExprOrSuper::Expr(box Expr::Ident(obj.clone())),
+                            prop: box Expr::Ident(key.clone()),
+                            computed: true,
+                        })),
+                        op: op!("="),
+                        right: box Expr::Member(MemberExpr {
+                            span: DUMMY_SP,
+                            obj: ExprOrSuper::Expr(box Expr::Ident(module.clone())),
+                            prop: box Expr::Ident(key.clone()),
+                            computed: true,
+                        }),
+                    }
+                    .into_stmt(),
+                    alt: None,
+                })],
+            }),
+        }),
+        CallExpr {
+            span: DUMMY_SP,
+            callee: export.clone().as_callee(),
+            args: vec![obj.as_arg()],
+            type_args: Default::default(),
+        }
+        .into_stmt(),
+    ]
+}
+
+mark_as_nested!(SystemJs);
@@ -2,16 +2,17 @@ use self::config::BuiltConfig;
 pub use self::config::Config;
 use super::util::{
     self, define_es_module, define_property, has_use_strict, initialize_to_undefined,
-    local_name_for_src, make_descriptor, make_require_call, use_strict, Exports, ModulePass, Scope,
+    local_name_for_src, make_descriptor, make_require_call, report_ts_module_decl, use_strict,
+    Exports, ModulePass, Scope,
 };
 use crate::{
     pass::Pass,
-    util::{prepend_stmts, var::VarCollector, DestructuringFinder, ExprFactory},
+    util::{prepend_stmts, var::VarCollector, DestructuringFinder, ExprFactory, HANDLER},
 };
 use fxhash::FxHashSet;
 use std::sync::Arc;
 use swc_atoms::js_word;
-use swc_common::{Fold, FoldWith, Mark, SourceMap, VisitWith, DUMMY_SP};
+use swc_common::{Fold, FoldWith, Mark, SourceMap, Span, VisitWith, DUMMY_SP};
 use swc_ecma_ast::*;
 
 mod config;
@@ -386,9 +387,11 @@ impl Fold<Module> for Umd {
                     }
                 }
 
-                ModuleDecl::TsImportEquals(..)
-                | ModuleDecl::TsExportAssignment(..)
-                | ModuleDecl::TsNamespaceExport(..) => {}
+                ModuleDecl::TsImportEquals(..) | ModuleDecl::TsExportAssignment(..) => {
+                    report_ts_module_decl(&decl, "umd")
+                }
+
+                ModuleDecl::TsNamespaceExport(..) => {}
             }
         }
 
@@ -754,5 +757,26 @@ impl ModulePass for Umd {
     fn scope_mut(&mut self) -> &mut Scope {
         &mut self.scope
     }
+
+    /// UMD bundles have no loader which works for all of the environments, so
+    /// `import()` is reported and left as is.
+    fn make_dynamic_import(&mut self, span: Span, args: Vec<ExprOrSpread>) -> Expr {
+        HANDLER.with(|handler| {
+            handler
+                .struct_span_err(
+                    span,
+                    "Dynamic import() cannot be used when targeting umd modules. Consider using \
+                     amd or commonjs modules instead.",
+                )
+                .emit()
+        });
+
+        Expr::Call(CallExpr {
+            span,
+            callee: quote_ident!("import").as_callee(),
+            args,
+            type_args: Default::default(),
+        })
+    }
 }
 mark_as_nested!(Umd);
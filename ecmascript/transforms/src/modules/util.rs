@@ -1,4 +1,4 @@
-use crate::util::{undefined, DestructuringFinder, ExprFactory};
+use crate::util::{undefined, DestructuringFinder, ExprFactory, HANDLER};
 use fxhash::FxHashSet;
 use indexmap::IndexMap;
 use inflector::Inflector;
@@ -15,6 +15,9 @@ pub(super) trait ModulePass {
     fn config(&self) -> &Config;
     fn scope(&self) -> &Scope;
     fn scope_mut(&mut self) -> &mut Scope;
+
+    /// Lowers `import(src)` using the loader of the module system.
+    fn make_dynamic_import(&mut self, span: Span, args: Vec<ExprOrSpread>) -> Expr;
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -505,6 +508,7 @@ impl Scope {
                                     right: box Expr::Lit(Lit::Num(Number {
                                         span: DUMMY_SP,
                                         value: 1.0,
+                                        raw: None,
                                     })),
                                 }),
                             })
@@ -643,11 +647,162 @@ impl Scope {
                     }
                 }
             }
+            Expr::Call(CallExpr {
+                span,
+                callee:
+                    ExprOrSuper::Expr(box Expr::Ident(Ident {
+                        sym: js_word!("import"),
+                        ..
+                    })),
+                args,
+                ..
+            }) => {
+                let args = args.fold_with(folder);
+                folder.make_dynamic_import(span, args)
+            }
+
             _ => expr.fold_children(folder),
         }
     }
 }
 
+/// Creates
+///
+/// ```js
+/// Promise.resolve().then(function () {
+///     return _interopRequireWildcard(require(src));
+/// })
+/// ```
+pub(super) fn make_dynamic_import_require(
+    span: Span,
+    require: Ident,
+    args: Vec<ExprOrSpread>,
+    no_interop: bool,
+) -> Expr {
+    let required = Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: require.as_callee(),
+        args,
+        type_args: Default::default(),
+    });
+
+    Expr::Call(CallExpr {
+        span,
+        callee: CallExpr {
+            span: DUMMY_SP,
+            callee: member_expr!(DUMMY_SP, Promise.resolve).as_callee(),
+            args: vec![],
+            type_args: Default::default(),
+        }
+        .member(quote_ident!("then"))
+        .as_callee(),
+        args: vec![fn_expr(
+            vec![],
+            vec![Stmt::Return(ReturnStmt {
+                span: DUMMY_SP,
+                arg: Some(box interop_wildcard(required, no_interop)),
+            })],
+        )
+        .as_arg()],
+        type_args: Default::default(),
+    })
+}
+
+/// Creates
+///
+/// ```js
+/// new Promise(function (resolve, reject) {
+///     require([src], function (imported) {
+///         return resolve(_interopRequireWildcard(imported));
+///     }, reject);
+/// })
+/// ```
+pub(super) fn make_dynamic_import_amd(
+    span: Span,
+    require: Ident,
+    args: Vec<ExprOrSpread>,
+    no_interop: bool,
+) -> Expr {
+    let resolve = private_ident!("_resolve");
+    let reject = private_ident!("_reject");
+    let imported = private_ident!("imported");
+
+    let on_load = fn_expr(
+        vec![imported.clone()],
+        vec![Stmt::Return(ReturnStmt {
+            span: DUMMY_SP,
+            arg: Some(box Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: resolve.clone().as_callee(),
+                args: vec![interop_wildcard(Expr::Ident(imported), no_interop).as_arg()],
+                type_args: Default::default(),
+            })),
+        })],
+    );
+
+    let require = CallExpr {
+        span: DUMMY_SP,
+        callee: require.as_callee(),
+        args: vec![
+            ArrayLit {
+                span: DUMMY_SP,
+                elems: args.into_iter().map(Some).collect(),
+            }
+            .as_arg(),
+            on_load.as_arg(),
+            reject.clone().as_arg(),
+        ],
+        type_args: Default::default(),
+    }
+    .into_stmt();
+
+    Expr::New(NewExpr {
+        span,
+        callee: box Expr::Ident(quote_ident!("Promise")),
+        args: Some(vec![fn_expr(vec![resolve, reject], vec![require]).as_arg()]),
+        type_args: Default::default(),
+    })
+}
+
+fn interop_wildcard(e: Expr, no_interop: bool) -> Expr {
+    if no_interop {
+        return e;
+    }
+
+    Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: helper!(interop_require_wildcard, "interopRequireWildcard"),
+        args: vec![e.as_arg()],
+        type_args: Default::default(),
+    })
+}
+
+pub(super) fn fn_expr(params: Vec<Ident>, stmts: Vec<Stmt>) -> Expr {
+    Expr::Fn(FnExpr {
+        ident: None,
+        function: Function {
+            span: DUMMY_SP,
+            is_async: false,
+            is_generator: false,
+            decorators: Default::default(),
+            params: params
+                .into_iter()
+                .map(|i| Param {
+                    span: DUMMY_SP,
+                    decorators: Default::default(),
+                    pat: Pat::Ident(i),
+                })
+                .collect(),
+            body: Some(BlockStmt {
+                span: DUMMY_SP,
+                stmts,
+            }),
+            type_params: Default::default(),
+            return_type: Default::default(),
+        },
+    })
+}
+
 pub(super) fn make_require_call(mark: Mark, src: JsWord) -> Expr {
     Expr::Call(CallExpr {
         span: DUMMY_SP,
@@ -817,3 +972,30 @@ macro_rules! mark_as_nested {
         }
     };
 }
+
+/// Reports `import foo = require('foo')` and `export = foo`, which cannot be
+/// expressed using `format` modules.
+pub(super) fn report_ts_module_decl(decl: &ModuleDecl, format: &str) {
+    let (span, syntax) = match decl {
+        ModuleDecl::TsImportEquals(TsImportEqualsDecl {
+            span,
+            module_ref: TsModuleRef::TsExternalModuleRef(..),
+            ..
+        }) => (*span, "`import = require()`"),
+        ModuleDecl::TsExportAssignment(export) => (export.span, "`export =`"),
+        _ => return,
+    };
+
+    HANDLER.with(|handler| {
+        handler
+            .struct_span_err(
+                span,
+                &format!(
+                    "{} cannot be used when targeting {} modules. Consider using commonjs modules \
+                     instead.",
+                    syntax, format
+                ),
+            )
+            .emit()
+    });
+}
@@ -229,6 +229,7 @@ impl SimplifyExpr {
                 KnownOp::Len => Expr::Lit(Lit::Num(Number {
                     value: value.chars().count() as f64,
                     span,
+                    raw: None,
                 })),
 
                 // 'foo'[1]
@@ -275,6 +276,7 @@ impl SimplifyExpr {
                 Expr::Lit(Lit::Num(Number {
                     value: elems.len() as _,
                     span,
+                    raw: None,
                 }))
             }
 
@@ -414,7 +416,11 @@ impl SimplifyExpr {
                     Known(v) => {
                         return preserve_effects(
                             span,
-                            Expr::Lit(Lit::Num(Number { value: v, span })),
+                            Expr::Lit(Lit::Num(Number {
+                                value: v,
+                                span,
+                                raw: None,
+                            })),
                             { iter::once(left).chain(iter::once(right)) },
                         );
                     }
@@ -487,7 +493,11 @@ impl SimplifyExpr {
                                 Known(v) => {
                                     return preserve_effects(
                                         span,
-                                        Expr::Lit(Lit::Num(Number { value: v, span })),
+                                        Expr::Lit(Lit::Num(Number {
+                                            value: v,
+                                            span,
+                                            raw: None,
+                                        })),
                                         iter::once(left).chain(iter::once(right)),
                                     );
                                 }
@@ -675,7 +685,11 @@ impl SimplifyExpr {
                                 span,
                                 left: left_lhs,
                                 op: left_op,
-                                right: box Expr::Lit(Lit::Num(Number { value, span })),
+                                right: box Expr::Lit(Lit::Num(Number {
+                                    value,
+                                    span,
+                                    raw: None,
+                                })),
                             });
                         }
                     }
@@ -768,7 +782,11 @@ impl SimplifyExpr {
                 Known(v) => {
                     return preserve_effects(
                         span,
-                        Expr::Lit(Lit::Num(Number { value: v, span })),
+                        Expr::Lit(Lit::Num(Number {
+                            value: v,
+                            span,
+                            raw: None,
+                        })),
                         iter::once(arg),
                     );
                 }
@@ -786,7 +804,11 @@ impl SimplifyExpr {
                     ..
                 }) => return *arg,
                 Expr::Lit(Lit::Num(Number { value: f, .. })) => {
-                    return Expr::Lit(Lit::Num(Number { value: -f, span }));
+                    return Expr::Lit(Lit::Num(Number {
+                        value: -f,
+                        span,
+                        raw: None,
+                    }));
                 }
                 _ => {
 
@@ -800,6 +822,7 @@ impl SimplifyExpr {
                     arg: box Expr::Lit(Lit::Num(Number {
                         value: 0.0,
                         span: arg.span(),
+                        raw: None,
                     })),
                     span,
                 });
@@ -823,6 +846,7 @@ impl SimplifyExpr {
                             } else {
                                 !(value as u32) as i32 as f64
                             },
+                            raw: None,
                         }));
                     }
                     // TODO: Report error
@@ -1046,7 +1070,11 @@ impl SimplifyExpr {
                 self.perform_abstract_eq_cmp(
                     span,
                     left,
-                    &Expr::Lit(Lit::Num(Number { value: rv, span })),
+                    &Expr::Lit(Lit::Num(Number {
+                        value: rv,
+                        span,
+                        raw: None,
+                    })),
                 )
             }
 
@@ -1054,7 +1082,11 @@ impl SimplifyExpr {
                 let lv = left.as_number()?;
                 self.perform_abstract_eq_cmp(
                     span,
-                    &Expr::Lit(Lit::Num(Number { value: lv, span })),
+                    &Expr::Lit(Lit::Num(Number {
+                        value: lv,
+                        span,
+                        raw: None,
+                    })),
                     right,
                 )
             }
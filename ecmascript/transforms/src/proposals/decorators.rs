@@ -1,6 +1,6 @@
-use self::legacy::Legacy;
+use self::{legacy::Legacy, metadata::Metadata};
 use crate::{
-    pass::Pass,
+    pass::{Optional, Pass},
     util::{
         alias_ident_for, constructor::inject_after_super, prop_name_to_expr_value, undefined,
         ExprFactory, IdentExt,
@@ -9,10 +9,11 @@ use crate::{
 use either::Either;
 use serde::Deserialize;
 use std::iter;
-use swc_common::{Fold, FoldWith, Spanned, Visit, VisitWith, DUMMY_SP};
+use swc_common::{chain, Fold, FoldWith, Spanned, Visit, VisitWith, DUMMY_SP};
 use swc_ecma_ast::*;
 
 mod legacy;
+mod metadata;
 mod usage;
 
 /// ## Simple class decorator
@@ -57,7 +58,10 @@ mod usage;
 /// ```
 pub fn decorators(c: Config) -> impl Pass {
     if c.legacy {
-        Either::Left(Legacy::default())
+        Either::Left(chain!(
+            Optional::new(Metadata, c.emit_metadata),
+            Legacy::default()
+        ))
     } else {
         Either::Right(Decorators {
             is_in_strict: false,
@@ -65,9 +69,24 @@ pub fn decorators(c: Config) -> impl Pass {
     }
 }
 
+/// Adds `Reflect.metadata` decorators like `emitDecoratorMetadata` of tsc.
+///
+/// This should run before type annotations are stripped, while
+/// [decorators] should run after them.
+pub fn metadata() -> impl Pass {
+    Metadata
+}
+
 #[derive(Debug, Default, Deserialize)]
 pub struct Config {
     pub legacy: bool,
+
+    /// Emits `Reflect.metadata` calls like `emitDecoratorMetadata` of tsc.
+    /// Only supported for legacy decorators (ignored otherwise), and type
+    /// annotations should not be stripped yet. See [metadata] to run it
+    /// separately.
+    #[serde(default)]
+    pub emit_metadata: bool,
 }
 
 #[derive(Debug, Default)]
@@ -0,0 +1,359 @@
+use crate::util::{undefined, ExprFactory};
+use swc_common::{util::move_map::MoveMap, Fold, FoldWith, DUMMY_SP};
+use swc_ecma_ast::*;
+
+/// Adds `Reflect.metadata` decorators to decorated classes, using the rules of
+/// `emitDecoratorMetadata` of tsc.
+///
+/// This should run before type annotations are stripped.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```ts
+/// class Foo {
+///     @dec
+///     bar(a: string): number {}
+/// }
+/// ```
+///
+/// ## Out
+///
+/// ```ts
+/// class Foo {
+///     @dec
+///     @Reflect.metadata("design:type", Function)
+///     @Reflect.metadata("design:paramtypes", [String])
+///     @Reflect.metadata("design:returntype", Number)
+///     bar(a: string): number {}
+/// }
+/// ```
+pub(super) struct Metadata;
+
+noop_fold_type!(Metadata);
+
+impl Fold<Class> for Metadata {
+    fn fold(&mut self, c: Class) -> Class {
+        let mut c = c.fold_children(self);
+
+        if !c.decorators.is_empty() {
+            let constructor = c.body.iter().find_map(|m| match m {
+                ClassMember::Constructor(c) if c.body.is_some() => Some(c),
+                _ => None,
+            });
+
+            if let Some(constructor) = constructor {
+                let param_types = constructor
+                    .params
+                    .iter()
+                    .map(|p| match p {
+                        ParamOrTsParamProp::Param(p) => serialize_param_type(&p.pat),
+                        ParamOrTsParamProp::TsParamProp(p) => match p.param {
+                            TsParamPropParam::Ident(ref i) => {
+                                serialize_type_ann(i.type_ann.as_ref())
+                            }
+                            TsParamPropParam::Assign(ref a) => {
+                                serialize_param_type(&Pat::Assign(a.clone()))
+                            }
+                        },
+                    })
+                    .collect();
+
+                c.decorators
+                    .push(metadata("design:paramtypes", array(param_types)));
+            }
+        }
+
+        c.body = c.body.move_map(|m| match m {
+            ClassMember::Method(mut m) if !m.function.decorators.is_empty() => {
+                let f = &m.function;
+
+                match m.kind {
+                    MethodKind::Method => {
+                        let design_type = metadata("design:type", ident("Function"));
+                        let param_types = metadata("design:paramtypes", param_types(&f.params));
+                        let return_type = metadata(
+                            "design:returntype",
+                            match f.return_type {
+                                Some(ref ty) => serialize_type(&ty.type_ann),
+                                None if f.is_async => ident("Promise"),
+                                None => *undefined(DUMMY_SP),
+                            },
+                        );
+
+                        m.function
+                            .decorators
+                            .extend(vec![design_type, param_types, return_type]);
+                    }
+                    MethodKind::Getter => {
+                        let design_type =
+                            metadata("design:type", serialize_type_ann(f.return_type.as_ref()));
+
+                        m.function.decorators.push(design_type);
+                    }
+                    MethodKind::Setter => {
+                        let design_type = metadata(
+                            "design:type",
+                            match f.params.first() {
+                                Some(p) => serialize_param_type(&p.pat),
+                                None => ident("Object"),
+                            },
+                        );
+                        let param_types = metadata("design:paramtypes", param_types(&f.params));
+
+                        m.function.decorators.extend(vec![design_type, param_types]);
+                    }
+                }
+
+                ClassMember::Method(m)
+            }
+
+            ClassMember::ClassProp(mut p) if !p.decorators.is_empty() => {
+                p.decorators.push(metadata(
+                    "design:type",
+                    serialize_type_ann(p.type_ann.as_ref()),
+                ));
+
+                ClassMember::ClassProp(p)
+            }
+
+            _ => m,
+        });
+
+        c
+    }
+}
+
+/// `@Reflect.metadata(key, value)`
+fn metadata(key: &str, value: Expr) -> Decorator {
+    Decorator {
+        span: DUMMY_SP,
+        expr: box Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: member_expr!(DUMMY_SP, Reflect.metadata).as_callee(),
+            args: vec![
+                Lit::Str(Str {
+                    span: DUMMY_SP,
+                    value: key.into(),
+                    has_escape: false,
+                })
+                .as_arg(),
+                value.as_arg(),
+            ],
+            type_args: None,
+        }),
+    }
+}
+
+fn ident(name: &str) -> Expr {
+    Expr::Ident(quote_ident!(name))
+}
+
+fn array(elems: Vec<Expr>) -> Expr {
+    Expr::Array(ArrayLit {
+        span: DUMMY_SP,
+        elems: elems.into_iter().map(|e| Some(e.as_arg())).collect(),
+    })
+}
+
+fn param_types(params: &[Param]) -> Expr {
+    array(
+        params
+            .iter()
+            .filter(|p| match p.pat {
+                // `this` parameter is not a real parameter.
+                Pat::Ident(ref i) => i.sym != *"this",
+                _ => true,
+            })
+            .map(|p| serialize_param_type(&p.pat))
+            .collect(),
+    )
+}
+
+fn serialize_param_type(pat: &Pat) -> Expr {
+    match pat {
+        Pat::Ident(i) => serialize_type_ann(i.type_ann.as_ref()),
+        Pat::Array(a) => serialize_type_ann(a.type_ann.as_ref()),
+        Pat::Object(o) => serialize_type_ann(o.type_ann.as_ref()),
+        Pat::Assign(a) => match a.type_ann {
+            Some(ref ty) => serialize_type(&ty.type_ann),
+            None => serialize_param_type(&a.left),
+        },
+        // The type of a rest parameter is the element type.
+        Pat::Rest(r) => match r.type_ann {
+            Some(TsTypeAnn {
+                type_ann: box TsType::TsArrayType(ref ty),
+                ..
+            }) => serialize_type(&ty.elem_type),
+            _ => ident("Object"),
+        },
+        _ => ident("Object"),
+    }
+}
+
+fn serialize_type_ann(ty: Option<&TsTypeAnn>) -> Expr {
+    match ty {
+        Some(ty) => serialize_type(&ty.type_ann),
+        None => ident("Object"),
+    }
+}
+
+/// Port of `serializeTypeNode` of tsc.
+fn serialize_type(ty: &TsType) -> Expr {
+    match ty {
+        TsType::TsKeywordType(ty) => match ty.kind {
+            TsKeywordTypeKind::TsVoidKeyword
+            | TsKeywordTypeKind::TsUndefinedKeyword
+            | TsKeywordTypeKind::TsNullKeyword
+            | TsKeywordTypeKind::TsNeverKeyword => *undefined(DUMMY_SP),
+
+            TsKeywordTypeKind::TsNumberKeyword => ident("Number"),
+            TsKeywordTypeKind::TsStringKeyword => ident("String"),
+            TsKeywordTypeKind::TsBooleanKeyword => ident("Boolean"),
+            TsKeywordTypeKind::TsBigIntKeyword => {
+                guarded(&TsEntityName::Ident(quote_ident!("BigInt")))
+            }
+            TsKeywordTypeKind::TsSymbolKeyword => {
+                guarded(&TsEntityName::Ident(quote_ident!("Symbol")))
+            }
+
+            TsKeywordTypeKind::TsAnyKeyword
+            | TsKeywordTypeKind::TsUnknownKeyword
+            | TsKeywordTypeKind::TsObjectKeyword => ident("Object"),
+        },
+
+        TsType::TsParenthesizedType(ty) => serialize_type(&ty.type_ann),
+
+        TsType::TsFnOrConstructorType(..) => ident("Function"),
+
+        TsType::TsArrayType(..) | TsType::TsTupleType(..) => ident("Array"),
+
+        TsType::TsTypePredicate(..) => ident("Boolean"),
+
+        TsType::TsLitType(ty) => match ty.lit {
+            TsLit::Str(..) | TsLit::Tpl(..) => ident("String"),
+            TsLit::Number(..) => ident("Number"),
+            TsLit::Bool(..) => ident("Boolean"),
+        },
+
+        TsType::TsTypeRef(ty) => guarded(&ty.type_name),
+
+        TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsUnionType(
+            TsUnionType { types, .. },
+        ))
+        | TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsIntersectionType(
+            TsIntersectionType { types, .. },
+        )) => serialize_type_list(types.iter().map(|ty| &**ty)),
+
+        TsType::TsConditionalType(ty) => {
+            serialize_type_list(vec![&*ty.true_type, &*ty.false_type].into_iter())
+        }
+
+        TsType::TsTypeOperator(TsTypeOperator {
+            op: TsTypeOperatorOp::ReadOnly,
+            type_ann,
+            ..
+        }) => serialize_type(type_ann),
+
+        _ => ident("Object"),
+    }
+}
+
+/// Serializes an union or an intersection. Returns `Object` if types are
+/// different.
+fn serialize_type_list<'a>(types: impl Iterator<Item = &'a TsType>) -> Expr {
+    let mut serialized: Option<Expr> = None;
+
+    for ty in types {
+        let mut ty = ty;
+        while let TsType::TsParenthesizedType(TsParenthesizedType { type_ann, .. }) = ty {
+            ty = type_ann;
+        }
+
+        match ty {
+            // Elide null, undefined and never
+            TsType::TsKeywordType(TsKeywordType {
+                kind: TsKeywordTypeKind::TsNullKeyword,
+                ..
+            })
+            | TsType::TsKeywordType(TsKeywordType {
+                kind: TsKeywordTypeKind::TsUndefinedKeyword,
+                ..
+            })
+            | TsType::TsKeywordType(TsKeywordType {
+                kind: TsKeywordTypeKind::TsNeverKeyword,
+                ..
+            }) => continue,
+            _ => {}
+        }
+
+        let individual = serialize_type(ty);
+        match individual {
+            Expr::Ident(ref i) if i.sym == *"Object" => return individual,
+            _ => {}
+        }
+
+        match serialized {
+            Some(Expr::Ident(ref prev)) => match individual {
+                Expr::Ident(ref i) if i.sym == prev.sym => {}
+                _ => return ident("Object"),
+            },
+            Some(..) => return ident("Object"),
+            None => serialized = Some(individual),
+        }
+    }
+
+    serialized.unwrap_or_else(|| *undefined(DUMMY_SP))
+}
+
+/// `typeof Foo === "undefined" ? Object : Foo`, as the type may not exist at
+/// runtime.
+fn guarded(name: &TsEntityName) -> Expr {
+    fn to_expr(name: &TsEntityName) -> Expr {
+        match name {
+            TsEntityName::Ident(i) => Expr::Ident(Ident::new(i.sym.clone(), i.span)),
+            TsEntityName::TsQualifiedName(q) => Expr::Member(MemberExpr {
+                span: DUMMY_SP,
+                obj: to_expr(&q.left).as_obj(),
+                prop: box Expr::Ident(Ident::new(q.right.sym.clone(), q.right.span)),
+                computed: false,
+            }),
+        }
+    }
+
+    /// `typeof A === "undefined" || typeof A.B === "undefined"`
+    fn check(name: &TsEntityName) -> Box<Expr> {
+        let is_undefined = box Expr::Bin(BinExpr {
+            span: DUMMY_SP,
+            op: op!("==="),
+            left: box Expr::Unary(UnaryExpr {
+                span: DUMMY_SP,
+                op: op!("typeof"),
+                arg: box to_expr(name),
+            }),
+            right: box Expr::Lit(Lit::Str(Str {
+                span: DUMMY_SP,
+                value: "undefined".into(),
+                has_escape: false,
+            })),
+        });
+
+        match name {
+            TsEntityName::Ident(..) => is_undefined,
+            TsEntityName::TsQualifiedName(q) => box Expr::Bin(BinExpr {
+                span: DUMMY_SP,
+                op: op!("||"),
+                left: check(&q.left),
+                right: is_undefined,
+            }),
+        }
+    }
+
+    Expr::Cond(CondExpr {
+        span: DUMMY_SP,
+        test: check(name),
+        cons: box ident("Object"),
+        alt: box to_expr(name),
+    })
+}
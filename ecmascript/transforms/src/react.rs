@@ -1,8 +1,9 @@
 pub use self::{
     display_name::display_name,
-    jsx::{jsx, Options},
+    jsx::{jsx, Options, Runtime},
     jsx_self::jsx_self,
     jsx_src::jsx_src,
+    refresh::{refresh, RefreshOptions},
 };
 use crate::pass::Pass;
 use std::sync::Arc;
@@ -12,17 +13,21 @@ mod display_name;
 mod jsx;
 mod jsx_self;
 mod jsx_src;
+mod refresh;
 
 /// `@babel/preset-react`
 ///
 /// Preset for all React plugins.
 pub fn react(cm: Arc<SourceMap>, options: Options) -> impl Pass {
     let Options { development, .. } = options;
+    let refresh_options = options.refresh.clone();
 
+    // `__source` and `__self` should be added before jsx elements are converted.
     chain!(
+        jsx_src(development, cm.clone()),
+        jsx_self(development),
+        refresh(development, refresh_options, cm),
         jsx(options),
-        display_name(),
-        jsx_src(development, cm),
-        jsx_self(development)
+        display_name()
     )
 }
@@ -115,7 +115,7 @@ impl Fold<Prop> for DisplayName {
                             has_escape: false,
                         })),
                         PropName::Str(ref s) => box Expr::Lit(Lit::Str(s.clone())),
-                        PropName::Num(n) => box Expr::Lit(Lit::Num(n)),
+                        PropName::Num(ref n) => box Expr::Lit(Lit::Num(n.clone())),
                         PropName::Computed(ref c) => c.expr.clone(),
                     }),
                 });
@@ -1,9 +1,10 @@
 use crate::{
     pass::Pass,
+    react::RefreshOptions,
     util::{
         drop_span,
         options::{CM, SESSION},
-        ExprFactory, HANDLER,
+        prepend_stmts, undefined, ExprFactory, COMMENTS, HANDLER,
     },
 };
 use dashmap::DashMap;
@@ -12,7 +13,7 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::{iter, mem, sync::Arc};
 use swc_atoms::{js_word, JsWord};
-use swc_common::{iter::IdentifyLast, FileName, Fold, FoldWith, Spanned, DUMMY_SP};
+use swc_common::{iter::IdentifyLast, BytePos, FileName, Fold, FoldWith, Span, Spanned, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_parser::{Parser, SourceFileInput, Syntax};
 
@@ -35,6 +36,17 @@ pub struct Options {
 
     #[serde(default)]
     pub use_builtins: bool,
+
+    #[serde(default)]
+    pub runtime: Runtime,
+
+    /// Used by the automatic runtime.
+    #[serde(default = "default_import_source")]
+    pub import_source: String,
+
+    /// Enables react fast refresh in development mode.
+    #[serde(default)]
+    pub refresh: Option<RefreshOptions>,
 }
 
 impl Default for Options {
@@ -45,10 +57,29 @@ impl Default for Options {
             throw_if_namespace: default_throw_if_namespace(),
             development: false,
             use_builtins: false,
+            runtime: Default::default(),
+            import_source: default_import_source(),
+            refresh: None,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Runtime {
+    /// `React.createElement(type, props, ...children)`
+    Classic,
+    /// `jsx(type, { ...props, children }, key)`, imported from
+    /// `<importSource>/jsx-runtime`.
+    Automatic,
+}
+
+impl Default for Runtime {
+    fn default() -> Self {
+        Runtime::Classic
+    }
+}
+
 fn default_pragma() -> String {
     "React.createElement".into()
 }
@@ -61,6 +92,10 @@ fn default_throw_if_namespace() -> bool {
     true
 }
 
+fn default_import_source() -> String {
+    "react".into()
+}
+
 fn parse_option(name: &str, src: String) -> Box<Expr> {
     static CACHE: Lazy<DashMap<Arc<String>, Box<Expr>>> = Lazy::new(|| DashMap::with_capacity(2));
 
@@ -104,6 +139,13 @@ pub fn jsx(options: Options) -> impl Pass {
         },
         use_builtins: options.use_builtins,
         throw_if_namespace: options.throw_if_namespace,
+        runtime: options.runtime,
+        import_source: options.import_source.into(),
+        development: options.development,
+        import_jsx: None,
+        import_jsxs: None,
+        import_fragment: None,
+        import_create_element: None,
     }
 }
 
@@ -112,6 +154,16 @@ struct Jsx {
     pragma_frag: ExprOrSpread,
     use_builtins: bool,
     throw_if_namespace: bool,
+
+    runtime: Runtime,
+    import_source: JsWord,
+    development: bool,
+    /// `jsx`, or `jsxDEV` in development mode.
+    import_jsx: Option<Ident>,
+    import_jsxs: Option<Ident>,
+    import_fragment: Option<Ident>,
+    /// `createElement` is used if `key` comes after a spread attribute.
+    import_create_element: Option<Ident>,
 }
 
 noop_fold_type!(Jsx);
@@ -120,6 +172,25 @@ impl Jsx {
     fn jsx_frag_to_expr(&mut self, el: JSXFragment) -> Expr {
         let span = el.span();
 
+        if self.runtime == Runtime::Automatic {
+            // <></> => _jsx(_Fragment, {});
+            let fragment = self
+                .import_fragment
+                .get_or_insert_with(|| private_ident!("_Fragment"))
+                .clone();
+            let (children, is_static) = self.jsx_children(el.children);
+            let props = self.fold_attrs(vec![], children);
+
+            return self.jsx_call(
+                span,
+                box Expr::Ident(fragment),
+                props,
+                None,
+                is_static,
+                (None, None),
+            );
+        }
+
         Expr::Call(CallExpr {
             span,
             callee: self.pragma.clone(),
@@ -138,17 +209,39 @@ impl Jsx {
     }
 
     fn jsx_elem_to_expr(&mut self, el: JSXElement) -> Expr {
+        match self.runtime {
+            Runtime::Classic => {
+                let callee = self.pragma.clone();
+                self.create_element(callee, el)
+            }
+            Runtime::Automatic => {
+                if has_key_after_spread(&el.opening.attrs) {
+                    // `key` must be evaluated after the spread, so `jsx()` can't be used.
+                    let callee = self
+                        .import_create_element
+                        .get_or_insert_with(|| private_ident!("_createElement"))
+                        .clone();
+                    return self.create_element(callee.as_callee(), el);
+                }
+
+                self.jsx_elem_to_jsx_call(el)
+            }
+        }
+    }
+
+    /// `createElement(type, props, ...children)`
+    fn create_element(&mut self, callee: ExprOrSuper, el: JSXElement) -> Expr {
         let span = el.span();
 
         let name = self.jsx_name(el.opening.name);
 
         Expr::Call(CallExpr {
             span,
-            callee: self.pragma.clone(),
+            callee,
             args: iter::once(name.as_arg())
                 .chain(iter::once({
                     // Attributes
-                    self.fold_attrs(el.opening.attrs).as_arg()
+                    self.fold_attrs(el.opening.attrs, None).as_arg()
                 }))
                 .chain({
                     // Children
@@ -161,6 +254,243 @@ impl Jsx {
         })
     }
 
+    /// `jsx(type, { ...props, children }, key)`
+    fn jsx_elem_to_jsx_call(&mut self, el: JSXElement) -> Expr {
+        let span = el.span();
+
+        let name = self.jsx_name(el.opening.name);
+
+        let mut key = None;
+        let mut source = None;
+        let mut self_ = None;
+        let mut attrs = Vec::with_capacity(el.opening.attrs.len());
+
+        for attr in el.opening.attrs {
+            let slot = match attr {
+                JSXAttrOrSpread::JSXAttr(JSXAttr {
+                    name: JSXAttrName::Ident(ref i),
+                    ..
+                }) => {
+                    if i.sym == *"key" {
+                        Some(&mut key)
+                    } else if self.development && i.sym == *"__source" {
+                        Some(&mut source)
+                    } else if self.development && i.sym == *"__self" {
+                        Some(&mut self_)
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            };
+
+            match (slot, attr) {
+                (Some(slot), JSXAttrOrSpread::JSXAttr(a)) => {
+                    let span = a.name.span();
+                    *slot = Some(jsx_attr_value_to_expr(a.value, span));
+                }
+                (_, attr) => attrs.push(attr),
+            }
+        }
+
+        let (children, is_static) = self.jsx_children(el.children);
+        let props = self.fold_attrs(attrs, children);
+
+        self.jsx_call(span, name, props, key, is_static, (source, self_))
+    }
+
+    /// Returns `(children, is_static)`, where `children` is an array if there
+    /// are multiple children or a spread child.
+    fn jsx_children(&mut self, children: Vec<JSXElementChild>) -> (Option<Box<Expr>>, bool) {
+        let mut children = children
+            .into_iter()
+            .filter_map(|c| self.jsx_elem_child_to_expr(c))
+            .collect::<Vec<_>>();
+
+        match children.len() {
+            0 => (None, false),
+            1 if children[0].spread.is_none() => (Some(children.pop().unwrap().expr), false),
+            len => (
+                Some(box Expr::Array(ArrayLit {
+                    span: DUMMY_SP,
+                    elems: children.into_iter().map(Some).collect(),
+                })),
+                len > 1,
+            ),
+        }
+    }
+
+    fn jsx_call(
+        &mut self,
+        span: Span,
+        ty: Box<Expr>,
+        props: Box<Expr>,
+        key: Option<Box<Expr>>,
+        is_static: bool,
+        (source, self_): (Option<Box<Expr>>, Option<Box<Expr>>),
+    ) -> Expr {
+        let mut args = vec![ty.as_arg(), props.as_arg()];
+
+        let callee = if self.development {
+            // jsxDEV(type, props, key, isStaticChildren, source, self)
+            args.push(key.unwrap_or_else(|| undefined(DUMMY_SP)).as_arg());
+            args.push(
+                Lit::Bool(Bool {
+                    span: DUMMY_SP,
+                    value: is_static,
+                })
+                .as_arg(),
+            );
+            args.push(source.unwrap_or_else(|| undefined(DUMMY_SP)).as_arg());
+            args.push(self_.unwrap_or_else(|| undefined(DUMMY_SP)).as_arg());
+
+            self.import_jsx
+                .get_or_insert_with(|| private_ident!("_jsxDEV"))
+                .clone()
+        } else {
+            if let Some(key) = key {
+                args.push(key.as_arg());
+            }
+
+            if is_static {
+                self.import_jsxs
+                    .get_or_insert_with(|| private_ident!("_jsxs"))
+                    .clone()
+            } else {
+                self.import_jsx
+                    .get_or_insert_with(|| private_ident!("_jsx"))
+                    .clone()
+            }
+        };
+
+        Expr::Call(CallExpr {
+            span,
+            callee: callee.as_callee(),
+            args,
+            type_args: None,
+        })
+    }
+
+    /// Reads `@jsxImportSource` from the comments at the start of the file.
+    fn import_source_pragma(&self, lo: BytePos, first: Option<BytePos>) -> Option<JsWord> {
+        static IMPORT_SOURCE: Lazy<Regex> =
+            Lazy::new(|| Regex::new("@jsxImportSource\\s+(\\S+)").unwrap());
+
+        if !COMMENTS.is_set() {
+            return None;
+        }
+
+        COMMENTS.with(|comments| {
+            let mut import_source = None;
+
+            for pos in iter::once(lo).chain(first) {
+                if let Some(cmts) = comments.leading_comments(pos) {
+                    for cmt in cmts.iter() {
+                        if let Some(caps) = IMPORT_SOURCE.captures(&cmt.text) {
+                            import_source = Some(caps[1].into());
+                        }
+                    }
+                }
+            }
+
+            import_source
+        })
+    }
+
+    /// Returns the runtime functions used by the file, grouped by the source
+    /// they are imported from.
+    fn take_runtime(&mut self, import_source: JsWord) -> Vec<(String, Vec<(Ident, &'static str)>)> {
+        let mut imports = vec![];
+
+        let specifiers = self
+            .import_jsx
+            .take()
+            .map(|local| (local, if self.development { "jsxDEV" } else { "jsx" }))
+            .into_iter()
+            .chain(self.import_jsxs.take().map(|local| (local, "jsxs")))
+            .chain(self.import_fragment.take().map(|local| (local, "Fragment")))
+            .collect::<Vec<_>>();
+        if !specifiers.is_empty() {
+            let runtime = if self.development {
+                "jsx-dev-runtime"
+            } else {
+                "jsx-runtime"
+            };
+            imports.push((format!("{}/{}", import_source, runtime), specifiers));
+        }
+
+        if let Some(local) = self.import_create_element.take() {
+            imports.push((import_source.to_string(), vec![(local, "createElement")]));
+        }
+
+        imports
+    }
+
+    /// Imports the runtime functions used by the module.
+    fn inject_runtime(&mut self, import_source: JsWord, body: &mut Vec<ModuleItem>) {
+        let imports = self
+            .take_runtime(import_source)
+            .into_iter()
+            .map(|(src, specifiers)| {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                    span: DUMMY_SP,
+                    specifiers: specifiers
+                        .into_iter()
+                        .map(|(local, imported)| {
+                            ImportSpecifier::Named(ImportNamedSpecifier {
+                                span: DUMMY_SP,
+                                local,
+                                imported: Some(quote_ident!(imported)),
+                            })
+                        })
+                        .collect(),
+                    src: Str {
+                        span: DUMMY_SP,
+                        value: src.into(),
+                        has_escape: false,
+                    },
+                    type_only: false,
+                }))
+            });
+
+        prepend_stmts(body, imports);
+    }
+
+    /// Scripts can't import, so the runtime functions are loaded using
+    /// `require`.
+    fn require_runtime(&mut self, import_source: JsWord, body: &mut Vec<Stmt>) {
+        let requires = self
+            .take_runtime(import_source)
+            .into_iter()
+            .map(|(src, specifiers)| {
+                Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    declare: false,
+                    decls: specifiers
+                        .into_iter()
+                        .map(|(local, imported)| {
+                            let require = Expr::Call(CallExpr {
+                                span: DUMMY_SP,
+                                callee: quote_ident!("require").as_callee(),
+                                args: vec![Lit::Str(quote_str!(src.clone())).as_arg()],
+                                type_args: None,
+                            });
+
+                            VarDeclarator {
+                                span: DUMMY_SP,
+                                name: Pat::Ident(local),
+                                init: Some(box require.member(quote_ident!(imported))),
+                                definite: false,
+                            }
+                        })
+                        .collect(),
+                }))
+            });
+
+        prepend_stmts(body, requires);
+    }
+
     fn jsx_elem_child_to_expr(&mut self, c: JSXElementChild) -> Option<ExprOrSpread> {
         Some(match c {
             JSXElementChild::JSXText(text) => {
@@ -186,17 +516,31 @@ impl Jsx {
             }) => return None,
             JSXElementChild::JSXElement(el) => self.jsx_elem_to_expr(*el).as_arg(),
             JSXElementChild::JSXFragment(el) => self.jsx_frag_to_expr(el).as_arg(),
-            JSXElementChild::JSXSpreadChild(JSXSpreadChild { .. }) => {
-                unimplemented!("jsx sperad child")
-            }
+            JSXElementChild::JSXSpreadChild(JSXSpreadChild { span, expr }) => ExprOrSpread {
+                spread: Some(span),
+                expr,
+            },
         })
     }
 
-    fn fold_attrs(&mut self, attrs: Vec<JSXAttrOrSpread>) -> Box<Expr> {
-        if attrs.is_empty() {
+    /// `children` is appended to the props. It's used by the automatic
+    /// runtime.
+    fn fold_attrs(
+        &mut self,
+        attrs: Vec<JSXAttrOrSpread>,
+        children: Option<Box<Expr>>,
+    ) -> Box<Expr> {
+        if attrs.is_empty() && self.runtime == Runtime::Classic {
             return box Expr::Lit(Lit::Null(Null { span: DUMMY_SP }));
         }
 
+        let children = children.map(|value| {
+            PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(quote_ident!("children")),
+                value,
+            }))
+        });
+
         let is_complex = attrs.iter().any(|a| match *a {
             JSXAttrOrSpread::SpreadElement(..) => true,
             _ => false,
@@ -229,6 +573,7 @@ impl Jsx {
                     }
                 }
             }
+            cur_obj_props.extend(children);
             check!();
 
             // calls `_extends` or `Object.assign`
@@ -257,12 +602,53 @@ impl Jsx {
                     .map(|v| v.fold_with(self))
                     .map(Box::new)
                     .map(PropOrSpread::Prop)
+                    .chain(children)
                     .collect(),
             })
         }
     }
 }
 
+impl Fold<Module> for Jsx {
+    fn fold(&mut self, module: Module) -> Module {
+        if self.runtime == Runtime::Classic {
+            return module.fold_children(self);
+        }
+
+        let import_source = self
+            .import_source_pragma(
+                module.span.lo(),
+                module.body.first().map(|item| item.span().lo()),
+            )
+            .unwrap_or_else(|| self.import_source.clone());
+
+        let mut module = module.fold_children(self);
+        self.inject_runtime(import_source, &mut module.body);
+
+        module
+    }
+}
+
+impl Fold<Script> for Jsx {
+    fn fold(&mut self, script: Script) -> Script {
+        if self.runtime == Runtime::Classic {
+            return script.fold_children(self);
+        }
+
+        let import_source = self
+            .import_source_pragma(
+                script.span.lo(),
+                script.body.first().map(|stmt| stmt.span().lo()),
+            )
+            .unwrap_or_else(|| self.import_source.clone());
+
+        let mut script = script.fold_children(self);
+        self.require_runtime(import_source, &mut script.body);
+
+        script
+    }
+}
+
 impl Fold<Expr> for Jsx {
     fn fold(&mut self, expr: Expr) -> Expr {
         let expr = expr.fold_children(self);
@@ -362,32 +748,47 @@ impl Jsx {
     }
 }
 
+fn has_key_after_spread(attrs: &[JSXAttrOrSpread]) -> bool {
+    let mut seen_spread = false;
+
+    for attr in attrs {
+        match *attr {
+            JSXAttrOrSpread::SpreadElement(..) => seen_spread = true,
+            JSXAttrOrSpread::JSXAttr(JSXAttr {
+                name: JSXAttrName::Ident(ref i),
+                ..
+            }) if i.sym == *"key" => return seen_spread,
+            _ => {}
+        }
+    }
+
+    false
+}
+
 fn attr_to_prop(a: JSXAttr) -> Prop {
     let key = to_prop_name(a.name);
-    let value = a
-        .value
-        .map(|v| match v {
-            JSXAttrValue::JSXExprContainer(JSXExprContainer {
-                expr: JSXExpr::Expr(e),
-                ..
-            }) => e,
-            JSXAttrValue::JSXElement(e) => box Expr::JSXElement(e),
-            JSXAttrValue::JSXFragment(e) => box Expr::JSXFragment(e),
-            JSXAttrValue::Lit(lit) => box lit.into(),
-            JSXAttrValue::JSXExprContainer(JSXExprContainer {
-                span: _,
-                expr: JSXExpr::JSXEmptyExpr(_),
-            }) => unreachable!("attr_to_prop(JSXEmptyExpr)"),
-        })
-        .unwrap_or_else(|| {
-            box Expr::Lit(Lit::Bool(Bool {
-                span: key.span(),
-                value: true,
-            }))
-        });
+    let value = jsx_attr_value_to_expr(a.value, key.span());
     Prop::KeyValue(KeyValueProp { key, value })
 }
 
+/// `span` is used for `true` if `v` is [None].
+fn jsx_attr_value_to_expr(v: Option<JSXAttrValue>, span: Span) -> Box<Expr> {
+    v.map(|v| match v {
+        JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            expr: JSXExpr::Expr(e),
+            ..
+        }) => e,
+        JSXAttrValue::JSXElement(e) => box Expr::JSXElement(e),
+        JSXAttrValue::JSXFragment(e) => box Expr::JSXFragment(e),
+        JSXAttrValue::Lit(lit) => box lit.into(),
+        JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            span: _,
+            expr: JSXExpr::JSXEmptyExpr(_),
+        }) => unreachable!("attr_to_prop(JSXEmptyExpr)"),
+    })
+    .unwrap_or_else(|| box Expr::Lit(Lit::Bool(Bool { span, value: true })))
+}
+
 fn to_prop_name(n: JSXAttrName) -> PropName {
     let span = n.span();
 
@@ -1145,3 +1145,171 @@ test!(
     "let page = React.createElement('p', null, 'Click ', React.createElement('em', null, 'New \
      melody'), ' listen to a randomly generated melody');"
 );
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| tr(Options {
+        runtime: Runtime::Automatic,
+        ..Default::default()
+    }),
+    automatic_runtime,
+    r#"
+var x = <div className="a" key="k">foo</div>;
+var y = <div>{a}{b}</div>;
+var z = <><A /><B {...props} c /></>;
+"#,
+    r#"
+import { jsx as _jsx, jsxs as _jsxs, Fragment as _Fragment } from "react/jsx-runtime";
+var x = _jsx("div", {
+    className: "a",
+    children: "foo"
+}, "k");
+var y = _jsxs("div", {
+    children: [a, b]
+});
+var z = _jsxs(_Fragment, {
+    children: [_jsx(A, {}), _jsx(B, _extends({}, props, {
+        c: true
+    }))]
+});
+"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| tr(Options {
+        runtime: Runtime::Automatic,
+        ..Default::default()
+    }),
+    automatic_runtime_key_after_spread,
+    r#"<div {...props} key="1">foo</div>;"#,
+    r#"
+import { createElement as _createElement } from "react";
+_createElement("div", _extends({}, props, {
+    key: "1"
+}), "foo");
+"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| tr(Options {
+        runtime: Runtime::Automatic,
+        development: true,
+        ..Default::default()
+    }),
+    automatic_runtime_development,
+    r#"
+<div key={k} __source={source} __self={self}><span /></div>;
+"#,
+    r#"
+import { jsxDEV as _jsxDEV } from "react/jsx-dev-runtime";
+_jsxDEV("div", {
+    children: _jsxDEV("span", {}, void 0, false, void 0, void 0)
+}, k, false, source, self);
+"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| tr(Options {
+        runtime: Runtime::Automatic,
+        import_source: "foo".into(),
+        ..Default::default()
+    }),
+    automatic_runtime_import_source_pragma,
+    r#"
+/** @jsxImportSource preact */
+<div />;
+"#,
+    r#"
+import { jsx as _jsx } from "preact/jsx-runtime";
+_jsx("div", {});
+"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| tr(Options {
+        runtime: Runtime::Automatic,
+        ..Default::default()
+    }),
+    automatic_runtime_spread_child,
+    r#"
+<div>{...xs}</div>;
+"#,
+    r#"
+import { jsx as _jsx } from "react/jsx-runtime";
+_jsx("div", {
+    children: [...xs]
+});
+"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| tr(Default::default()),
+    spread_child,
+    r#"
+<div>{...xs}</div>;
+"#,
+    r#"
+React.createElement("div", null, ...xs);
+"#
+);
+
+#[test]
+fn automatic_runtime_script() {
+    crate::tests::Tester::run(|tester| {
+        let syntax = ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+            jsx: true,
+            ..Default::default()
+        });
+        let script = tester.with_parser("input.js", syntax, "<><div /><div /></>;", |p| {
+            p.parse_script().map_err(|mut e| {
+                e.emit();
+            })
+        })?;
+        let script = script.fold_with(&mut jsx(Options {
+            runtime: Runtime::Automatic,
+            ..Default::default()
+        }));
+
+        let actual = tester.print(&Module {
+            span: DUMMY_SP,
+            body: script.body.into_iter().map(ModuleItem::Stmt).collect(),
+            shebang: None,
+        });
+        let expected = tester.parse_module(
+            "output.js",
+            r#"
+var _jsx = require("react/jsx-runtime").jsx, _jsxs = require("react/jsx-runtime").jsxs, _Fragment = require("react/jsx-runtime").Fragment;
+_jsxs(_Fragment, {
+    children: [_jsx("div", {}), _jsx("div", {})]
+});
+"#,
+        )?;
+        let expected = tester.print(&expected);
+
+        assert_eq!(actual, expected);
+        Ok(())
+    });
+}
@@ -44,7 +44,7 @@ impl Fold<JSXOpeningElement> for JsxSrc {
                                     span: DUMMY_SP,
                                     value: match file_lines.file.name {
                                         FileName::Real(ref p) => p.display().to_string().into(),
-                                        _ => unimplemented!("file name for other than real files"),
+                                        ref name => name.to_string().into(),
                                     },
                                     has_escape: false,
                                 })),
@@ -54,6 +54,7 @@ impl Fold<JSXOpeningElement> for JsxSrc {
                                 value: box Expr::Lit(Lit::Num(Number {
                                     span: DUMMY_SP,
                                     value: (file_lines.lines[0].line_index + 1) as _,
+                                    raw: None,
                                 })),
                             })),
                         ],
@@ -0,0 +1,999 @@
+use crate::{
+    pass::{Optional, Pass},
+    util::{find_ids, prepend, DestructuringFinder, ExprFactory},
+};
+use fxhash::FxHashSet;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::{mem, sync::Arc};
+use swc_atoms::JsWord;
+use swc_common::{
+    Fold, FoldWith, SourceMap, Span, Spanned, SyntaxContext, Visit, VisitWith, DUMMY_SP,
+};
+use swc_ecma_ast::*;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshOptions {
+    #[serde(default = "default_refresh_reg")]
+    pub refresh_reg: String,
+    #[serde(default = "default_refresh_sig")]
+    pub refresh_sig: String,
+    /// Emit signatures as is instead of hashing them.
+    #[serde(default)]
+    pub emit_full_signatures: bool,
+}
+
+impl Default for RefreshOptions {
+    fn default() -> Self {
+        RefreshOptions {
+            refresh_reg: default_refresh_reg(),
+            refresh_sig: default_refresh_sig(),
+            emit_full_signatures: false,
+        }
+    }
+}
+
+fn default_refresh_reg() -> String {
+    "$RefreshReg$".into()
+}
+
+fn default_refresh_sig() -> String {
+    "$RefreshSig$".into()
+}
+
+/// `react-refresh/babel`
+///
+/// Registers components with `$RefreshReg$` and hook signatures with
+/// `$RefreshSig$` so they can be hot reloaded.
+///
+/// This is a no-op if `dev` is false or `options` is [None].
+pub fn refresh(dev: bool, options: Option<RefreshOptions>, cm: Arc<SourceMap>) -> impl Pass {
+    let enabled = dev && options.is_some();
+
+    Optional::new(
+        Refresh {
+            options: options.unwrap_or_default(),
+            cm,
+            registrations: Default::default(),
+            signature_count: 0,
+            scope_signatures: Default::default(),
+            insert_after: Default::default(),
+            scope_bindings: Default::default(),
+            used_as_component: Default::default(),
+        },
+        enabled,
+    )
+}
+
+struct Refresh {
+    options: RefreshOptions,
+    cm: Arc<SourceMap>,
+
+    /// `(handle, persistent id)`
+    registrations: Vec<(Ident, JsWord)>,
+    signature_count: usize,
+    /// `var _s = $RefreshSig$()` for the current function or module.
+    scope_signatures: Vec<VarDeclarator>,
+    /// Statements to insert after the current statement.
+    insert_after: Vec<Stmt>,
+
+    /// Bindings of the module and of the enclosing functions, innermost last.
+    scope_bindings: Vec<FxHashSet<(JsWord, SyntaxContext)>>,
+    /// Names used as a jsx element type.
+    used_as_component: FxHashSet<JsWord>,
+}
+
+noop_fold_type!(Refresh);
+
+struct Signature {
+    key: String,
+    custom_hooks: Vec<Expr>,
+}
+
+fn is_componentish_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+fn is_hook_name(name: &str) -> bool {
+    name.starts_with("use") && name[3..].starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+fn is_builtin_hook(name: &str) -> bool {
+    match name {
+        "useState"
+        | "useReducer"
+        | "useEffect"
+        | "useLayoutEffect"
+        | "useMemo"
+        | "useCallback"
+        | "useRef"
+        | "useContext"
+        | "useImperativeHandle"
+        | "useDebugValue" => true,
+        _ => false,
+    }
+}
+
+impl Refresh {
+    fn snippet(&self, span: Span) -> String {
+        self.cm.span_to_snippet(span).unwrap_or_default()
+    }
+
+    /// Creates a handle for `$RefreshReg$(handle, id)`.
+    fn create_registration(&mut self, id: JsWord) -> Ident {
+        let handle = match self.registrations.len() {
+            0 => private_ident!("_c"),
+            n => private_ident!(format!("_c{}", n + 1)),
+        };
+        self.registrations.push((handle.clone(), id));
+
+        handle
+    }
+
+    /// `_c = Foo;`
+    fn register_after(&mut self, id: &Ident) {
+        let handle = self.create_registration(id.sym.clone());
+
+        self.insert_after.push(Stmt::Expr(ExprStmt {
+            span: DUMMY_SP,
+            expr: box Expr::Assign(AssignExpr {
+                span: DUMMY_SP,
+                op: op!("="),
+                left: PatOrExpr::Pat(box Pat::Ident(handle)),
+                right: box Expr::Ident(id.clone()),
+            }),
+        }));
+    }
+
+    /// `e` => `_c = e`
+    fn register_expr(&mut self, id: JsWord, e: &mut Box<Expr>) {
+        let handle = self.create_registration(id);
+
+        let right = mem::replace(e, box Expr::Invalid(Invalid { span: DUMMY_SP }));
+        *e = box Expr::Assign(AssignExpr {
+            span: DUMMY_SP,
+            op: op!("="),
+            left: PatOrExpr::Pat(box Pat::Ident(handle)),
+            right,
+        });
+    }
+
+    /// Port of `findInnerComponents` for expressions. Returns true if a
+    /// component is found.
+    fn find_inner_components(&mut self, name: JsWord, e: &mut Box<Expr>) -> bool {
+        match **e {
+            // export default hoc(Foo)
+            // const X = hoc(Foo)
+            //
+            // `Foo` is registered at its definition.
+            Expr::Ident(ref i) => is_componentish_name(&i.sym),
+
+            Expr::Arrow(ArrowExpr {
+                body: BlockStmtOrExpr::Expr(box Expr::Arrow(..)),
+                ..
+            }) => false,
+
+            // export default hoc1(hoc2(() => {}))
+            // const Foo = hoc1(forwardRef(function renderFoo() {}))
+            Expr::Arrow(..) | Expr::Fn(..) => {
+                self.register_expr(name, e);
+                true
+            }
+
+            Expr::Call(CallExpr {
+                callee: ExprOrSuper::Expr(ref callee),
+                ref mut args,
+                ..
+            }) if !args.is_empty() => {
+                match **callee {
+                    Expr::Ident(..) | Expr::Member(..) => {}
+                    _ => return false,
+                }
+                let inner_name = format!("{}${}", name, self.snippet(callee.span()));
+
+                if !self.find_inner_components(inner_name.into(), &mut args[0].expr) {
+                    return false;
+                }
+
+                // const Foo = hoc1(hoc2(() => {}))
+                self.register_expr(name, e);
+                true
+            }
+
+            _ => false,
+        }
+    }
+
+    /// Registers `const Foo = ...`
+    fn register_var_decl(&mut self, var: &mut VarDecl) {
+        if var.decls.len() != 1 {
+            return;
+        }
+        let decl = &mut var.decls[0];
+        let id = match decl.name {
+            Pat::Ident(ref i) if is_componentish_name(&i.sym) => i.clone(),
+            _ => return,
+        };
+        let init = match decl.init {
+            Some(ref mut init) => init,
+            None => return,
+        };
+
+        let found = match **init {
+            Expr::Arrow(ArrowExpr {
+                body: BlockStmtOrExpr::Expr(box Expr::Arrow(..)),
+                ..
+            }) => false,
+
+            // const Foo = () => {};
+            // _c = Foo;
+            Expr::Arrow(..) | Expr::Fn(..) => {
+                self.register_after(&id);
+                return;
+            }
+
+            // Maybe a HOC. Imports are not.
+            Expr::Call(CallExpr {
+                callee: ExprOrSuper::Expr(box Expr::Ident(ref callee)),
+                ..
+            }) if callee.sym.starts_with("require") || callee.sym.starts_with("import") => return,
+            Expr::Call(..) => self.find_inner_components(id.sym.clone(), init),
+
+            // Maybe something like styled.div`...`
+            Expr::TaggedTpl(..) => false,
+
+            _ => return,
+        };
+
+        if !found && self.used_as_component.contains(&id.sym) {
+            self.register_after(&id);
+        }
+    }
+
+    fn register_module_item(&mut self, item: &mut ModuleItem) {
+        match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Fn(FnDecl { ident, .. })))
+            | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                decl: Decl::Fn(FnDecl { ident, .. }),
+                ..
+            }))
+            | ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                decl:
+                    DefaultDecl::Fn(FnExpr {
+                        ident: Some(ident), ..
+                    }),
+                ..
+            })) => {
+                if is_componentish_name(&ident.sym) {
+                    let ident = ident.clone();
+                    self.register_after(&ident);
+                }
+            }
+
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var)))
+            | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                decl: Decl::Var(var),
+                ..
+            })) => self.register_var_decl(var),
+
+            // export default memo(() => {})
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
+                expr,
+                ..
+            })) => match **expr {
+                Expr::Call(..) => {
+                    self.find_inner_components("%default%".into(), expr);
+                }
+                _ => {}
+            },
+
+            _ => {}
+        }
+    }
+
+    /// Returns the signature of hooks called by the function.
+    fn hook_signature<N>(&self, body: &N) -> Option<Signature>
+    where
+        N: for<'a> VisitWith<HookCollector<'a>>,
+    {
+        let mut v = HookCollector {
+            cm: &self.cm,
+            calls: vec![],
+        };
+        body.visit_with(&mut v);
+
+        if v.calls.is_empty() {
+            return None;
+        }
+
+        let key = v
+            .calls
+            .iter()
+            .map(|call| format!("{}{{{}}}", call.name, call.key))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let custom_hooks = v
+            .calls
+            .into_iter()
+            .filter(|call| !is_builtin_hook(&call.name))
+            .map(|call| call.callee)
+            .collect();
+
+        Some(Signature { key, custom_hooks })
+    }
+
+    /// Declares `var _s = $RefreshSig$()` in the current scope and returns
+    /// `_s`.
+    fn create_signature(&mut self) -> Ident {
+        self.signature_count += 1;
+        let id = match self.signature_count {
+            1 => private_ident!("_s"),
+            n => private_ident!(format!("_s{}", n)),
+        };
+
+        self.scope_signatures.push(VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(id.clone()),
+            init: Some(box Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: quote_ident!(&*self.options.refresh_sig).as_callee(),
+                args: vec![],
+                type_args: None,
+            })),
+            definite: false,
+        });
+
+        id
+    }
+
+    /// `_s(target, "key", forceReset, () => [customHooks])`
+    fn signature_call(&self, sig_id: Ident, target: Box<Expr>, sig: Signature) -> Expr {
+        let key = if self.options.emit_full_signatures {
+            sig.key
+        } else {
+            let mut hasher = Sha1::new();
+            hasher.input(sig.key.as_bytes());
+            base64::encode(&hasher.result())
+        };
+
+        let mut args = vec![
+            target.as_arg(),
+            Lit::Str(Str {
+                span: DUMMY_SP,
+                value: key.into(),
+                has_escape: false,
+            })
+            .as_arg(),
+        ];
+
+        let mut force_reset = false;
+        let mut custom_hooks_in_scope = vec![];
+        for callee in sig.custom_hooks {
+            let binding = match callee {
+                Expr::Ident(ref i)
+                | Expr::Member(MemberExpr {
+                    obj: ExprOrSuper::Expr(box Expr::Ident(ref i)),
+                    ..
+                }) => Some((i.sym.clone(), i.span.ctxt())),
+                _ => None,
+            };
+
+            // `() => [useFoo]` is emitted next to the function, so the hook has
+            // to be declared in one of the enclosing scopes.
+            match binding {
+                Some(ref binding) if self.scope_bindings.iter().any(|s| s.contains(binding)) => {
+                    custom_hooks_in_scope.push(Some(callee.as_arg()))
+                }
+                _ => force_reset = true,
+            }
+        }
+
+        if force_reset || !custom_hooks_in_scope.is_empty() {
+            args.push(
+                Lit::Bool(Bool {
+                    span: DUMMY_SP,
+                    value: force_reset,
+                })
+                .as_arg(),
+            );
+        }
+
+        if !custom_hooks_in_scope.is_empty() {
+            args.push(
+                FnExpr {
+                    ident: None,
+                    function: Function {
+                        params: vec![],
+                        decorators: vec![],
+                        span: DUMMY_SP,
+                        body: Some(BlockStmt {
+                            span: DUMMY_SP,
+                            stmts: vec![Stmt::Return(ReturnStmt {
+                                span: DUMMY_SP,
+                                arg: Some(box Expr::Array(ArrayLit {
+                                    span: DUMMY_SP,
+                                    elems: custom_hooks_in_scope,
+                                })),
+                            })],
+                        }),
+                        is_generator: false,
+                        is_async: false,
+                        type_params: None,
+                        return_type: None,
+                    },
+                }
+                .as_arg(),
+            );
+        }
+
+        Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: sig_id.as_callee(),
+            args,
+            type_args: None,
+        })
+    }
+
+    /// `_s();`
+    fn call_signature(sig_id: &Ident) -> Stmt {
+        Stmt::Expr(ExprStmt {
+            span: DUMMY_SP,
+            expr: box Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: sig_id.clone().as_callee(),
+                args: vec![],
+                type_args: None,
+            }),
+        })
+    }
+
+    /// Adds `_s()` to the function and returns `_s` if it calls hooks.
+    fn instrument_arrow(&mut self, f: &mut ArrowExpr) -> Option<(Ident, Signature)> {
+        let sig = self.hook_signature(&f.body)?;
+        let sig_id = self.create_signature();
+
+        let body = mem::replace(
+            &mut f.body,
+            BlockStmtOrExpr::Expr(box Expr::Invalid(Invalid { span: DUMMY_SP })),
+        );
+        let mut body = match body {
+            BlockStmtOrExpr::BlockStmt(block) => block,
+            BlockStmtOrExpr::Expr(expr) => BlockStmt {
+                span: expr.span(),
+                stmts: vec![Stmt::Return(ReturnStmt {
+                    span: DUMMY_SP,
+                    arg: Some(expr),
+                })],
+            },
+        };
+        body.stmts.insert(0, Refresh::call_signature(&sig_id));
+        f.body = BlockStmtOrExpr::BlockStmt(body);
+
+        Some((sig_id, sig))
+    }
+
+    /// Adds `_s()` to the function and returns `_s` if it calls hooks.
+    fn instrument_fn(&mut self, f: &mut Function) -> Option<(Ident, Signature)> {
+        let sig = self.hook_signature(f.body.as_ref()?)?;
+        let sig_id = self.create_signature();
+
+        f.body
+            .as_mut()
+            .unwrap()
+            .stmts
+            .insert(0, Refresh::call_signature(&sig_id));
+
+        Some((sig_id, sig))
+    }
+
+    /// Instruments `e` without wrapping it, and adds `_s(id, ...)` after the
+    /// current statement.
+    fn instrument_declarator_init(&mut self, id: &Ident, e: &mut Expr) {
+        let sig = match e {
+            Expr::Arrow(f) => self.instrument_arrow(f),
+            Expr::Fn(FnExpr { function, .. }) => self.instrument_fn(function),
+            _ => None,
+        };
+
+        if let Some((sig_id, sig)) = sig {
+            let call = self.signature_call(sig_id, box Expr::Ident(id.clone()), sig);
+            self.insert_after.push(Stmt::Expr(ExprStmt {
+                span: DUMMY_SP,
+                expr: box call,
+            }));
+        }
+    }
+
+    /// Collects top-level bindings of the module.
+    fn collect_bindings(module: &Module) -> FxHashSet<(JsWord, SyntaxContext)> {
+        fn id(i: &Ident) -> (JsWord, SyntaxContext) {
+            (i.sym.clone(), i.span.ctxt())
+        }
+
+        let mut bindings = FxHashSet::default();
+
+        for item in &module.body {
+            match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+                    bindings.extend(import.specifiers.iter().map(|s| match s {
+                        ImportSpecifier::Named(ImportNamedSpecifier { local, .. })
+                        | ImportSpecifier::Default(ImportDefaultSpecifier { local, .. })
+                        | ImportSpecifier::Namespace(ImportStarAsSpecifier { local, .. }) => {
+                            id(local)
+                        }
+                    }))
+                }
+
+                ModuleItem::Stmt(Stmt::Decl(decl))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => {
+                    match decl {
+                        Decl::Fn(FnDecl { ident, .. }) | Decl::Class(ClassDecl { ident, .. }) => {
+                            bindings.insert(id(ident));
+                        }
+                        Decl::Var(var) => bindings.extend(find_ids(&var.decls)),
+                        _ => {}
+                    }
+                }
+
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                    decl:
+                        DefaultDecl::Fn(FnExpr {
+                            ident: Some(ident), ..
+                        }),
+                    ..
+                })) => {
+                    bindings.insert(id(ident));
+                }
+
+                _ => {}
+            }
+        }
+
+        bindings
+    }
+
+    /// Collects bindings of a function, excluding nested functions.
+    fn collect_fn_bindings<P, B>(params: &P, body: &B) -> FxHashSet<(JsWord, SyntaxContext)>
+    where
+        P: for<'a> VisitWith<DestructuringFinder<'a, (JsWord, SyntaxContext)>>,
+        B: for<'a> VisitWith<BindingCollector<'a>>,
+    {
+        let mut bindings: FxHashSet<_> = find_ids::<_, (JsWord, SyntaxContext)>(params)
+            .into_iter()
+            .collect();
+        body.visit_with(&mut BindingCollector {
+            bindings: &mut bindings,
+        });
+
+        bindings
+    }
+}
+
+impl Fold<Module> for Refresh {
+    fn fold(&mut self, mut module: Module) -> Module {
+        let bindings = Refresh::collect_bindings(&module);
+        self.scope_bindings.push(bindings);
+        module.visit_with(&mut ComponentUsageFinder {
+            found: &mut self.used_as_component,
+        });
+
+        let mut body = Vec::with_capacity(module.body.len() + 2);
+        for mut item in module.body {
+            self.register_module_item(&mut item);
+            let registrations = mem::replace(&mut self.insert_after, vec![]);
+
+            body.push(item.fold_with(self));
+
+            // Signatures come before registrations.
+            body.extend(self.insert_after.drain(..).map(ModuleItem::Stmt));
+            body.extend(registrations.into_iter().map(ModuleItem::Stmt));
+        }
+
+        if !self.scope_signatures.is_empty() {
+            prepend(
+                &mut body,
+                ModuleItem::Stmt(Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    declare: false,
+                    decls: mem::replace(&mut self.scope_signatures, vec![]),
+                }))),
+            );
+        }
+
+        if !self.registrations.is_empty() {
+            let registrations = mem::replace(&mut self.registrations, vec![]);
+
+            // var _c, _c2;
+            body.push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                declare: false,
+                decls: registrations
+                    .iter()
+                    .map(|(handle, _)| VarDeclarator {
+                        span: DUMMY_SP,
+                        name: Pat::Ident(handle.clone()),
+                        init: None,
+                        definite: false,
+                    })
+                    .collect(),
+            }))));
+
+            // $RefreshReg$(_c, "Foo");
+            for (handle, id) in registrations {
+                body.push(ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+                    span: DUMMY_SP,
+                    expr: box Expr::Call(CallExpr {
+                        span: DUMMY_SP,
+                        callee: quote_ident!(&*self.options.refresh_reg).as_callee(),
+                        args: vec![
+                            handle.as_arg(),
+                            Lit::Str(Str {
+                                span: DUMMY_SP,
+                                value: id,
+                                has_escape: false,
+                            })
+                            .as_arg(),
+                        ],
+                        type_args: None,
+                    }),
+                })));
+            }
+        }
+
+        self.scope_bindings.pop();
+
+        Module { body, ..module }
+    }
+}
+
+impl Fold<Vec<Stmt>> for Refresh {
+    fn fold(&mut self, stmts: Vec<Stmt>) -> Vec<Stmt> {
+        let old = mem::replace(&mut self.insert_after, vec![]);
+
+        let mut buf = Vec::with_capacity(stmts.len());
+        for stmt in stmts {
+            buf.push(stmt.fold_with(self));
+            buf.extend(self.insert_after.drain(..));
+        }
+
+        self.insert_after = old;
+
+        buf
+    }
+}
+
+impl Fold<Function> for Refresh {
+    fn fold(&mut self, f: Function) -> Function {
+        let bindings = Refresh::collect_fn_bindings(&f.params, &f.body);
+        self.scope_bindings.push(bindings);
+        let old = mem::replace(&mut self.scope_signatures, vec![]);
+        let mut f = f.fold_children(self);
+        self.scope_bindings.pop();
+        let decls = mem::replace(&mut self.scope_signatures, old);
+
+        if !decls.is_empty() {
+            if let Some(body) = &mut f.body {
+                prepend(
+                    &mut body.stmts,
+                    Stmt::Decl(Decl::Var(VarDecl {
+                        span: DUMMY_SP,
+                        kind: VarDeclKind::Var,
+                        declare: false,
+                        decls,
+                    })),
+                );
+            }
+        }
+
+        f
+    }
+}
+
+impl Fold<ArrowExpr> for Refresh {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        let bindings = Refresh::collect_fn_bindings(&f.params, &f.body);
+        self.scope_bindings.push(bindings);
+        let old = mem::replace(&mut self.scope_signatures, vec![]);
+        let mut f = f.fold_children(self);
+        self.scope_bindings.pop();
+        let decls = mem::replace(&mut self.scope_signatures, old);
+
+        if !decls.is_empty() {
+            let decl = Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                declare: false,
+                decls,
+            }));
+
+            f.body = match f.body {
+                BlockStmtOrExpr::BlockStmt(mut block) => {
+                    prepend(&mut block.stmts, decl);
+                    BlockStmtOrExpr::BlockStmt(block)
+                }
+                BlockStmtOrExpr::Expr(expr) => BlockStmtOrExpr::BlockStmt(BlockStmt {
+                    span: expr.span(),
+                    stmts: vec![
+                        decl,
+                        Stmt::Return(ReturnStmt {
+                            span: DUMMY_SP,
+                            arg: Some(expr),
+                        }),
+                    ],
+                }),
+            };
+        }
+
+        f
+    }
+}
+
+impl Fold<FnDecl> for Refresh {
+    fn fold(&mut self, decl: FnDecl) -> FnDecl {
+        let mut decl = decl.fold_children(self);
+
+        // function Foo() {}
+        // _s(Foo, "key");
+        if let Some((sig_id, sig)) = self.instrument_fn(&mut decl.function) {
+            let call = self.signature_call(sig_id, box Expr::Ident(decl.ident.clone()), sig);
+            self.insert_after.push(Stmt::Expr(ExprStmt {
+                span: DUMMY_SP,
+                expr: box call,
+            }));
+        }
+
+        decl
+    }
+}
+
+impl Fold<ExportDefaultDecl> for Refresh {
+    fn fold(&mut self, decl: ExportDefaultDecl) -> ExportDefaultDecl {
+        let mut decl = decl.fold_children(self);
+
+        // Anonymous functions are not handled, as we can't refer to them.
+        if let DefaultDecl::Fn(FnExpr {
+            ident: Some(ref ident),
+            ref mut function,
+        }) = decl.decl
+        {
+            if let Some((sig_id, sig)) = self.instrument_fn(function) {
+                let call = self.signature_call(sig_id, box Expr::Ident(ident.clone()), sig);
+                self.insert_after.push(Stmt::Expr(ExprStmt {
+                    span: DUMMY_SP,
+                    expr: box call,
+                }));
+            }
+        }
+
+        decl
+    }
+}
+
+impl Fold<VarDeclarator> for Refresh {
+    fn fold(&mut self, decl: VarDeclarator) -> VarDeclarator {
+        let name = decl.name.fold_with(self);
+
+        let id = match name {
+            Pat::Ident(ref id) => Some(id.clone()),
+            _ => None,
+        };
+
+        let init = match (id, decl.init) {
+            // let Foo = () => {};
+            // _s(Foo, "key");
+            (Some(id), Some(box init @ Expr::Arrow(..)))
+            | (Some(id), Some(box init @ Expr::Fn(..))) => {
+                let mut init = init.fold_children(self);
+                self.instrument_declarator_init(&id, &mut init);
+                Some(box init)
+            }
+            (_, init) => init.fold_with(self),
+        };
+
+        VarDeclarator { name, init, ..decl }
+    }
+}
+
+impl Fold<Expr> for Refresh {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let mut e = e.fold_children(self);
+
+        // hoc(() => {}) => hoc(_s(() => {}, "key"))
+        let sig = match e {
+            Expr::Arrow(ref mut f) => self.instrument_arrow(f),
+            Expr::Fn(FnExpr {
+                ref mut function, ..
+            }) => self.instrument_fn(function),
+            _ => None,
+        };
+
+        match sig {
+            Some((sig_id, sig)) => self.signature_call(sig_id, box e, sig),
+            None => e,
+        }
+    }
+}
+
+/// Collects hook calls of a function, excluding nested functions.
+struct HookCollector<'a> {
+    cm: &'a SourceMap,
+    calls: Vec<HookCall>,
+}
+
+struct HookCall {
+    callee: Expr,
+    name: JsWord,
+    key: String,
+}
+
+noop_visit_type!(HookCollector<'_>);
+
+impl HookCollector<'_> {
+    fn record(&mut self, call: &CallExpr, lhs: Option<&Pat>) -> bool {
+        let callee = match call.callee {
+            ExprOrSuper::Expr(ref callee) => callee,
+            _ => return false,
+        };
+        let name = match **callee {
+            Expr::Ident(ref i) => &i.sym,
+            Expr::Member(MemberExpr {
+                prop: box Expr::Ident(ref prop),
+                computed: false,
+                ..
+            }) => &prop.sym,
+            _ => return false,
+        };
+        if !is_hook_name(name) {
+            return false;
+        }
+
+        let mut key = lhs
+            .and_then(|lhs| self.cm.span_to_snippet(lhs.span()).ok())
+            .unwrap_or_default();
+
+        // Some built-in hooks reset on edits to the initial state.
+        let initial_state = match &**name {
+            "useState" => call.args.get(0),
+            "useReducer" => call.args.get(1),
+            _ => None,
+        };
+        if let Some(arg) = initial_state {
+            key.push('(');
+            key.push_str(&self.cm.span_to_snippet(arg.expr.span()).unwrap_or_default());
+            key.push(')');
+        }
+
+        self.calls.push(HookCall {
+            callee: (**callee).clone(),
+            name: name.clone(),
+            key,
+        });
+
+        true
+    }
+}
+
+impl Visit<VarDeclarator> for HookCollector<'_> {
+    fn visit(&mut self, d: &VarDeclarator) {
+        d.name.visit_with(self);
+
+        match d.init {
+            // const [foo, setFoo] = useState(0);
+            Some(box Expr::Call(ref call)) if self.record(call, Some(&d.name)) => {
+                call.visit_children(self)
+            }
+            _ => d.init.visit_with(self),
+        }
+    }
+}
+
+impl Visit<CallExpr> for HookCollector<'_> {
+    fn visit(&mut self, call: &CallExpr) {
+        self.record(call, None);
+        call.visit_children(self)
+    }
+}
+
+impl Visit<Function> for HookCollector<'_> {
+    fn visit(&mut self, _: &Function) {}
+}
+
+impl Visit<ArrowExpr> for HookCollector<'_> {
+    fn visit(&mut self, _: &ArrowExpr) {}
+}
+
+/// Collects declarations of a function body, excluding nested functions.
+struct BindingCollector<'a> {
+    bindings: &'a mut FxHashSet<(JsWord, SyntaxContext)>,
+}
+
+noop_visit_type!(BindingCollector<'_>);
+
+impl Visit<VarDeclarator> for BindingCollector<'_> {
+    fn visit(&mut self, d: &VarDeclarator) {
+        self.bindings
+            .extend(find_ids::<_, (JsWord, SyntaxContext)>(&d.name));
+        d.init.visit_with(self);
+    }
+}
+
+impl Visit<FnDecl> for BindingCollector<'_> {
+    fn visit(&mut self, f: &FnDecl) {
+        self.bindings
+            .insert((f.ident.sym.clone(), f.ident.span.ctxt()));
+    }
+}
+
+impl Visit<ClassDecl> for BindingCollector<'_> {
+    fn visit(&mut self, c: &ClassDecl) {
+        self.bindings
+            .insert((c.ident.sym.clone(), c.ident.span.ctxt()));
+        c.class.visit_with(self);
+    }
+}
+
+impl Visit<CatchClause> for BindingCollector<'_> {
+    fn visit(&mut self, c: &CatchClause) {
+        self.bindings
+            .extend(find_ids::<_, (JsWord, SyntaxContext)>(&c.param));
+        c.body.visit_with(self);
+    }
+}
+
+impl Visit<Function> for BindingCollector<'_> {
+    fn visit(&mut self, _: &Function) {}
+}
+
+impl Visit<ArrowExpr> for BindingCollector<'_> {
+    fn visit(&mut self, _: &ArrowExpr) {}
+}
+
+/// Finds names used as `<Foo />` or `createElement(Foo)`.
+struct ComponentUsageFinder<'a> {
+    found: &'a mut FxHashSet<JsWord>,
+}
+
+noop_visit_type!(ComponentUsageFinder<'_>);
+
+impl Visit<JSXElementName> for ComponentUsageFinder<'_> {
+    fn visit(&mut self, n: &JSXElementName) {
+        if let JSXElementName::Ident(ref i) = *n {
+            self.found.insert(i.sym.clone());
+        }
+    }
+}
+
+impl Visit<CallExpr> for ComponentUsageFinder<'_> {
+    fn visit(&mut self, call: &CallExpr) {
+        call.visit_children(self);
+
+        let name = match call.callee {
+            ExprOrSuper::Expr(box Expr::Ident(ref i)) => &i.sym,
+            ExprOrSuper::Expr(box Expr::Member(MemberExpr {
+                prop: box Expr::Ident(ref i),
+                computed: false,
+                ..
+            })) => &i.sym,
+            _ => return,
+        };
+
+        match &**name {
+            "createElement" | "jsx" | "jsxDEV" | "jsxs" => {}
+            _ => return,
+        }
+
+        if let Some(ExprOrSpread {
+            expr: box Expr::Ident(ref i),
+            ..
+        }) = call.args.get(0)
+        {
+            self.found.insert(i.sym.clone());
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use super::*;
+use swc_ecma_parser::{EsConfig, Syntax};
+
+fn syntax() -> Syntax {
+    Syntax::Es(EsConfig {
+        jsx: true,
+        ..Default::default()
+    })
+}
+
+fn tr(cm: Arc<SourceMap>) -> impl Fold<Module> {
+    refresh(
+        true,
+        Some(RefreshOptions {
+            emit_full_signatures: true,
+            ..Default::default()
+        }),
+        cm,
+    )
+}
+
+test!(
+    syntax(),
+    |t| tr(t.cm.clone()),
+    register_components,
+    r#"
+function Hello() {
+    function handleClick() {}
+    return <h1 onClick={handleClick}>Hi</h1>;
+}
+const Bar = () => <Hello />;
+export default function Baz() {
+    return <Bar />;
+}
+const NotAComponent = 1;
+function lowercase() {}
+"#,
+    r#"
+function Hello() {
+    function handleClick() {}
+    return <h1 onClick={handleClick}>Hi</h1>;
+}
+_c = Hello;
+const Bar = () => <Hello />;
+_c2 = Bar;
+export default function Baz() {
+    return <Bar />;
+}
+_c3 = Baz;
+const NotAComponent = 1;
+function lowercase() {}
+var _c, _c2, _c3;
+$RefreshReg$(_c, "Hello");
+$RefreshReg$(_c2, "Bar");
+$RefreshReg$(_c3, "Baz");
+"#
+);
+
+test!(
+    syntax(),
+    |t| tr(t.cm.clone()),
+    register_hocs,
+    r#"
+const A = memo(() => <div />);
+const B = React.forwardRef(function (props, ref) {
+    return <div ref={ref} />;
+});
+const C = styled.div`color: red;`;
+const D = require('./d');
+export default React.memo(() => <C />);
+"#,
+    r#"
+const A = _c2 = memo(_c = () => <div />);
+const B = _c4 = React.forwardRef(_c3 = function (props, ref) {
+    return <div ref={ref} />;
+});
+const C = styled.div`color: red;`;
+_c5 = C;
+const D = require('./d');
+export default _c7 = React.memo(_c6 = () => <C />);
+var _c, _c2, _c3, _c4, _c5, _c6, _c7;
+$RefreshReg$(_c, "A$memo");
+$RefreshReg$(_c2, "A");
+$RefreshReg$(_c3, "B$React.forwardRef");
+$RefreshReg$(_c4, "B");
+$RefreshReg$(_c5, "C");
+$RefreshReg$(_c6, "%default%$React.memo");
+$RefreshReg$(_c7, "%default%");
+"#
+);
+
+test!(
+    syntax(),
+    |t| tr(t.cm.clone()),
+    hook_signatures,
+    r#"
+import { useFoo } from './foo';
+export default function App() {
+    const [foo, setFoo] = useState(0);
+    React.useEffect(() => {});
+    useFoo();
+    return <h1>{foo}</h1>;
+}
+"#,
+    r#"
+var _s = $RefreshSig$();
+import { useFoo } from './foo';
+export default function App() {
+    _s();
+    const [foo, setFoo] = useState(0);
+    React.useEffect(() => {});
+    useFoo();
+    return <h1>{foo}</h1>;
+}
+_s(App, "useState{[foo, setFoo](0)}\nuseEffect{}\nuseFoo{}", false, function () {
+    return [useFoo];
+});
+_c = App;
+var _c;
+$RefreshReg$(_c, "App");
+"#
+);
+
+test!(
+    syntax(),
+    |t| tr(t.cm.clone()),
+    hook_signatures_of_expressions,
+    r#"
+const Foo = () => {
+    const bar = useBar();
+    return <div />;
+};
+const Bar = memo(() => {
+    useState(1);
+    return null;
+});
+"#,
+    r#"
+var _s = $RefreshSig$(), _s2 = $RefreshSig$();
+const Foo = () => {
+    _s();
+    const bar = useBar();
+    return <div />;
+};
+_s(Foo, "useBar{bar}", true);
+_c = Foo;
+const Bar = _c3 = memo(_c2 = _s2(() => {
+    _s2();
+    useState(1);
+    return null;
+}, "useState{(1)}"));
+var _c, _c2, _c3;
+$RefreshReg$(_c, "Foo");
+$RefreshReg$(_c2, "Bar$memo");
+$RefreshReg$(_c3, "Bar");
+"#
+);
+
+test!(
+    syntax(),
+    |t| tr(t.cm.clone()),
+    hooks_of_enclosing_scopes,
+    r#"
+function Parent() {
+    const useLocal = createHook();
+    function Child() {
+        useLocal();
+        useMissing();
+        return null;
+    }
+    return <Child />;
+}
+"#,
+    r#"
+function Parent() {
+    var _s = $RefreshSig$();
+    const useLocal = createHook();
+    function Child() {
+        _s();
+        useLocal();
+        useMissing();
+        return null;
+    }
+    _s(Child, "useLocal{}\nuseMissing{}", true, function () {
+        return [useLocal];
+    });
+    return <Child />;
+}
+_c = Parent;
+var _c;
+$RefreshReg$(_c, "Parent");
+"#
+);
+
+test!(
+    syntax(),
+    |t| refresh(true, Some(Default::default()), t.cm.clone()),
+    hashed_signature,
+    r#"
+function useFancyState() {
+    const [value, setValue] = React.useState(0);
+    return value;
+}
+"#,
+    r#"
+var _s = $RefreshSig$();
+function useFancyState() {
+    _s();
+    const [value, setValue] = React.useState(0);
+    return value;
+}
+_s(useFancyState, "qPBOvRc2Co1iWTsdTL0g7j/rpjU=");
+"#
+);
+
+test!(
+    syntax(),
+    |t| refresh(false, Some(Default::default()), t.cm.clone()),
+    disabled_in_production,
+    "function Foo() { useState(0); }",
+    "function Foo() { useState(0); }"
+);
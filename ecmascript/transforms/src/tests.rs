@@ -1,6 +1,7 @@
 use crate::{
     helpers::{InjectHelpers, HELPERS},
     pass::Pass,
+    util::COMMENTS,
 };
 use std::{
     fmt,
@@ -119,16 +120,23 @@ impl<'a> Tester<'a> {
                 handler: &self.handler,
             };
 
-            let mut p = Parser::new(sess, syntax, SourceFileInput::from(&*fm), None);
+            let mut p = Parser::new(
+                sess,
+                syntax,
+                SourceFileInput::from(&*fm),
+                Some(&self.comments),
+            );
             p.parse_module().map_err(|mut e| {
                 e.emit();
             })?
         };
 
-        let module = validate!(module)
-            .fold_with(&mut tr)
-            .fold_with(&mut ::testing::DropSpan)
-            .fold_with(&mut Normalizer);
+        let module = COMMENTS.set(&self.comments, || {
+            validate!(module)
+                .fold_with(&mut tr)
+                .fold_with(&mut ::testing::DropSpan)
+                .fold_with(&mut Normalizer)
+        });
 
         Ok(module)
     }
@@ -2,13 +2,18 @@ use crate::{
     pass::Pass,
     util::{prepend_stmts, var::VarCollector, ExprFactory},
 };
-use fxhash::FxHashMap;
-use swc_atoms::js_word;
+use fxhash::{FxHashMap, FxHashSet};
+use std::mem::take;
+use swc_atoms::{js_word, JsWord};
 use swc_common::{
     util::move_map::MoveMap, Fold, FoldWith, Span, Spanned, Visit, VisitWith, DUMMY_SP,
 };
 use swc_ecma_ast::*;
-use swc_ecma_utils::{ident::IdentLike, Id};
+use swc_ecma_utils::{find_ids, ident::IdentLike, Id};
+
+pub use self::dts::dts;
+
+mod dts;
 
 /// Strips type annotations out.
 pub fn strip() -> impl Pass {
@@ -22,6 +27,10 @@ struct Strip {
     phase: Phase,
 
     was_side_effect_import: bool,
+
+    /// Exported variables of namespaces declared in the current module item
+    /// list, used to merge namespace declarations.
+    namespace_exports: FxHashMap<JsWord, FxHashSet<JsWord>>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -92,11 +101,19 @@ impl Strip {
                 store!(id.sym, id.span.ctxt(), false);
             }
 
-            Decl::TsInterface(TsInterfaceDecl { ref id, .. })
-            | Decl::TsModule(TsModuleDecl {
+            Decl::TsModule(TsModuleDecl {
                 id: TsModuleName::Ident(ref id),
+                ref body,
+                declare,
                 ..
-            })
+            }) => {
+                store!(id.sym, id.span.ctxt(), false);
+                if !declare && body.as_ref().map(is_instantiated).unwrap_or(false) {
+                    store!(id.sym, id.span.ctxt(), true);
+                }
+            }
+
+            Decl::TsInterface(TsInterfaceDecl { ref id, .. })
             | Decl::TsTypeAlias(TsTypeAliasDecl { ref id, .. }) => {
                 store!(id.sym, id.span.ctxt(), false)
             }
@@ -199,6 +216,16 @@ impl Fold<Vec<ClassMember>> for Strip {
             })
             | ClassMember::ClassProp(ClassProp { value: None, .. }) => None,
 
+            ClassMember::Method(m) => Some(ClassMember::Method(ClassMethod {
+                is_optional: false,
+                ..m
+            })),
+            ClassMember::ClassProp(p) => Some(ClassMember::ClassProp(ClassProp {
+                is_optional: false,
+                definite: false,
+                ..p
+            })),
+
             _ => Some(member),
         })
     }
@@ -231,6 +258,24 @@ impl Fold<Vec<ModuleItem>> for Strip {
 
         self.phase = Phase::DropImports;
 
+        let old_namespace_exports = take(&mut self.namespace_exports);
+        // Namespaces should not redeclare classes, functions and enums.
+        let mut declared = items
+            .iter()
+            .filter_map(|item| match item {
+                ModuleItem::Stmt(Stmt::Decl(decl))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => {
+                    match decl {
+                        Decl::Class(ClassDecl { ident, .. })
+                        | Decl::Fn(FnDecl { ident, .. })
+                        | Decl::TsEnum(TsEnumDecl { id: ident, .. }) => Some(ident.sym.clone()),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .collect::<FxHashSet<_>>();
+
         // Second pass
         let mut stmts = Vec::with_capacity(items.len());
         for item in items {
@@ -296,6 +341,14 @@ impl Fold<Vec<ModuleItem>> for Strip {
                     self.handle_enum(e, &mut stmts)
                 }
 
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    decl: Decl::TsModule(decl),
+                    ..
+                })) => self.handle_namespace(decl, true, &mut declared, &mut stmts),
+                ModuleItem::Stmt(Stmt::Decl(Decl::TsModule(decl))) => {
+                    self.handle_namespace(decl, false, &mut declared, &mut stmts)
+                }
+
                 ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
                     expr: box Expr::Ident(ref i),
                     ..
@@ -320,16 +373,11 @@ impl Fold<Vec<ModuleItem>> for Strip {
                     ..
                 })))
                 | ModuleItem::Stmt(Stmt::Decl(Decl::TsInterface(..)))
-                | ModuleItem::Stmt(Stmt::Decl(Decl::TsModule(..)))
                 | ModuleItem::Stmt(Stmt::Decl(Decl::TsTypeAlias(..)))
                 | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
                     decl: Decl::TsInterface(..),
                     ..
                 }))
-                | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
-                    decl: Decl::TsModule(..),
-                    ..
-                }))
                 | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
                     decl: Decl::TsTypeAlias(..),
                     ..
@@ -357,35 +405,62 @@ impl Fold<Vec<ModuleItem>> for Strip {
                 | ModuleItem::ModuleDecl(ModuleDecl::TsNamespaceExport(..)) => continue,
 
                 ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(import)) => {
+                    // Aliases which are not used as a value are removed.
                     if !import.is_export {
-                        continue;
+                        let entry = self
+                            .scope
+                            .imported_idents
+                            .get(&(import.id.sym.clone(), import.id.span.ctxt()));
+                        match entry {
+                            Some(&DeclInfo {
+                                has_concrete: true, ..
+                            }) => {}
+                            _ => continue,
+                        }
                     }
 
-                    stmts.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
-                        span: DUMMY_SP,
-                        decl: Decl::Var(VarDecl {
-                            span: DUMMY_SP,
-                            kind: VarDeclKind::Var,
-                            decls: vec![VarDeclarator {
-                                span: DUMMY_SP,
-                                name: Pat::Ident(import.id),
-                                init: Some(box module_ref_to_expr(import.module_ref)),
-                                definite: false,
-                            }],
-                            declare: false,
-                        }),
-                    })));
-                }
+                    match import.module_ref {
+                        TsModuleRef::TsEntityName(name) => {
+                            // import A = B.C;
+                            //  -> var A = B.C;
+                            let decl = Decl::Var(VarDecl {
+                                span: import.span,
+                                kind: VarDeclKind::Var,
+                                decls: vec![VarDeclarator {
+                                    span: DUMMY_SP,
+                                    name: Pat::Ident(import.id),
+                                    init: Some(box ts_entity_name_to_expr(name)),
+                                    definite: false,
+                                }],
+                                declare: false,
+                            });
+
+                            if import.is_export {
+                                stmts.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(
+                                    ExportDecl {
+                                        span: DUMMY_SP,
+                                        decl,
+                                    },
+                                )));
+                            } else {
+                                stmts.push(ModuleItem::Stmt(Stmt::Decl(decl)));
+                            }
+                        }
 
-                ModuleItem::ModuleDecl(ModuleDecl::TsExportAssignment(export)) => {
-                    stmts.push(ModuleItem::ModuleDecl(
-                        ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
-                            span: export.span(),
-                            expr: export.expr,
-                        })
-                        .fold_with(self),
-                    ))
+                        // `import foo = require('foo')` is handled by module transforms.
+                        TsModuleRef::TsExternalModuleRef(..) => {
+                            stmts.push(ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(import)))
+                        }
+                    }
                 }
+
+                // `export = foo` is handled by module transforms.
+                ModuleItem::ModuleDecl(ModuleDecl::TsExportAssignment(export)) => stmts.push(
+                    ModuleItem::ModuleDecl(ModuleDecl::TsExportAssignment(TsExportAssignment {
+                        expr: export.expr.fold_with(self),
+                        ..export
+                    })),
+                ),
                 ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(mut export)) => {
                     // if specifier become empty, we remove export statement.
 
@@ -414,6 +489,7 @@ impl Fold<Vec<ModuleItem>> for Strip {
             };
         }
         self.phase = old;
+        self.namespace_exports = old_namespace_exports;
 
         stmts
     }
@@ -434,6 +510,258 @@ impl Strip {
         node
     }
 
+    /// Lowers a namespace to the pattern `tsc` emits.
+    ///
+    /// ```js
+    /// var Foo;
+    /// (function (Foo) {
+    ///     Foo.a = 1;
+    /// })(Foo || (Foo = {}));
+    /// ```
+    ///
+    /// Items of the namespace should be stripped before calling this method.
+    fn handle_namespace(
+        &mut self,
+        decl: TsModuleDecl,
+        is_export: bool,
+        declared: &mut FxHashSet<JsWord>,
+        stmts: &mut Vec<ModuleItem>,
+    ) {
+        let id = match decl.id {
+            TsModuleName::Ident(id) => id,
+            TsModuleName::Str(..) => return,
+        };
+        if decl.declare || decl.global {
+            return;
+        }
+
+        let items = match decl.body {
+            Some(TsNamespaceBody::TsModuleBlock(block)) => block.body,
+            // namespace Foo.Bar {}
+            Some(TsNamespaceBody::TsNamespaceDecl(inner)) => {
+                let mut items = vec![];
+                self.handle_namespace(
+                    TsModuleDecl {
+                        span: inner.span,
+                        declare: inner.declare,
+                        global: inner.global,
+                        id: TsModuleName::Ident(inner.id),
+                        body: Some(*inner.body),
+                    },
+                    true,
+                    &mut Default::default(),
+                    &mut items,
+                );
+                items
+            }
+            None => return,
+        };
+
+        // Non-instantiated namespace
+        if items.iter().all(|item| match item {
+            ModuleItem::Stmt(Stmt::Empty(..)) => true,
+            _ => false,
+        }) {
+            return;
+        }
+
+        // Enums and namespaces nested in this block are lowered to
+        // `export var E;` followed by an IIFE.
+        let nested: FxHashSet<JsWord> = items
+            .iter()
+            .filter_map(|item| match item {
+                ModuleItem::Stmt(stmt) => namespace_iife_id(stmt).map(|i| i.sym.clone()),
+                _ => None,
+            })
+            .collect();
+
+        // Names declared in this block. References to them are not rewritten
+        // even if they are exported.
+        let mut locals = FxHashSet::default();
+        let exported = self
+            .namespace_exports
+            .entry(id.sym.clone())
+            .or_insert_with(Default::default);
+        for item in &items {
+            match item {
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    decl: Decl::Var(var),
+                    ..
+                })) => {
+                    let ids: Vec<Id> = find_ids(&var.decls);
+                    for (sym, _) in ids {
+                        if nested.contains(&sym) {
+                            locals.insert(sym.clone());
+                        }
+                        exported.insert(sym);
+                    }
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    decl: Decl::Fn(FnDecl { ident, .. }),
+                    ..
+                }))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    decl: Decl::Class(ClassDecl { ident, .. }),
+                    ..
+                })) => {
+                    locals.insert(ident.sym.clone());
+                    exported.insert(ident.sym.clone());
+                }
+                ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => {
+                    let ids: Vec<Id> = find_ids(&var.decls);
+                    locals.extend(ids.into_iter().map(|id| id.0));
+                }
+                ModuleItem::Stmt(Stmt::Decl(Decl::Fn(FnDecl { ident, .. })))
+                | ModuleItem::Stmt(Stmt::Decl(Decl::Class(ClassDecl { ident, .. }))) => {
+                    locals.insert(ident.sym.clone());
+                }
+                _ => {}
+            }
+        }
+        let mut refs = exported.clone();
+        for local in &locals {
+            refs.remove(local);
+        }
+
+        let mut body = Vec::with_capacity(items.len());
+        for item in items {
+            match item {
+                ModuleItem::Stmt(Stmt::Empty(..)) => {}
+
+                // (function (E) {})(E || (E = {}));
+                // ->
+                // (function (E) {})(E = Foo.E || (Foo.E = {}));
+                ModuleItem::Stmt(stmt) => match namespace_iife_id(&stmt).cloned() {
+                    Some(inner) if exported.contains(&inner.sym) => match stmt {
+                        Stmt::Expr(ExprStmt {
+                            span,
+                            expr: box Expr::Call(call),
+                        }) => body.push(Stmt::Expr(ExprStmt {
+                            span,
+                            expr: box Expr::Call(CallExpr {
+                                args: vec![AssignExpr {
+                                    span: DUMMY_SP,
+                                    op: op!("="),
+                                    left: PatOrExpr::Pat(box Pat::Ident(inner.clone())),
+                                    right: box namespace_iife_arg(id.clone().member(inner.clone())),
+                                }
+                                .as_arg()],
+                                ..call
+                            }),
+                        })),
+                        stmt => body.push(stmt),
+                    },
+                    _ => body.push(stmt),
+                },
+
+                // export const a = 1; -> Foo.a = 1;
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    decl: Decl::Var(var),
+                    ..
+                })) => {
+                    for decl in var.decls {
+                        let init = match decl.init {
+                            Some(init) => init,
+                            None => {
+                                // export var E; -> var E;
+                                let is_nested = match decl.name {
+                                    Pat::Ident(ref i) => nested.contains(&i.sym),
+                                    _ => false,
+                                };
+                                if is_nested {
+                                    body.push(Stmt::Decl(Decl::Var(VarDecl {
+                                        span: var.span,
+                                        kind: VarDeclKind::Var,
+                                        declare: false,
+                                        decls: vec![decl],
+                                    })));
+                                }
+                                continue;
+                            }
+                        };
+
+                        let expr = match decl.name {
+                            Pat::Ident(i) => Expr::Assign(AssignExpr {
+                                span: decl.span,
+                                op: op!("="),
+                                left: PatOrExpr::Expr(box id.clone().member(i)),
+                                right: init,
+                            }),
+                            // ({ a: Foo.a } = init);
+                            name => Expr::Paren(ParenExpr {
+                                span: decl.span,
+                                expr: box Expr::Assign(AssignExpr {
+                                    span: decl.span,
+                                    op: op!("="),
+                                    left: PatOrExpr::Pat(
+                                        box name.fold_with(&mut NamespaceMember { namespace: &id }),
+                                    ),
+                                    right: init,
+                                }),
+                            }),
+                        };
+                        body.push(expr.into_stmt());
+                    }
+                }
+
+                // export function a() {} -> function a() {} Foo.a = a;
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => {
+                    let ident = match decl {
+                        Decl::Fn(FnDecl { ref ident, .. })
+                        | Decl::Class(ClassDecl { ref ident, .. }) => Some(ident.clone()),
+                        _ => None,
+                    };
+                    body.push(Stmt::Decl(decl));
+
+                    if let Some(ident) = ident {
+                        body.push(
+                            AssignExpr {
+                                span: DUMMY_SP,
+                                op: op!("="),
+                                left: PatOrExpr::Expr(box id.clone().member(ident.clone())),
+                                right: box Expr::Ident(ident),
+                            }
+                            .into_stmt(),
+                        );
+                    }
+                }
+
+                // Other module declarations are not allowed in a namespace.
+                ModuleItem::ModuleDecl(..) => {}
+            }
+        }
+
+        let body = body.fold_with(&mut NamespaceRefRewriter {
+            namespace: &id,
+            exported: &refs,
+        });
+
+        if declared.insert(id.sym.clone()) {
+            let var = VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                declare: false,
+                decls: vec![VarDeclarator {
+                    span: id.span,
+                    name: Pat::Ident(id.clone()),
+                    init: None,
+                    definite: false,
+                }],
+            };
+
+            if is_export {
+                stmts.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    span: decl.span,
+                    decl: Decl::Var(var),
+                })));
+            } else {
+                stmts.push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))));
+            }
+        }
+
+        stmts.push(ModuleItem::Stmt(namespace_iife(id, body)));
+    }
+
     fn handle_enum(&mut self, e: TsEnumDecl, stmts: &mut Vec<ModuleItem>) {
         /// Value does not contain TsLit::Bool
         type EnumValues = FxHashMap<Id, TsLit>;
@@ -482,6 +810,7 @@ impl Strip {
                                 op!(">>>") => ((l.round() as u64) >> (r.round() as u64)) as _,
                                 _ => Err(())?,
                             },
+                            raw: None,
                         })
                     }
                     (TsLit::Str(l), TsLit::Str(r)) if expr.op == op!(bin, "+") => TsLit::Str(Str {
@@ -510,7 +839,7 @@ impl Strip {
             if let Some(expr) = init {
                 match expr {
                     Expr::Lit(Lit::Str(s)) => return Ok(TsLit::Str(s.clone())),
-                    Expr::Lit(Lit::Num(s)) => return Ok(TsLit::Number(*s)),
+                    Expr::Lit(Lit::Num(s)) => return Ok(TsLit::Number(s.clone())),
                     Expr::Bin(ref bin) => return compute_bin(e, span, values, &bin),
                     Expr::Paren(ref paren) => {
                         return compute(e, span, values, default, Some(&paren.expr))
@@ -558,6 +887,7 @@ impl Strip {
                                         op!("~") => (!(v as i32)) as f64,
                                         _ => Err(())?,
                                     },
+                                    raw: None,
                                 }))
                             }
                             TsLit::Str(_) => {}
@@ -579,6 +909,7 @@ impl Strip {
                     return Ok(TsLit::Number(Number {
                         span,
                         value: value as _,
+                        raw: None,
                     }));
                 }
             }
@@ -650,100 +981,80 @@ impl Strip {
             _ => true,
         });
 
-        stmts.push(
-            CallExpr {
-                span: DUMMY_SP,
-                callee: FnExpr {
-                    ident: None,
-                    function: Function {
+        let body = members
+            .into_iter()
+            .enumerate()
+            .map(|(_, (m, val))| {
+                let value = match m.id {
+                    TsEnumMemberId::Str(s) => s,
+                    TsEnumMemberId::Ident(i) => Str {
+                        span: i.span,
+                        value: i.sym,
+                        has_escape: false,
+                    },
+                };
+                let prop = if no_init_required {
+                    box Expr::Lit(Lit::Str(value.clone()))
+                } else {
+                    box Expr::Assign(AssignExpr {
                         span: DUMMY_SP,
-                        decorators: Default::default(),
-                        is_async: false,
-                        is_generator: false,
-                        type_params: Default::default(),
-                        params: vec![Param {
-                            span: id.span,
-                            decorators: vec![],
-                            pat: Pat::Ident(id.clone()),
-                        }],
-                        body: Some(BlockStmt {
+                        left: PatOrExpr::Expr(box Expr::Member(MemberExpr {
                             span: DUMMY_SP,
-                            stmts: members
-                                .into_iter()
-                                .enumerate()
-                                .map(|(_, (m, val))| {
-                                    let value = match m.id {
-                                        TsEnumMemberId::Str(s) => s,
-                                        TsEnumMemberId::Ident(i) => Str {
-                                            span: i.span,
-                                            value: i.sym,
-                                            has_escape: false,
-                                        },
-                                    };
-                                    let prop = if no_init_required {
-                                        box Expr::Lit(Lit::Str(value.clone()))
-                                    } else {
-                                        box Expr::Assign(AssignExpr {
-                                            span: DUMMY_SP,
-                                            left: PatOrExpr::Expr(box Expr::Member(MemberExpr {
-                                                span: DUMMY_SP,
-                                                obj: id.clone().as_obj(),
-                                                prop: box Expr::Lit(Lit::Str(value.clone())),
-                                                computed: true,
-                                            })),
-                                            op: op!("="),
-                                            right: box val,
-                                        })
-                                    };
-
-                                    // Foo[Foo["a"] = 0] = "a";
-                                    AssignExpr {
-                                        span: DUMMY_SP,
-                                        left: PatOrExpr::Expr(box Expr::Member(MemberExpr {
-                                            obj: id.clone().as_obj(),
-                                            span: DUMMY_SP,
-                                            computed: true,
-
-                                            // Foo["a"] = 0
-                                            prop,
-                                        })),
-                                        op: op!("="),
-                                        right: if rhs_should_be_name {
-                                            box Expr::Lit(Lit::Str(value.clone()))
-                                        } else {
-                                            m.init.unwrap_or_else(|| {
-                                                box Expr::Lit(Lit::Str(value.clone()))
-                                            })
-                                        },
-                                    }
-                                    .into_stmt()
-                                })
-                                .collect(),
-                        }),
-                        return_type: Default::default(),
-                    },
-                }
-                .as_callee(),
-                args: vec![BinExpr {
+                            obj: id.clone().as_obj(),
+                            prop: box Expr::Lit(Lit::Str(value.clone())),
+                            computed: true,
+                        })),
+                        op: op!("="),
+                        right: box val,
+                    })
+                };
+
+                // Foo[Foo["a"] = 0] = "a";
+                AssignExpr {
                     span: DUMMY_SP,
-                    left: box Expr::Ident(id.clone()),
-                    op: op!("||"),
-                    right: box Expr::Assign(AssignExpr {
+                    left: PatOrExpr::Expr(box Expr::Member(MemberExpr {
+                        obj: id.clone().as_obj(),
                         span: DUMMY_SP,
-                        left: PatOrExpr::Pat(Pat::Ident(id.clone()).into()),
-                        op: op!("="),
-                        right: box Expr::Object(ObjectLit {
-                            span: DUMMY_SP,
-                            props: vec![],
-                        }),
-                    }),
+                        computed: true,
+
+                        // Foo["a"] = 0
+                        prop,
+                    })),
+                    op: op!("="),
+                    right: if rhs_should_be_name {
+                        box Expr::Lit(Lit::Str(value.clone()))
+                    } else {
+                        m.init
+                            .unwrap_or_else(|| box Expr::Lit(Lit::Str(value.clone())))
+                    },
+                }
+                .into_stmt()
+            })
+            .collect();
+
+        stmts.push(ModuleItem::Stmt(namespace_iife(id, body)))
+    }
+}
+
+impl Fold<TsImportEqualsDecl> for Strip {
+    fn fold(&mut self, import: TsImportEqualsDecl) -> TsImportEqualsDecl {
+        match self.phase {
+            Phase::Analysis => {
+                // Idents in module reference are used as a value.
+                let module_ref = import.module_ref.fold_with(self);
+
+                self.scope.imported_idents.insert(
+                    (import.id.sym.clone(), import.id.span.ctxt()),
+                    Default::default(),
+                );
+
+                TsImportEqualsDecl {
+                    module_ref,
+                    ..import
                 }
-                .as_arg()],
-                type_args: Default::default(),
             }
-            .into_stmt()
-            .into(),
-        )
+            Phase::DropImports => import,
+        }
     }
 }
 
@@ -885,6 +1196,14 @@ impl Fold<Stmt> for Strip {
 
         match stmt {
             Stmt::Decl(decl) => match decl {
+                // Instantiated namespaces are handled by `handle_namespace`.
+                Decl::TsModule(TsModuleDecl {
+                    id: TsModuleName::Ident(..),
+                    declare: false,
+                    body: Some(ref body),
+                    ..
+                }) if is_instantiated(body) => Stmt::Decl(decl),
+
                 Decl::TsInterface(..)
                 | Decl::TsModule(..)
                 | Decl::TsTypeAlias(..)
@@ -976,10 +1295,338 @@ impl Fold<Module> for Strip {
     }
 }
 
-fn module_ref_to_expr(r: TsModuleRef) -> Expr {
-    match r {
-        TsModuleRef::TsEntityName(name) => ts_entity_name_to_expr(name),
-        _ => unimplemented!("export import A = B where B != TsEntityName\nB: {:?}", r),
+/// Returns true if the namespace contains a value.
+fn is_instantiated(body: &TsNamespaceBody) -> bool {
+    fn is_decl_instantiated(decl: &Decl) -> bool {
+        match *decl {
+            Decl::TsInterface(..) | Decl::TsTypeAlias(..) => false,
+            Decl::Var(VarDecl { declare, .. })
+            | Decl::Class(ClassDecl { declare, .. })
+            | Decl::TsEnum(TsEnumDecl { declare, .. }) => !declare,
+            Decl::Fn(FnDecl {
+                declare,
+                ref function,
+                ..
+            }) => !declare && function.body.is_some(),
+            Decl::TsModule(TsModuleDecl {
+                declare, ref body, ..
+            }) => !declare && body.as_ref().map(is_instantiated).unwrap_or(false),
+        }
+    }
+
+    match *body {
+        TsNamespaceBody::TsModuleBlock(ref block) => block.body.iter().any(|item| match item {
+            ModuleItem::Stmt(Stmt::Decl(decl))
+            | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => {
+                is_decl_instantiated(decl)
+            }
+            ModuleItem::Stmt(Stmt::Empty(..)) => false,
+            ModuleItem::Stmt(..) => true,
+            ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(import)) => import.is_export,
+            ModuleItem::ModuleDecl(..) => false,
+        }),
+        TsNamespaceBody::TsNamespaceDecl(ref decl) => !decl.declare && is_instantiated(&decl.body),
+    }
+}
+
+/// `(function (Foo) { ... })(Foo || (Foo = {}));`
+fn namespace_iife(id: Ident, body: Vec<Stmt>) -> Stmt {
+    CallExpr {
+        span: DUMMY_SP,
+        callee: FnExpr {
+            ident: None,
+            function: Function {
+                span: DUMMY_SP,
+                decorators: Default::default(),
+                is_async: false,
+                is_generator: false,
+                type_params: Default::default(),
+                params: vec![Param {
+                    span: id.span,
+                    decorators: vec![],
+                    pat: Pat::Ident(id.clone()),
+                }],
+                body: Some(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: body,
+                }),
+                return_type: Default::default(),
+            },
+        }
+        .as_callee(),
+        args: vec![namespace_iife_arg(Expr::Ident(id)).as_arg()],
+        type_args: Default::default(),
+    }
+    .into_stmt()
+}
+
+/// `Foo || (Foo = {})`
+fn namespace_iife_arg(target: Expr) -> Expr {
+    Expr::Bin(BinExpr {
+        span: DUMMY_SP,
+        left: box target.clone(),
+        op: op!("||"),
+        right: box Expr::Assign(AssignExpr {
+            span: DUMMY_SP,
+            left: match target {
+                Expr::Ident(i) => PatOrExpr::Pat(box Pat::Ident(i)),
+                _ => PatOrExpr::Expr(box target),
+            },
+            op: op!("="),
+            right: box Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props: vec![],
+            }),
+        }),
+    })
+}
+
+/// Returns the parameter of an IIFE created by [namespace_iife].
+fn namespace_iife_id(stmt: &Stmt) -> Option<&Ident> {
+    match stmt {
+        Stmt::Expr(ExprStmt {
+            expr:
+                box Expr::Call(CallExpr {
+                    callee: ExprOrSuper::Expr(callee),
+                    args,
+                    ..
+                }),
+            ..
+        }) if args.len() == 1 => match &**callee {
+            Expr::Fn(FnExpr {
+                ident: None,
+                function,
+            }) => match &*function.params {
+                [Param {
+                    pat: Pat::Ident(i), ..
+                }] => match &*args[0].expr {
+                    Expr::Bin(BinExpr {
+                        left: box Expr::Ident(left),
+                        op: op!("||"),
+                        ..
+                    }) if left.sym == i.sym => Some(i),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Converts a pattern of an exported variable to an assignment target.
+///
+/// `{ a, b: [c] }` -> `{ a: Foo.a, b: [Foo.c] }`
+struct NamespaceMember<'a> {
+    namespace: &'a Ident,
+}
+
+impl Fold<Pat> for NamespaceMember<'_> {
+    fn fold(&mut self, p: Pat) -> Pat {
+        match p {
+            Pat::Ident(i) => Pat::Expr(box self.namespace.clone().member(i)),
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+impl Fold<ObjectPatProp> for NamespaceMember<'_> {
+    fn fold(&mut self, p: ObjectPatProp) -> ObjectPatProp {
+        match p {
+            ObjectPatProp::Assign(AssignPatProp { span, key, value }) => {
+                let target = Pat::Expr(box self.namespace.clone().member(key.clone()));
+
+                ObjectPatProp::KeyValue(KeyValuePatProp {
+                    key: PropName::Ident(key),
+                    value: box match value {
+                        Some(right) => Pat::Assign(AssignPat {
+                            span,
+                            left: box target,
+                            right,
+                            type_ann: None,
+                        }),
+                        None => target,
+                    },
+                })
+            }
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Expr> for NamespaceMember<'_> {
+    fn fold(&mut self, e: Expr) -> Expr {
+        e
+    }
+}
+
+impl Fold<PropName> for NamespaceMember<'_> {
+    fn fold(&mut self, n: PropName) -> PropName {
+        n
+    }
+}
+
+/// Replaces references to exported variables of a namespace with member
+/// expressions.
+struct NamespaceRefRewriter<'a> {
+    namespace: &'a Ident,
+    exported: &'a FxHashSet<JsWord>,
+}
+
+impl NamespaceRefRewriter<'_> {
+    fn should_rewrite(&self, i: &Ident) -> bool {
+        self.exported.contains(&i.sym)
+    }
+
+    fn shadowed<T>(&mut self, names: Vec<Id>, node: T) -> T
+    where
+        for<'any> T: FoldWith<NamespaceRefRewriter<'any>>,
+    {
+        if names.iter().all(|name| !self.exported.contains(&name.0)) {
+            return node.fold_children(self);
+        }
+
+        let mut exported = self.exported.clone();
+        for name in names {
+            exported.remove(&name.0);
+        }
+
+        node.fold_children(&mut NamespaceRefRewriter {
+            namespace: self.namespace,
+            exported: &exported,
+        })
+    }
+}
+
+impl Fold<Expr> for NamespaceRefRewriter<'_> {
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::Ident(i) if self.should_rewrite(&i) => self.namespace.clone().member(i),
+            Expr::Member(MemberExpr {
+                span,
+                obj,
+                prop,
+                computed,
+            }) => Expr::Member(MemberExpr {
+                span,
+                obj: obj.fold_with(self),
+                prop: if computed { prop.fold_with(self) } else { prop },
+                computed,
+            }),
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+impl Fold<PatOrExpr> for NamespaceRefRewriter<'_> {
+    fn fold(&mut self, n: PatOrExpr) -> PatOrExpr {
+        match n {
+            PatOrExpr::Pat(box Pat::Ident(i)) if self.should_rewrite(&i) => {
+                PatOrExpr::Expr(box self.namespace.clone().member(i))
+            }
+            _ => n.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Prop> for NamespaceRefRewriter<'_> {
+    fn fold(&mut self, p: Prop) -> Prop {
+        match p {
+            Prop::Shorthand(i) if self.should_rewrite(&i) => Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(i.clone()),
+                value: box self.namespace.clone().member(i),
+            }),
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+impl Fold<PropName> for NamespaceRefRewriter<'_> {
+    fn fold(&mut self, n: PropName) -> PropName {
+        match n {
+            PropName::Computed(c) => PropName::Computed(c.fold_with(self)),
+            _ => n,
+        }
+    }
+}
+
+impl Fold<Function> for NamespaceRefRewriter<'_> {
+    fn fold(&mut self, f: Function) -> Function {
+        let mut names: Vec<Id> = find_ids(&f.params);
+        f.body.visit_with(&mut VarCollector { to: &mut names });
+
+        self.shadowed(names, f)
+    }
+}
+
+impl Fold<ArrowExpr> for NamespaceRefRewriter<'_> {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        let mut names: Vec<Id> = find_ids(&f.params);
+        f.body.visit_with(&mut VarCollector { to: &mut names });
+
+        self.shadowed(names, f)
+    }
+}
+
+impl Fold<BlockStmt> for NamespaceRefRewriter<'_> {
+    fn fold(&mut self, b: BlockStmt) -> BlockStmt {
+        let mut names = vec![];
+        for stmt in &b.stmts {
+            match stmt {
+                Stmt::Decl(Decl::Var(var)) if var.kind != VarDeclKind::Var => {
+                    names.extend(find_ids::<_, Id>(&var.decls))
+                }
+                Stmt::Decl(Decl::Fn(FnDecl { ident, .. }))
+                | Stmt::Decl(Decl::Class(ClassDecl { ident, .. })) => names.push(ident.to_id()),
+                _ => {}
+            }
+        }
+
+        self.shadowed(names, b)
+    }
+}
+
+impl Fold<ForStmt> for NamespaceRefRewriter<'_> {
+    fn fold(&mut self, s: ForStmt) -> ForStmt {
+        let names = match &s.init {
+            Some(VarDeclOrExpr::VarDecl(var)) if var.kind != VarDeclKind::Var => {
+                find_ids(&var.decls)
+            }
+            _ => vec![],
+        };
+
+        self.shadowed(names, s)
+    }
+}
+
+impl Fold<ForInStmt> for NamespaceRefRewriter<'_> {
+    fn fold(&mut self, s: ForInStmt) -> ForInStmt {
+        let names = match &s.left {
+            VarDeclOrPat::VarDecl(var) if var.kind != VarDeclKind::Var => find_ids(&var.decls),
+            _ => vec![],
+        };
+
+        self.shadowed(names, s)
+    }
+}
+
+impl Fold<ForOfStmt> for NamespaceRefRewriter<'_> {
+    fn fold(&mut self, s: ForOfStmt) -> ForOfStmt {
+        let names = match &s.left {
+            VarDeclOrPat::VarDecl(var) if var.kind != VarDeclKind::Var => find_ids(&var.decls),
+            _ => vec![],
+        };
+
+        self.shadowed(names, s)
+    }
+}
+
+impl Fold<CatchClause> for NamespaceRefRewriter<'_> {
+    fn fold(&mut self, c: CatchClause) -> CatchClause {
+        let names: Vec<Id> = find_ids(&c.param);
+
+        self.shadowed(names, c)
     }
 }
 
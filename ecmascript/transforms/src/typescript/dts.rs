@@ -0,0 +1,903 @@
+use crate::{
+    pass::Pass,
+    util::{find_ids, prop_name_to_expr, HANDLER},
+};
+use fxhash::FxHashSet;
+use std::mem::replace;
+use swc_atoms::{js_word, JsWord};
+use swc_common::{Fold, Span, Spanned, Visit, VisitWith, DUMMY_SP};
+use swc_ecma_ast::*;
+
+/// Reduces a typescript module to its declarations, like `tsc
+/// --emitDeclarationOnly`.
+///
+///  - Bodies and initializers are removed, and `declare` is added.
+///  - Private members are emitted without types.
+///  - Statements and declarations which are not reachable from exports are
+///    removed.
+///
+/// Types are not inferred, so exported values should have explicit type
+/// annotations unless they are initialized with a literal. An error is
+/// reported for each missing annotation.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```ts
+/// export function foo(a: number, b = ''): string {
+///     return a + b;
+/// }
+/// ```
+///
+/// ## Out
+///
+/// ```ts
+/// export declare function foo(a: number, b?: string): string;
+/// ```
+pub fn dts() -> impl Pass {
+    Dts::default()
+}
+
+#[derive(Default)]
+struct Dts {
+    in_namespace: bool,
+}
+
+/// A declaration (or an import) in a module item list.
+struct Entry {
+    item: ModuleItem,
+    /// Names declared by `item`. Empty if the item should always be emitted.
+    names: Vec<JsWord>,
+}
+
+impl Entry {
+    fn always(item: ModuleItem) -> Self {
+        Entry {
+            item,
+            names: vec![],
+        }
+    }
+}
+
+impl Fold<Module> for Dts {
+    fn fold(&mut self, m: Module) -> Module {
+        let is_module = m.body.iter().any(is_module_decl);
+
+        let mut body = self.fold_items(m.body, is_module);
+
+        // `export {}` is required to make the declaration file a module.
+        if is_module && !body.iter().any(is_module_decl) {
+            body.push(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+                NamedExport {
+                    span: DUMMY_SP,
+                    specifiers: vec![],
+                    src: None,
+                    type_only: false,
+                },
+            )));
+        }
+
+        Module { body, ..m }
+    }
+}
+
+impl Fold<Script> for Dts {
+    fn fold(&mut self, s: Script) -> Script {
+        let body = self
+            .fold_items(s.body.into_iter().map(ModuleItem::Stmt).collect(), false)
+            .into_iter()
+            .filter_map(|item| match item {
+                ModuleItem::Stmt(stmt) => Some(stmt),
+                _ => None,
+            })
+            .collect();
+
+        Script { body, ..s }
+    }
+}
+
+impl Dts {
+    /// If `is_module` is true, declarations which are not exported nor used by
+    /// exported declarations are removed.
+    fn fold_items(&mut self, items: Vec<ModuleItem>, is_module: bool) -> Vec<ModuleItem> {
+        let mut entries = Vec::with_capacity(items.len());
+
+        // Name of the last overload signature.
+        let mut overloaded = None;
+        for item in items {
+            match fn_decl(&item) {
+                Some((name, true)) => {
+                    // Implementation of overloaded function is not a part of
+                    // declarations.
+                    if overloaded.as_ref() == Some(name) {
+                        overloaded = None;
+                        continue;
+                    }
+                    overloaded = None;
+                }
+                Some((name, false)) => overloaded = Some(name.clone()),
+                None => overloaded = None,
+            }
+
+            if let Some(entry) = self.strip_item(item) {
+                entries.push(entry);
+            }
+        }
+
+        let mut used = FxHashSet::default();
+        let mut kept: Vec<_> = entries
+            .iter()
+            .map(|entry| !is_module || entry.names.is_empty())
+            .collect();
+
+        for (entry, _) in entries.iter().zip(&kept).filter(|(_, kept)| **kept) {
+            entry.item.visit_with(&mut RefFinder { used: &mut used });
+        }
+
+        // Declarations used by kept declarations should also be kept.
+        loop {
+            let mut changed = false;
+
+            for (entry, kept) in entries.iter().zip(kept.iter_mut()) {
+                if !*kept && entry.names.iter().any(|name| used.contains(name)) {
+                    *kept = true;
+                    changed = true;
+                    entry.item.visit_with(&mut RefFinder { used: &mut used });
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        entries
+            .into_iter()
+            .zip(kept)
+            .filter(|(_, kept)| *kept)
+            .map(|(entry, _)| match entry.item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(mut import))
+                    if is_module && !import.specifiers.is_empty() =>
+                {
+                    import.specifiers.retain(|s| {
+                        let local = match s {
+                            ImportSpecifier::Named(s) => &s.local,
+                            ImportSpecifier::Default(s) => &s.local,
+                            ImportSpecifier::Namespace(s) => &s.local,
+                        };
+                        used.contains(&local.sym)
+                    });
+
+                    ModuleItem::ModuleDecl(ModuleDecl::Import(import))
+                }
+                item => item,
+            })
+            .collect()
+    }
+
+    fn strip_item(&mut self, item: ModuleItem) -> Option<Entry> {
+        match item {
+            ModuleItem::ModuleDecl(decl) => match decl {
+                ModuleDecl::Import(import) => {
+                    let names = import
+                        .specifiers
+                        .iter()
+                        .map(|s| match s {
+                            ImportSpecifier::Named(s) => s.local.sym.clone(),
+                            ImportSpecifier::Default(s) => s.local.sym.clone(),
+                            ImportSpecifier::Namespace(s) => s.local.sym.clone(),
+                        })
+                        .collect();
+
+                    Some(Entry {
+                        item: ModuleItem::ModuleDecl(ModuleDecl::Import(import)),
+                        names,
+                    })
+                }
+
+                ModuleDecl::ExportDecl(ExportDecl { span, decl }) => {
+                    let decl = self.strip_decl(decl)?;
+
+                    Some(Entry::always(ModuleItem::ModuleDecl(
+                        ModuleDecl::ExportDecl(ExportDecl { span, decl }),
+                    )))
+                }
+
+                ModuleDecl::ExportDefaultDecl(ExportDefaultDecl { span, decl }) => {
+                    let decl = match decl {
+                        DefaultDecl::Class(c) => DefaultDecl::Class(ClassExpr {
+                            class: self.strip_class(c.class),
+                            ..c
+                        }),
+                        DefaultDecl::Fn(f) => DefaultDecl::Fn(FnExpr {
+                            function: strip_fn(f.function, true),
+                            ..f
+                        }),
+                        DefaultDecl::TsInterfaceDecl(i) => DefaultDecl::TsInterfaceDecl(i),
+                    };
+
+                    Some(Entry::always(ModuleItem::ModuleDecl(
+                        ModuleDecl::ExportDefaultDecl(ExportDefaultDecl { span, decl }),
+                    )))
+                }
+
+                ModuleDecl::ExportDefaultExpr(export) => match *export.expr {
+                    Expr::Ident(..) => Some(Entry::always(ModuleItem::ModuleDecl(
+                        ModuleDecl::ExportDefaultExpr(export),
+                    ))),
+                    _ => {
+                        error(
+                            export.span,
+                            "default export should be an identifier to generate declarations",
+                        );
+                        None
+                    }
+                },
+
+                ModuleDecl::TsImportEquals(import) if !import.is_export => Some(Entry {
+                    names: vec![import.id.sym.clone()],
+                    item: ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(import)),
+                }),
+
+                _ => Some(Entry::always(ModuleItem::ModuleDecl(decl))),
+            },
+
+            ModuleItem::Stmt(Stmt::Decl(decl)) => {
+                let names = match decl {
+                    // Global augmentations and ambient modules
+                    Decl::TsModule(TsModuleDecl { global: true, .. })
+                    | Decl::TsModule(TsModuleDecl {
+                        id: TsModuleName::Str(..),
+                        ..
+                    }) => vec![],
+                    _ => decl_names(&decl),
+                };
+                let decl = self.strip_decl(decl)?;
+
+                Some(Entry {
+                    item: ModuleItem::Stmt(Stmt::Decl(decl)),
+                    names,
+                })
+            }
+
+            ModuleItem::Stmt(..) => None,
+        }
+    }
+
+    fn strip_decl(&mut self, decl: Decl) -> Option<Decl> {
+        // `declare` is not allowed in an ambient context.
+        let declare = !self.in_namespace;
+
+        Some(match decl {
+            Decl::Class(c) => Decl::Class(ClassDecl {
+                declare,
+                class: self.strip_class(c.class),
+                ..c
+            }),
+            Decl::Fn(f) => Decl::Fn(FnDecl {
+                declare,
+                function: strip_fn(f.function, true),
+                ..f
+            }),
+            Decl::Var(v) => {
+                let kind = v.kind;
+                let decls: Vec<_> = v
+                    .decls
+                    .into_iter()
+                    .filter_map(|d| strip_var_declarator(kind, d))
+                    .collect();
+                if decls.is_empty() {
+                    return None;
+                }
+
+                Decl::Var(VarDecl {
+                    declare,
+                    decls,
+                    ..v
+                })
+            }
+            Decl::TsEnum(e) => Decl::TsEnum(TsEnumDecl { declare, ..e }),
+            Decl::TsModule(m) => {
+                let body = m.body.map(|body| self.strip_namespace_body(body));
+
+                Decl::TsModule(TsModuleDecl { declare, body, ..m })
+            }
+            Decl::TsInterface(..) | Decl::TsTypeAlias(..) => decl,
+        })
+    }
+
+    fn strip_namespace_body(&mut self, body: TsNamespaceBody) -> TsNamespaceBody {
+        let old = replace(&mut self.in_namespace, true);
+
+        let body = match body {
+            TsNamespaceBody::TsModuleBlock(block) => {
+                let is_module = block.body.iter().any(is_module_decl);
+
+                TsNamespaceBody::TsModuleBlock(TsModuleBlock {
+                    body: self.fold_items(block.body, is_module),
+                    ..block
+                })
+            }
+            TsNamespaceBody::TsNamespaceDecl(decl) => {
+                TsNamespaceBody::TsNamespaceDecl(TsNamespaceDecl {
+                    declare: false,
+                    body: box self.strip_namespace_body(*decl.body),
+                    ..decl
+                })
+            }
+        };
+
+        self.in_namespace = old;
+        body
+    }
+
+    fn strip_class(&mut self, class: Class) -> Class {
+        let mut body = Vec::with_capacity(class.body.len());
+        let mut private_names = vec![];
+
+        // Name of the last overload signature.
+        let mut overloaded: Option<JsWord> = None;
+
+        for member in class.body {
+            let signature = match member {
+                ClassMember::Constructor(ref c) => {
+                    Some((js_word!("constructor"), c.body.is_some()))
+                }
+                ClassMember::Method(ref m) => {
+                    prop_name_sym(&m.key).map(|name| (name, m.function.body.is_some()))
+                }
+                _ => None,
+            };
+            match signature {
+                Some((name, true)) => {
+                    if overloaded.take() == Some(name) {
+                        continue;
+                    }
+                }
+                Some((name, false)) => overloaded = Some(name),
+                None => overloaded = None,
+            }
+
+            match member {
+                ClassMember::Constructor(c) => {
+                    let is_private = c.accessibility == Some(Accessibility::Private);
+
+                    let mut params = Vec::with_capacity(c.params.len());
+                    for param in c.params {
+                        match param {
+                            ParamOrTsParamProp::Param(p) => {
+                                params.push(ParamOrTsParamProp::Param(strip_param(p)))
+                            }
+                            ParamOrTsParamProp::TsParamProp(p) => {
+                                let pat = strip_pat(match p.param {
+                                    TsParamPropParam::Ident(i) => Pat::Ident(i),
+                                    TsParamPropParam::Assign(a) => Pat::Assign(a),
+                                });
+
+                                // Parameter properties are declared as properties.
+                                if let Pat::Ident(ref i) = pat {
+                                    let type_ann =
+                                        if p.accessibility == Some(Accessibility::Private) {
+                                            None
+                                        } else {
+                                            i.type_ann.clone()
+                                        };
+
+                                    body.push(ClassMember::ClassProp(ClassProp {
+                                        span: p.span,
+                                        key: box Expr::Ident(Ident::new(i.sym.clone(), i.span)),
+                                        value: None,
+                                        type_ann,
+                                        is_static: false,
+                                        decorators: vec![],
+                                        computed: false,
+                                        accessibility: p.accessibility,
+                                        is_abstract: false,
+                                        is_optional: false,
+                                        readonly: p.readonly,
+                                        definite: false,
+                                    }));
+                                }
+
+                                params.push(ParamOrTsParamProp::Param(Param {
+                                    span: p.span,
+                                    decorators: vec![],
+                                    pat,
+                                }))
+                            }
+                        }
+                    }
+
+                    body.push(ClassMember::Constructor(Constructor {
+                        params: if is_private { vec![] } else { params },
+                        body: None,
+                        ..c
+                    }));
+                }
+
+                ClassMember::Method(m) => {
+                    if m.accessibility == Some(Accessibility::Private) {
+                        let name = prop_name_sym(&m.key);
+                        // Getter and setter are emitted only once.
+                        if name.is_some() && private_names.contains(&name) {
+                            continue;
+                        }
+                        private_names.push(name);
+
+                        body.push(ClassMember::ClassProp(ClassProp {
+                            span: m.span,
+                            computed: match m.key {
+                                PropName::Computed(..) => true,
+                                _ => false,
+                            },
+                            key: box prop_name_to_expr(m.key),
+                            value: None,
+                            type_ann: None,
+                            is_static: m.is_static,
+                            decorators: vec![],
+                            accessibility: m.accessibility,
+                            is_abstract: false,
+                            is_optional: false,
+                            readonly: false,
+                            definite: false,
+                        }));
+                        continue;
+                    }
+
+                    let needs_return_type = match m.kind {
+                        MethodKind::Method | MethodKind::Getter => true,
+                        MethodKind::Setter => false,
+                    };
+
+                    body.push(ClassMember::Method(ClassMethod {
+                        function: strip_fn(m.function, needs_return_type),
+                        ..m
+                    }));
+                }
+
+                ClassMember::ClassProp(p) => {
+                    if p.accessibility == Some(Accessibility::Private) {
+                        body.push(ClassMember::ClassProp(ClassProp {
+                            value: None,
+                            type_ann: None,
+                            decorators: vec![],
+                            definite: false,
+                            ..p
+                        }));
+                        continue;
+                    }
+
+                    let (type_ann, value) = match (p.type_ann, p.value) {
+                        (Some(type_ann), _) => (Some(type_ann), None),
+                        (None, Some(value)) if p.readonly && is_const_lit(&value) => {
+                            (None, Some(value))
+                        }
+                        (None, Some(value)) => (Some(infer_type(value, "property")), None),
+                        (None, None) => (Some(keyword(TsKeywordTypeKind::TsAnyKeyword)), None),
+                    };
+
+                    body.push(ClassMember::ClassProp(ClassProp {
+                        type_ann,
+                        value,
+                        decorators: vec![],
+                        definite: false,
+                        ..p
+                    }));
+                }
+
+                // TODO: Emit `#private;` for private names
+                ClassMember::PrivateMethod(..) | ClassMember::PrivateProp(..) => {}
+
+                ClassMember::TsIndexSignature(..) => body.push(member),
+            }
+        }
+
+        Class {
+            decorators: vec![],
+            body,
+            ..class
+        }
+    }
+}
+
+fn strip_var_declarator(kind: VarDeclKind, d: VarDeclarator) -> Option<VarDeclarator> {
+    let mut name = match d.name {
+        Pat::Ident(i) => i,
+        _ => {
+            error(
+                d.span,
+                "destructuring variable declarations are not supported while generating \
+                 declarations",
+            );
+            return None;
+        }
+    };
+
+    let init = match d.init {
+        Some(init) if name.type_ann.is_none() => {
+            // `declare const a = 1;`
+            if kind == VarDeclKind::Const && is_const_lit(&init) {
+                Some(init)
+            } else {
+                name.type_ann = Some(infer_type(init, "variable"));
+                None
+            }
+        }
+        _ => {
+            if name.type_ann.is_none() {
+                name.type_ann = Some(keyword(TsKeywordTypeKind::TsAnyKeyword));
+            }
+            None
+        }
+    };
+
+    Some(VarDeclarator {
+        name: Pat::Ident(name),
+        init,
+        definite: false,
+        ..d
+    })
+}
+
+fn strip_fn(f: Function, needs_return_type: bool) -> Function {
+    let return_type = match f.return_type {
+        Some(return_type) => Some(return_type),
+        None if needs_return_type => {
+            error(
+                f.span,
+                "function should have an explicit return type annotation to generate declarations",
+            );
+            Some(keyword(TsKeywordTypeKind::TsAnyKeyword))
+        }
+        None => None,
+    };
+
+    Function {
+        params: f.params.into_iter().map(strip_param).collect(),
+        decorators: vec![],
+        body: None,
+        is_async: false,
+        is_generator: false,
+        return_type,
+        ..f
+    }
+}
+
+fn strip_param(p: Param) -> Param {
+    Param {
+        decorators: vec![],
+        pat: strip_pat(p.pat),
+        ..p
+    }
+}
+
+/// Removes default value of a parameter and makes it optional.
+fn strip_pat(pat: Pat) -> Pat {
+    let span = pat.span();
+    let (default, mut pat) = match pat {
+        Pat::Assign(a) => (Some((a.right, a.type_ann)), remove_defaults(*a.left)),
+        _ => (None, remove_defaults(pat)),
+    };
+
+    let is_rest = match pat {
+        Pat::Rest(..) => true,
+        _ => false,
+    };
+
+    if let Some((optional, type_ann)) = binding_mut(&mut pat) {
+        if let Some((right, ann)) = default {
+            if let Some(optional) = optional {
+                *optional = true;
+            }
+            if type_ann.is_none() {
+                *type_ann = Some(match ann {
+                    Some(ann) => ann,
+                    None => infer_type(right, "parameter"),
+                });
+            }
+        }
+
+        if type_ann.is_none() {
+            error(
+                span,
+                "parameter should have an explicit type annotation to generate declarations",
+            );
+
+            let any = keyword(TsKeywordTypeKind::TsAnyKeyword);
+            *type_ann = Some(if is_rest {
+                TsTypeAnn {
+                    span: DUMMY_SP,
+                    type_ann: box TsType::TsArrayType(TsArrayType {
+                        span: DUMMY_SP,
+                        elem_type: any.type_ann,
+                    }),
+                }
+            } else {
+                any
+            });
+        }
+    }
+
+    pat
+}
+
+/// Removes default values in a binding pattern.
+fn remove_defaults(pat: Pat) -> Pat {
+    match pat {
+        Pat::Assign(a) => remove_defaults(*a.left),
+        Pat::Array(a) => Pat::Array(ArrayPat {
+            elems: a
+                .elems
+                .into_iter()
+                .map(|elem| elem.map(remove_defaults))
+                .collect(),
+            ..a
+        }),
+        Pat::Object(o) => Pat::Object(ObjectPat {
+            props: o
+                .props
+                .into_iter()
+                .map(|prop| match prop {
+                    ObjectPatProp::KeyValue(p) => ObjectPatProp::KeyValue(KeyValuePatProp {
+                        value: box remove_defaults(*p.value),
+                        ..p
+                    }),
+                    ObjectPatProp::Assign(p) => {
+                        ObjectPatProp::Assign(AssignPatProp { value: None, ..p })
+                    }
+                    ObjectPatProp::Rest(p) => ObjectPatProp::Rest(RestPat {
+                        arg: box remove_defaults(*p.arg),
+                        ..p
+                    }),
+                })
+                .collect(),
+            ..o
+        }),
+        Pat::Rest(r) => Pat::Rest(RestPat {
+            arg: box remove_defaults(*r.arg),
+            ..r
+        }),
+        _ => pat,
+    }
+}
+
+/// Returns `optional` and `type_ann` of a parameter.
+fn binding_mut(pat: &mut Pat) -> Option<(Option<&mut bool>, &mut Option<TsTypeAnn>)> {
+    match pat {
+        Pat::Ident(i) => Some((Some(&mut i.optional), &mut i.type_ann)),
+        Pat::Array(a) => Some((Some(&mut a.optional), &mut a.type_ann)),
+        Pat::Object(o) => Some((Some(&mut o.optional), &mut o.type_ann)),
+        Pat::Rest(r) => Some((None, &mut r.type_ann)),
+        _ => None,
+    }
+}
+
+/// Infers type of `e` without type information, and reports an error if it's
+/// not possible.
+fn infer_type(e: Box<Expr>, what: &str) -> TsTypeAnn {
+    let span = e.span();
+
+    let kind = match *e {
+        Expr::Lit(Lit::Str(..)) | Expr::Tpl(..) => TsKeywordTypeKind::TsStringKeyword,
+        Expr::Lit(Lit::Num(..))
+        | Expr::Unary(UnaryExpr {
+            op: op!(unary, "-"),
+            arg: box Expr::Lit(Lit::Num(..)),
+            ..
+        }) => TsKeywordTypeKind::TsNumberKeyword,
+        Expr::Lit(Lit::Bool(..)) => TsKeywordTypeKind::TsBooleanKeyword,
+        Expr::Lit(Lit::BigInt(..)) => TsKeywordTypeKind::TsBigIntKeyword,
+
+        Expr::TsAs(TsAsExpr { type_ann, .. })
+        | Expr::TsTypeAssertion(TsTypeAssertion { type_ann, .. }) => {
+            return TsTypeAnn { span, type_ann };
+        }
+
+        Expr::Paren(ParenExpr { expr, .. }) => return infer_type(expr, what),
+
+        Expr::Arrow(ArrowExpr {
+            span,
+            params,
+            type_params,
+            return_type,
+            ..
+        }) => return fn_type(span, params, type_params, return_type),
+        Expr::Fn(FnExpr {
+            function:
+                Function {
+                    span,
+                    params,
+                    type_params,
+                    return_type,
+                    ..
+                },
+            ..
+        }) => {
+            let params = params.into_iter().map(|p| p.pat).collect();
+            return fn_type(span, params, type_params, return_type);
+        }
+
+        _ => {
+            error(
+                span,
+                &format!(
+                    "{} should have an explicit type annotation to generate declarations",
+                    what
+                ),
+            );
+            TsKeywordTypeKind::TsAnyKeyword
+        }
+    };
+
+    keyword(kind)
+}
+
+/// Creates the type of a function expression.
+fn fn_type(
+    span: Span,
+    params: Vec<Pat>,
+    type_params: Option<TsTypeParamDecl>,
+    return_type: Option<TsTypeAnn>,
+) -> TsTypeAnn {
+    let params = params
+        .into_iter()
+        .filter_map(|pat| match strip_pat(pat) {
+            Pat::Ident(i) => Some(TsFnParam::Ident(i)),
+            Pat::Array(a) => Some(TsFnParam::Array(a)),
+            Pat::Rest(r) => Some(TsFnParam::Rest(r)),
+            Pat::Object(o) => Some(TsFnParam::Object(o)),
+            _ => None,
+        })
+        .collect();
+
+    let type_ann = return_type.unwrap_or_else(|| {
+        error(
+            span,
+            "function should have an explicit return type annotation to generate declarations",
+        );
+        keyword(TsKeywordTypeKind::TsAnyKeyword)
+    });
+
+    TsTypeAnn {
+        span: DUMMY_SP,
+        type_ann: box TsType::TsFnOrConstructorType(TsFnOrConstructorType::TsFnType(TsFnType {
+            span: DUMMY_SP,
+            params,
+            type_params,
+            type_ann,
+        })),
+    }
+}
+
+fn keyword(kind: TsKeywordTypeKind) -> TsTypeAnn {
+    TsTypeAnn {
+        span: DUMMY_SP,
+        type_ann: box TsType::TsKeywordType(TsKeywordType {
+            span: DUMMY_SP,
+            kind,
+        }),
+    }
+}
+
+/// Literals which can be used as an initializer in an ambient context.
+fn is_const_lit(e: &Expr) -> bool {
+    match e {
+        Expr::Lit(Lit::Str(..))
+        | Expr::Lit(Lit::Num(..))
+        | Expr::Lit(Lit::Bool(..))
+        | Expr::Lit(Lit::BigInt(..))
+        | Expr::Unary(UnaryExpr {
+            op: op!(unary, "-"),
+            arg: box Expr::Lit(Lit::Num(..)),
+            ..
+        }) => true,
+        _ => false,
+    }
+}
+
+fn error(span: Span, msg: &str) {
+    HANDLER.with(|handler| handler.struct_span_err(span, msg).emit());
+}
+
+fn is_module_decl(item: &ModuleItem) -> bool {
+    match item {
+        ModuleItem::ModuleDecl(..) => true,
+        _ => false,
+    }
+}
+
+/// Returns the name of a function declaration and whether it has a body.
+fn fn_decl(item: &ModuleItem) -> Option<(&JsWord, bool)> {
+    match item {
+        ModuleItem::Stmt(Stmt::Decl(Decl::Fn(f)))
+        | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            decl: Decl::Fn(f), ..
+        })) => Some((&f.ident.sym, f.function.body.is_some())),
+        _ => None,
+    }
+}
+
+fn decl_names(decl: &Decl) -> Vec<JsWord> {
+    match decl {
+        Decl::Class(c) => vec![c.ident.sym.clone()],
+        Decl::Fn(f) => vec![f.ident.sym.clone()],
+        Decl::Var(v) => find_ids::<_, Ident>(&v.decls)
+            .into_iter()
+            .map(|i| i.sym)
+            .collect(),
+        Decl::TsInterface(i) => vec![i.id.sym.clone()],
+        Decl::TsTypeAlias(a) => vec![a.id.sym.clone()],
+        Decl::TsEnum(e) => vec![e.id.sym.clone()],
+        Decl::TsModule(m) => match m.id {
+            TsModuleName::Ident(ref i) => vec![i.sym.clone()],
+            TsModuleName::Str(..) => vec![],
+        },
+    }
+}
+
+fn prop_name_sym(key: &PropName) -> Option<JsWord> {
+    match key {
+        PropName::Ident(i) => Some(i.sym.clone()),
+        PropName::Str(s) => Some(s.value.clone()),
+        _ => None,
+    }
+}
+
+/// Finds names referenced by declarations.
+struct RefFinder<'a> {
+    used: &'a mut FxHashSet<JsWord>,
+}
+
+impl Visit<TsEntityName> for RefFinder<'_> {
+    fn visit(&mut self, name: &TsEntityName) {
+        match name {
+            TsEntityName::Ident(i) => {
+                self.used.insert(i.sym.clone());
+            }
+            TsEntityName::TsQualifiedName(q) => q.left.visit_with(self),
+        }
+    }
+}
+
+impl Visit<Expr> for RefFinder<'_> {
+    fn visit(&mut self, e: &Expr) {
+        match e {
+            Expr::Ident(i) => {
+                self.used.insert(i.sym.clone());
+            }
+            Expr::Member(m) => {
+                m.obj.visit_with(self);
+                if m.computed {
+                    m.prop.visit_with(self);
+                }
+            }
+            _ => e.visit_children(self),
+        }
+    }
+}
+
+impl Visit<ClassProp> for RefFinder<'_> {
+    fn visit(&mut self, p: &ClassProp) {
+        if p.computed {
+            p.key.visit_with(self);
+        }
+        p.type_ann.visit_with(self);
+        p.value.visit_with(self);
+    }
+}
+
+impl Visit<NamedExport> for RefFinder<'_> {
+    fn visit(&mut self, export: &NamedExport) {
+        // Re-exports do not reference local declarations.
+        if export.src.is_some() {
+            return;
+        }
+
+        for s in &export.specifiers {
+            if let ExportSpecifier::Named(s) = s {
+                self.used.insert(s.orig.sym.clone());
+            }
+        }
+    }
+}
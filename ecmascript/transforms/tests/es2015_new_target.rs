@@ -0,0 +1,103 @@
+#![feature(box_syntax)]
+#![feature(test)]
+#![feature(box_patterns)]
+#![feature(specialization)]
+
+use swc_common::chain;
+use swc_ecma_parser::Syntax;
+use swc_ecma_transforms::compat::es2015::{new_target, Classes};
+
+#[macro_use]
+mod common;
+
+fn syntax() -> Syntax {
+    Default::default()
+}
+
+test!(
+    syntax(),
+    |_| new_target(),
+    function,
+    r#"
+function Foo() {
+  console.log(new.target);
+  var bar = () => new.target;
+}
+"#,
+    r#"
+function Foo() {
+  console.log(this instanceof Foo ? this.constructor : void 0);
+  var bar = () => this instanceof Foo ? this.constructor : void 0;
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| new_target(),
+    anonymous_function,
+    r#"
+var Foo = function () {
+  return new.target;
+};
+var bar = function () {};
+"#,
+    r#"
+var Foo = function _target() {
+  return this instanceof _target ? this.constructor : void 0;
+};
+var bar = function () {};
+"#
+);
+
+test!(
+    syntax(),
+    |_| new_target(),
+    class,
+    r#"
+class Foo {
+  constructor() {
+    this.target = new.target;
+  }
+
+  method() {
+    return new.target;
+  }
+}
+"#,
+    r#"
+class Foo {
+  constructor() {
+    this.target = this.constructor;
+  }
+
+  method() {
+    return void 0;
+  }
+}
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| chain!(new_target(), Classes::default()),
+    exec,
+    r#"
+function Foo() {
+  return new.target;
+}
+
+class Bar {
+  constructor() {
+    this.target = new.target;
+  }
+}
+
+class Baz extends Bar {}
+
+expect(new Foo()).toBe(Foo);
+expect(Foo()).toBeUndefined();
+expect(new Bar().target).toBe(Bar);
+expect(new Baz().target).toBe(Baz);
+"#
+);
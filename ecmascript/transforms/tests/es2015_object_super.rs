@@ -0,0 +1,117 @@
+#![feature(box_syntax)]
+#![feature(test)]
+#![feature(box_patterns)]
+#![feature(specialization)]
+
+use swc_ecma_parser::Syntax;
+use swc_ecma_transforms::compat::es2015::object_super;
+
+#[macro_use]
+mod common;
+
+fn syntax() -> Syntax {
+    Default::default()
+}
+
+test!(
+    syntax(),
+    |_| object_super(),
+    method,
+    r#"
+var obj = {
+  foo() {
+    return super.foo();
+  },
+  bar() {}
+};
+"#,
+    r#"
+var _obj;
+var obj = _obj = {
+  foo() {
+    return _get(_getPrototypeOf(_obj), "foo", this).call(this);
+  },
+  bar() {}
+};
+"#
+);
+
+test!(
+    syntax(),
+    |_| object_super(),
+    set,
+    r#"
+var obj = {
+  set foo(v) {
+    super.foo = v;
+  }
+};
+"#,
+    r#"
+var _obj;
+var obj = _obj = {
+  set foo(v) {
+    _set(_getPrototypeOf(_obj), "foo", v, this, true);
+  }
+};
+"#
+);
+
+test!(
+    syntax(),
+    |_| object_super(),
+    nested,
+    r#"
+var obj = {
+  foo() {
+    return {
+      bar() {
+        return super.bar;
+      }
+    };
+  }
+};
+"#,
+    r#"
+var obj = {
+  foo() {
+    var _obj;
+    return _obj = {
+      bar() {
+        return _get(_getPrototypeOf(_obj), "bar", this);
+      }
+    };
+  }
+};
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| object_super(),
+    exec,
+    r#"
+var proto = {
+  value: 1,
+  greet() {
+    return "hi";
+  }
+};
+
+var obj = {
+  greet() {
+    return super.greet() + "!";
+  },
+  get value() {
+    return super.value + 1;
+  },
+  set value(v) {
+    super.value = v;
+  }
+};
+Object.setPrototypeOf(obj, proto);
+
+expect(obj.greet()).toBe("hi!");
+expect(obj.value).toBe(2);
+"#
+);
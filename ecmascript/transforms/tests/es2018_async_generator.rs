@@ -0,0 +1,192 @@
+#![feature(box_syntax)]
+#![feature(test)]
+#![feature(box_patterns)]
+#![feature(specialization)]
+
+use swc_common::{chain, Fold};
+use swc_ecma_ast::Module;
+use swc_ecma_parser::Syntax;
+use swc_ecma_transforms::compat::{es2017::async_to_generator, es2018::async_generator};
+
+#[macro_use]
+mod common;
+
+fn syntax() -> Syntax {
+    Syntax::default()
+}
+
+fn tr() -> impl Fold<Module> {
+    async_generator()
+}
+
+test!(
+    syntax(),
+    |_| tr(),
+    fn_decl,
+    r#"
+async function* foo() {
+    await 1;
+    yield 2;
+    yield* bar();
+}
+"#,
+    r#"
+function foo() {
+    return _wrapAsyncGenerator(function* () {
+        yield _awaitAsyncGenerator(1);
+        yield 2;
+        yield* _asyncGeneratorDelegate(_asyncIterator(bar()), _awaitAsyncGenerator);
+    }).apply(this, arguments);
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    params,
+    r#"
+const foo = async function* (a, { b }, c = 1) {
+    yield a + b + c;
+};
+"#,
+    r#"
+const foo = function (a, _) {
+    return _wrapAsyncGenerator(function* (a, { b }, c = 1) {
+        yield a + b + c;
+    }).apply(this, arguments);
+};
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    nested_fn,
+    r#"
+async function* foo() {
+    yield async () => await 1;
+}
+"#,
+    r#"
+function foo() {
+    return _wrapAsyncGenerator(function* () {
+        yield async () => await 1;
+    }).apply(this, arguments);
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    class_method_super,
+    r#"
+class Foo extends Bar {
+    async *foo() {
+        yield super.foo();
+    }
+}
+"#,
+    r#"
+class Foo extends Bar {
+    foo() {
+        var _super_foo = (..._args) => super.foo(..._args);
+        return _wrapAsyncGenerator(function* () {
+            yield _super_foo();
+        }).apply(this, arguments);
+    }
+}
+"#,
+    ok_if_code_eq
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    for_await_in_async_fn,
+    r#"
+async function foo() {
+    for await (const x of y) {
+        console.log(x);
+    }
+}
+"#,
+    r#"
+async function foo() {
+    {
+        var _iteratorNormalCompletion = true, _didIteratorError = false, _iteratorError;
+        try {
+            for (var _iterator = _asyncIterator(y), _step, _value; _step = await _iterator.next(), _iteratorNormalCompletion = _step.done, _value = await _step.value, !_iteratorNormalCompletion; _iteratorNormalCompletion = true) {
+                const x = _value;
+                console.log(x);
+            }
+        } catch (err) {
+            _didIteratorError = true;
+            _iteratorError = err;
+        } finally {
+            try {
+                if (!_iteratorNormalCompletion && _iterator.return != null) {
+                    await _iterator.return();
+                }
+            } finally {
+                if (_didIteratorError) {
+                    throw _iteratorError;
+                }
+            }
+        }
+    }
+}
+"#,
+    ok_if_code_eq
+);
+
+test_exec!(
+    syntax(),
+    |_| chain!(async_generator(), async_to_generator()),
+    exec_async_generator,
+    r#"
+async function* gen() {
+    const a = await Promise.resolve(1);
+    yield a;
+    yield* [2, 3];
+    return 4;
+}
+
+const values = [];
+return (async () => {
+    for await (const v of gen()) {
+        values.push(v);
+    }
+    expect(values).toEqual([1, 2, 3]);
+})();
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| chain!(async_generator(), async_to_generator()),
+    exec_for_await_break,
+    r#"
+let returned = false;
+const iterable = {
+    [Symbol.asyncIterator]() {
+        let i = 0;
+        return {
+            next: () => Promise.resolve({ value: i++, done: false }),
+            return() {
+                returned = true;
+                return Promise.resolve({ done: true });
+            },
+        };
+    },
+};
+
+return (async () => {
+    for await (const v of iterable) {
+        if (v === 2) break;
+    }
+    expect(returned).toBe(true);
+})();
+"#
+);
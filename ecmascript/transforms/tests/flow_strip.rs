@@ -0,0 +1,94 @@
+#![feature(box_syntax)]
+#![feature(test)]
+#![feature(box_patterns)]
+#![feature(specialization)]
+
+use swc_ecma_parser::{FlowConfig, Syntax};
+use swc_ecma_transforms::flow::strip;
+
+#[macro_use]
+mod common;
+
+macro_rules! to {
+    ($name:ident, $from:expr, $to:expr) => {
+        test!(
+            Syntax::Flow(Default::default()),
+            |_| strip(),
+            $name,
+            $from,
+            $to,
+            ok_if_code_eq
+        );
+    };
+}
+
+to!(
+    annotations,
+    "// @flow
+function foo(a: number, b?: ?string, ...rest: Array<mixed>): boolean %checks {
+    return typeof a === 'number';
+}
+const f = (x: number): string => String(x);
+const g = <T>(x: T, cb: (T, ?Error) => void): (T => void) => cb;
+let y: { +a: string, ...B } = (foo(1): any);",
+    "function foo(a, b, ...rest) {
+    return typeof a === 'number';
+}
+const f = (x) => String(x);
+const g = (x, cb) => cb;
+let y = foo(1);"
+);
+
+to!(
+    type_decls,
+    "type A = {| a: string, b: { [string]: number } |};
+opaque type B = string;
+export type C = ?A;
+export opaque type D: string = string;
+declare opaque type E;
+interface F { +x: number; -y: string }
+declare function g(x: number): string;
+export type { A };",
+    ""
+);
+
+to!(
+    imports,
+    "import type A from 'a';
+import typeof B from 'b';
+import { type C, D } from 'c';
+import E, { type F } from 'e';
+const x: A | B | C | F = new D(E);",
+    "import { D } from 'c';
+import E from 'e';
+const x = new D(E);"
+);
+
+to!(
+    class,
+    "class A<+T: Object> extends B<T> implements C {
+    +x: T;
+    static y: number = 1;
+    method<U>(u: U): T {
+        return (u: any);
+    }
+}",
+    "class A extends B {
+    static y = 1;
+    method(u) {
+        return u;
+    }
+}"
+);
+
+test!(
+    Syntax::Flow(FlowConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| strip(),
+    jsx,
+    "const el = <div>{(x: any)}</div>;",
+    "const el = <div>{x}</div>;",
+    ok_if_code_eq
+);
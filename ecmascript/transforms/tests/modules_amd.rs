@@ -1176,3 +1176,33 @@ export const foo = function () {
 });
 "
 );
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        dynamic_import: true,
+        ..Default::default()
+    }),
+    |_| tr(Default::default()),
+    dynamic_import,
+    "
+export function load() {
+    return import('foo');
+}
+",
+    "
+define(['require', 'exports'], function(_require, _exports) {
+    'use strict';
+    Object.defineProperty(_exports, '__esModule', {
+        value: true
+    });
+    _exports.load = load;
+    function load() {
+        return new Promise(function(_resolve, _reject) {
+            _require(['foo'], function(imported) {
+                return _resolve(_interopRequireWildcard(imported));
+            }, _reject);
+        });
+    }
+});
+"
+);
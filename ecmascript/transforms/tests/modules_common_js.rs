@@ -4157,3 +4157,104 @@ const resources = [
       }
   ];"
 );
+
+fn dynamic_import_syntax() -> ::swc_ecma_parser::Syntax {
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        dynamic_import: true,
+        ..Default::default()
+    })
+}
+
+test!(
+    dynamic_import_syntax(),
+    |_| tr(Default::default()),
+    dynamic_import,
+    "import('foo').then(m => m.default);",
+    "
+'use strict';
+Promise.resolve().then(function() {
+    return _interopRequireWildcard(require('foo'));
+}).then((m) => m.default);
+"
+);
+
+test!(
+    dynamic_import_syntax(),
+    |_| tr(Config {
+        no_interop: true,
+        ..Default::default()
+    }),
+    dynamic_import_no_interop,
+    "
+import foo from 'foo';
+export async function load() {
+    return import(foo.path);
+}
+",
+    "
+'use strict';
+Object.defineProperty(exports, '__esModule', {
+    value: true
+});
+exports.load = load;
+var _foo = require('foo');
+async function load() {
+    return Promise.resolve().then(function() {
+        return require(_foo.default.path);
+    });
+}
+"
+);
+
+fn ts_syntax() -> ::swc_ecma_parser::Syntax {
+    ::swc_ecma_parser::Syntax::Typescript(Default::default())
+}
+
+test!(
+    ts_syntax(),
+    |_| chain!(typescript::strip(), tr(Default::default())),
+    ts_import_equals,
+    "
+import foo = require('foo');
+import unused = require('unused');
+foo.bar();
+",
+    "
+'use strict';
+var foo = require('foo');
+foo.bar();
+"
+);
+
+test!(
+    ts_syntax(),
+    |_| chain!(typescript::strip(), tr(Default::default())),
+    ts_export_import_equals,
+    "
+export import foo = require('foo');
+",
+    "
+'use strict';
+Object.defineProperty(exports, '__esModule', {
+    value: true
+});
+var foo = require('foo');
+exports.foo = foo;
+"
+);
+
+test!(
+    ts_syntax(),
+    |_| chain!(typescript::strip(), tr(Default::default())),
+    ts_export_assignment,
+    "
+class Foo {}
+export = Foo;
+",
+    "
+'use strict';
+class Foo {
+}
+module.exports = Foo;
+"
+);
@@ -0,0 +1,191 @@
+#![feature(box_syntax)]
+#![feature(test)]
+#![feature(box_patterns)]
+#![feature(specialization)]
+
+use swc_common::{chain, Fold};
+use swc_ecma_ast::Module;
+use swc_ecma_parser::{EsConfig, Syntax};
+use swc_ecma_transforms::{
+    modules::system_js::{system_js, Config},
+    resolver,
+};
+
+#[macro_use]
+mod common;
+
+fn syntax() -> Syntax {
+    Syntax::Es(EsConfig {
+        dynamic_import: true,
+        import_meta: true,
+        ..Default::default()
+    })
+}
+
+fn tr(config: Config) -> impl Fold<Module> {
+    chain!(resolver(), system_js(config))
+}
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    imports_and_exports,
+    "
+import foo, { bar as baz } from 'foo';
+import * as ns from 'ns';
+export var a = 1;
+export function f() {
+    return a + foo + baz + ns;
+}
+export default class {}
+a = 2;
+",
+    "
+System.register(['foo', 'ns'], function(_export, _context) {
+    'use strict';
+    var foo, baz, ns, a, _default;
+    function f() {
+        return a + foo + baz + ns;
+    }
+    _export('f', f);
+    return {
+        setters: [function(_foo) {
+            foo = _foo.default;
+            baz = _foo.bar;
+        }, function(_ns) {
+            ns = _ns;
+        }],
+        execute: function() {
+            _export('a', a = 1);
+            _export('default', _default = class _default {
+            });
+            _export('a', a = 2);
+        }
+    };
+});
+"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    re_exports,
+    "
+export * from 'a';
+export { x as y } from 'b';
+import { z } from 'c';
+export { z };
+console.log(import.meta.url);
+import('d');
+",
+    "
+System.register(['a', 'b', 'c'], function(_export, _context) {
+    'use strict';
+    var z;
+    return {
+        setters: [function(_a) {
+            var _exportObj = {
+            };
+            for(var _key in _a){
+                if (_key !== 'default' && _key !== '__esModule') _exportObj[_key] = _a[_key];
+            }
+            _export(_exportObj);
+        }, function(_b) {
+            _export({
+                'y': _b.x
+            });
+        }, function(_c) {
+            z = _c.z;
+            _export('z', _c.z);
+        }],
+        execute: function() {
+            console.log(_context.meta.url);
+            _context.import('d');
+        }
+    };
+});
+"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    live_binding,
+    "
+export let count = 0;
+export function inc() {
+    count++;
+}
+export { count as c };
+",
+    "
+System.register([], function(_export, _context) {
+    'use strict';
+    var count;
+    function inc() {
+        _export('c', _export('count', count = +count + 1)), count - 1;
+    }
+    _export('inc', inc);
+    return {
+        setters: [],
+        execute: function() {
+            _export('c', _export('count', count = 0));
+        }
+    };
+});
+"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    export_without_init,
+    "
+export let a, b = 1;
+let c;
+export { c as d };
+a = 2;
+",
+    "
+System.register([], function(_export, _context) {
+    'use strict';
+    var a, b, c;
+    return {
+        setters: [],
+        execute: function() {
+            _export('a', void 0);
+            _export('b', b = 1);
+            _export('d', void 0);
+            _export('a', a = 2);
+        }
+    };
+});
+"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    top_level_this,
+    "
+const self = this;
+function foo() {
+    return this;
+}
+",
+    "
+System.register([], function(_export, _context) {
+    'use strict';
+    var self;
+    function foo() {
+        return this;
+    }
+    return {
+        setters: [],
+        execute: function() {
+            self = void 0;
+        }
+    };
+});
+"
+);
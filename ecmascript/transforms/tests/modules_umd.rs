@@ -1878,3 +1878,22 @@ export const foo = function () {
     _exports.foo = foo;
 });"
 );
+
+#[test]
+#[should_panic(expected = "Dynamic import() cannot be used when targeting umd modules")]
+fn dynamic_import() {
+    Tester::run(|tester| {
+        let tr = tr(tester, Default::default());
+        tester.apply_transform(
+            tr,
+            "input.js",
+            ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+                dynamic_import: true,
+                ..Default::default()
+            }),
+            "import('foo');",
+        )?;
+
+        Ok(())
+    });
+}
@@ -3551,7 +3551,10 @@ test!(
     ignore,
     syntax(),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
         Classes::default(),
     ),
@@ -4436,7 +4439,10 @@ test!(
     ignore,
     syntax(),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
         Classes::default()
     ),
@@ -4857,3 +4863,99 @@ expect(foo.y).toBe('bar');
 
 "#
 );
+
+test!(
+    syntax(),
+    |_| class_properties(),
+    private_logical_or_assign,
+    r#"
+class A {
+  #x;
+
+  m() {
+    this.#x ||= 1;
+  }
+}
+"#,
+    r#"
+class A {
+  m() {
+    _classPrivateFieldGet(this, _x) || _classPrivateFieldSet(this, _x, 1);
+  }
+
+  constructor() {
+    _x.set(this, {
+      writable: true,
+      value: void 0
+    });
+  }
+}
+
+var _x = new WeakMap();
+"#
+);
+
+test!(
+    syntax(),
+    |_| class_properties(),
+    private_logical_and_assign,
+    r#"
+class A {
+  #x;
+
+  m(obj) {
+    obj.#x &&= 1;
+  }
+}
+"#,
+    r#"
+class A {
+  m(obj) {
+    _classPrivateFieldGet(obj, _x) && _classPrivateFieldSet(obj, _x, 1);
+  }
+
+  constructor() {
+    _x.set(this, {
+      writable: true,
+      value: void 0
+    });
+  }
+}
+
+var _x = new WeakMap();
+"#
+);
+
+test!(
+    syntax(),
+    |_| class_properties(),
+    private_nullish_assign,
+    r#"
+class A {
+  #x;
+
+  m() {
+    this.#x ??= 1;
+  }
+}
+"#,
+    r#"
+class A {
+  m() {
+    var ref;
+    (ref = _classPrivateFieldGet(this, _x)) !== null && ref !== void 0
+      ? ref
+      : _classPrivateFieldSet(this, _x, 1);
+  }
+
+  constructor() {
+    _x.set(this, {
+      writable: true,
+      value: void 0
+    });
+  }
+}
+
+var _x = new WeakMap();
+"#
+);
@@ -2033,7 +2033,10 @@ let A = _decorate([], function(_initialize) {
 test_exec!(
     syntax(true),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_constructors_return_new_constructor_exec,
@@ -2060,7 +2063,10 @@ test!(
     syntax(true),
     |_| chain!(
         typescript::strip(),
-        decorators(decorators::Config { legacy: true })
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        })
     ),
     legacy_regression_10264,
     r#"
@@ -2465,7 +2471,10 @@ export { _class1 as default }
 // legacy_decl_to_expression_class_decorators
 test!(
     syntax(false),
-    |_| decorators(Config { legacy: true }),
+    |_| decorators(Config {
+        legacy: true,
+        ..Default::default()
+    }),
     legacy_decl_to_expression_class_decorators,
     r#"
 export default @dec class A {}
@@ -2484,7 +2493,10 @@ let B = dec((_class1 = class B{
 test_exec!(
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_prototype_methods_numeric_props_exec,
@@ -2507,7 +2519,10 @@ class Example {
 test_exec!(
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_static_properties_mutate_descriptor_exec,
@@ -2619,7 +2634,10 @@ expect(Example._).toBe("__8__");
 test_exec!(
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_static_methods_string_props_exec,
@@ -2642,7 +2660,10 @@ class Example {
 test_exec!(
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_prototype_properties_string_literal_properties_exec,
@@ -2684,7 +2705,10 @@ expect(descs["a-prop"].configurable).toBeTruthy();
 test_exec!(
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_prototype_methods_mutate_descriptor_exec,
@@ -2814,7 +2838,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_object_properties_numeric_props_exec,
@@ -2836,7 +2863,10 @@ const inst = {
 // legacy_decl_to_expression_method_decorators
 test!(
     syntax(false),
-    |_| decorators(Config { legacy: true }),
+    |_| decorators(Config {
+        legacy: true,
+        ..Default::default()
+    }),
     legacy_decl_to_expression_method_decorators,
     r#"
 export default class A {
@@ -2865,7 +2895,10 @@ export { A as default }
 test_exec!(
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_prototype_properties_return_descriptor_exec,
@@ -2979,7 +3012,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_object_properties_string_props_exec,
@@ -3004,7 +3040,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_object_properties_return_descriptor_exec,
@@ -3114,7 +3153,10 @@ expect(inst._).toBe("__8__");
 test_exec!(
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_prototype_methods_string_props_exec,
@@ -3137,7 +3179,10 @@ class Example {
 test!(
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_regression_8041,
@@ -3165,7 +3210,10 @@ export { _class1 as default }
 test_exec!(
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_prototype_methods_return_descriptor_exec,
@@ -3297,7 +3345,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_object_ordering_reverse_order_exec,
@@ -3338,7 +3389,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_object_methods_numeric_props_exec,
@@ -3362,7 +3416,10 @@ const inst = {
 test_exec!(
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_static_properties_return_descriptor_exec,
@@ -3479,7 +3536,10 @@ test_exec!(
     ignore,
     syntax(true),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_export_default_exec,
@@ -3506,7 +3566,10 @@ expect(calls).toEqual(["Foo"]);
 test_exec!(
     syntax(true),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_ordering_reverse_order_exec,
@@ -3550,7 +3613,10 @@ test_exec!(
     ignore,
     syntax(true),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_object_methods_mutate_descriptor_exec,
@@ -3676,7 +3742,10 @@ expect(inst._()).toBe("__8__");
 test_exec!(
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_static_methods_return_descriptor_exec,
@@ -3805,7 +3874,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_object_methods_return_descriptor_exec,
@@ -3933,7 +4005,10 @@ test_exec!(
     ignore,
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_object_methods_string_props_exec,
@@ -3958,7 +4033,10 @@ const inst = {
 test_exec!(
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_prototype_properties_child_classes_properties_exec,
@@ -3998,7 +4076,10 @@ expect(inst.prop2).toBe("__4__");
 test_exec!(
     syntax(false),
     |_| chain!(
-        decorators(decorators::Config { legacy: true }),
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
     ),
     legacy_class_static_methods_mutate_descriptor_exec,
@@ -4123,7 +4204,10 @@ expect(Example._()).toBe("__8__");
 // legacy_regression_8512
 test_exec!(
     syntax(false),
-    |_| decorators(Config { legacy: true }),
+    |_| decorators(Config {
+        legacy: true,
+        ..Default::default()
+    }),
     legacy_regression_8512_exec,
     r#"
 function dec(Class, key, desc) {
@@ -4140,7 +4224,10 @@ class Foo {
 
 test!(
     syntax(false),
-    |_| decorators(Config { legacy: true }),
+    |_| decorators(Config {
+        legacy: true,
+        ..Default::default()
+    }),
     issue_591_1,
     "
 export class Example {
@@ -4174,7 +4261,10 @@ export let Example = ((_class = class Example{
 
 test!(
     syntax(false),
-    |_| decorators(Config { legacy: true }),
+    |_| decorators(Config {
+        legacy: true,
+        ..Default::default()
+    }),
     issue_591_2,
     "class Example {
   @foo() bar = '1';
@@ -4210,7 +4300,13 @@ test!(
         decorators: true,
         ..Default::default()
     }),
-    |_| chain!(typescript::strip(), decorators(Config { legacy: true })),
+    |_| chain!(
+        typescript::strip(),
+        decorators(Config {
+            legacy: true,
+            ..Default::default()
+        })
+    ),
     issue_823_1,
     "import {Debounce} from 'lodash-decorators';
 class Person {
@@ -4246,7 +4342,10 @@ test!(
     }),
     |_| chain!(
         typescript::strip(),
-        decorators(Config { legacy: true }),
+        decorators(Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
         // Classes::default(),
     ),
@@ -4289,7 +4388,10 @@ test!(
     }),
     |_| chain!(
         typescript::strip(),
-        decorators(Config { legacy: true }),
+        decorators(Config {
+            legacy: true,
+            ..Default::default()
+        }),
         class_properties(),
         Classes::default(),
     ),
@@ -4333,3 +4435,79 @@ let Person = ((_class = function() {
 const p = new Person();
 p.save();"
 );
+
+test!(
+    Syntax::Typescript(TsConfig {
+        decorators: true,
+        ..Default::default()
+    }),
+    |_| chain!(
+        decorators(Config {
+            legacy: true,
+            emit_metadata: true,
+        }),
+        typescript::strip(),
+    ),
+    legacy_metadata_method,
+    "class Foo {
+  @dec
+  method(a: string, b: Bar, c?: number | null): void {}
+}",
+    "var _class, _dec, _dec1, _dec2;
+let Foo = ((_class = class Foo {
+    method(a, b, c) {
+    }
+}) || _class, _dec = Reflect.metadata('design:type', Function), _dec1 = \
+     Reflect.metadata('design:paramtypes', [
+    String,
+    typeof Bar === 'undefined' ? Object : Bar,
+    Number
+]), _dec2 = Reflect.metadata('design:returntype', void 0), \
+     _applyDecoratedDescriptor(_class.prototype, 'method', [
+    dec,
+    _dec,
+    _dec1,
+    _dec2
+], Object.getOwnPropertyDescriptor(_class.prototype, 'method'), _class.prototype), _class);"
+);
+
+test_exec!(
+    Syntax::Typescript(TsConfig {
+        decorators: true,
+        ..Default::default()
+    }),
+    |_| chain!(
+        decorators(Config {
+            legacy: true,
+            emit_metadata: true,
+        }),
+        typescript::strip(),
+    ),
+    legacy_metadata_exec,
+    "const metadata = {};
+Reflect.metadata = (k, v) => (target, key) => {
+  metadata[(key || 'class') + ':' + k] = v;
+};
+function dec() {}
+
+class Bar {}
+
+@dec
+class Foo {
+  constructor(a: number, b: Bar | undefined, ...rest: string[]) {}
+
+  @dec
+  get value(): boolean {
+    return true;
+  }
+
+  @dec
+  async run(x: 'a' | 'b', y: number | string) {}
+}
+
+expect(metadata['class:design:paramtypes']).toEqual([Number, Bar, String]);
+expect(metadata['value:design:type']).toBe(Boolean);
+expect(metadata['run:design:type']).toBe(Function);
+expect(metadata['run:design:paramtypes']).toEqual([String, Object]);
+expect(metadata['run:design:returntype']).toBe(Promise);"
+);
@@ -0,0 +1,329 @@
+#![feature(box_syntax)]
+#![feature(test)]
+#![feature(box_patterns)]
+#![feature(specialization)]
+use swc_common::chain;
+use swc_ecma_parser::{EsConfig, Syntax};
+use swc_ecma_transforms::{
+    compat::{
+        es2015::{block_scoping, function_name, Classes},
+        es2020::class_properties,
+    },
+    pass::Pass,
+    resolver,
+};
+
+#[macro_use]
+mod common;
+
+fn syntax() -> Syntax {
+    Syntax::Es(EsConfig {
+        class_private_props: true,
+        class_private_methods: true,
+        class_props: true,
+        ..Default::default()
+    })
+}
+
+fn tr() -> impl Pass {
+    chain!(
+        resolver(),
+        function_name(),
+        class_properties(),
+        Classes::default(),
+        block_scoping(),
+    )
+}
+
+test!(
+    syntax(),
+    |_| chain!(resolver(), class_properties()),
+    method,
+    r#"
+class Foo {
+  #bar() {
+    return this.#baz;
+  }
+
+  #baz = 1;
+
+  test() {
+    return this.#bar();
+  }
+}
+"#,
+    r#"
+class Foo {
+  test() {
+    return _classPrivateMethodGet(this, _bar, _bar_method).call(this);
+  }
+
+  constructor() {
+    _bar.add(this);
+    _baz.set(this, {
+      writable: true,
+      value: 1
+    });
+  }
+}
+
+var _bar = new WeakSet();
+
+function _bar_method() {
+  return _classPrivateFieldGet(this, _baz);
+}
+
+var _baz = new WeakMap();
+"#
+);
+
+test!(
+    syntax(),
+    |_| chain!(resolver(), class_properties()),
+    accessor,
+    r#"
+class Foo {
+  #value = 0;
+
+  get #bar() {
+    return this.#value;
+  }
+
+  set #bar(v) {
+    this.#value = v;
+  }
+
+  test() {
+    this.#bar = 1;
+    return this.#bar;
+  }
+}
+"#,
+    r#"
+class Foo {
+  test() {
+    _classPrivateFieldSet(this, _bar, 1);
+    return _classPrivateFieldGet(this, _bar);
+  }
+
+  constructor() {
+    _bar.set(this, {
+      get: _get_bar,
+      set: _set_bar
+    });
+    _value.set(this, {
+      writable: true,
+      value: 0
+    });
+  }
+}
+
+var _bar = new WeakMap();
+
+function _get_bar() {
+  return _classPrivateFieldGet(this, _value);
+}
+
+function _set_bar(v) {
+  _classPrivateFieldSet(this, _value, v);
+}
+
+var _value = new WeakMap();
+"#
+);
+
+test!(
+    syntax(),
+    |_| chain!(resolver(), class_properties()),
+    static_method,
+    r#"
+class Foo {
+  static #bar() {
+    return 1;
+  }
+
+  static test() {
+    return Foo.#bar();
+  }
+}
+"#,
+    r#"
+class Foo {
+  static test() {
+    return _classStaticPrivateMethodGet(Foo, Foo, _bar_method).call(Foo);
+  }
+}
+
+function _bar_method() {
+  return 1;
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| chain!(resolver(), class_properties()),
+    method_super,
+    r#"
+class Foo extends Bar {
+  #bar() {
+    return super.foo() + super.x;
+  }
+
+  test() {
+    return this.#bar();
+  }
+}
+"#,
+    r#"
+class Foo extends Bar {
+  test() {
+    return _classPrivateMethodGet(this, _bar, _bar_method).call(this);
+  }
+
+  constructor(...args) {
+    super(...args);
+    _bar.add(this);
+  }
+}
+
+var _bar = new WeakSet();
+
+function _bar_method() {
+  return _get(_getPrototypeOf(Foo.prototype), "foo", this).call(this) +
+    _get(_getPrototypeOf(Foo.prototype), "x", this);
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| chain!(resolver(), class_properties()),
+    static_method_super,
+    r#"
+class Foo extends Bar {
+  static #bar() {
+    return super.foo();
+  }
+
+  static test() {
+    return Foo.#bar();
+  }
+}
+"#,
+    r#"
+class Foo extends Bar {
+  static test() {
+    return _classStaticPrivateMethodGet(Foo, Foo, _bar_method).call(Foo);
+  }
+}
+
+function _bar_method() {
+  return _get(_getPrototypeOf(Foo), "foo", this).call(this);
+}
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(),
+    method_exec,
+    r#"
+class Foo {
+  #count = 0;
+
+  #inc(by) {
+    this.#count += by;
+    return this;
+  }
+
+  run() {
+    this.#inc(1);
+    return this.#inc(2) === this ? this.#count : -1;
+  }
+
+  check(other) {
+    return other.#inc(1);
+  }
+
+  reassign() {
+    this.#inc = null;
+  }
+}
+
+const foo = new Foo();
+expect(foo.run()).toBe(3);
+expect(() => foo.check({})).toThrow(TypeError);
+expect(() => foo.reassign()).toThrow(TypeError);
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(),
+    accessor_exec,
+    r#"
+class Foo {
+  #value = 1;
+
+  get #double() {
+    return this.#value * 2;
+  }
+
+  set #double(v) {
+    this.#value = v / 2;
+  }
+
+  get #readonly() {
+    return this.#value;
+  }
+
+  test() {
+    this.#double = 10;
+    this.#double += 2;
+    return [this.#value, this.#double, this.#readonly];
+  }
+
+  write() {
+    this.#readonly = 1;
+  }
+}
+
+const foo = new Foo();
+expect(foo.test()).toEqual([6, 12, 6]);
+expect(() => foo.write()).toThrow(TypeError);
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(),
+    static_exec,
+    r#"
+class Foo {
+  static #value = 1;
+
+  static #inc() {
+    return ++Foo.#value;
+  }
+
+  static get #current() {
+    return Foo.#value;
+  }
+
+  static test() {
+    Foo.#inc();
+    return Foo.#current;
+  }
+
+  static check(other) {
+    return other.#inc();
+  }
+}
+
+class Bar extends Foo {}
+
+expect(Foo.test()).toBe(2);
+expect(() => Foo.check(Bar)).toThrow(TypeError);
+"#
+);
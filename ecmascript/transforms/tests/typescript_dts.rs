@@ -0,0 +1,136 @@
+#![feature(box_syntax)]
+#![feature(test)]
+#![feature(box_patterns)]
+#![feature(specialization)]
+
+use swc_ecma_transforms::typescript::dts;
+
+#[macro_use]
+mod common;
+
+macro_rules! to {
+    ($name:ident, $from:expr, $to:expr) => {
+        test!(
+            ::swc_ecma_parser::Syntax::Typescript(Default::default()),
+            |_| dts(),
+            $name,
+            $from,
+            $to,
+            ok_if_code_eq
+        );
+    };
+}
+
+to!(
+    functions,
+    "export function foo(a: number, b = ''): string {
+    return a + b;
+}
+export function bar(a: string): string;
+export function bar(a: number): number;
+export function bar(a: any): any {
+    return a;
+}
+export async function baz({ a = 1 }: { a?: number }, ...rest: string[]): Promise<void> {}",
+    "export declare function foo(a: number, b?: string): string;
+export declare function bar(a: string): string;
+export declare function bar(a: number): number;
+export declare function baz({ a }: {
+    a?: number;
+}, ...rest: string[]): Promise<void>;"
+);
+
+to!(
+    variables,
+    "interface Foo {}
+const internal = 1;
+export const a = 1;
+export let b = 'b';
+export const c: Foo = make();
+export const d = (x: number): string => String(x);
+export let e;",
+    "interface Foo {
+}
+export declare const a = 1;
+export declare let b: string;
+export declare const c: Foo;
+export declare const d: (x: number) => string;
+export declare let e: any;"
+);
+
+to!(
+    class,
+    "import { Base, unused } from './base';
+interface I {}
+export class A extends Base implements I {
+    private secret: string = '';
+    static readonly version = 1;
+    name: string;
+    constructor(public x: number, private y = 2) {
+        super();
+    }
+    get value(): number {
+        return this.x;
+    }
+    method<T>(v: T): T {
+        return v;
+    }
+    private helper() {}
+}
+console.log(new A(1));",
+    "import { Base } from './base';
+interface I {
+}
+export declare class A extends Base implements I {
+    private secret;
+    static readonly version = 1;
+    name: string;
+    x: number;
+    private y;
+    constructor(x: number, y?: number);
+    get value(): number;
+    method<T>(v: T): T;
+    private helper;
+}"
+);
+
+to!(
+    namespace_and_enum,
+    "export enum E {
+    A = 1,
+    B
+}
+export type T = string | E;
+export namespace NS {
+    export const x = 1;
+    const y = 2;
+}",
+    "export declare enum E {
+    A = 1,
+    B
+}
+export type T = string | E;
+export declare namespace NS {
+    export const x = 1;
+}"
+);
+
+to!(
+    local_exports,
+    "class Foo {
+    foo(): void {}
+}
+function unused() {}
+export { Foo };",
+    "declare class Foo {
+    foo(): void;
+}
+export { Foo };"
+);
+
+to!(
+    module_marker,
+    "import foo from 'foo';
+foo();",
+    "export {};"
+);
@@ -51,7 +51,7 @@ to!(
 
 to!(export_import, "export import A = B", "export var A = B;");
 
-to!(export_equals, "export = Foo", "export default Foo");
+to!(export_equals, "export = Foo", "export = Foo;");
 
 to!(
     issue_196_01,
@@ -598,3 +598,186 @@ to!(
     Direction[Direction['Left'] = 3] = 'Left';
 })(Direction || (Direction = {}));"
 );
+
+to!(
+    namespace_001,
+    "namespace Foo {
+    export const a = 1;
+    export function b() {
+        return a;
+    }
+    const c = 2;
+}",
+    "var Foo;
+(function (Foo) {
+    Foo.a = 1;
+    function b() {
+        return Foo.a;
+    }
+    Foo.b = b;
+    const c = 2;
+})(Foo || (Foo = {}));"
+);
+
+to!(
+    namespace_002,
+    "export namespace Foo.Bar {
+    export let a = 1;
+}",
+    "export var Foo;
+(function (Foo) {
+    var Bar;
+    (function (Bar) {
+        Bar.a = 1;
+    })(Bar = Foo.Bar || (Foo.Bar = {}));
+})(Foo || (Foo = {}));"
+);
+
+to!(
+    namespace_003,
+    "namespace Foo {
+    export const a = 1;
+}
+namespace Foo {
+    export const b = a;
+}",
+    "var Foo;
+(function (Foo) {
+    Foo.a = 1;
+})(Foo || (Foo = {}));
+(function (Foo) {
+    Foo.b = Foo.a;
+})(Foo || (Foo = {}));"
+);
+
+to!(
+    namespace_004,
+    "namespace Foo {
+    export interface A {}
+    export type B = string;
+}
+declare namespace Bar {
+    export const a: number;
+}",
+    ""
+);
+
+to!(
+    namespace_005,
+    "class Foo {}
+namespace Foo {
+    export const { a, b: [c] } = foo();
+    function bar(a) {
+        return a + c;
+    }
+}",
+    "class Foo {
+}
+(function (Foo) {
+    ({ a: Foo.a, b: [Foo.c] } = foo());
+    function bar(a) {
+        return a + Foo.c;
+    }
+})(Foo || (Foo = {}));"
+);
+
+to!(
+    namespace_006,
+    "namespace Foo {
+    export enum E { A }
+    export const b = E.A;
+}
+namespace Foo {
+    export const c = E.A;
+}",
+    "var Foo;
+(function (Foo) {
+    var E;
+    (function (E) {
+        E[E['A'] = 0] = 'A';
+    })(E = Foo.E || (Foo.E = {}));
+    Foo.b = E.A;
+})(Foo || (Foo = {}));
+(function (Foo) {
+    Foo.c = Foo.E.A;
+})(Foo || (Foo = {}));"
+);
+
+to!(
+    namespace_007,
+    "namespace Foo {
+    export namespace Bar {
+        export const a = 1;
+    }
+    export const b = Bar.a;
+}",
+    "var Foo;
+(function (Foo) {
+    var Bar;
+    (function (Bar) {
+        Bar.a = 1;
+    })(Bar = Foo.Bar || (Foo.Bar = {}));
+    Foo.b = Bar.a;
+})(Foo || (Foo = {}));"
+);
+
+to!(
+    namespace_008,
+    "namespace Foo {
+    export function a() {}
+    export class B {}
+}
+namespace Foo {
+    a();
+    new B();
+}",
+    "var Foo;
+(function (Foo) {
+    function a() {
+    }
+    Foo.a = a;
+    class B {
+    }
+    Foo.B = B;
+})(Foo || (Foo = {}));
+(function (Foo) {
+    Foo.a();
+    new Foo.B();
+})(Foo || (Foo = {}));"
+);
+
+to!(
+    namespace_009,
+    "namespace Foo {
+    export const a = 1;
+    {
+        let a = 2;
+        a;
+    }
+    for (const a of []) {
+        a;
+    }
+    if (true) {
+        class a {}
+        a;
+    }
+    a;
+}",
+    "var Foo;
+(function (Foo) {
+    Foo.a = 1;
+    {
+        let a = 2;
+        a;
+    }
+    for (const a of []) {
+        a;
+    }
+    if (true) {
+        class a {
+        }
+        a;
+    }
+    Foo.a;
+})(Foo || (Foo = {}));"
+);
@@ -1374,7 +1374,11 @@ pub fn undefined(span: Span) -> Box<Expr> {
     box Expr::Unary(UnaryExpr {
         span,
         op: op!("void"),
-        arg: box Expr::Lit(Lit::Num(Number { value: 0.0, span })),
+        arg: box Expr::Lit(Lit::Num(Number {
+            value: 0.0,
+            span,
+            raw: None,
+        })),
     })
 }
 
@@ -452,6 +452,7 @@ define!({
     pub struct BigInt {
         pub span: Span,
         pub value: BigIntValue,
+        pub raw: Option<JsWord>,
     }
     pub struct Str {
         pub span: Span,
@@ -473,6 +474,7 @@ define!({
     pub struct Number {
         pub span: Span,
         pub value: f64,
+        pub raw: Option<JsWord>,
     }
     pub enum Program {
         Module(Module),
@@ -612,6 +614,9 @@ define!({
         BitXorAssign,
         BitAndAssign,
         ExpAssign,
+        AndAssign,
+        OrAssign,
+        NullishAssign,
     }
     pub enum UpdateOp {
         PlusPlus,
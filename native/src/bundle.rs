@@ -59,38 +59,33 @@ impl Task for BundleTask {
 
             let result = bundler.bundle(&self.config.static_items.config)?;
 
+            let minify = self
+                .config
+                .static_items
+                .config
+                .options
+                .as_ref()
+                .map(|v| {
+                    v.config
+                        .as_ref()
+                        .and_then(|v| v.minify.as_ref())
+                        .map(|v| v.enabled())
+                        .unwrap_or(false)
+                })
+                .unwrap_or(false);
+
             let result = result
                 .into_iter()
-                .map(|bundle| match bundle.kind {
-                    BundleKind::Named { name } | BundleKind::Lib { name } => {
-                        Ok((name, bundle.module))
-                    }
-                    BundleKind::Dynamic => bail!("unimplemented: dynamic code splitting"),
-                })
-                .map(|res| {
-                    res.and_then(|(k, m)| {
-                        // TODO: Source map
-                        let minify = self
-                            .config
-                            .static_items
-                            .config
-                            .options
-                            .as_ref()
-                            .map(|v| {
-                                v.config
-                                    .as_ref()
-                                    .map(|v| v.minify)
-                                    .flatten()
-                                    .unwrap_or(false)
-                            })
-                            .unwrap_or(false);
-
-                        let output =
-                            self.swc
-                                .print(&m, SourceMapsConfig::Bool(true), None, minify)?;
-
-                        Ok((k, output))
-                    })
+                .map(|bundle| {
+                    let name = match &bundle.kind {
+                        BundleKind::Named { name }
+                        | BundleKind::Lib { name }
+                        | BundleKind::Dynamic { name } => name.clone(),
+                    };
+
+                    let output = bundler.print(&bundle, SourceMapsConfig::Bool(true), minify)?;
+
+                    Ok((name, output))
                 })
                 .collect::<Result<_, _>>()?;
 
@@ -134,7 +129,16 @@ pub(crate) fn bundle(mut cx: MethodContext<JsCompiler>) -> JsResult<JsValue> {
 
     let opt = cx.argument::<JsObject>(0)?;
     let callback = cx.argument::<JsFunction>(1)?;
-    let static_items = neon_serde::from_value(&mut cx, opt.upcast())?;
+    let static_items: StaticConfigItem = neon_serde::from_value(&mut cx, opt.upcast())?;
+
+    let resolver = match &static_items.config.resolve {
+        Some(resolve) => NodeResolver::new(resolve, &static_items.config.working_dir),
+        None => Ok(NodeResolver::default()),
+    };
+    let resolver = match resolver {
+        Ok(resolver) => resolver,
+        Err(err) => return cx.throw_error(format!("{:?}", err)),
+    };
 
     let loader = opt
         .get(&mut cx, "loader")?
@@ -153,7 +157,7 @@ pub(crate) fn bundle(mut cx: MethodContext<JsCompiler>) -> JsResult<JsValue> {
         swc: c.clone(),
         config: ConfigItem {
             loader,
-            resolver: box NodeResolver as Box<_>,
+            resolver: box resolver as Box<_>,
             static_items,
         },
     }
@@ -439,6 +439,7 @@ impl Task for PrintTask {
                     .clone()
                     .unwrap_or_default()
                     .minify
+                    .map(|v| v.enabled())
                     .unwrap_or(false),
             )
         })
@@ -503,7 +504,12 @@ fn print_sync(mut cx: MethodContext<JsCompiler>) -> JsResult<JsValue> {
                     .clone()
                     .unwrap_or(SourceMapsConfig::Bool(false)),
                 None,
-                options.config.unwrap_or_default().minify.unwrap_or(false),
+                options
+                    .config
+                    .unwrap_or_default()
+                    .minify
+                    .map(|v| v.enabled())
+                    .unwrap_or(false),
             )
         };
         complete_output(cx, result)
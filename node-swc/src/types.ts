@@ -206,7 +206,62 @@ export interface Config {
   env?: EnvConfig;
   jsc?: JscConfig;
   module?: ModuleConfig;
-  minify?: boolean;
+  minify?: boolean | MinifyOptions;
+}
+
+/**
+ * `true` is equivalent to `{}`.
+ */
+export interface MinifyOptions {
+  /**
+   * Use `null` to disable compression.
+   */
+  compress?: CompressOptions | null;
+
+  /**
+   * Use `null` to disable mangling.
+   */
+  mangle?: MangleOptions | null;
+}
+
+/**
+ * All options defaults to `true`.
+ */
+export interface CompressOptions {
+  /**
+   * `true` -> `!0`, `false` -> `!1`
+   */
+  booleans?: boolean;
+  /**
+   * `if` statements -> conditional / logical expressions
+   */
+  conditionals?: boolean;
+  /**
+   * Join consecutive expression statements with the comma operator.
+   */
+  sequences?: boolean;
+  /**
+   * `{ a: a }` -> `{ a }`
+   */
+  shorthand?: boolean;
+  /**
+   * `undefined` -> `void 0`
+   */
+  undefined?: boolean;
+}
+
+export interface MangleOptions {
+  /**
+   * Mangle names declared in the top level scope.
+   *
+   * Defaults to `false`.
+   */
+  topLevel?: boolean;
+
+  /**
+   * Names which should not be mangled.
+   */
+  reserved?: string[];
 }
 
 /**
@@ -235,6 +290,13 @@ export interface EnvConfig {
    */
   coreJs?: string;
 
+  /**
+   * Name of the function used to load polyfills in scripts, like `importScripts`.
+   *
+   * Defaults to `require`.
+   */
+  scriptLoader?: string;
+
   targets?: any;
 
   shippedProposals?: boolean;
@@ -262,6 +324,13 @@ export interface JscConfig {
    * Defaults to `es3` (which enableds **all** pass).
    */
   target?: JscTarget;
+
+  /**
+   * Warn about syntax newer than `target` which can't be lowered.
+   *
+   * Defaults to false.
+   */
+  targetDiagnostics?: boolean;
 }
 
 export type JscTarget =
@@ -271,9 +340,12 @@ export type JscTarget =
   | "es2016"
   | "es2017"
   | "es2018"
-  | "es2019";
+  | "es2019"
+  | "es2020"
+  | "es2021"
+  | "esnext";
 
-export type ParserConfig = TsParserConfig | EsParserConfig;
+export type ParserConfig = TsParserConfig | EsParserConfig | FlowParserConfig;
 export interface TsParserConfig {
   syntax: "typescript";
   /**
@@ -290,6 +362,22 @@ export interface TsParserConfig {
   dynamicImport?: boolean;
 }
 
+export interface FlowParserConfig {
+  syntax: "flow";
+  /**
+   * Defaults to `false`.
+   */
+  jsx?: boolean;
+  /**
+   * Defaults to `false`.
+   */
+  decorators?: boolean;
+  /**
+   * Defaults to `false`
+   */
+  dynamicImport?: boolean;
+}
+
 export interface EsParserConfig {
   syntax: "ecmascript";
   /**
@@ -349,6 +437,16 @@ export interface TransformConfig {
    * Defaults to null, which skips optimizer pass.
    */
   optimizer?: OptimizerConfig;
+
+  legacyDecorator?: boolean;
+
+  /**
+   * Emits `design:type`, `design:paramtypes` and `design:returntype` metadata
+   * for decorated classes, like `emitDecoratorMetadata` of tsc.
+   *
+   * Requires `legacyDecorator`, which is enabled by default for typescript.
+   */
+  decoratorMetadata?: boolean;
 }
 
 export interface ReactConfig {
@@ -385,6 +483,41 @@ export interface ReactConfig {
    * Use `Object.assign()` instead of `_extends`. Defaults to false.
    */
   useBuiltins: boolean;
+  /**
+   * `classic` compiles JSX to `pragma` calls. `automatic` imports `jsx` / `jsxs`
+   * from `${importSource}/jsx-runtime` instead.
+   *
+   * Defaults to `classic`.
+   */
+  runtime?: "classic" | "automatic";
+  /**
+   * Module to import the automatic runtime from. Can be overriden per file with
+   * a `@jsxImportSource` pragma comment.
+   *
+   * Defaults to `react`.
+   */
+  importSource?: String;
+  /**
+   * Enables react fast refresh. Requires `development` to be true.
+   *
+   * Defaults to null, which disables it.
+   */
+  refresh?: ReactRefreshConfig;
+}
+
+export interface ReactRefreshConfig {
+  /**
+   * Defaults to `$RefreshReg$`.
+   */
+  refreshReg?: String;
+  /**
+   * Defaults to `$RefreshSig$`.
+   */
+  refreshSig?: String;
+  /**
+   * Emit hook signatures as is, instead of hashing them. Defaults to false.
+   */
+  emitFullSignatures?: boolean;
 }
 /**
  *  - `import { DEBUG } from '@ember/env-flags';`
@@ -423,7 +556,7 @@ export interface GlobalPassOption {
   envs?: string[];
 }
 
-export type ModuleConfig = CommonJsConfig | UmdConfig | AmdConfig;
+export type ModuleConfig = CommonJsConfig | UmdConfig | AmdConfig | SystemJsConfig;
 
 export interface BaseModuleConfig {
   /**
@@ -507,6 +640,16 @@ export interface AmdConfig extends BaseModuleConfig {
   moduleId: string;
 }
 
+export interface SystemJsConfig {
+  type: "systemjs";
+  /**
+   * If false, top-level `this` is replaced with `undefined`.
+   *
+   * Defaults to `false`.
+   */
+  allowTopLevelThis?: boolean;
+}
+
 export interface Output {
   /**
    * Transformed code
@@ -1134,6 +1277,10 @@ export interface NumericLiteral extends Node, HasSpan {
   type: "NumericLiteral";
 
   value: number;
+  /**
+   * Source text of the literal. Absent if the node is created by a transform.
+   */
+  raw?: string;
 }
 
 export type ModuleDeclaration =
@@ -11,6 +11,8 @@ use swc_common::fold::FoldWith;
 use swc_ecma_transforms::{fixer, hygiene, optimization::simplify::dce::dce};
 
 mod merge;
+#[cfg(test)]
+mod tests;
 
 pub(super) type ModuleGraph = DiGraphMap<ModuleId, usize>;
 
@@ -73,11 +75,35 @@ impl Bundler<'_> {
         mut entries: FxHashMap<String, TransformedModule>,
     ) -> Vec<(BundleKind, ModuleId, Vec<ModuleId>)> {
         let mut graph = ModuleGraph::default();
+        let mut state = State::default();
         let mut kinds = vec![];
 
         for (name, module) in entries.drain() {
             kinds.push((BundleKind::Named { name }, module.id));
-            self.add_to_graph(&mut graph, module.id);
+            self.add_to_graph(&mut graph, &mut state, module.id);
+        }
+
+        // Each module loaded with `import()` becomes a separate chunk.
+        //
+        // If it's also imported statically, the static import is changed to
+        // import the chunk so the module is evaluated only once.
+        let mut dynamic = FxHashSet::default();
+        let mut queue: Vec<_> = state.dynamic_entries.drain().collect();
+        while let Some(id) = queue.pop() {
+            if kinds.iter().any(|(_, entry)| *entry == id) {
+                continue;
+            }
+
+            let module = self.scope.get_module(id).unwrap();
+            kinds.push((
+                BundleKind::Dynamic {
+                    name: module.fm.name.to_string(),
+                },
+                id,
+            ));
+            dynamic.insert(id);
+            self.add_to_graph(&mut graph, &mut state, id);
+            queue.extend(state.dynamic_entries.drain());
         }
 
         let mut metadata = FxHashMap::<ModuleId, Metadata>::default();
@@ -98,8 +124,7 @@ impl Bundler<'_> {
 
         // Promote modules to entry.
         for (id, md) in &metadata {
-            if md.access_cnt > 1 {
-                // TODO: Dynamic import
+            if md.access_cnt > 1 && !dynamic.contains(id) {
                 let module = self.scope.get_module(*id).unwrap();
                 kinds.push((
                     BundleKind::Lib {
@@ -116,8 +141,8 @@ impl Bundler<'_> {
             let mut bfs = Bfs::new(&graph, *id);
 
             while let Some(dep) = bfs.next(&graph) {
-                if dep == *id {
-                    // Useless
+                if dep == *id || dynamic.contains(&dep) {
+                    // Useless, or loaded from its own chunk
                     continue;
                 }
 
@@ -138,7 +163,10 @@ impl Bundler<'_> {
             .collect()
     }
 
-    fn add_to_graph(&self, graph: &mut ModuleGraph, module_id: ModuleId) {
+    fn add_to_graph(&self, graph: &mut ModuleGraph, state: &mut State, module_id: ModuleId) {
+        if graph.contains_node(module_id) {
+            return;
+        }
         graph.add_node(module_id);
 
         let m = self
@@ -147,8 +175,13 @@ impl Bundler<'_> {
             .expect("failed to get module");
 
         for (src, _) in &*m.imports.specifiers {
-            //
-            self.add_to_graph(graph, src.module_id);
+            if !src.is_loaded_synchronously {
+                // Loaded by `import()`, so it should not be merged.
+                state.dynamic_entries.insert(src.module_id);
+                continue;
+            }
+
+            self.add_to_graph(graph, state, src.module_id);
             graph.add_edge(
                 module_id,
                 src.module_id,
@@ -0,0 +1,177 @@
+use crate::{
+    bundler::tests::test_bundler,
+    config::{Config, EntryConfig},
+    BundleKind,
+};
+use std::fs::write;
+use swc::{config::SourceMapsConfig, sourcemap::SourceMap};
+
+#[test]
+fn dynamic_import_creates_chunk() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path().join("entry.js"),
+        "import('./a').then(v => console.log(v));",
+    )
+    .unwrap();
+    write(dir.path().join("a.js"), "export default 5;").unwrap();
+
+    test_bundler(|t| {
+        let bundles = t
+            .bundler
+            .bundle(&Config {
+                working_dir: Default::default(),
+                mode: Default::default(),
+                entry: EntryConfig::File(dir.path().join("entry.js").display().to_string()),
+                output: None,
+                module: Default::default(),
+                optimization: None,
+                resolve: None,
+                options: None,
+            })
+            .unwrap();
+        assert_eq!(bundles.len(), 2);
+
+        let mut entry = None;
+        let mut chunk = None;
+        for bundle in bundles {
+            let name = match bundle.kind {
+                BundleKind::Named { name } => name,
+                kind => panic!("bundle should be named after finalize: {:?}", kind),
+            };
+            let code = t
+                .bundler
+                .swc()
+                .print(&bundle.module, SourceMapsConfig::Bool(false), None, false)
+                .unwrap()
+                .code;
+
+            if name.ends_with("entry.js") {
+                entry = Some(code);
+            } else {
+                chunk = Some((name, code));
+            }
+        }
+        let entry = entry.expect("entry should be emitted");
+        let (chunk_name, chunk) = chunk.expect("dynamic chunk should be emitted");
+
+        assert!(chunk_name.starts_with("a-"), "{}", chunk_name);
+        assert!(chunk.contains("export default 5"), "{}", chunk);
+        assert!(
+            entry.contains(&format!("import('./{}')", chunk_name)),
+            "{}",
+            entry
+        );
+    });
+}
+
+#[test]
+fn source_map_of_merged_modules() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path().join("entry.js"),
+        "import { a } from './a';\nconsole.log(a);",
+    )
+    .unwrap();
+    write(dir.path().join("a.js"), "export const a = 5;").unwrap();
+
+    test_bundler(|t| {
+        let bundles = t
+            .bundler
+            .bundle(&Config {
+                working_dir: Default::default(),
+                mode: Default::default(),
+                entry: EntryConfig::File(dir.path().join("entry.js").display().to_string()),
+                output: None,
+                module: Default::default(),
+                optimization: None,
+                resolve: None,
+                options: None,
+            })
+            .unwrap();
+        assert_eq!(bundles.len(), 1);
+
+        let output = t
+            .bundler
+            .print(&bundles[0], SourceMapsConfig::Bool(true), false)
+            .unwrap();
+        let map = SourceMap::from_slice(output.map.expect("map should exist").as_bytes()).unwrap();
+
+        let sources = map.sources().collect::<Vec<_>>();
+        for name in &["entry.js", "a.js"] {
+            let idx = sources
+                .iter()
+                .position(|s| s.ends_with(name))
+                .unwrap_or_else(|| panic!("{} should be in sources: {:?}", name, sources));
+
+            assert!(map.get_source_contents(idx as u32).is_some());
+        }
+    });
+}
+
+#[test]
+fn dynamic_import_of_static_dep_is_not_duplicated() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path().join("entry.js"),
+        "import a from './a';
+console.log(a);
+import('./a').then(v => console.log(v));",
+    )
+    .unwrap();
+    write(
+        dir.path().join("a.js"),
+        "console.log('side effect');
+export default 5;",
+    )
+    .unwrap();
+
+    test_bundler(|t| {
+        let bundles = t
+            .bundler
+            .bundle(&Config {
+                working_dir: Default::default(),
+                mode: Default::default(),
+                entry: EntryConfig::File(dir.path().join("entry.js").display().to_string()),
+                output: None,
+                module: Default::default(),
+                optimization: None,
+                resolve: None,
+                options: None,
+            })
+            .unwrap();
+        assert_eq!(bundles.len(), 2);
+
+        let mut entry = None;
+        let mut chunk = None;
+        for bundle in bundles {
+            let name = match bundle.kind {
+                BundleKind::Named { name } => name,
+                kind => panic!("bundle should be named after finalize: {:?}", kind),
+            };
+            let code = t
+                .bundler
+                .swc()
+                .print(&bundle.module, SourceMapsConfig::Bool(false), None, false)
+                .unwrap()
+                .code;
+
+            if name.ends_with("entry.js") {
+                entry = Some(code);
+            } else {
+                chunk = Some((name, code));
+            }
+        }
+        let entry = entry.expect("entry should be emitted");
+        let (chunk_name, chunk) = chunk.expect("dynamic chunk should be emitted");
+
+        assert!(!entry.contains("side effect"), "{}", entry);
+        assert_eq!(chunk.matches("side effect").count(), 1, "{}", chunk);
+        assert_eq!(
+            entry.matches(&format!("'./{}'", chunk_name)).count(),
+            2,
+            "static and dynamic imports should load the chunk:\n{}",
+            entry
+        );
+    });
+}
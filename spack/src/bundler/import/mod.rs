@@ -357,7 +357,7 @@ impl Fold<Expr> for ImportHandler<'_, '_> {
                     ExprOrSuper::Expr(box Expr::Ident(Ident {
                         sym: js_word!("import"),
                         ..
-                    })) => {
+                    })) if !self.deglob_phase => {
                         self.info.dynamic_imports.push(src.clone());
                     }
 
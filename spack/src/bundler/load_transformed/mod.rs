@@ -15,6 +15,7 @@ use std::{
     path::{Path, PathBuf},
     sync::Arc,
 };
+use swc::sourcemap;
 use swc_atoms::js_word;
 use swc_common::{fold::FoldWith, FileName, Mark, SourceFile, Visit, VisitWith};
 use swc_ecma_ast::{
@@ -41,6 +42,9 @@ pub(super) struct TransformedModule {
     /// Used helpers
     pub helpers: Arc<Helpers>,
 
+    /// Input source map of the file.
+    pub input_map: Option<Arc<sourcemap::SourceMap>>,
+
     mark: Mark,
 }
 
@@ -258,6 +262,7 @@ impl Bundler<'_> {
             }
 
             let module = Arc::new(module);
+            let input_map = self.load_input_map(&fm);
 
             Ok(TransformedModule {
                 id,
@@ -267,11 +272,29 @@ impl Bundler<'_> {
                 exports: Arc::new(exports),
                 is_es6,
                 helpers: Default::default(),
+                input_map,
                 mark,
             })
         })
     }
 
+    /// Loads the input source map of `fm`, if any.
+    ///
+    /// Errors are not fatal because the bundle can be emitted without the
+    /// input source map.
+    fn load_input_map(&self, fm: &SourceFile) -> Option<Arc<sourcemap::SourceMap>> {
+        match self
+            .swc
+            .get_orig_src_map(fm, &self.swc_options.input_source_map)
+        {
+            Ok(map) => map.map(Arc::new),
+            Err(err) => {
+                log::warn!("failed to load input source map of {}: {:?}", fm.name, err);
+                None
+            }
+        }
+    }
+
     fn load_exports(&self, base: &Path, raw: RawExports) -> Result<Exports, Error> {
         self.swc.run(|| {
             log::trace!("load_exports({})", base.display());
@@ -10,7 +10,10 @@ use anyhow::{Context, Error};
 use fxhash::FxHashMap;
 use rayon::prelude::*;
 use std::{path::PathBuf, sync::Arc};
-use swc::config::ModuleConfig;
+use swc::{
+    config::{ModuleConfig, SourceMapsConfig},
+    TransformOutput,
+};
 use swc_common::{Mark, DUMMY_SP};
 use swc_ecma_ast::Module;
 
@@ -43,7 +46,7 @@ pub enum BundleKind {
     /// User-provided entry
     Named { name: String },
     /// Auto-generated entry (created by import expression)
-    Dynamic,
+    Dynamic { name: String },
     /// A lazy-loaded shared library
     Lib { name: String },
 }
@@ -125,9 +128,7 @@ impl<'a> Bundler<'a> {
             })
             .collect::<Vec<_>>();
 
-        // We collect at here to handle dynamic imports
-        // TODO: Handle dynamic imports
-
+        // Modules loaded by `import()` are split into chunks by `chunk`.
         let local = self.swc.run(|| -> Result<_, Error> {
             let mut output = FxHashMap::default();
 
@@ -145,6 +146,26 @@ impl<'a> Bundler<'a> {
         Ok(self.finalize(bundles)?)
     }
 
+    /// Prints a bundle.
+    ///
+    /// Mappings of the source map point to the files merged into the bundle,
+    /// and input source maps of those files are applied.
+    pub fn print(
+        &self,
+        bundle: &Bundle,
+        source_map: SourceMapsConfig,
+        minify: bool,
+    ) -> Result<TransformOutput, Error> {
+        let input_maps = self.scope.input_maps();
+
+        self.swc.print_with(
+            &bundle.module,
+            source_map,
+            |fm| input_maps.get(&fm.name).map(|map| &**map),
+            minify,
+        )
+    }
+
     pub fn swc(&self) -> &swc::Compiler {
         &self.swc
     }
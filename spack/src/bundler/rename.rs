@@ -8,8 +8,11 @@ use std::{
     path::{Path, PathBuf},
 };
 use swc::config::Options;
+use swc_atoms::js_word;
 use swc_common::{util::move_map::MoveMap, FileName, Fold, FoldWith, Span};
-use swc_ecma_ast::{ImportDecl, Module, Str};
+use swc_ecma_ast::{
+    CallExpr, Expr, ExprOrSpread, ExprOrSuper, Ident, ImportDecl, Lit, Module, Str,
+};
 use swc_ecma_codegen::{text_writer::WriteJs, Emitter};
 use swc_ecma_transforms::noop_fold_type;
 
@@ -21,57 +24,33 @@ impl Bundler<'_> {
         for mut bundle in bundles {
             match bundle.kind {
                 BundleKind::Named { .. } => {
-                    // Inject helpers
-                    let helpers = self
-                        .scope
-                        .get_module(bundle.id)
-                        .expect("module should exist at this point")
-                        .helpers;
-
-                    self.swc
-                        .run_transform(true, || helpers.append_to(&mut bundle.module.body));
+                    self.inject_helpers(&mut bundle);
 
                     new.push(Bundle { ..bundle });
                 }
                 BundleKind::Lib { name } => {
-                    let hash = self.calc_hash(&bundle.module)?;
-                    let mut new_name = PathBuf::from(name);
-                    let key = new_name.clone();
-                    let file_name = new_name
-                        .file_name()
-                        .map(|path| -> PathBuf {
-                            let path = Path::new(path);
-                            let ext = path.extension();
-                            if let Some(ext) = ext {
-                                return format!(
-                                    "{}-{}.{}",
-                                    path.file_stem().unwrap().to_string_lossy(),
-                                    hash,
-                                    ext.to_string_lossy()
-                                )
-                                .into();
-                            }
-                            return format!(
-                                "{}-{}",
-                                path.file_stem().unwrap().to_string_lossy(),
-                                hash,
-                            )
-                            .into();
-                        })
-                        .expect("javascript file should have name");
-                    new_name.pop();
-                    new_name = new_name.join(file_name.clone());
-
-                    renamed.insert(key, new_name.to_string_lossy().to_string());
+                    let (key, new_name, file_name) = self.hashed_name(&bundle.module, name)?;
+
+                    renamed.insert(key, new_name);
+
+                    new.push(Bundle {
+                        kind: BundleKind::Named { name: file_name },
+                        ..bundle
+                    })
+                }
+                BundleKind::Dynamic { name } => {
+                    // Dynamic chunks are loaded independently.
+                    self.inject_helpers(&mut bundle);
+
+                    let (key, new_name, file_name) = self.hashed_name(&bundle.module, name)?;
+
+                    renamed.insert(key, new_name);
 
                     new.push(Bundle {
-                        kind: BundleKind::Named {
-                            name: file_name.display().to_string(),
-                        },
+                        kind: BundleKind::Named { name: file_name },
                         ..bundle
                     })
                 }
-                _ => new.push(bundle),
             }
         }
 
@@ -114,6 +93,49 @@ impl Bundler<'_> {
         Ok(new)
     }
 
+    fn inject_helpers(&self, bundle: &mut Bundle) {
+        let helpers = self
+            .scope
+            .get_module(bundle.id)
+            .expect("module should exist at this point")
+            .helpers;
+
+        self.swc
+            .run_transform(true, || helpers.append_to(&mut bundle.module.body));
+    }
+
+    /// Returns `(original path, path with hash, file name with hash)`.
+    fn hashed_name(&self, m: &Module, name: String) -> Result<(PathBuf, String, String), Error> {
+        let hash = self.calc_hash(m)?;
+        let mut new_name = PathBuf::from(name);
+        let key = new_name.clone();
+        let file_name = new_name
+            .file_name()
+            .map(|path| -> PathBuf {
+                let path = Path::new(path);
+                let ext = path.extension();
+                if let Some(ext) = ext {
+                    return format!(
+                        "{}-{}.{}",
+                        path.file_stem().unwrap().to_string_lossy(),
+                        hash,
+                        ext.to_string_lossy()
+                    )
+                    .into();
+                }
+                return format!("{}-{}", path.file_stem().unwrap().to_string_lossy(), hash,).into();
+            })
+            .expect("javascript file should have name");
+        new_name.pop();
+        new_name = new_name.join(file_name.clone());
+
+        Ok((
+            key,
+            new_name.to_string_lossy().to_string(),
+            file_name.display().to_string(),
+        ))
+    }
+
     fn calc_hash(&self, m: &Module) -> Result<String, Error> {
         let digest = crc64::Digest::new(crc64::ECMA);
         let mut buf = Hasher { digest };
@@ -147,42 +169,83 @@ struct Renamer<'a, 'b> {
 
 noop_fold_type!(Renamer<'_, '_>);
 
+impl Renamer<'_, '_> {
+    /// Returns the new import path if `src` is renamed.
+    fn rename(&self, base: &Path, src: &Str) -> Option<Str> {
+        let resolved = self.bundler.resolve(base, &src.value).ok()?;
+        let v = self.renamed.get(&*resolved)?;
+
+        // We use parent because RelativePath uses ../common-[hash].js
+        // if we use `entry-a.js` as a base.
+        //
+        // entry-a.js
+        // common.js
+        let base = self
+            .path
+            .parent()
+            .unwrap_or(self.path)
+            .as_os_str()
+            .to_string_lossy();
+        let base = RelativePath::new(&*base);
+        let v = base.relative(&*v);
+        let value = v.as_str();
+
+        Some(Str {
+            value: if value.starts_with(".") {
+                value.into()
+            } else {
+                format!("./{}", value).into()
+            },
+            ..src.clone()
+        })
+    }
+}
+
 impl Fold<ImportDecl> for Renamer<'_, '_> {
     fn fold(&mut self, import: ImportDecl) -> ImportDecl {
-        let resolved = match self.bundler.resolve(self.path, &import.src.value) {
-            Ok(v) => v,
-            Err(_) => return import,
-        };
-
-        if let Some(v) = self.renamed.get(&*resolved) {
-            // We use parent because RelativePath uses ../common-[hash].js
-            // if we use `entry-a.js` as a base.
-            //
-            // entry-a.js
-            // common.js
-            let base = self
-                .path
-                .parent()
-                .unwrap_or(self.path)
-                .as_os_str()
-                .to_string_lossy();
-            let base = RelativePath::new(&*base);
-            let v = base.relative(&*v);
-            let value = v.as_str();
-            return ImportDecl {
-                src: Str {
-                    value: if value.starts_with(".") {
-                        value.into()
-                    } else {
-                        format!("./{}", value).into()
-                    },
-                    ..import.src
-                },
-                ..import
+        match self.rename(self.path, &import.src) {
+            Some(src) => ImportDecl { src, ..import },
+            None => import,
+        }
+    }
+}
+
+/// Changes `import('./foo')` to `import('./foo-[hash].js')`.
+impl Fold<CallExpr> for Renamer<'_, '_> {
+    fn fold(&mut self, mut e: CallExpr) -> CallExpr {
+        e = e.fold_children(self);
+
+        match e.callee {
+            ExprOrSuper::Expr(box Expr::Ident(Ident {
+                sym: js_word!("import"),
+                ..
+            })) if e.args.len() == 1 => {}
+            _ => return e,
+        }
+
+        if let ExprOrSpread {
+            spread: None,
+            expr: box Expr::Lit(Lit::Str(ref mut src)),
+        } = e.args[0]
+        {
+            // The call may be merged from another module, so we resolve it from
+            // the file which contains the call.
+            let base = if src.span.is_dummy() {
+                None
+            } else {
+                match self.bundler.swc.cm.span_to_filename(src.span) {
+                    FileName::Real(path) => Some(path),
+                    _ => None,
+                }
             };
+            let base = base.as_ref().map(|v| &**v).unwrap_or(self.path);
+
+            if let Some(new) = self.rename(base, src) {
+                *src = new;
+            }
         }
 
-        import
+        e
     }
 }
 
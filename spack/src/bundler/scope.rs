@@ -1,7 +1,9 @@
 use crate::{bundler::load_transformed::TransformedModule, id::ModuleIdGenerator, ModuleId};
 use dashmap::DashMap;
-use fxhash::FxBuildHasher;
+use fxhash::{FxBuildHasher, FxHashMap};
 use std::{path::PathBuf, sync::Arc};
+use swc::sourcemap::SourceMap;
+use swc_common::FileName;
 
 #[derive(Debug, Default)]
 pub(super) struct Scope {
@@ -27,4 +29,12 @@ impl Scope {
     pub fn get_module(&self, id: ModuleId) -> Option<TransformedModule> {
         Some(self.modules.get(&id)?.value().clone())
     }
+
+    /// Input source maps of loaded files.
+    pub fn input_maps(&self) -> FxHashMap<FileName, Arc<SourceMap>> {
+        self.modules
+            .iter()
+            .filter_map(|m| Some((m.fm.name.clone(), m.input_map.clone()?)))
+            .collect()
+    }
 }
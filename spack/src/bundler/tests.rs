@@ -57,13 +57,14 @@ where
     testing::run_test2(true, |cm, handler| {
         let compiler = Arc::new(swc::Compiler::new(cm.clone(), Arc::new(handler)));
         let loader = SwcLoader::new(compiler.clone(), Default::default());
+        let resolver = NodeResolver::default();
         let bundler = Bundler::new(
             compiler.clone(),
             swc::config::Options {
                 swcrc: true,
                 ..Default::default()
             },
-            &NodeResolver,
+            &resolver,
             &loader,
         );
 
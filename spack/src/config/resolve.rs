@@ -10,10 +10,16 @@ pub struct ResolveConfig {
     #[serde(default)]
     pub alias: Option<AliasConfig>,
 
-    #[serde(default)]
+    #[serde(default = "default_alias_fields")]
     pub alias_fields: Vec<JsWord>,
 
-    #[serde(default)]
+    /// Conditions used to resolve `exports` and `imports` of `package.json`.
+    ///
+    /// `default` is always matched.
+    #[serde(default = "default_condition_names")]
+    pub condition_names: Vec<JsWord>,
+
+    #[serde(default = "default_description_files")]
     pub description_files: Vec<JsWord>,
 
     #[serde(default)]
@@ -22,11 +28,44 @@ pub struct ResolveConfig {
     #[serde(default = "default_extensions")]
     pub extensions: Vec<JsWord>,
 
-    #[serde(default)]
+    #[serde(default = "default_main_fields")]
     pub main_fields: Vec<JsWord>,
 
     #[serde(default = "default_symlinks")]
     pub symlinks: bool,
+
+    /// Path to `tsconfig.json`. If specified, `compilerOptions.baseUrl` and
+    /// `compilerOptions.paths` are used to resolve modules.
+    #[serde(default)]
+    pub tsconfig: Option<PathBuf>,
+}
+
+impl Default for ResolveConfig {
+    fn default() -> Self {
+        ResolveConfig {
+            alias: None,
+            alias_fields: default_alias_fields(),
+            condition_names: default_condition_names(),
+            description_files: default_description_files(),
+            enforce_extension: false,
+            extensions: default_extensions(),
+            main_fields: default_main_fields(),
+            symlinks: default_symlinks(),
+            tsconfig: None,
+        }
+    }
+}
+
+fn default_alias_fields() -> Vec<JsWord> {
+    vec!["browser".into()]
+}
+
+fn default_condition_names() -> Vec<JsWord> {
+    vec!["browser".into(), "import".into(), "module".into()]
+}
+
+fn default_description_files() -> Vec<JsWord> {
+    vec!["package.json".into()]
 }
 
 fn default_extensions() -> Vec<JsWord> {
@@ -36,14 +75,20 @@ fn default_extensions() -> Vec<JsWord> {
         ".js".into(),
         ".json".into(),
         ".ts".into(),
-        ".tsc".into(),
+        ".tsx".into(),
     ]
 }
 
+fn default_main_fields() -> Vec<JsWord> {
+    vec!["swc-main".into(), "esnext".into(), "main".into()]
+}
+
 fn default_symlinks() -> bool {
     true
 }
 
+/// Keys ending with `$` match only the exact request, while other keys also
+/// match requests for a file in the aliased module.
 #[derive(Debug, Deserialize)]
 #[serde(rename = "Resolve.Alias", rename_all = "camelCase")]
 pub struct AliasConfig {
@@ -21,7 +21,7 @@ impl SwcLoader {
             let v = options.config.as_mut().unwrap();
             // TODO: Some(Esm)
             v.module = None;
-            v.minify = Some(false);
+            v.minify = Some(swc::config::MinifyOptions::Bool(false));
 
             v.jsc.target = JscTarget::Es2019;
         }
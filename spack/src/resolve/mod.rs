@@ -0,0 +1,506 @@
+use self::{
+    package::{AliasField, Package},
+    tsconfig::TsConfig,
+};
+use crate::config::ResolveConfig;
+use anyhow::{bail, Context, Error};
+use dashmap::DashMap;
+use fxhash::FxBuildHasher;
+use std::{
+    env,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+use swc_atoms::JsWord;
+
+mod package;
+#[cfg(test)]
+mod tests;
+mod tsconfig;
+
+pub trait Resolve: Send + Sync {
+    ///
+    /// Returned filename will be hashed if possible and used to generate module
+    /// id.
+    fn resolve(&self, base: &Path, import: &str) -> Result<PathBuf, Error>;
+}
+
+impl<T: ?Sized + Resolve> Resolve for Box<T> {
+    fn resolve(&self, base: &Path, import: &str) -> Result<PathBuf, Error> {
+        T::resolve(self, base, import)
+    }
+}
+
+impl<'a, T: ?Sized + Resolve> Resolve for &'a T {
+    fn resolve(&self, base: &Path, import: &str) -> Result<PathBuf, Error> {
+        T::resolve(self, base, import)
+    }
+}
+
+/// Resolves modules like node.js, with options of [ResolveConfig].
+///
+/// In addition to the node.js algorithm, this supports aliases, the `browser`
+/// field, `exports` / `imports` of `package.json` and `paths` of
+/// `tsconfig.json`.
+pub struct NodeResolver {
+    /// Sorted by the length of keys, so the most specific alias wins.
+    alias: Vec<(String, String)>,
+    alias_fields: Vec<String>,
+    conditions: Vec<String>,
+    description_files: Vec<String>,
+    enforce_extension: bool,
+    extensions: Vec<String>,
+    main_fields: Vec<String>,
+    symlinks: bool,
+    tsconfig: Option<TsConfig>,
+
+    /// Description files, keyed by directory.
+    packages: DashMap<PathBuf, Option<Arc<Package>>, FxBuildHasher>,
+}
+
+impl Default for NodeResolver {
+    fn default() -> Self {
+        NodeResolver::new(&Default::default(), Path::new("."))
+            .expect("failed to create a resolver with the default config")
+    }
+}
+
+impl NodeResolver {
+    /// Relative paths in `config` are resolved from `working_dir`.
+    pub fn new(config: &ResolveConfig, working_dir: &Path) -> Result<Self, Error> {
+        let working_dir = if working_dir.is_absolute() {
+            working_dir.to_path_buf()
+        } else {
+            env::current_dir()
+                .context("failed to get current directory")?
+                .join(working_dir)
+        };
+
+        let mut alias = config
+            .alias
+            .iter()
+            .flat_map(|alias| &alias.map)
+            .map(|(key, target)| {
+                let target = if is_path_like(&target.to_string_lossy()) {
+                    normalize(&working_dir.join(target))
+                } else {
+                    target.clone()
+                };
+
+                (key.to_string(), target.to_string_lossy().into_owned())
+            })
+            .collect::<Vec<_>>();
+        alias.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+
+        let tsconfig = match &config.tsconfig {
+            Some(path) => Some(
+                TsConfig::load(&working_dir.join(path))
+                    .with_context(|| format!("failed to load {}", path.display()))?,
+            ),
+            None => None,
+        };
+
+        Ok(NodeResolver {
+            alias,
+            alias_fields: strings(&config.alias_fields),
+            conditions: strings(&config.condition_names),
+            description_files: strings(&config.description_files),
+            enforce_extension: config.enforce_extension,
+            extensions: strings(&config.extensions),
+            main_fields: strings(&config.main_fields),
+            symlinks: config.symlinks,
+            tsconfig,
+            packages: Default::default(),
+        })
+    }
+
+    fn resolve_request(&self, base_dir: &Path, import: &str) -> Result<PathBuf, Error> {
+        let path = match self.alias(import) {
+            Some(aliased) => self.resolve_unaliased(base_dir, &aliased)?,
+            None => self.resolve_unaliased(base_dir, import)?,
+        };
+
+        self.apply_alias_fields_to_file(path)
+    }
+
+    fn resolve_unaliased(&self, base_dir: &Path, import: &str) -> Result<PathBuf, Error> {
+        if is_path_like(import) {
+            return self.resolve_path(&base_dir.join(import));
+        }
+
+        if let Some(path) = self.apply_alias_fields_to_module(base_dir, import)? {
+            return Ok(path);
+        }
+
+        if import.starts_with('#') {
+            return self.resolve_package_import(base_dir, import);
+        }
+
+        if let Some(tsconfig) = &self.tsconfig {
+            let path = tsconfig
+                .candidates(import)
+                .into_iter()
+                .find_map(|path| self.resolve_path(&path).ok());
+            if let Some(path) = path {
+                return Ok(path);
+            }
+        }
+
+        self.resolve_node_modules(base_dir, import)
+    }
+
+    /// Applies webpack-style aliases.
+    fn alias(&self, import: &str) -> Option<String> {
+        self.alias.iter().find_map(|(key, target)| {
+            if key.ends_with('$') {
+                if key[..key.len() - 1] == *import {
+                    return Some(target.clone());
+                }
+                return None;
+            }
+
+            if import == key {
+                return Some(target.clone());
+            }
+            if import.starts_with(&**key) && import[key.len()..].starts_with('/') {
+                return Some(format!("{}{}", target, &import[key.len()..]));
+            }
+
+            None
+        })
+    }
+
+    /// Replaces a module using the `browser` field of the package which
+    /// contains `base_dir`.
+    fn apply_alias_fields_to_module(
+        &self,
+        base_dir: &Path,
+        import: &str,
+    ) -> Result<Option<PathBuf>, Error> {
+        let (pkg_dir, pkg) = match self.find_package(base_dir) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        for field in &self.alias_fields {
+            if let Some(AliasField::Map(map)) = pkg.alias_field(field) {
+                if let Some((_, target)) = map.iter().find(|(key, _)| *key == import) {
+                    return self
+                        .resolve_alias_field_target(&pkg_dir, field, import, *target)
+                        .map(Some);
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Replaces a file using the `browser` field of the package which contains
+    /// `path`.
+    fn apply_alias_fields_to_file(&self, path: PathBuf) -> Result<PathBuf, Error> {
+        let (pkg_dir, pkg) = match path.parent().and_then(|dir| self.find_package(dir)) {
+            Some(v) => v,
+            None => return Ok(path),
+        };
+
+        for field in &self.alias_fields {
+            if let Some(AliasField::Map(map)) = pkg.alias_field(field) {
+                for (key, target) in map {
+                    if !is_path_like(key) {
+                        continue;
+                    }
+
+                    let key_path = normalize(&pkg_dir.join(key));
+                    if self.load_as_file(&key_path).as_ref() == Some(&path) {
+                        return self.resolve_alias_field_target(&pkg_dir, field, key, target);
+                    }
+                }
+            }
+        }
+
+        Ok(path)
+    }
+
+    fn resolve_alias_field_target(
+        &self,
+        pkg_dir: &Path,
+        field: &str,
+        key: &str,
+        target: Option<&str>,
+    ) -> Result<PathBuf, Error> {
+        let target = match target {
+            Some(target) => target,
+            None => bail!(
+                "`{}` is disabled by the `{}` field of {}",
+                key,
+                field,
+                pkg_dir.display()
+            ),
+        };
+
+        if is_path_like(target) {
+            self.resolve_path(&pkg_dir.join(target))
+        } else {
+            self.resolve_node_modules(pkg_dir, target)
+        }
+    }
+
+    /// Resolves `#specifier` using `imports` of the nearest `package.json`.
+    fn resolve_package_import(&self, base_dir: &Path, import: &str) -> Result<PathBuf, Error> {
+        let (pkg_dir, pkg) = self
+            .find_package(base_dir)
+            .context("failed to find package.json")?;
+
+        let target = pkg
+            .imports
+            .as_ref()
+            .and_then(|imports| imports.lookup_import(import))
+            .and_then(|(target, matched)| target.pick(&self.conditions(), matched));
+
+        match target {
+            Some(target) if target.starts_with("./") => {
+                self.exact_file(&pkg_dir.join(target), import, &pkg_dir)
+            }
+            Some(target) => self.resolve_node_modules(&pkg_dir, &target),
+            None => bail!(
+                "`{}` is not defined in `imports` of {}",
+                import,
+                pkg_dir.display()
+            ),
+        }
+    }
+
+    fn resolve_node_modules(&self, base_dir: &Path, import: &str) -> Result<PathBuf, Error> {
+        let (name, rest) = split_package_name(import);
+
+        for dir in base_dir.ancestors() {
+            if dir.file_name().map_or(false, |n| n == "node_modules") {
+                continue;
+            }
+
+            let pkg_dir = dir.join("node_modules").join(name);
+            if !pkg_dir.is_dir() {
+                continue;
+            }
+
+            if let Some(pkg) = self.package(&pkg_dir) {
+                if let Some(exports) = &pkg.exports {
+                    let subpath = format!(".{}", rest);
+                    let target = exports
+                        .lookup(&subpath)
+                        .and_then(|(target, matched)| target.pick(&self.conditions(), matched));
+
+                    return match target {
+                        Some(target) if target.starts_with("./") => {
+                            self.exact_file(&pkg_dir.join(target), &subpath, &pkg_dir)
+                        }
+                        Some(target) => bail!(
+                            "invalid target `{}` for `{}` in `exports` of {}",
+                            target,
+                            subpath,
+                            pkg_dir.display()
+                        ),
+                        None => bail!("`{}` is not exported from {}", subpath, pkg_dir.display()),
+                    };
+                }
+            }
+
+            if let Ok(path) = self.resolve_path(&pkg_dir.join(rest.trim_start_matches('/'))) {
+                return Ok(path);
+            }
+        }
+
+        bail!("cannot find module `{}`", import)
+    }
+
+    fn resolve_path(&self, path: &Path) -> Result<PathBuf, Error> {
+        let path = normalize(path);
+
+        if let Some(file) = self.load_as_file(&path) {
+            return Ok(file);
+        }
+        if let Some(file) = self.load_as_directory(&path) {
+            return Ok(file);
+        }
+
+        bail!("cannot find {}", path.display())
+    }
+
+    /// Targets of `exports` and `imports` are not completed with extensions.
+    fn exact_file(&self, path: &Path, key: &str, pkg_dir: &Path) -> Result<PathBuf, Error> {
+        let path = normalize(path);
+        if path.is_file() {
+            return Ok(path);
+        }
+
+        bail!(
+            "{} (mapped from `{}` of {}) does not exist",
+            path.display(),
+            key,
+            pkg_dir.display()
+        )
+    }
+
+    fn load_as_file(&self, path: &Path) -> Option<PathBuf> {
+        if path.is_file() {
+            return Some(path.to_path_buf());
+        }
+        if self.enforce_extension {
+            return None;
+        }
+
+        self.with_extensions(path)
+    }
+
+    fn load_as_directory(&self, dir: &Path) -> Option<PathBuf> {
+        if !dir.is_dir() {
+            return None;
+        }
+
+        if let Some(pkg) = self.package(dir) {
+            let browser_mains =
+                self.alias_fields
+                    .iter()
+                    .filter_map(|field| match pkg.alias_field(field) {
+                        Some(AliasField::Main(main)) => Some(main),
+                        _ => None,
+                    });
+            let mains = self
+                .main_fields
+                .iter()
+                .filter_map(|field| pkg.main_field(field));
+
+            for main in browser_mains.chain(mains) {
+                let path = normalize(&dir.join(main));
+                if let Some(file) = self.load_as_file(&path).or_else(|| self.load_index(&path)) {
+                    return Some(file);
+                }
+            }
+        }
+
+        self.load_index(dir)
+    }
+
+    fn load_index(&self, dir: &Path) -> Option<PathBuf> {
+        self.with_extensions(&dir.join("index"))
+    }
+
+    fn with_extensions(&self, path: &Path) -> Option<PathBuf> {
+        self.extensions
+            .iter()
+            .map(|ext| {
+                let mut path = path.as_os_str().to_os_string();
+                path.push(ext);
+                PathBuf::from(path)
+            })
+            .find(|path| path.is_file())
+    }
+
+    /// Finds the nearest package of `dir`.
+    fn find_package(&self, dir: &Path) -> Option<(PathBuf, Arc<Package>)> {
+        dir.ancestors()
+            .find_map(|dir| Some((dir.to_path_buf(), self.package(dir)?)))
+    }
+
+    fn package(&self, dir: &Path) -> Option<Arc<Package>> {
+        if let Some(pkg) = self.packages.get(dir) {
+            return pkg.value().clone();
+        }
+
+        let pkg = self
+            .description_files
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|file| file.is_file())
+            .and_then(|file| match Package::load(&file) {
+                Ok(pkg) => Some(Arc::new(pkg)),
+                Err(err) => {
+                    log::warn!("{:?}", err);
+                    None
+                }
+            });
+
+        self.packages.insert(dir.to_path_buf(), pkg.clone());
+        pkg
+    }
+
+    fn conditions(&self) -> Vec<&str> {
+        self.conditions.iter().map(|s| &**s).collect()
+    }
+}
+
+impl Resolve for NodeResolver {
+    fn resolve(&self, base: &Path, import: &str) -> Result<PathBuf, Error> {
+        let base_dir = base
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        let path = self.resolve_request(&base_dir, import).with_context(|| {
+            format!(
+                "node-resolve failed; basedir = {}, import = {}",
+                base_dir.display(),
+                import
+            )
+        })?;
+
+        if self.symlinks {
+            return path
+                .canonicalize()
+                .with_context(|| format!("failed to canonicalize {}", path.display()));
+        }
+
+        Ok(path)
+    }
+}
+
+fn strings(v: &[JsWord]) -> Vec<String> {
+    v.iter().map(|s| s.to_string()).collect()
+}
+
+fn is_path_like(s: &str) -> bool {
+    s == "."
+        || s == ".."
+        || s.starts_with("./")
+        || s.starts_with("../")
+        || Path::new(s).is_absolute()
+}
+
+/// Splits `@scope/pkg/file` into `@scope/pkg` and `/file`.
+fn split_package_name(import: &str) -> (&str, &str) {
+    let idx = if import.starts_with('@') {
+        import.match_indices('/').nth(1).map(|(idx, _)| idx)
+    } else {
+        import.find('/')
+    };
+
+    match idx {
+        Some(idx) => (&import[..idx], &import[idx..]),
+        None => (import, ""),
+    }
+}
+
+/// Removes `.` and `..` without touching the file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut buf = PathBuf::new();
+
+    for c in path.components() {
+        match c {
+            Component::CurDir => {}
+            Component::ParentDir => match buf.components().next_back() {
+                Some(Component::Normal(..)) => {
+                    buf.pop();
+                }
+                Some(Component::RootDir) | Some(Component::Prefix(..)) => {}
+                _ => buf.push(".."),
+            },
+            c => buf.push(c.as_os_str()),
+        }
+    }
+
+    if buf.as_os_str().is_empty() {
+        buf.push(".");
+    }
+
+    buf
+}
@@ -0,0 +1,238 @@
+//! `package.json`
+
+use anyhow::{Context, Error};
+use fxhash::FxHashMap;
+use serde::{
+    de::{self, MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use serde_json::Value;
+use std::{fmt, fs, path::Path};
+
+#[derive(Debug, Deserialize)]
+pub(super) struct Package {
+    #[serde(default)]
+    pub exports: Option<Target>,
+
+    #[serde(default)]
+    pub imports: Option<Target>,
+
+    #[serde(flatten)]
+    fields: FxHashMap<String, Value>,
+}
+
+impl Package {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+
+        serde_json::from_str(&content)
+            .with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    /// Returns a field like `main` or `module`.
+    pub fn main_field(&self, name: &str) -> Option<&str> {
+        self.fields.get(name)?.as_str()
+    }
+
+    /// Returns a field like `browser`.
+    pub fn alias_field(&self, name: &str) -> Option<AliasField> {
+        match self.fields.get(name)? {
+            Value::String(s) => Some(AliasField::Main(s)),
+            Value::Object(map) => Some(AliasField::Map(
+                map.iter()
+                    .filter_map(|(k, v)| {
+                        let v = match v {
+                            Value::String(s) => Some(&**s),
+                            Value::Bool(false) => None,
+                            _ => return None,
+                        };
+
+                        Some((&**k, v))
+                    })
+                    .collect(),
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// https://github.com/defunctzombie/package-browser-field-spec
+#[derive(Debug)]
+pub(super) enum AliasField<'a> {
+    /// Replaces the main field.
+    Main(&'a str),
+    /// Replaces modules or files. `None` means the module is disabled.
+    Map(Vec<(&'a str, Option<&'a str>)>),
+}
+
+/// Value of `exports` or `imports`.
+///
+/// As the order of conditions matters, this preserves the order of keys.
+#[derive(Debug)]
+pub(super) enum Target {
+    Null,
+    Path(String),
+    Fallbacks(Vec<Target>),
+    /// Conditions or subpaths.
+    Map(Vec<(String, Target)>),
+}
+
+impl Target {
+    /// Finds the target of `subpath` (like `.` or `./feature`) from a map of
+    /// subpaths.
+    ///
+    /// Returns the target and the string matched by `*` in the key.
+    pub fn lookup<'a, 'b>(&'a self, subpath: &'b str) -> Option<(&'a Target, Option<&'b str>)> {
+        let map = match self {
+            Target::Map(map) if map.iter().all(|(k, _)| k.starts_with('.')) => map,
+            // Sugar for `{ ".": target }`
+            _ if subpath == "." => return Some((self, None)),
+            _ => return None,
+        };
+
+        lookup_key(map, subpath)
+    }
+
+    /// Finds the target of `#specifier` from `imports`.
+    pub fn lookup_import<'a, 'b>(
+        &'a self,
+        specifier: &'b str,
+    ) -> Option<(&'a Target, Option<&'b str>)> {
+        match self {
+            Target::Map(map) => lookup_key(map, specifier),
+            _ => None,
+        }
+    }
+
+    /// Picks a path using `conditions`. `*` in the path is replaced with
+    /// `matched`.
+    pub fn pick(&self, conditions: &[&str], matched: Option<&str>) -> Option<String> {
+        match self {
+            Target::Null => None,
+            Target::Path(path) => Some(match matched {
+                Some(matched) if path.contains('*') => path.replace('*', matched),
+                Some(matched) if path.ends_with('/') => format!("{}{}", path, matched),
+                _ => path.clone(),
+            }),
+            Target::Fallbacks(targets) => targets
+                .iter()
+                .filter_map(|target| target.pick(conditions, matched))
+                .next(),
+            Target::Map(map) => map
+                .iter()
+                .filter(|(k, _)| k == "default" || conditions.contains(&&**k))
+                .filter_map(|(_, target)| target.pick(conditions, matched))
+                .next(),
+        }
+    }
+}
+
+fn lookup_key<'a, 'b>(
+    map: &'a [(String, Target)],
+    key: &'b str,
+) -> Option<(&'a Target, Option<&'b str>)> {
+    if let Some((_, target)) = map.iter().find(|(k, _)| k == key && !k.contains('*')) {
+        return Some((target, None));
+    }
+
+    // The longest prefix wins.
+    let mut best: Option<(usize, &Target, &str)> = None;
+    for (k, target) in map {
+        let matched = match k.find('*') {
+            Some(idx) => {
+                let (prefix, suffix) = (&k[..idx], &k[idx + 1..]);
+                if key.len() >= prefix.len() + suffix.len()
+                    && key.starts_with(prefix)
+                    && key.ends_with(suffix)
+                {
+                    Some((prefix.len(), &key[prefix.len()..key.len() - suffix.len()]))
+                } else {
+                    None
+                }
+            }
+            // Deprecated folder mappings
+            None if k.ends_with('/') && key.starts_with(&**k) => Some((k.len(), &key[k.len()..])),
+            None => None,
+        };
+
+        if let Some((len, matched)) = matched {
+            if best.map_or(true, |(best_len, ..)| len > best_len) {
+                best = Some((len, target, matched));
+            }
+        }
+    }
+
+    best.map(|(_, target, matched)| (target, Some(matched)))
+}
+
+impl<'de> Deserialize<'de> for Target {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(TargetVisitor)
+    }
+}
+
+struct TargetVisitor;
+
+impl<'de> Visitor<'de> for TargetVisitor {
+    type Value = Target;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("null, string, array or object")
+    }
+
+    fn visit_unit<E>(self) -> Result<Target, E>
+    where
+        E: de::Error,
+    {
+        Ok(Target::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<Target, E>
+    where
+        E: de::Error,
+    {
+        Ok(Target::Null)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Target, E>
+    where
+        E: de::Error,
+    {
+        Ok(Target::Path(v.into()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Target, E>
+    where
+        E: de::Error,
+    {
+        Ok(Target::Path(v))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Target, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut targets = vec![];
+        while let Some(target) = seq.next_element()? {
+            targets.push(target);
+        }
+
+        Ok(Target::Fallbacks(targets))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Target, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entries = vec![];
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+
+        Ok(Target::Map(entries))
+    }
+}
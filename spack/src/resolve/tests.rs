@@ -0,0 +1,297 @@
+use super::{NodeResolver, Resolve};
+use crate::config::{AliasConfig, ResolveConfig};
+use std::{
+    fs::{create_dir_all, write},
+    path::Path,
+};
+use tempfile::TempDir;
+
+fn files(files: &[(&str, &str)]) -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    for (path, content) in files {
+        let path = dir.path().join(path);
+        create_dir_all(path.parent().unwrap()).unwrap();
+        write(path, content).unwrap();
+    }
+
+    dir
+}
+
+fn resolve(resolver: &NodeResolver, dir: &TempDir, import: &str) -> String {
+    let path = resolver
+        .resolve(&dir.path().join("src/index.js"), import)
+        .unwrap();
+    let root = dir.path().canonicalize().unwrap();
+
+    path.strip_prefix(&root)
+        .unwrap()
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+#[test]
+fn extensions_and_main_fields() {
+    let dir = files(&[
+        ("src/index.js", ""),
+        ("src/a.ts", ""),
+        ("src/b/index.js", ""),
+        (
+            "node_modules/foo/package.json",
+            r#"{ "main": "./lib/main.js" }"#,
+        ),
+        ("node_modules/foo/lib/main.js", ""),
+        ("node_modules/@scope/bar/index.js", ""),
+        ("node_modules/@scope/bar/lib.js", ""),
+    ]);
+    let resolver = NodeResolver::default();
+
+    assert_eq!(resolve(&resolver, &dir, "./a"), "src/a.ts");
+    assert_eq!(resolve(&resolver, &dir, "./b"), "src/b/index.js");
+    assert_eq!(
+        resolve(&resolver, &dir, "foo"),
+        "node_modules/foo/lib/main.js"
+    );
+    assert_eq!(
+        resolve(&resolver, &dir, "@scope/bar"),
+        "node_modules/@scope/bar/index.js"
+    );
+    assert_eq!(
+        resolve(&resolver, &dir, "@scope/bar/lib"),
+        "node_modules/@scope/bar/lib.js"
+    );
+}
+
+#[test]
+fn enforce_extension() {
+    let dir = files(&[("src/index.js", ""), ("src/a.js", "")]);
+    let resolver = NodeResolver::new(
+        &ResolveConfig {
+            enforce_extension: true,
+            ..Default::default()
+        },
+        dir.path(),
+    )
+    .unwrap();
+
+    assert_eq!(resolve(&resolver, &dir, "./a.js"), "src/a.js");
+    assert!(resolver
+        .resolve(&dir.path().join("src/index.js"), "./a")
+        .is_err());
+}
+
+#[test]
+fn alias() {
+    let dir = files(&[
+        ("src/index.js", ""),
+        ("packages/utils/index.js", ""),
+        ("packages/utils/string.js", ""),
+        ("packages/exact.js", ""),
+    ]);
+    let resolver = NodeResolver::new(
+        &ResolveConfig {
+            alias: Some(AliasConfig {
+                map: vec![
+                    ("@utils".into(), "./packages/utils".into()),
+                    ("exact$".into(), "./packages/exact.js".into()),
+                ]
+                .into_iter()
+                .collect(),
+            }),
+            ..Default::default()
+        },
+        dir.path(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        resolve(&resolver, &dir, "@utils"),
+        "packages/utils/index.js"
+    );
+    assert_eq!(
+        resolve(&resolver, &dir, "@utils/string"),
+        "packages/utils/string.js"
+    );
+    assert_eq!(resolve(&resolver, &dir, "exact"), "packages/exact.js");
+    assert!(resolver
+        .resolve(&dir.path().join("src/index.js"), "exact/foo")
+        .is_err());
+}
+
+#[test]
+fn browser_field() {
+    let dir = files(&[
+        ("src/index.js", ""),
+        (
+            "node_modules/foo/package.json",
+            r#"{
+                "main": "./node.js",
+                "browser": "./browser.js"
+            }"#,
+        ),
+        ("node_modules/foo/node.js", ""),
+        ("node_modules/foo/browser.js", ""),
+        (
+            "node_modules/bar/package.json",
+            r#"{
+                "browser": {
+                    "./lib/node.js": "./lib/browser.js",
+                    "buffer": "buffer-shim",
+                    "./lib/disabled.js": false
+                }
+            }"#,
+        ),
+        ("node_modules/bar/index.js", ""),
+        ("node_modules/bar/lib/node.js", ""),
+        ("node_modules/bar/lib/browser.js", ""),
+        ("node_modules/bar/lib/disabled.js", ""),
+        ("node_modules/buffer-shim/index.js", ""),
+    ]);
+    let resolver = NodeResolver::default();
+
+    assert_eq!(
+        resolve(&resolver, &dir, "foo"),
+        "node_modules/foo/browser.js"
+    );
+    assert_eq!(
+        resolve(&resolver, &dir, "bar/lib/node"),
+        "node_modules/bar/lib/browser.js"
+    );
+    assert!(resolver
+        .resolve(&dir.path().join("src/index.js"), "bar/lib/disabled")
+        .is_err());
+
+    // Modules imported from `bar` are replaced.
+    let path = resolver
+        .resolve(&dir.path().join("node_modules/bar/index.js"), "buffer")
+        .unwrap();
+    assert!(
+        path.ends_with("node_modules/buffer-shim/index.js"),
+        "{}",
+        path.display()
+    );
+}
+
+#[test]
+fn exports() {
+    let dir = files(&[
+        ("src/index.js", ""),
+        (
+            "node_modules/foo/package.json",
+            r#"{
+                "main": "./main.js",
+                "exports": {
+                    ".": {
+                        "require": "./main.cjs",
+                        "import": "./main.mjs"
+                    },
+                    "./features/*": "./src/features/*.js",
+                    "./internal/*": null
+                }
+            }"#,
+        ),
+        ("node_modules/foo/main.js", ""),
+        ("node_modules/foo/main.cjs", ""),
+        ("node_modules/foo/main.mjs", ""),
+        ("node_modules/foo/src/features/a.js", ""),
+        ("node_modules/foo/src/internal.js", ""),
+        (
+            "node_modules/bar/package.json",
+            r#"{ "exports": "./bar.js" }"#,
+        ),
+        ("node_modules/bar/bar.js", ""),
+    ]);
+    let resolver = NodeResolver::default();
+
+    assert_eq!(resolve(&resolver, &dir, "foo"), "node_modules/foo/main.mjs");
+    assert_eq!(
+        resolve(&resolver, &dir, "foo/features/a"),
+        "node_modules/foo/src/features/a.js"
+    );
+    assert!(resolver
+        .resolve(&dir.path().join("src/index.js"), "foo/src/internal")
+        .is_err());
+    assert_eq!(resolve(&resolver, &dir, "bar"), "node_modules/bar/bar.js");
+}
+
+#[test]
+fn imports() {
+    let dir = files(&[
+        (
+            "package.json",
+            r##"{
+                "imports": {
+                    "#dep": {
+                        "browser": "dep-browser",
+                        "default": "./src/dep.js"
+                    },
+                    "#internal/*": "./src/internal/*.js"
+                }
+            }"##,
+        ),
+        ("src/index.js", ""),
+        ("src/dep.js", ""),
+        ("src/internal/a.js", ""),
+        ("node_modules/dep-browser/index.js", ""),
+    ]);
+    let resolver = NodeResolver::default();
+
+    assert_eq!(
+        resolve(&resolver, &dir, "#dep"),
+        "node_modules/dep-browser/index.js"
+    );
+    assert_eq!(resolve(&resolver, &dir, "#internal/a"), "src/internal/a.js");
+}
+
+#[test]
+fn tsconfig_paths() {
+    let dir = files(&[
+        (
+            "tsconfig.json",
+            r#"{
+                // Comments are allowed
+                "compilerOptions": {
+                    "baseUrl": ".",
+                    "paths": {
+                        "@app/*": ["src/app/*"],
+                        "config": ["src/config/prod.ts"],
+                    },
+                },
+            }"#,
+        ),
+        ("src/index.js", ""),
+        ("src/app/a.ts", ""),
+        ("src/config/prod.ts", ""),
+        ("lib/util.js", ""),
+    ]);
+    let resolver = NodeResolver::new(
+        &ResolveConfig {
+            tsconfig: Some("tsconfig.json".into()),
+            ..Default::default()
+        },
+        dir.path(),
+    )
+    .unwrap();
+
+    assert_eq!(resolve(&resolver, &dir, "@app/a"), "src/app/a.ts");
+    assert_eq!(resolve(&resolver, &dir, "config"), "src/config/prod.ts");
+    assert_eq!(resolve(&resolver, &dir, "lib/util"), "lib/util.js");
+}
+
+#[test]
+fn symlinks() {
+    let dir = files(&[("src/index.js", ""), ("src/a.js", "")]);
+    let resolver = NodeResolver::new(
+        &ResolveConfig {
+            symlinks: false,
+            ..Default::default()
+        },
+        dir.path(),
+    )
+    .unwrap();
+
+    let path = resolver
+        .resolve(&dir.path().join("src/index.js"), "./a")
+        .unwrap();
+    assert_eq!(path, dir.path().join("src/a.js"));
+    assert!(Path::new(&path).is_file());
+}
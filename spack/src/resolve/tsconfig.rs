@@ -0,0 +1,208 @@
+//! `compilerOptions.baseUrl` and `compilerOptions.paths` of `tsconfig.json`.
+
+use anyhow::{Context, Error};
+use fxhash::FxHashMap;
+use serde::Deserialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug)]
+pub(super) struct TsConfig {
+    /// Absolute path of `baseUrl`.
+    base_url: Option<PathBuf>,
+    /// Substitutions of `paths` are relative to this.
+    paths_base: PathBuf,
+    paths: Vec<(String, Vec<String>)>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawConfig {
+    #[serde(default)]
+    extends: Option<String>,
+    #[serde(default)]
+    compiler_options: RawCompilerOptions,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawCompilerOptions {
+    #[serde(default)]
+    base_url: Option<String>,
+    #[serde(default)]
+    paths: Option<FxHashMap<String, Vec<String>>>,
+}
+
+impl TsConfig {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let (base_url, paths) = load_options(path)?;
+        let paths_base = base_url.clone().unwrap_or_else(|| {
+            path.parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| PathBuf::from("."))
+        });
+
+        Ok(TsConfig {
+            base_url,
+            paths_base,
+            paths: paths.unwrap_or_default().into_iter().collect(),
+        })
+    }
+
+    /// Returns paths to try, in order.
+    pub fn candidates(&self, import: &str) -> Vec<PathBuf> {
+        let mut best: Option<(usize, &[String], &str)> = None;
+
+        for (pattern, substitutions) in &self.paths {
+            let matched = match pattern.find('*') {
+                Some(idx) => {
+                    let (prefix, suffix) = (&pattern[..idx], &pattern[idx + 1..]);
+                    if import.len() >= prefix.len() + suffix.len()
+                        && import.starts_with(prefix)
+                        && import.ends_with(suffix)
+                    {
+                        Some((
+                            prefix.len(),
+                            &import[prefix.len()..import.len() - suffix.len()],
+                        ))
+                    } else {
+                        None
+                    }
+                }
+                None if pattern == import => Some((usize::MAX, "")),
+                None => None,
+            };
+
+            if let Some((len, matched)) = matched {
+                if best.map_or(true, |(best_len, ..)| len > best_len) {
+                    best = Some((len, &substitutions[..], matched));
+                }
+            }
+        }
+
+        match best {
+            Some((_, substitutions, matched)) => substitutions
+                .iter()
+                .map(|s| self.paths_base.join(s.replace('*', matched)))
+                .collect(),
+            None => self
+                .base_url
+                .iter()
+                .map(|base_url| base_url.join(import))
+                .collect(),
+        }
+    }
+}
+
+/// Loads `baseUrl` (as an absolute path) and `paths`, following `extends`.
+fn load_options(
+    path: &Path,
+) -> Result<(Option<PathBuf>, Option<FxHashMap<String, Vec<String>>>), Error> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let config: RawConfig = serde_json::from_str(&strip_comments(&content))
+        .with_context(|| format!("failed to parse {}", path.display()))?;
+
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+
+    let (mut base_url, mut paths) = match config.extends {
+        Some(extends) if extends.starts_with('.') => {
+            let mut file = dir.join(extends);
+            if file.extension().is_none() {
+                file.set_extension("json");
+            }
+
+            load_options(&file)?
+        }
+        _ => (None, None),
+    };
+
+    if let Some(v) = config.compiler_options.base_url {
+        base_url = Some(dir.join(v));
+    }
+    if let Some(v) = config.compiler_options.paths {
+        paths = Some(v);
+    }
+
+    Ok((base_url, paths))
+}
+
+/// `tsconfig.json` may contain comments and trailing commas.
+fn strip_comments(s: &str) -> String {
+    let mut buf = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                buf.push(c);
+                while let Some(c) = chars.next() {
+                    buf.push(c);
+                    match c {
+                        '\\' => buf.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                while let Some(&c) = chars.peek() {
+                    if c == '\n' {
+                        break;
+                    }
+                    chars.next();
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = None;
+                while let Some(c) = chars.next() {
+                    if prev == Some('*') && c == '/' {
+                        break;
+                    }
+                    prev = Some(c);
+                }
+            }
+            '}' | ']' => {
+                let trimmed = buf.trim_end().len();
+                if buf[..trimmed].ends_with(',') {
+                    buf.truncate(trimmed - 1);
+                }
+                buf.push(c);
+            }
+            _ => buf.push(c),
+        }
+    }
+
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comments() {
+        let s = strip_comments(
+            r#"{
+    // comment
+    "compilerOptions": {
+        /* comment */
+        "baseUrl": "./src", // comment
+        "paths": {
+            "@app/*": ["app/*",],
+            "url": ["http://example.com"]
+        },
+    },
+}"#,
+        );
+
+        let config: RawConfig = serde_json::from_str(&s).unwrap();
+        let paths = config.compiler_options.paths.unwrap();
+        assert_eq!(config.compiler_options.base_url.as_deref(), Some("./src"));
+        assert_eq!(paths["@app/*"], vec!["app/*".to_string()]);
+        assert_eq!(paths["url"], vec!["http://example.com".to_string()]);
+    }
+}
@@ -9,6 +9,7 @@ use fxhash::FxHashMap;
 use spack::{
     config::{Config, EntryConfig},
     loaders::swc::SwcLoader,
+    resolve::NodeResolver,
     BundleKind, Bundler,
 };
 use std::{
@@ -137,13 +138,14 @@ fn reference_tests(tests: &mut Vec<TestDescAndFn>, errors: bool) -> Result<(), i
                     resolve: None,
                     options: None,
                 };
+                let resolver = NodeResolver::default();
                 let bundler = Bundler::new(
                     compiler.clone(),
                     swc::config::Options {
                         swcrc: true,
                         ..Default::default()
                     },
-                    &spack::resolve::NodeResolver,
+                    &resolver,
                     &loader,
                 );
 
@@ -163,7 +165,7 @@ fn reference_tests(tests: &mut Vec<TestDescAndFn>, errors: bool) -> Result<(), i
                         BundleKind::Named { name } | BundleKind::Lib { name } => {
                             PathBuf::from(name)
                         }
-                        BundleKind::Dynamic => format!("dynamic.{}.js", bundled.id).into(),
+                        BundleKind::Dynamic { .. } => format!("dynamic.{}.js", bundled.id).into(),
                     };
 
                     let output_path = entry.path().join("output").join(name.file_name().unwrap());
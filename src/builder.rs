@@ -1,4 +1,5 @@
 use crate::config::{GlobalPassOption, JscTarget, ModuleConfig};
+use anyhow::{Context, Error};
 use either::Either;
 use std::{collections::HashMap, sync::Arc};
 use swc_atoms::JsWord;
@@ -117,34 +118,57 @@ impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
         root_mark: Mark,
         syntax: Syntax,
         module: Option<ModuleConfig>,
-    ) -> impl Pass {
+    ) -> Result<impl Pass, Error> {
         let need_interop_analysis = match module {
             Some(ModuleConfig::CommonJs(ref c)) => !c.no_interop,
             Some(ModuleConfig::Amd(ref c)) => !c.config.no_interop,
             Some(ModuleConfig::Umd(ref c)) => !c.config.no_interop,
-            Some(ModuleConfig::Es6) | None => false,
+            Some(ModuleConfig::SystemJs(..)) | Some(ModuleConfig::Es6) | None => false,
         };
 
         // compat
         let compat_pass = if let Some(env) = self.env {
-            Either::Left(preset_env::preset_env(self.global_mark, env))
+            Either::Left(
+                preset_env::preset_env(self.global_mark, env).context("invalid `env` config")?,
+            )
         } else {
+            let target = self.target;
+            let lower = |f: preset_env::Feature| f.should_enable_for(target);
+
             Either::Right(chain!(
+                Optional::new(
+                    compat::es2021::logical_assignments(),
+                    lower(preset_env::Feature::LogicalAssignmentOperators)
+                ),
+                Optional::new(
+                    compat::es2021::numeric_separator(),
+                    lower(preset_env::Feature::NumericSeparator)
+                ),
                 Optional::new(
                     compat::es2020::nullish_coalescing(),
-                    self.target < JscTarget::Es2020
+                    lower(preset_env::Feature::NullishCoalescing)
                 ),
                 Optional::new(
                     compat::es2020::optional_chaining(),
-                    self.target < JscTarget::Es2020
+                    lower(preset_env::Feature::OptionalChaining)
                 ),
                 Optional::new(
                     compat::es2020::class_properties(),
-                    self.target < JscTarget::Es2020
+                    lower(preset_env::Feature::ClassProperties)
+                        || lower(preset_env::Feature::PrivateMethods)
+                ),
+                Optional::new(
+                    compat::es2018(),
+                    lower(preset_env::Feature::ObjectRestSpread)
+                ),
+                Optional::new(
+                    compat::es2017(),
+                    lower(preset_env::Feature::AsyncToGenerator)
+                ),
+                Optional::new(
+                    compat::es2016(),
+                    lower(preset_env::Feature::ExponentiationOperator)
                 ),
-                Optional::new(compat::es2018(), self.target <= JscTarget::Es2018),
-                Optional::new(compat::es2017(), self.target <= JscTarget::Es2017),
-                Optional::new(compat::es2016(), self.target <= JscTarget::Es2016),
                 Optional::new(
                     compat::es2015(
                         self.global_mark,
@@ -158,16 +182,16 @@ impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
                             },
                         }
                     ),
-                    self.target <= JscTarget::Es2015
+                    lower(preset_env::Feature::Classes)
                 ),
                 Optional::new(
                     compat::es3(syntax.dynamic_import()),
-                    self.target <= JscTarget::Es3
+                    lower(preset_env::Feature::ReservedWords)
                 )
             ))
         };
 
-        chain!(
+        Ok(chain!(
             self.pass,
             compat_pass,
             compat::reserved_words::reserved_words(),
@@ -180,6 +204,6 @@ impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
             ModuleConfig::build(self.cm.clone(), root_mark, module),
             Optional::new(hygiene(), self.hygiene),
             Optional::new(fixer(), self.fixer),
-        )
+        ))
     }
 }
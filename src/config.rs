@@ -20,9 +20,9 @@ use swc_ecmascript::{
     parser::{lexer::Lexer, Parser, Session as ParseSess, SourceFileInput, Syntax, TsConfig},
     preset_env,
     transforms::{
-        const_modules, modules,
+        const_modules, flow, minify, modules,
         optimization::{simplifier, InlineGlobals, JsonParse},
-        pass::{noop, Optional, Pass},
+        pass::{Optional, Pass},
         proposals::{decorators, export},
         react, resolver_with_mark, typescript,
     },
@@ -156,7 +156,7 @@ impl Options {
         handler: &Handler,
         is_module: bool,
         config: Option<Config>,
-    ) -> BuiltConfig<impl Pass> {
+    ) -> Result<BuiltConfig<impl Pass>, Error> {
         let mut config = config.unwrap_or_else(Default::default);
         if let Some(ref c) = self.config {
             config.merge(c)
@@ -168,6 +168,7 @@ impl Options {
             external_helpers,
             target,
             loose,
+            target_diagnostics,
         } = config.jsc;
 
         let syntax = syntax.unwrap_or_default();
@@ -176,6 +177,9 @@ impl Options {
         if syntax.typescript() {
             transform.legacy_decorator = true;
         }
+        if transform.decorator_metadata && !transform.legacy_decorator {
+            bail!("`jsc.transform.decoratorMetadata` requires `jsc.transform.legacyDecorator`")
+        }
         let optimizer = transform.optimizer;
         let enable_optimizer = optimizer.is_some();
 
@@ -218,13 +222,20 @@ impl Options {
         let pass = chain!(
             // handle jsx
             Optional::new(react::react(cm.clone(), transform.react), syntax.jsx()),
+            // Decorator metadata uses type information
+            Optional::new(
+                decorators::metadata(),
+                syntax.decorators() && transform.decorator_metadata
+            ),
             Optional::new(typescript::strip(), syntax.typescript()),
+            Optional::new(flow::strip(), syntax.flow()),
             resolver_with_mark(root_mark),
             const_modules,
             optimization,
             Optional::new(
                 decorators(decorators::Config {
-                    legacy: transform.legacy_decorator
+                    legacy: transform.legacy_decorator,
+                    ..Default::default()
                 }),
                 syntax.decorators()
             ),
@@ -241,21 +252,31 @@ impl Options {
             .hygiene(!self.disable_hygiene)
             .fixer(!self.disable_fixer)
             .preset_env(config.env)
-            .finalize(root_mark, syntax, config.module);
+            .finalize(root_mark, syntax, config.module)?;
 
-        BuiltConfig {
-            minify: config.minify.unwrap_or(false),
+        let minify = config.minify.and_then(MinifyOptions::into_config);
+        let pass = chain!(
+            pass,
+            Optional::new(
+                minify::minifier(target, minify.clone().unwrap_or_default()),
+                minify.is_some()
+            )
+        );
+
+        Ok(BuiltConfig {
+            minify: minify.is_some(),
             pass,
             external_helpers,
             syntax,
             target,
+            target_diagnostics,
             is_module,
             source_maps: self
                 .source_maps
                 .clone()
                 .unwrap_or(SourceMapsConfig::Bool(false)),
             input_source_map: self.input_source_map.clone(),
-        }
+        })
     }
 }
 
@@ -323,6 +344,7 @@ impl Default for Rc {
                     external_helpers: false,
                     target: Default::default(),
                     loose: false,
+                    target_diagnostics: false,
                 },
                 module: None,
                 minify: None,
@@ -340,6 +362,7 @@ impl Default for Rc {
                     external_helpers: false,
                     target: Default::default(),
                     loose: false,
+                    target_diagnostics: false,
                 },
                 module: None,
                 minify: None,
@@ -357,6 +380,7 @@ impl Default for Rc {
                     external_helpers: false,
                     target: Default::default(),
                     loose: false,
+                    target_diagnostics: false,
                 },
                 module: None,
                 minify: None,
@@ -418,7 +442,34 @@ pub struct Config {
     pub module: Option<ModuleConfig>,
 
     #[serde(default)]
-    pub minify: Option<bool>,
+    pub minify: Option<MinifyOptions>,
+}
+
+/// `minify` in `.swcrc`
+///
+/// `true` is equivalent to an empty object.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MinifyOptions {
+    Bool(bool),
+    Config(minify::Config),
+}
+
+impl MinifyOptions {
+    pub fn enabled(&self) -> bool {
+        match *self {
+            MinifyOptions::Bool(b) => b,
+            MinifyOptions::Config(..) => true,
+        }
+    }
+
+    pub fn into_config(self) -> Option<minify::Config> {
+        match self {
+            MinifyOptions::Bool(false) => None,
+            MinifyOptions::Bool(true) => Some(Default::default()),
+            MinifyOptions::Config(c) => Some(c),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -491,6 +542,8 @@ pub struct BuiltConfig<P: Pass> {
     pub pass: P,
     pub syntax: Syntax,
     pub target: JscTarget,
+    /// Warn about syntax newer than `target` while parsing.
+    pub target_diagnostics: bool,
     pub minify: bool,
     pub external_helpers: bool,
     pub source_maps: SourceMapsConfig,
@@ -515,6 +568,10 @@ pub struct JscConfig {
 
     #[serde(default)]
     pub loose: bool,
+
+    /// Warns about syntax newer than `target` which can't be lowered.
+    #[serde(default)]
+    pub target_diagnostics: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -527,6 +584,8 @@ pub enum ModuleConfig {
     Umd(modules::umd::Config),
     #[serde(rename = "amd")]
     Amd(modules::amd::Config),
+    #[serde(rename = "systemjs")]
+    SystemJs(modules::system_js::Config),
     #[serde(rename = "es6")]
     Es6,
 }
@@ -538,12 +597,13 @@ impl ModuleConfig {
         config: Option<ModuleConfig>,
     ) -> Box<dyn Pass> {
         match config {
-            None | Some(ModuleConfig::Es6) => box noop(),
+            None | Some(ModuleConfig::Es6) => box modules::es6::es6(),
             Some(ModuleConfig::CommonJs(config)) => {
                 box modules::common_js::common_js(root_mark, config)
             }
             Some(ModuleConfig::Umd(config)) => box modules::umd::umd(cm, root_mark, config),
             Some(ModuleConfig::Amd(config)) => box modules::amd::amd(config),
+            Some(ModuleConfig::SystemJs(config)) => box modules::system_js::system_js(config),
         }
     }
 }
@@ -562,6 +622,9 @@ pub struct TransformConfig {
 
     #[serde(default)]
     pub legacy_decorator: bool,
+
+    #[serde(default)]
+    pub decorator_metadata: bool,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -730,6 +793,7 @@ impl Merge for JscConfig {
         self.transform.merge(&from.transform);
         self.target.merge(&from.target);
         self.external_helpers.merge(&from.external_helpers);
+        self.target_diagnostics.merge(&from.target_diagnostics);
     }
 }
 
@@ -767,6 +831,7 @@ impl Merge for TransformConfig {
         self.optimizer.merge(&from.optimizer);
         self.const_modules.merge(&from.const_modules);
         self.react.merge(&from.react);
+        self.decorator_metadata.merge(&from.decorator_metadata);
     }
 }
 
@@ -788,6 +853,12 @@ impl Merge for react::Options {
     }
 }
 
+impl Merge for MinifyOptions {
+    fn merge(&mut self, from: &Self) {
+        *self = from.clone()
+    }
+}
+
 impl Merge for ConstModulesConfig {
     fn merge(&mut self, from: &Self) {
         *self = from.clone()
@@ -10,3 +10,13 @@ fn object() {
 fn array() {
     let _: Rc = serde_json::from_str(include_str!("array.json")).expect("failed to parse");
 }
+
+#[test]
+fn minify() {
+    let rc: Rc = serde_json::from_str(include_str!("minify.json")).expect("failed to parse");
+    let config = rc.into_config(None).unwrap();
+
+    let minify = config.minify.unwrap().into_config().unwrap();
+    assert!(!minify.compress.unwrap().sequences);
+    assert!(minify.mangle.unwrap().top_level);
+}
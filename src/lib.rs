@@ -22,7 +22,7 @@ use ecmascript::{
     parser::{lexer::Lexer, Parser, Session as ParseSess, Syntax},
     transforms::{
         helpers::{self, Helpers},
-        util,
+        typescript, util,
         util::COMMENTS,
     },
 };
@@ -81,7 +81,8 @@ impl Compiler {
         })
     }
 
-    fn get_orig_src_map(
+    /// Loads the input source map of `fm`.
+    pub fn get_orig_src_map(
         &self,
         fm: &SourceFile,
         input_src_map: &InputSourceMap,
@@ -150,6 +151,20 @@ impl Compiler {
         syntax: Syntax,
         is_module: bool,
         parse_comments: bool,
+    ) -> Result<Program, Error> {
+        self.parse_js_inner(fm, target, syntax, is_module, parse_comments, false)
+    }
+
+    /// Same as [Compiler::parse_js], but warns about syntax newer than `target`
+    /// if `target_diagnostics` is true.
+    fn parse_js_inner(
+        &self,
+        fm: Arc<SourceFile>,
+        target: JscTarget,
+        syntax: Syntax,
+        is_module: bool,
+        parse_comments: bool,
+        target_diagnostics: bool,
     ) -> Result<Program, Error> {
         self.run(|| {
             let session = ParseSess {
@@ -167,6 +182,9 @@ impl Compiler {
                 },
             );
             let mut parser = Parser::new_from(session, lexer);
+            if target_diagnostics {
+                parser = parser.with_target_diagnostics();
+            }
             let program = if is_module {
                 parser
                     .parse_module()
@@ -198,17 +216,62 @@ impl Compiler {
     ) -> Result<TransformOutput, Error>
     where
         T: Node,
+    {
+        self.print_with(node, source_map, |_| orig, minify)
+    }
+
+    /// Prints a node which may contain code from multiple source files.
+    ///
+    /// `orig` is called for each source file and the returned input source map
+    /// is chained into the output source map.
+    pub fn print_with<'a, T, F>(
+        &self,
+        node: &T,
+        source_map: SourceMapsConfig,
+        orig: F,
+        minify: bool,
+    ) -> Result<TransformOutput, Error>
+    where
+        T: Node,
+        F: FnMut(&SourceFile) -> Option<&'a sourcemap::SourceMap>,
+    {
+        self.emit(
+            node,
+            source_map,
+            orig,
+            codegen::Config {
+                minify,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn emit<'a, T, F>(
+        &self,
+        node: &T,
+        source_map: SourceMapsConfig,
+        orig: F,
+        cfg: codegen::Config,
+    ) -> Result<TransformOutput, Error>
+    where
+        T: Node,
+        F: FnMut(&SourceFile) -> Option<&'a sourcemap::SourceMap>,
     {
         self.run(|| {
             let mut src_map_buf = vec![];
+            let mut names = vec![];
 
             let src = {
                 let mut buf = vec![];
                 {
                     let handlers = box MyHandlers;
                     let mut emitter = Emitter {
-                        cfg: codegen::Config { minify },
-                        comments: if minify { None } else { Some(&self.comments) },
+                        cfg,
+                        comments: if cfg.minify {
+                            None
+                        } else {
+                            Some(&self.comments)
+                        },
                         cm: self.cm.clone(),
                         wr: box codegen::text_writer::JsWriter::new(
                             self.cm.clone(),
@@ -219,7 +282,8 @@ impl Compiler {
                             } else {
                                 None
                             },
-                        ),
+                        )
+                        .with_names(&mut names),
                         handlers,
                     };
 
@@ -235,7 +299,7 @@ impl Compiler {
                         let mut buf = vec![];
 
                         self.cm
-                            .build_source_map_from(&mut src_map_buf, orig)
+                            .build_source_map_with(&mut src_map_buf, &names, orig)
                             .to_writer(&mut buf)
                             .context("failed to write source map")?;
                         let map = String::from_utf8(buf).context("source map is not utf-8")?;
@@ -250,7 +314,7 @@ impl Compiler {
                     let mut buf = vec![];
 
                     self.cm
-                        .build_source_map_from(&mut src_map_buf, orig)
+                        .build_source_map_with(&mut src_map_buf, &names, orig)
                         .to_writer(&mut buf)
                         .context("failed to write source map file")?;
                     let map = String::from_utf8(buf).context("source map is not utf-8")?;
@@ -358,7 +422,7 @@ impl Compiler {
     ) -> Result<BuiltConfig<impl Pass>, Error> {
         self.run(|| -> Result<_, Error> {
             let config = self.read_config(opts, name)?;
-            let built = opts.build(&self.cm, &self.handler, opts.is_module, Some(config));
+            let built = opts.build(&self.cm, &self.handler, opts.is_module, Some(config))?;
             Ok(built)
         })
         .with_context(|| format!("failed to load config for file '{:?}'", name))
@@ -395,12 +459,13 @@ impl Compiler {
         self.run(|| -> Result<_, Error> {
             let config = self.run(|| self.config_for_file(opts, &fm.name))?;
             let orig = self.get_orig_src_map(&fm, &opts.input_source_map)?;
-            let program = self.parse_js(
+            let program = self.parse_js_inner(
                 fm.clone(),
                 config.target,
                 config.syntax,
                 config.is_module,
                 true,
+                config.target_diagnostics,
             )?;
 
             self.process_js_inner(program, orig.as_ref(), config)
@@ -424,6 +489,41 @@ impl Compiler {
         .context("failed to process js module")
     }
 
+    /// Generates a typescript declaration file (`.d.ts`) from a typescript
+    /// file.
+    ///
+    /// Types are not inferred, so an error is emitted for each exported value
+    /// without an explicit type annotation.
+    pub fn process_dts_file(
+        &self,
+        fm: Arc<SourceFile>,
+        opts: &Options,
+    ) -> Result<TransformOutput, Error> {
+        self.run(|| -> Result<_, Error> {
+            let config = self.run(|| self.config_for_file(opts, &fm.name))?;
+            if !config.syntax.typescript() {
+                bail!("declarations can only be generated from typescript files");
+            }
+
+            let program = self.parse_js(
+                fm.clone(),
+                config.target,
+                config.syntax,
+                config.is_module,
+                true,
+            )?;
+
+            let err_count = self.handler.err_count();
+            let program = self.transform(program, config.external_helpers, typescript::dts());
+            if self.handler.err_count() != err_count {
+                bail!("failed to generate declarations");
+            }
+
+            self.print(&program, SourceMapsConfig::Bool(false), None, false)
+        })
+        .context("failed to generate typescript declarations")
+    }
+
     fn process_js_inner(
         &self,
         program: Program,
@@ -447,7 +547,15 @@ impl Compiler {
                 })
             });
 
-            self.print(&program, config.source_maps, orig, config.minify)
+            self.emit(
+                &program,
+                config.source_maps,
+                |_| orig,
+                codegen::Config {
+                    minify: config.minify,
+                    target: Some(config.target),
+                },
+            )
         })
     }
 }
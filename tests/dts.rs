@@ -0,0 +1,59 @@
+use std::sync::Arc;
+use swc::{
+    config::{Config, JscConfig, Options},
+    ecmascript::parser::{Syntax, TsConfig},
+    Compiler,
+};
+use swc_common::FileName;
+use testing::{StdErr, Tester};
+
+fn dts(src: &str) -> Result<String, StdErr> {
+    Tester::new().print_errors(|cm, handler| {
+        let c = Compiler::new(cm.clone(), Arc::new(handler));
+
+        let fm = cm.new_source_file(FileName::Real("input.ts".into()), src.into());
+        let s = c.process_dts_file(
+            fm,
+            &Options {
+                config: Some(Config {
+                    jsc: JscConfig {
+                        syntax: Some(Syntax::Typescript(TsConfig::default())),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }),
+                swcrc: false,
+                is_module: true,
+                ..Default::default()
+            },
+        );
+
+        match s {
+            Ok(v) => Ok(v.code),
+            Err(..) => Err(()),
+        }
+    })
+}
+
+#[test]
+fn declarations() {
+    let code = dts("export function add(a: number, b = 1): number {
+    return a + b;
+}
+export const version = '1.0.0';")
+    .unwrap();
+
+    assert!(code.contains("export declare function add(a: number, b?: number): number;"));
+    assert!(code.contains("export declare const version = '1.0.0';"));
+}
+
+#[test]
+fn missing_annotation() {
+    let err = dts("export function add(a, b) {
+    return a + b;
+}")
+    .unwrap_err();
+
+    assert!(err.contains("parameter should have an explicit type annotation"));
+    assert!(err.contains("function should have an explicit return type annotation"));
+}
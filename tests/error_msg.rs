@@ -30,3 +30,21 @@ fn swcrc_simple() {
     let f = file("tests/swcrc_errors/simple/foo.js");
     println!("{}", f);
 }
+
+#[test]
+fn swcrc_decorator_metadata_without_legacy() {
+    let f = file("tests/swcrc_errors/decorator_metadata/foo.js").to_string();
+    assert!(f.contains("legacyDecorator"), "{}", f);
+}
+
+#[test]
+fn swcrc_invalid_env_targets() {
+    let f = file("tests/swcrc_errors/invalid_env_targets/foo.js").to_string();
+    assert!(f.contains("failed to resolve targets"), "{}", f);
+}
+
+#[test]
+fn swcrc_unsupported_core_js() {
+    let f = file("tests/swcrc_errors/unsupported_core_js/foo.js").to_string();
+    assert!(f.contains("core js 4.0 is not supported"), "{}", f);
+}
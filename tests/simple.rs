@@ -1,6 +1,6 @@
 use std::sync::Arc;
 use swc::{
-    config::{Config, JscConfig, Options},
+    config::{Config, JscConfig, JscTarget, Options},
     ecmascript::parser::{EsConfig, Syntax},
     Compiler,
 };
@@ -94,3 +94,42 @@ fn issue_834_3() {
         },
     );
 }
+
+/// Returns the warnings emitted while compiling `src` for `target`.
+fn target_warnings(src: &str, target: JscTarget, target_diagnostics: bool) -> String {
+    Tester::new()
+        .print_errors(|cm, handler| -> Result<(), _> {
+            let c = Compiler::new(cm.clone(), Arc::new(handler));
+
+            let fm = cm.new_source_file(FileName::Real("input.js".into()), src.into());
+            c.process_js_file(
+                fm,
+                &Options {
+                    config: Some(Config {
+                        jsc: JscConfig {
+                            target,
+                            target_diagnostics,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    }),
+                    swcrc: false,
+                    is_module: true,
+                    ..Default::default()
+                },
+            )
+            .expect("failed to process js file");
+
+            // Make the handler return the output.
+            Err(())
+        })
+        .expect_err("output should be returned")
+        .to_string()
+}
+
+#[test]
+fn target_diagnostics() {
+    assert!(target_warnings("var foo = 2n;", JscTarget::Es2019, true).contains("BigInt literal"));
+    assert!(!target_warnings("var foo = 2n;", JscTarget::Es2020, true).contains("BigInt literal"));
+    assert!(!target_warnings("var foo = 2n;", JscTarget::Es2019, false).contains("BigInt literal"));
+}
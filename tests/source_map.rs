@@ -1,8 +1,10 @@
 use std::{fs::canonicalize, process::Command, sync::Arc};
 use swc::{
     config::{Options, SourceMapsConfig},
+    sourcemap::SourceMap,
     Compiler,
 };
+use swc_common::FileName;
 use testing::{StdErr, Tester};
 
 fn file(f: &str) -> Result<(), StdErr> {
@@ -99,3 +101,55 @@ fn inline(f: &str) -> Result<(), StdErr> {
 fn issue_706() {
     inline("tests/srcmap/issue-706/index.js").unwrap();
 }
+
+#[test]
+fn names_of_renamed_identifiers() {
+    Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Compiler::new(cm.clone(), Arc::new(handler));
+
+            let fm = cm.new_source_file(
+                FileName::Real("input.js".into()),
+                "let userSession = 1;
+{
+    let userSession = 2;
+    console.log(userSession);
+}
+console.log(userSession);"
+                    .into(),
+            );
+            let s = c
+                .process_js_file(
+                    fm,
+                    &Options {
+                        is_module: true,
+                        source_maps: Some(SourceMapsConfig::Bool(true)),
+                        ..Default::default()
+                    },
+                )
+                .expect("failed to process js file");
+
+            let map = SourceMap::from_slice(s.map.unwrap().as_bytes()).unwrap();
+            let lines = s.code.lines().collect::<Vec<_>>();
+
+            let renamed = map
+                .tokens()
+                .filter(|token| token.get_name() == Some("userSession"))
+                .map(|token| {
+                    let line = lines[token.get_dst_line() as usize];
+                    line[token.get_dst_col() as usize..]
+                        .chars()
+                        .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == '$')
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>();
+
+            assert!(!renamed.is_empty(), "{}", s.code);
+            for name in renamed {
+                assert_ne!(name, "userSession", "{}", s.code);
+            }
+
+            Ok(())
+        })
+        .unwrap();
+}
@@ -56,7 +56,11 @@ pub fn print_sync(s: JsValue, opts: JsValue) -> Result<JsValue, JsValue> {
                 .clone()
                 .unwrap_or(SourceMapsConfig::Bool(false)),
             None,
-            opts.config.unwrap_or_default().minify.unwrap_or_default(),
+            opts.config
+                .unwrap_or_default()
+                .minify
+                .map(|v| v.enabled())
+                .unwrap_or_default(),
         )
         .map_err(|err| format!("failed to print: {}\n{}", err, errors))?;
 